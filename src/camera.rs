@@ -1,17 +1,37 @@
 use bevy::input::mouse::AccumulatedMouseMotion;
 use bevy::pbr::ClusterConfig;
 use bevy::prelude::*;
+use bevy::render::view::RenderLayers;
 use bevy::window::PrimaryWindow;
 
-use crate::game::spawn::player::Player;
+use crate::game::{
+    core_mechanics::{movement::PlayerTeleported, triggers::camera_free},
+    spawn::player::{Player, SHADOW_ONLY_LAYER},
+    ui::world_map::world_map_closed,
+};
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(Startup, spawn_camera)
-        .add_systems(Update, (update_camera, camera_look));
+    app.add_systems(Startup, spawn_camera).add_systems(
+        Update,
+        (
+            snap_camera_on_teleport,
+            update_camera,
+            camera_look.run_if(world_map_closed),
+        )
+            .chain()
+            .run_if(camera_free),
+    );
 }
 
 fn spawn_camera(mut commands: Commands) {
-    commands.spawn(DirectionalLight::default());
+    // Also lights the shadow-only layer so the player's body casts a shadow
+    commands.spawn((
+        DirectionalLight {
+            shadows_enabled: true,
+            ..Default::default()
+        },
+        RenderLayers::from_layers(&[0, SHADOW_ONLY_LAYER]),
+    ));
 
     commands.spawn((
         Name::new("Camera"),
@@ -20,9 +40,20 @@ fn spawn_camera(mut commands: Commands) {
             ..Default::default()
         },
         IsDefaultUiCamera,     
+        SpatialListener::new(0.5),
         ClusterConfig::Single, 
     ));
 }
+// Skip the follow lerp so the camera doesn't sweep across the whole map
+fn snap_camera_on_teleport(
+    mut teleports: EventReader<PlayerTeleported>,
+    mut camera: Single<&mut Transform, With<Camera3d>>,
+) {
+    if let Some(teleport) = teleports.read().last() {
+        camera.translation = teleport.to + Vec3::Y * 3.0;
+    }
+}
+
 fn update_camera(
     mut camera: Single<&mut Transform, (With<Camera3d>, Without<Player>)>,
     player: Single<&Transform, (With<Player>, Without<Camera3d>)>,
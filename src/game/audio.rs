@@ -0,0 +1,449 @@
+use bevy::{
+    audio::{AddAudioSource, Decodable, DefaultSpatialScale, Sample, Source, SpatialScale, Volume},
+    ecs::system::SystemParam,
+    platform::collections::HashMap,
+    prelude::*,
+    time::common_conditions::on_timer,
+};
+use std::{
+    collections::HashSet,
+    f32::consts::TAU,
+    sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    },
+    time::Duration,
+};
+
+use crate::game::core_mechanics::oz_devinimli_yaratim::{
+    cells::{Cell, CellSpatialIndex, GenerationSettings},
+    grid::world_to_grid,
+    odyrules::commons::{AmbientEmitter, AmbientSound},
+};
+
+const OCCLUSION_INTERVAL_MS: u64 = 200;
+const SPATIAL_SCALE: f32 = 0.1; // 10 world units = 1 "audio meter"
+const OCCLUSION_PER_TILE: f32 = 0.55; // Volume kept per blocking tile in between
+const MIN_OCCLUDED_VOLUME: f32 = 0.1;
+const OPEN_CUTOFF_HZ: f32 = 18_000.0; // Low-pass cutoff with nothing in between, about inaudible
+const CUTOFF_PER_TILE: f32 = 0.35; // Cutoff kept per blocking tile in between
+const MIN_CUTOFF_HZ: f32 = 350.0;
+const AMBIENCE_INTERVAL_MS: u64 = 250;
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(DefaultSpatialScale(SpatialScale::new(SPATIAL_SCALE)))
+        .add_audio_source::<MuffledAudio>()
+        .add_event::<PlaySfx>()
+        .init_resource::<AmbientTiles>()
+        .add_systems(Startup, setup_sfx_handles)
+        .add_systems(
+            Update,
+            (
+                play_sfx,
+                (
+                    track_ambient_tiles,
+                    update_tile_ambience
+                        .run_if(on_timer(Duration::from_millis(AMBIENCE_INTERVAL_MS))),
+                )
+                    .chain(),
+                apply_sound_occlusion,
+            ),
+        );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SfxKind {
+    Voice,
+    Footstep,
+    Fountain,
+    Drink,
+    Hit,
+    Wind,
+}
+
+impl SfxKind {
+    pub const ALL: [SfxKind; 6] = [
+        SfxKind::Voice,
+        SfxKind::Footstep,
+        SfxKind::Fountain,
+        SfxKind::Drink,
+        SfxKind::Hit,
+        SfxKind::Wind,
+    ];
+
+    pub fn path(self) -> &'static str {
+        match self {
+            SfxKind::Voice => "sounds/voice.ogg",
+            SfxKind::Footstep => "sounds/footstep.ogg",
+            SfxKind::Fountain => "sounds/fountain.ogg",
+            SfxKind::Drink => "sounds/drink.ogg",
+            SfxKind::Hit => "sounds/hit.ogg",
+            SfxKind::Wind => "sounds/wind.ogg",
+        }
+    }
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PlaySfx {
+    pub kind: SfxKind,
+    pub position: Vec3,
+}
+
+#[derive(Resource)]
+pub struct SfxHandles {
+    pub voice: Handle<AudioSource>,
+    pub footstep: Handle<AudioSource>,
+    pub fountain: Handle<AudioSource>,
+    pub drink: Handle<AudioSource>,
+    pub hit: Handle<AudioSource>,
+    pub wind: Handle<AudioSource>,
+}
+
+impl SfxHandles {
+    pub fn get(&self, kind: SfxKind) -> Handle<AudioSource> {
+        match kind {
+            SfxKind::Voice => self.voice.clone(),
+            SfxKind::Footstep => self.footstep.clone(),
+            SfxKind::Fountain => self.fountain.clone(),
+            SfxKind::Drink => self.drink.clone(),
+            SfxKind::Hit => self.hit.clone(),
+            SfxKind::Wind => self.wind.clone(),
+        }
+    }
+}
+
+// Emitters with this component get their volume scaled by the tiles between them and the listener
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SoundOcclusion {
+    pub base_volume: f32,
+}
+
+impl Default for SoundOcclusion {
+    fn default() -> Self {
+        Self { base_volume: 1.0 }
+    }
+}
+
+// Collapsed cells whose tile declares an ambient loop, kept up to date as cells collapse and
+// despawn
+#[derive(Resource, Default)]
+struct AmbientTiles(HashMap<Entity, AmbientTile>);
+
+struct AmbientTile {
+    emitter: AmbientEmitter,
+    playing: Option<Entity>,
+}
+
+// A clip streamed through a low-pass filter whose cutoff can be moved while it plays. Each
+// emitter gets its own asset, so the cutoff is per emitter.
+#[derive(Asset, TypePath)]
+pub struct MuffledAudio {
+    source: AudioSource,
+    cutoff_hz: Arc<AtomicU32>, // f32 bits, shared with the emitter's Muffler
+}
+
+// Emitter side of a MuffledAudio cutoff
+#[derive(Component, Clone)]
+struct Muffler(Arc<AtomicU32>);
+
+impl Muffler {
+    fn set_cutoff(&self, cutoff_hz: f32) {
+        self.0.store(cutoff_hz.to_bits(), Ordering::Relaxed);
+    }
+}
+
+impl Decodable for MuffledAudio {
+    type DecoderItem = f32;
+    type Decoder = LowPassDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        LowPassDecoder {
+            inner: self.source.decoder(),
+            cutoff_hz: self.cutoff_hz.clone(),
+            alpha: 1.0,
+            alpha_cutoff_bits: None,
+            previous: Vec::new(),
+            channel: 0,
+        }
+    }
+}
+
+// One-pole low-pass over the decoded samples, one filter state per channel
+pub struct LowPassDecoder {
+    inner: <AudioSource as Decodable>::Decoder,
+    cutoff_hz: Arc<AtomicU32>,
+    alpha: f32,
+    alpha_cutoff_bits: Option<u32>, // Cutoff `alpha` was computed for
+    previous: Vec<f32>,
+    channel: usize,
+}
+
+impl Iterator for LowPassDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let sample = self.inner.next()?.to_f32();
+        let channels = self.inner.channels().max(1) as usize;
+        if self.previous.len() != channels {
+            self.previous = vec![0.0; channels];
+            self.channel = 0;
+        }
+
+        // The cutoff is picked up at the start of each frame, so channels never disagree
+        if self.channel == 0 {
+            let cutoff_bits = self.cutoff_hz.load(Ordering::Relaxed);
+            if self.alpha_cutoff_bits != Some(cutoff_bits) {
+                let cutoff = f32::from_bits(cutoff_bits);
+                let sample_rate = self.inner.sample_rate().max(1) as f32;
+                self.alpha = 1.0 - (-TAU * cutoff / sample_rate).exp();
+                self.alpha_cutoff_bits = Some(cutoff_bits);
+            }
+        }
+
+        let filtered = &mut self.previous[self.channel];
+        *filtered += self.alpha * (sample - *filtered);
+        self.channel = (self.channel + 1) % channels;
+        Some(*filtered)
+    }
+}
+
+impl Source for LowPassDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        self.inner.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.inner.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration()
+    }
+}
+
+// Everything needed to give an emitter its AudioPlayer
+#[derive(SystemParam)]
+struct AudioPlayers<'w> {
+    handles: Res<'w, SfxHandles>,
+    sources: Res<'w, Assets<AudioSource>>,
+    muffled: ResMut<'w, Assets<MuffledAudio>>,
+}
+
+impl AudioPlayers<'_> {
+    // Plays `kind` muffled if it's loaded; clips still loading play unfiltered
+    fn insert(&mut self, emitter: &mut EntityCommands, kind: SfxKind) {
+        let handle = self.handles.get(kind);
+        let Some(source) = self.sources.get(&handle) else {
+            emitter.insert(AudioPlayer(handle));
+            return;
+        };
+
+        let cutoff_hz = Arc::new(AtomicU32::new(OPEN_CUTOFF_HZ.to_bits()));
+        emitter.insert((
+            AudioPlayer(self.muffled.add(MuffledAudio {
+                source: source.clone(),
+                cutoff_hz: cutoff_hz.clone(),
+            })),
+            Muffler(cutoff_hz),
+        ));
+    }
+}
+
+fn setup_sfx_handles(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(SfxHandles {
+        voice: asset_server.load(SfxKind::Voice.path()),
+        footstep: asset_server.load(SfxKind::Footstep.path()),
+        fountain: asset_server.load(SfxKind::Fountain.path()),
+        drink: asset_server.load(SfxKind::Drink.path()),
+        hit: asset_server.load(SfxKind::Hit.path()),
+        wind: asset_server.load(SfxKind::Wind.path()),
+    });
+}
+
+fn play_sfx(mut commands: Commands, mut events: EventReader<PlaySfx>, mut players: AudioPlayers) {
+    for event in events.read() {
+        let mut emitter = commands.spawn((
+            Name::new(format!("Sfx_{:?}", event.kind)),
+            PlaybackSettings::DESPAWN.with_spatial(true),
+            Transform::from_translation(event.position),
+            SoundOcclusion::default(),
+        ));
+        players.insert(&mut emitter, event.kind);
+    }
+}
+
+fn track_ambient_tiles(
+    mut commands: Commands,
+    mut ambient: ResMut<AmbientTiles>,
+    changed_cells: Query<(Entity, &Cell), Changed<Cell>>,
+    mut removed_cells: RemovedComponents<Cell>,
+) {
+    // The playing loop was a child of the cell and went with it
+    for entity in removed_cells.read() {
+        ambient.0.remove(&entity);
+    }
+
+    for (entity, cell) in changed_cells.iter() {
+        let emitter = cell
+            .tile_type
+            .filter(|_| cell.is_collapsed)
+            .and_then(|tile| tile.metadata().ambient);
+        if ambient.0.get(&entity).map(|tile| tile.emitter) == emitter {
+            continue;
+        }
+
+        // Backtracking can switch a collapsed tile, so the old loop has to go
+        if let Some(previous) = ambient.0.remove(&entity)
+            && let Some(playing) = previous.playing
+        {
+            commands.entity(playing).try_despawn();
+        }
+        if let Some(emitter) = emitter {
+            ambient.0.insert(
+                entity,
+                AmbientTile {
+                    emitter,
+                    playing: None,
+                },
+            );
+        }
+    }
+}
+
+// Loops start when the listener comes within their radius and stop once it leaves, so only
+// the tiles nearby hold an audio sink
+fn update_tile_ambience(
+    mut commands: Commands,
+    mut ambient: ResMut<AmbientTiles>,
+    listener: Single<&GlobalTransform, With<SpatialListener>>,
+    cells: Query<&GlobalTransform, With<Cell>>,
+    mut occlusions: Query<&mut SoundOcclusion>,
+    mut players: AudioPlayers,
+) {
+    let listener_position = listener.translation();
+
+    for (cell_entity, tile) in ambient.0.iter_mut() {
+        let Ok(cell_transform) = cells.get(*cell_entity) else {
+            continue;
+        };
+        let distance = cell_transform.translation().distance(listener_position);
+        let volume = tile.emitter.volume * (1.0 - distance / tile.emitter.radius);
+
+        match tile.playing {
+            Some(playing) if volume <= 0.0 => {
+                commands.entity(playing).try_despawn();
+                tile.playing = None;
+            }
+            Some(playing) => {
+                if let Ok(mut occlusion) = occlusions.get_mut(playing) {
+                    occlusion.base_volume = volume;
+                }
+            }
+            None if volume > 0.0 => {
+                // Child of the cell, so it despawns together with it
+                let mut emitter = commands.spawn((
+                    Name::new(format!("Ambience_{:?}", tile.emitter.sound)),
+                    PlaybackSettings::LOOP.with_spatial(true),
+                    Transform::default(),
+                    SoundOcclusion {
+                        base_volume: volume,
+                    },
+                    ChildOf(*cell_entity),
+                ));
+                players.insert(&mut emitter, ambient_sfx(tile.emitter.sound));
+                tile.playing = Some(emitter.id());
+            }
+            None => {}
+        }
+    }
+}
+
+fn ambient_sfx(sound: AmbientSound) -> SfxKind {
+    match sound {
+        AmbientSound::FountainGurgle => SfxKind::Fountain,
+        AmbientSound::TreeWind => SfxKind::Wind,
+    }
+}
+
+fn apply_sound_occlusion(
+    listener: Single<&GlobalTransform, With<SpatialListener>>,
+    mut emitters: Query<(
+        &mut SpatialAudioSink,
+        &GlobalTransform,
+        &SoundOcclusion,
+        Option<&Muffler>,
+    )>,
+    cells: Query<&Cell>,
+    spatial_index: Res<CellSpatialIndex>,
+    settings: Res<GenerationSettings>,
+    mut last_update: Local<Duration>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed();
+    if *last_update + Duration::from_millis(OCCLUSION_INTERVAL_MS) > now {
+        return;
+    }
+    *last_update = now;
+
+    let listener_position = listener.translation();
+
+    for (mut sink, emitter_transform, occlusion, muffler) in emitters.iter_mut() {
+        let blocking_tiles = count_blocking_tiles(
+            listener_position,
+            emitter_transform.translation(),
+            &cells,
+            &spatial_index,
+            settings.cell_edge_length as f32,
+        );
+
+        let volume = (occlusion.base_volume * OCCLUSION_PER_TILE.powi(blocking_tiles as i32))
+            .max(MIN_OCCLUDED_VOLUME * occlusion.base_volume);
+        sink.set_volume(Volume::Linear(volume));
+
+        // Walls and trees let the low end through, so blocked sounds get duller, not just quieter
+        if let Some(muffler) = muffler {
+            let cutoff =
+                (OPEN_CUTOFF_HZ * CUTOFF_PER_TILE.powi(blocking_tiles as i32)).max(MIN_CUTOFF_HZ);
+            muffler.set_cutoff(cutoff);
+        }
+    }
+}
+
+pub fn count_blocking_tiles(
+    from: Vec3,
+    to: Vec3,
+    cells: &Query<&Cell>,
+    spatial_index: &CellSpatialIndex,
+    cell_edge_length: f32,
+) -> usize {
+    let to_grid = |point: Vec3| world_to_grid(point, cell_edge_length);
+
+    let start = to_grid(from);
+    let end = to_grid(to);
+
+    // Half-cell steps so no tile on the line is skipped
+    let flat_distance = Vec2::new(to.x - from.x, to.z - from.z).length();
+    let steps = (flat_distance / (cell_edge_length * 0.5)).ceil() as usize;
+
+    let mut visited = HashSet::new();
+    for step in 1..steps {
+        let point = from.lerp(to, step as f32 / steps as f32);
+        let grid_position = to_grid(point);
+
+        if grid_position == start || grid_position == end {
+            continue;
+        }
+        visited.insert(grid_position);
+    }
+
+    visited
+        .iter()
+        .filter_map(|position| spatial_index.ground(position))
+        .filter_map(|entity| cells.get(*entity).ok())
+        .filter(|cell| cell.tile_type.is_some_and(|tile| tile.blocks_sound()))
+        .count()
+}
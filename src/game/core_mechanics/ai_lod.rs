@@ -0,0 +1,110 @@
+use bevy::{diagnostic::FrameCount, prelude::*};
+use std::time::Duration;
+
+use crate::game::{core_mechanics::pause::simulation_running, spawn::player::Player};
+
+const LOD_UPDATE_INTERVAL_MS: u64 = 250;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<AiLodSettings>()
+        .add_systems(Update, assign_lod_tiers.run_if(simulation_running));
+}
+
+#[derive(Resource)]
+pub struct AiLodSettings {
+    pub near_distance: f32,
+    pub far_distance: f32,
+    pub mid_tick_interval: u32, // Mid tier agents update once every n frames
+}
+
+impl Default for AiLodSettings {
+    fn default() -> Self {
+        Self {
+            near_distance: 40.0,
+            far_distance: 90.0,
+            mid_tick_interval: 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LodTier {
+    #[default]
+    Near, // Every frame
+    Mid, // Every `mid_tick_interval` frames
+    Far, // Frozen
+}
+
+// Agents with this component are scheduled by distance to the player
+#[derive(Component, Debug, Default)]
+pub struct AiLod {
+    pub tier: LodTier,
+    frame_offset: u32,
+    accumulated_delta: f32,
+}
+
+impl AiLod {
+    pub fn new(frame_offset: u32) -> Self {
+        Self {
+            frame_offset,
+            ..Default::default()
+        }
+    }
+
+    // Returns the time since this agent last ran when it is its turn this frame
+    pub fn tick(
+        &mut self,
+        frame: &FrameCount,
+        delta: f32,
+        settings: &AiLodSettings,
+    ) -> Option<f32> {
+        self.accumulated_delta += delta;
+
+        let runs_this_frame = match self.tier {
+            LodTier::Near => true,
+            LodTier::Mid => {
+                (frame.0 + self.frame_offset).is_multiple_of(settings.mid_tick_interval.max(1))
+            }
+            LodTier::Far => false,
+        };
+
+        if self.tier == LodTier::Far {
+            self.accumulated_delta = 0.0;
+        }
+
+        if runs_this_frame {
+            Some(std::mem::take(&mut self.accumulated_delta))
+        } else {
+            None
+        }
+    }
+}
+
+fn assign_lod_tiers(
+    player: Single<&Transform, With<Player>>,
+    mut agents: Query<(&Transform, &mut AiLod)>,
+    settings: Res<AiLodSettings>,
+    mut last_update: Local<Duration>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed();
+    if *last_update + Duration::from_millis(LOD_UPDATE_INTERVAL_MS) > now {
+        return;
+    }
+    *last_update = now;
+
+    for (transform, mut lod) in agents.iter_mut() {
+        let distance = transform.translation.distance(player.translation);
+        let tier = if distance <= settings.near_distance {
+            LodTier::Near
+        } else if distance <= settings.far_distance {
+            LodTier::Mid
+        } else {
+            LodTier::Far
+        };
+
+        if lod.tier != tier {
+            lod.tier = tier;
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    pbr::{NotShadowCaster, NotShadowReceiver},
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+use crate::game::core_mechanics::{
+    enemy_ai::{encounters::Trader, o_insan::components::OInsanAI},
+    oz_devinimli_yaratim::{
+        cells::{Cell, GenerationSettings},
+        elevation::cell_lift,
+        grid::grid_to_world,
+        odyrules::{commons::TileType, open_space_rules::OpenSpaceRules},
+        tiles_meshes_models::update_tile_visuals,
+    },
+};
+
+const GRADIENT_TEXTURE_SIZE: u32 = 32;
+const DARKNESS: f32 = 0.55; // Alpha at the center of a blob
+const LIFT: f32 = 0.03; // Above the ground, below nothing else
+const CHARACTER_RADIUS: f32 = 1.1;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Startup, setup_blob_shadow_assets)
+        .add_systems(
+            Update,
+            (
+                attach_tile_shadows.after(update_tile_visuals),
+                attach_character_shadows,
+            ),
+        );
+}
+
+// One flat quad and material for every blob, so they draw in a single batch whatever the
+// shadow settings
+#[derive(Resource)]
+struct BlobShadowAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+// The blob drawn under this entity, replaced when a cell collapses again
+#[derive(Component)]
+struct HasBlobShadow(Entity);
+
+fn setup_blob_shadow_assets(
+    mut commands: Commands,
+    mut mesh_assets: ResMut<Assets<Mesh>>,
+    mut material_assets: ResMut<Assets<StandardMaterial>>,
+    mut image_assets: ResMut<Assets<Image>>,
+) {
+    commands.insert_resource(BlobShadowAssets {
+        mesh: mesh_assets.add(Plane3d::default().mesh().size(1.0, 1.0)),
+        material: material_assets.add(StandardMaterial {
+            base_color: Color::BLACK,
+            base_color_texture: Some(image_assets.add(gradient_texture())),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..Default::default()
+        }),
+    });
+}
+
+// Opaque in the middle, fading to nothing at the quad's inscribed circle
+fn gradient_texture() -> Image {
+    let size = GRADIENT_TEXTURE_SIZE;
+    let mut data = Vec::with_capacity((size * size * 4) as usize);
+    for y in 0..size {
+        for x in 0..size {
+            let to_center = Vec2::new(x as f32 + 0.5, y as f32 + 0.5) / size as f32 - 0.5;
+            let falloff = (1.0 - to_center.length() * 2.0).clamp(0.0, 1.0);
+            let alpha = (falloff * falloff * (3.0 - 2.0 * falloff) * DARKNESS * 255.0) as u8;
+            data.extend([255, 255, 255, alpha]);
+        }
+    }
+
+    Image::new(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+// Center relative to the cell's center and radius, for tiles that stand up off the ground
+fn tile_blob(tile: TileType, edge: f32, footprint: (i32, i32)) -> Option<(Vec3, f32)> {
+    match tile {
+        TileType::Tree => Some((Vec3::ZERO, edge * 0.35)),
+        TileType::Chest => Some((Vec3::ZERO, edge * 0.3)),
+        // Centered on the whole footprint, past the anchor cell
+        TileType::LargeTree => Some((
+            Vec3::new(
+                (footprint.0 - 1) as f32 * edge / 2.0,
+                0.0,
+                (footprint.1 - 1) as f32 * edge / 2.0,
+            ),
+            edge * footprint.0.min(footprint.1) as f32 * 0.4,
+        )),
+        _ => None,
+    }
+}
+
+// Tiles are scaled and placed differently per type, so the blob is laid out in world space and
+// brought into the tile's frame
+fn attach_tile_shadows(
+    mut commands: Commands,
+    changed_cells: Query<(Entity, &Cell, &Transform, Option<&HasBlobShadow>), Changed<Cell>>,
+    assets: Res<BlobShadowAssets>,
+    settings: Res<GenerationSettings>,
+    rules: Res<OpenSpaceRules>,
+) {
+    let edge = settings.cell_edge_length as f32;
+    for (entity, cell, transform, existing) in changed_cells.iter() {
+        if let Some(HasBlobShadow(blob)) = existing {
+            commands.entity(*blob).despawn();
+            commands.entity(entity).remove::<HasBlobShadow>();
+        }
+
+        let Some(tile) = cell.tile_type.filter(|_| cell.is_collapsed) else {
+            continue;
+        };
+        let Some((offset, radius)) = tile_blob(tile, edge, rules.footprint(tile)) else {
+            continue;
+        };
+
+        let center = grid_to_world(cell.position, edge).with_y(cell_lift(cell) + LIFT) + offset;
+        let world = Transform::from_translation(center).with_scale(Vec3::splat(radius * 2.0));
+        let local = transform.compute_matrix().inverse() * world.compute_matrix();
+        let blob = commands
+            .spawn((
+                Mesh3d(assets.mesh.clone()),
+                MeshMaterial3d(assets.material.clone()),
+                NotShadowCaster,
+                NotShadowReceiver,
+                Transform::from_matrix(local),
+                ChildOf(entity),
+            ))
+            .id();
+        commands.entity(entity).insert(HasBlobShadow(blob));
+    }
+}
+
+type UnshadowedCharacters<'w, 's> =
+    Query<'w, 's, Entity, (Or<(With<OInsanAI>, With<Trader>)>, Without<HasBlobShadow>)>;
+
+// Characters stand with their origin on the ground and are never scaled
+fn attach_character_shadows(
+    mut commands: Commands,
+    characters: UnshadowedCharacters,
+    assets: Res<BlobShadowAssets>,
+) {
+    for entity in characters.iter() {
+        let blob = commands
+            .spawn((
+                Mesh3d(assets.mesh.clone()),
+                MeshMaterial3d(assets.material.clone()),
+                NotShadowCaster,
+                NotShadowReceiver,
+                Transform::from_xyz(0.0, LIFT, 0.0).with_scale(Vec3::splat(CHARACTER_RADIUS * 2.0)),
+                ChildOf(entity),
+            ))
+            .id();
+        commands.entity(entity).insert(HasBlobShadow(blob));
+    }
+}
@@ -0,0 +1,192 @@
+use std::fs;
+
+use bevy::prelude::*;
+use rand::prelude::*;
+use serde::Deserialize;
+
+use crate::game::{
+    core_mechanics::{
+        interactions::{InteractionStore, RegisterInteractionHook, TileUse},
+        inventory::{Inventory, Item, ItemStack},
+        loot::{LootAssets, LootKind, spawn_loot_drop},
+        oz_devinimli_yaratim::{
+            cells::Cell, odyrules::commons::TileType, tiles_meshes_models::TileMeshes,
+        },
+    },
+    spawn::player::Player,
+};
+
+pub const CHEST_LOOT_PATH: &str = "assets/chest_loot.ron";
+const DROP_OFFSET: f32 = 2.0; // Towards the player, so the loot doesn't land inside the chest
+// The chest cell is scaled by the cell edge, so the lid is in fractions of a cell
+const LID_SIZE: Vec3 = Vec3::new(0.52, 0.04, 0.42);
+const LID_HEIGHT: f32 = 0.32; // Resting on top of the chest's collider
+const LID_OPEN_ANGLE: f32 = 1.9; // Radians swung back round the hinge
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(ChestLootTable::load_or_default())
+        .add_event::<ChestOpened>()
+        .register_interaction_hook(TileType::Chest, open_chest)
+        .add_systems(Update, attach_chests);
+}
+
+// A chest the player has just looted
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ChestOpened {
+    pub position: Vec3,
+}
+
+// The openable part of a chest tile: its lid, spawned under the cell once it collapses and
+// swung open for good once the chest is looted
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Chest {
+    pub position: (i32, i32),
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LootEntry {
+    pub loot: LootKind,
+    pub weight: f32,
+}
+
+// What a chest can hold, one entry rolled per chest
+#[derive(Resource, Deserialize, Debug, Clone)]
+pub struct ChestLootTable {
+    pub entries: Vec<LootEntry>,
+}
+
+impl Default for ChestLootTable {
+    // Only used when the asset is missing or broken: what chests held before the table
+    fn default() -> Self {
+        Self {
+            entries: vec![
+                LootEntry {
+                    loot: LootKind::Item(ItemStack::new(Item::Blade)),
+                    weight: 1.0,
+                },
+                LootEntry {
+                    loot: LootKind::Health(40.0),
+                    weight: 1.0,
+                },
+            ],
+        }
+    }
+}
+
+impl ChestLootTable {
+    pub fn load() -> Result<Self, String> {
+        let text = fs::read_to_string(CHEST_LOOT_PATH)
+            .map_err(|error| format!("{}: {}", CHEST_LOOT_PATH, error))?;
+        ron::from_str(&text).map_err(|error| format!("{}: {}", CHEST_LOOT_PATH, error))
+    }
+
+    fn load_or_default() -> Self {
+        Self::load().unwrap_or_else(|error| {
+            warn!(
+                "Failed to load chest loot, using the built-in table: {}",
+                error
+            );
+            Self::default()
+        })
+    }
+
+    // A player isn't handed a weapon they already carry
+    fn roll(&self, inventory: &Inventory) -> Option<LootKind> {
+        self.entries
+            .iter()
+            .filter(|entry| match entry.loot {
+                LootKind::Item(stack) if stack.item.is_weapon() => !inventory.contains(stack.item),
+                _ => true,
+            })
+            .collect::<Vec<_>>()
+            .choose_weighted(&mut rand::rng(), |entry| entry.weight)
+            .ok()
+            .map(|entry| entry.loot)
+    }
+}
+
+// Hinged along the back edge, so opening tips it up and over backwards
+fn lid_transform(opened: bool) -> Transform {
+    let closed = Transform::from_xyz(0.0, LID_HEIGHT, 0.0).with_scale(LID_SIZE);
+    if !opened {
+        return closed;
+    }
+    let hinge = Vec3::new(0.0, LID_HEIGHT, -LID_SIZE.z / 2.0);
+    let mut open = closed;
+    open.rotate_around(hinge, Quat::from_rotation_x(-LID_OPEN_ANGLE));
+    open
+}
+
+// Chests come back open if they were looted before their cell was unloaded
+fn attach_chests(
+    mut commands: Commands,
+    changed_cells: Query<(Entity, &Cell), Changed<Cell>>,
+    store: Res<InteractionStore>,
+    tile_meshes: Res<TileMeshes>,
+) {
+    for (entity, cell) in changed_cells.iter() {
+        if cell.tile_type != Some(TileType::Chest) || !cell.is_collapsed {
+            continue;
+        }
+
+        let looted = store.state(cell.position, TileType::Chest).looted;
+        commands.entity(entity).with_child((
+            Name::new("Chest"),
+            Chest {
+                position: cell.position,
+            },
+            Mesh3d(tile_meshes.block_mesh.clone()),
+            MeshMaterial3d(tile_meshes.door_material.clone()),
+            lid_transform(looted),
+        ));
+    }
+}
+
+// Found through its lid, which sits on the chest wherever the cell's model put it
+fn open_chest(
+    In(tile_use): In<TileUse>,
+    mut commands: Commands,
+    mut store: ResMut<InteractionStore>,
+    player: Single<(&Transform, &Inventory), With<Player>>,
+    mut lids: Query<(&Chest, &mut Transform, &GlobalTransform), Without<Player>>,
+    loot_table: Res<ChestLootTable>,
+    loot_assets: Res<LootAssets>,
+) {
+    let (player_transform, inventory) = *player;
+    let Some((_, mut lid, lid_global)) = lids
+        .iter_mut()
+        .find(|(chest, ..)| chest.position == tile_use.position)
+    else {
+        return;
+    };
+
+    let state = store.state_mut(tile_use.position, TileType::Chest);
+    if state.looted {
+        println!("📦 The chest is empty");
+        return;
+    }
+    state.opened = true;
+    state.looted = true;
+    store.mark_used(tile_use.position, TileType::Chest);
+    *lid = lid_transform(true);
+
+    let Some(loot) = loot_table.roll(inventory) else {
+        println!("📦 Opened a chest, but it was empty");
+        return;
+    };
+    let chest_position = lid_global.translation();
+    let towards_player = (player_transform.translation - chest_position)
+        .with_y(0.0)
+        .normalize_or_zero();
+    spawn_loot_drop(
+        &mut commands,
+        &loot_assets,
+        loot,
+        chest_position + towards_player * DROP_OFFSET,
+    );
+
+    commands.send_event(ChestOpened {
+        position: chest_position,
+    });
+    println!("📦 Opened a chest");
+}
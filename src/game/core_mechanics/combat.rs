@@ -0,0 +1,317 @@
+use std::collections::VecDeque;
+
+use bevy::{ecs::system::SystemParam, prelude::*, window::PrimaryWindow};
+use bevy_rapier3d::prelude::*;
+
+use crate::game::{
+    core_mechanics::{
+        enemy_ai::o_insan::components::{AIBehavior, Dying, OInsanAI, Possessed},
+        health::{DamageEvent, DamageSource},
+        inventory::Inventory,
+        pause::simulation_running,
+        physics::SOLID_GROUP,
+        status_effects::{StatusEffects, player_can_act},
+    },
+    input::{Action, action_just_pressed},
+    spawn::player::Player,
+    ui::{console::console_closed, toasts::ShowToast, world_map::world_map_closed},
+};
+
+const HISTORY_TICKS: usize = 16;
+pub const PLAYER_RADIUS: f32 = 1.0;
+pub const AI_RADIUS: f32 = 1.2;
+
+const PLAYER_REACH: f32 = 6.0;
+const PLAYER_DAMAGE: f32 = 25.0;
+const WEAPON_WEAR_WARNING: u32 = 3;
+
+const AI_REACH: f32 = 4.5;
+const AI_DAMAGE: f32 = 8.0;
+const AI_ATTACK_COOLDOWN_SECS: f32 = 1.2;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<SimulationTick>()
+        .add_event::<AttackHitEvent>()
+        .add_systems(FixedFirst, advance_simulation_tick)
+        .add_systems(FixedPostUpdate, record_transform_history)
+        .add_systems(
+            Update,
+            (
+                (player_attack, wear_weapon).chain().run_if(
+                    action_just_pressed(Action::Attack)
+                        .and(console_closed)
+                        .and(world_map_closed)
+                        .and(player_can_act)
+                        .and(simulation_running),
+                ),
+                ai_melee_attack.run_if(simulation_running),
+            ),
+        );
+}
+
+// A melee swing or projectile that connected; damage itself goes through DamageEvent
+#[derive(Event, Debug, Clone, Copy)]
+pub struct AttackHitEvent {
+    pub attacker: Entity,
+    pub target: Entity,
+}
+
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct SimulationTick(pub u64);
+
+impl SimulationTick {
+    // Fractional tick matching what is on screen: between the last two fixed steps
+    pub fn sample_point(&self, fixed_time: &Time<Fixed>) -> f32 {
+        self.0.saturating_sub(1) as f32 + fixed_time.overstep_fraction()
+    }
+}
+
+// The tick on screen, read off the two resources it takes
+#[derive(SystemParam)]
+struct OnScreenTick<'w> {
+    tick: Res<'w, SimulationTick>,
+    fixed_time: Res<'w, Time<Fixed>>,
+}
+
+impl OnScreenTick<'_> {
+    fn sample_point(&self) -> f32 {
+        self.tick.sample_point(&self.fixed_time)
+    }
+}
+
+// Positions at the last few fixed ticks, so attacker and target can be compared at the same tick
+#[derive(Component, Debug, Default)]
+pub struct TransformHistory {
+    samples: VecDeque<(u64, Vec3)>,
+}
+
+impl TransformHistory {
+    pub fn position_at(&self, tick: f32) -> Option<Vec3> {
+        let (first_tick, first) = *self.samples.front()?;
+        if tick <= first_tick as f32 {
+            return Some(first);
+        }
+
+        for i in 1..self.samples.len() {
+            let (from_tick, from) = self.samples[i - 1];
+            let (to_tick, to) = self.samples[i];
+            if tick <= to_tick as f32 {
+                let t = (tick - from_tick as f32) / (to_tick - from_tick).max(1) as f32;
+                return Some(from.lerp(to, t));
+            }
+        }
+
+        self.samples.back().map(|(_, position)| *position)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct HitProbe {
+    pub origin: Vec3,
+    pub direction: Vec3, // Flattened to the ground plane
+    pub reach: f32,
+}
+
+impl HitProbe {
+    // Distance along the probe if a target of `radius` at `position` is touched
+    pub fn test(&self, position: Vec3, radius: f32) -> Option<f32> {
+        let to_target = (position - self.origin).with_y(0.0);
+        let direction = self.direction.with_y(0.0).normalize_or_zero();
+        let along = to_target.dot(direction).clamp(0.0, self.reach);
+        let closest = direction * along;
+
+        (closest.distance(to_target) <= radius).then_some(along)
+    }
+}
+
+// Samples attacker and target at the same simulation tick before testing the probe
+pub fn interpolated_hit(
+    attacker: &TransformHistory,
+    target: &TransformHistory,
+    tick: f32,
+    direction: Vec3,
+    reach: f32,
+    target_radius: f32,
+) -> Option<f32> {
+    let probe = HitProbe {
+        origin: attacker.position_at(tick)?,
+        direction,
+        reach,
+    };
+    probe.test(target.position_at(tick)?, target_radius)
+}
+
+#[derive(Component, Debug)]
+pub struct MeleeCooldown(pub Timer);
+
+impl Default for MeleeCooldown {
+    fn default() -> Self {
+        Self(Timer::from_seconds(
+            AI_ATTACK_COOLDOWN_SECS,
+            TimerMode::Once,
+        ))
+    }
+}
+
+fn advance_simulation_tick(mut tick: ResMut<SimulationTick>) {
+    tick.0 += 1;
+}
+
+fn record_transform_history(
+    tick: Res<SimulationTick>,
+    mut histories: Query<(&Transform, &mut TransformHistory)>,
+) {
+    for (transform, mut history) in histories.iter_mut() {
+        history.samples.push_back((tick.0, transform.translation));
+        while history.samples.len() > HISTORY_TICKS {
+            history.samples.pop_front();
+        }
+    }
+}
+
+// Only the closest AI in front takes the swing, and a wall or tree in between takes it instead
+fn player_attack(
+    mut damage_events: EventWriter<DamageEvent>,
+    mut hit_events: EventWriter<AttackHitEvent>,
+    player: Single<(Entity, &Transform, &Inventory, &TransformHistory), With<Player>>,
+    targets: Query<(Entity, &TransformHistory, Has<Dying>), With<OInsanAI>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    tick: OnScreenTick,
+    rapier: ReadRapierContext,
+) {
+    let (player_entity, transform, inventory, history) = *player;
+    if !inventory.holds_melee_weapon() || !window.focused {
+        return;
+    }
+
+    let sample = tick.sample_point();
+    let forward = transform.forward().as_vec3();
+
+    let hit = targets
+        .iter()
+        .filter(|(_, _, is_dying)| !is_dying)
+        .filter_map(|(entity, target, _)| {
+            interpolated_hit(history, target, sample, forward, PLAYER_REACH, AI_RADIUS)
+                .map(|distance| (entity, distance))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1));
+    let Some((target, distance)) = hit else {
+        return;
+    };
+
+    let level = forward.with_y(0.0).normalize_or_zero();
+    let walls = QueryFilter::default().groups(CollisionGroups::new(Group::ALL, SOLID_GROUP));
+    let blocked = level != Vec3::ZERO
+        && rapier.single().is_ok_and(|context| {
+            context
+                .cast_ray(transform.translation, level, distance, true, walls)
+                .is_some()
+        });
+    if blocked {
+        return;
+    }
+
+    hit_events.write(AttackHitEvent {
+        attacker: player_entity,
+        target,
+    });
+    damage_events.write(DamageEvent {
+        target,
+        amount: PLAYER_DAMAGE,
+        source: DamageSource::Attack {
+            attacker: player_entity,
+        },
+    });
+}
+
+// The AI reads the held item every frame, so a blade breaking mid-fight turns the chase at once
+fn wear_weapon(
+    mut inventory: Single<&mut Inventory, With<Player>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    if !inventory.holds_melee_weapon() || !window.focused {
+        return;
+    }
+    let Some(blade) = inventory.held_mut() else {
+        return;
+    };
+
+    blade.wear += 1;
+    let remaining = blade.uses_left().unwrap_or_default();
+    if remaining == WEAPON_WEAR_WARNING {
+        toasts.write(ShowToast {
+            message: "Your blade is about to give".to_string(),
+        });
+    } else if remaining == 0 {
+        println!("💥 Your blade broke");
+        inventory.consume_held();
+        toasts.write(ShowToast {
+            message: "Your blade breaks!".to_string(),
+        });
+    }
+}
+
+type MeleeAttackerQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static OInsanAI,
+        &'static TransformHistory,
+        &'static mut MeleeCooldown,
+        Option<&'static StatusEffects>,
+    ),
+    (Without<Dying>, Without<Possessed>),
+>;
+
+fn ai_melee_attack(
+    mut damage_events: EventWriter<DamageEvent>,
+    mut hit_events: EventWriter<AttackHitEvent>,
+    mut attackers: MeleeAttackerQuery,
+    player: Single<(Entity, &TransformHistory), With<Player>>,
+    tick: OnScreenTick,
+    time: Res<Time>,
+) {
+    let (player_entity, player_history) = *player;
+    let sample = tick.sample_point();
+
+    for (entity, ai, history, mut cooldown, effects) in attackers.iter_mut() {
+        cooldown.0.tick(time.delta());
+        if ai.current_behavior != AIBehavior::Chasing
+            || !cooldown.0.finished()
+            || effects.is_some_and(StatusEffects::is_stunned)
+        {
+            continue;
+        }
+
+        let (Some(from), Some(to)) = (
+            history.position_at(sample),
+            player_history.position_at(sample),
+        ) else {
+            continue;
+        };
+
+        if interpolated_hit(
+            history,
+            player_history,
+            sample,
+            to - from,
+            AI_REACH,
+            PLAYER_RADIUS,
+        )
+        .is_some()
+        {
+            cooldown.0.reset();
+            hit_events.write(AttackHitEvent {
+                attacker: entity,
+                target: player_entity,
+            });
+            damage_events.write(DamageEvent {
+                target: player_entity,
+                amount: AI_DAMAGE,
+                source: DamageSource::Attack { attacker: entity },
+            });
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+
+use crate::game::core_mechanics::pause::simulation_running;
+
+const DAY_LENGTH_SECS: f32 = 600.0; // One full 24 hour cycle
+const NOON_ILLUMINANCE: f32 = 10_000.0;
+const NIGHT_ILLUMINANCE: f32 = 50.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<WorldClock>().add_systems(
+        Update,
+        (advance_clock, update_sun)
+            .chain()
+            .run_if(simulation_running),
+    );
+}
+
+#[derive(Resource, Debug)]
+pub struct WorldClock {
+    pub hour: f32, // 0.0..24.0
+}
+
+impl Default for WorldClock {
+    fn default() -> Self {
+        Self { hour: 9.0 }
+    }
+}
+
+impl WorldClock {
+    pub fn is_night(&self) -> bool {
+        self.hour < 6.0 || self.hour >= 20.0
+    }
+
+    // 0.0 at midnight, 1.0 at noon
+    pub fn daylight(&self) -> f32 {
+        let angle = (self.hour / 24.0) * std::f32::consts::TAU;
+        (0.5 - 0.5 * angle.cos()).clamp(0.0, 1.0)
+    }
+}
+
+fn advance_clock(mut clock: ResMut<WorldClock>, time: Res<Time>) {
+    clock.hour = (clock.hour + time.delta_secs() * 24.0 / DAY_LENGTH_SECS) % 24.0;
+}
+
+fn update_sun(
+    clock: Res<WorldClock>,
+    sun: Single<(&mut DirectionalLight, &mut Transform)>,
+    mut ambient: ResMut<AmbientLight>,
+) {
+    let (mut light, mut transform) = sun.into_inner();
+    let daylight = clock.daylight();
+
+    light.illuminance = NIGHT_ILLUMINANCE + (NOON_ILLUMINANCE - NIGHT_ILLUMINANCE) * daylight;
+    ambient.brightness = 20.0 + 60.0 * daylight;
+
+    // Sun circles around the world, highest at noon
+    let yaw = (clock.hour / 24.0) * std::f32::consts::TAU;
+    let elevation = daylight.max(0.1) * std::f32::consts::FRAC_PI_2;
+    transform.rotation = Quat::from_euler(EulerRot::YXZ, yaw, -elevation, 0.0);
+}
@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    core_mechanics::enemy_ai::director::AiDirector,
+    ui::console::{ConsoleCommand, ConsoleState},
+};
+
+pub const MIN_MULTIPLIER: f32 = 0.1;
+pub const MAX_MULTIPLIER: f32 = 5.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Difficulty>().add_systems(
+        Update,
+        (
+            difficulty_command,
+            apply_to_director.run_if(resource_changed::<Difficulty>),
+        )
+            .chain(),
+    );
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DifficultyPreset {
+    Story,
+    Normal,
+    Hard,
+    Custom, // Any multiplier changed by hand
+}
+
+// Saved with the world, so each save keeps the difficulty it was played on
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Difficulty {
+    pub preset: DifficultyPreset,
+    pub enemy_damage: f32,  // Scales damage enemies deal to the player
+    pub spawn_density: f32, // Scales how many enemies the director keeps around
+    pub stamina_drain: f32, // Scales stamina costs
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Self::preset(DifficultyPreset::Normal)
+    }
+}
+
+impl Difficulty {
+    pub fn preset(preset: DifficultyPreset) -> Self {
+        let (enemy_damage, spawn_density, stamina_drain) = match preset {
+            DifficultyPreset::Story => (0.5, 0.5, 0.5),
+            DifficultyPreset::Normal | DifficultyPreset::Custom => (1.0, 1.0, 1.0),
+            DifficultyPreset::Hard => (1.5, 1.5, 1.25),
+        };
+
+        Self {
+            preset,
+            enemy_damage,
+            spawn_density,
+            stamina_drain,
+        }
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "difficulty: {:?} (damage x{}, density x{}, stamina x{})",
+            self.preset, self.enemy_damage, self.spawn_density, self.stamina_drain
+        )
+    }
+}
+
+// `difficulty` shows the current settings, `difficulty story|normal|hard` picks a preset,
+// `difficulty damage|density|stamina <multiplier>` tunes one value
+fn difficulty_command(
+    mut commands_in: EventReader<ConsoleCommand>,
+    mut console: ResMut<ConsoleState>,
+    mut difficulty: ResMut<Difficulty>,
+) {
+    for command in commands_in.read().filter(|c| c.name == "difficulty") {
+        let args: Vec<&str> = command.args.iter().map(String::as_str).collect();
+
+        match args.as_slice() {
+            [] => {}
+            ["story"] => *difficulty = Difficulty::preset(DifficultyPreset::Story),
+            ["normal"] => *difficulty = Difficulty::preset(DifficultyPreset::Normal),
+            ["hard"] => *difficulty = Difficulty::preset(DifficultyPreset::Hard),
+            [setting, value] => {
+                let Ok(value) = value.parse::<f32>() else {
+                    console.print(format!("not a number: {}", value));
+                    continue;
+                };
+                let value = value.clamp(MIN_MULTIPLIER, MAX_MULTIPLIER);
+                match *setting {
+                    "damage" => difficulty.enemy_damage = value,
+                    "density" => difficulty.spawn_density = value,
+                    "stamina" => difficulty.stamina_drain = value,
+                    _ => {
+                        console.print(format!("unknown difficulty setting: {}", setting));
+                        continue;
+                    }
+                }
+                difficulty.preset = DifficultyPreset::Custom;
+            }
+            _ => {
+                console.print(
+                    "usage: difficulty [story|normal|hard] | difficulty <damage|density|stamina> <multiplier>",
+                );
+                continue;
+            }
+        }
+
+        console.print(difficulty.describe());
+    }
+}
+
+fn apply_to_director(difficulty: Res<Difficulty>, mut director: ResMut<AiDirector>) {
+    director.spawn_density = difficulty.spawn_density;
+}
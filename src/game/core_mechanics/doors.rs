@@ -0,0 +1,109 @@
+use bevy::prelude::*;
+
+use crate::game::{
+    core_mechanics::{
+        interactions::{InteractionStore, nearest_tile},
+        oz_devinimli_yaratim::{
+            cells::{Cell, CellSpatialIndex, GenerationSettings},
+            odyrules::commons::TileType,
+            tiles_meshes_models::TileMeshes,
+        },
+        pause::simulation_running,
+    },
+    input::{Action, action_just_pressed},
+    spawn::player::Player,
+    ui::{console::console_closed, world_map::world_map_closed},
+};
+
+const TOGGLE_RANGE: f32 = 8.0;
+const LEAF_HEIGHT: f32 = 4.5;
+const LEAF_THICKNESS: f32 = 0.15; // Fraction of the cell
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            attach_door_leaves,
+            toggle_door.run_if(
+                action_just_pressed(Action::Interact)
+                    .and(console_closed)
+                    .and(world_map_closed)
+                    .and(simulation_running),
+            ),
+        )
+            .chain(),
+    );
+}
+
+// The closed door panel; hidden while the door is open
+#[derive(Component)]
+struct DoorLeaf;
+
+fn leaf_visibility(opened: bool) -> Visibility {
+    if opened {
+        Visibility::Hidden
+    } else {
+        Visibility::Inherited
+    }
+}
+
+// Doors come back in the state they were left in when their cell respawns
+fn attach_door_leaves(
+    mut commands: Commands,
+    changed_cells: Query<(Entity, &Cell), Changed<Cell>>,
+    store: Res<InteractionStore>,
+    tile_meshes: Res<TileMeshes>,
+) {
+    for (entity, cell) in changed_cells.iter() {
+        if cell.tile_type != Some(TileType::Door) {
+            continue;
+        }
+
+        let opened = store.state(cell.position, TileType::Door).opened;
+        // The door cell is scaled by the cell edge on X and Z, but not on Y
+        commands.entity(entity).with_child((
+            DoorLeaf,
+            Mesh3d(tile_meshes.block_mesh.clone()),
+            MeshMaterial3d(tile_meshes.door_material.clone()),
+            Transform::from_xyz(0.0, LEAF_HEIGHT / 2.0, 0.0).with_scale(Vec3::new(
+                1.0,
+                LEAF_HEIGHT,
+                LEAF_THICKNESS,
+            )),
+            leaf_visibility(opened),
+        ));
+    }
+}
+
+fn toggle_door(
+    mut store: ResMut<InteractionStore>,
+    player: Single<&Transform, With<Player>>,
+    cells: Query<(&Cell, &Transform)>,
+    mut leaves: Query<(&ChildOf, &mut Visibility), With<DoorLeaf>>,
+    spatial_index: Res<CellSpatialIndex>,
+    settings: Res<GenerationSettings>,
+) {
+    let Some((door_entity, door, _)) = nearest_tile(
+        player.translation,
+        TOGGLE_RANGE,
+        TileType::Door,
+        &cells,
+        &spatial_index,
+        settings.cell_edge_length as f32,
+    ) else {
+        return;
+    };
+
+    let state = store.state_mut(door.position, TileType::Door);
+    state.opened = !state.opened;
+    let opened = state.opened;
+    store.mark_used(door.position, TileType::Door);
+
+    for (child_of, mut visibility) in leaves.iter_mut() {
+        if child_of.parent() == door_entity {
+            *visibility = leaf_visibility(opened);
+        }
+    }
+
+    println!("🚪 Door {}", if opened { "opened" } else { "closed" });
+}
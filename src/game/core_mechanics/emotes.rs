@@ -0,0 +1,152 @@
+use bevy::{input::mouse::AccumulatedMouseScroll, prelude::*};
+
+use crate::game::{
+    core_mechanics::pause::simulation_running,
+    input::{Action, Actions},
+    spawn::player::Player,
+    ui::{console::console_closed, layers::UiLayer, world_map::world_map_closed},
+};
+
+const SELECTED_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+const IDLE_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.6);
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_event::<SocialEvent>()
+        .init_resource::<EmoteWheel>()
+        .add_systems(Startup, setup_emote_wheel)
+        .add_systems(
+            Update,
+            (emote_wheel_input, update_emote_wheel)
+                .chain()
+                .run_if(simulation_running.and(console_closed).and(world_map_closed)),
+        );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Emote {
+    Wave,
+    Bow,
+    Threaten,
+}
+
+impl Emote {
+    const ALL: [Emote; 3] = [Emote::Wave, Emote::Bow, Emote::Threaten];
+
+    fn label(self) -> &'static str {
+        match self {
+            Emote::Wave => "👋 Wave",
+            Emote::Bow => "🙇 Bow",
+            Emote::Threaten => "😠 Threaten",
+        }
+    }
+}
+
+// The player did something at `position` that nearby AI can react to
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SocialEvent {
+    pub emote: Emote,
+    pub position: Vec3,
+}
+
+#[derive(Resource, Default)]
+struct EmoteWheel {
+    open: bool,
+    selected: usize, // Into Emote::ALL
+}
+
+#[derive(Component)]
+struct EmoteWheelRoot;
+
+#[derive(Component)]
+struct EmoteOption(usize);
+
+fn setup_emote_wheel(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("EmoteWheel"),
+            EmoteWheelRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                bottom: Val::Percent(20.0),
+                justify_content: JustifyContent::Center,
+                column_gap: Val::Px(24.0),
+                display: Display::None,
+                ..Default::default()
+            },
+            UiLayer::Hud.z_index(),
+        ))
+        .with_children(|parent| {
+            for (index, emote) in Emote::ALL.iter().enumerate() {
+                parent.spawn((
+                    EmoteOption(index),
+                    Text::new(emote.label()),
+                    TextFont::from_font_size(22.0),
+                    TextColor(IDLE_COLOR),
+                    Node {
+                        padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                        ..Default::default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+                ));
+            }
+        });
+}
+
+// Hold to open, scroll to pick, release to emote
+fn emote_wheel_input(
+    mut wheel: ResMut<EmoteWheel>,
+    mut social_events: EventWriter<SocialEvent>,
+    actions: Actions,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+    player: Single<&Transform, With<Player>>,
+) {
+    if actions.just_pressed(Action::EmoteWheel) {
+        wheel.open = true;
+    }
+    if !wheel.open {
+        return;
+    }
+
+    if mouse_scroll.delta.y != 0.0 {
+        let step = if mouse_scroll.delta.y > 0.0 {
+            Emote::ALL.len() - 1
+        } else {
+            1
+        };
+        wheel.selected = (wheel.selected + step) % Emote::ALL.len();
+    }
+
+    if actions.just_released(Action::EmoteWheel) {
+        wheel.open = false;
+        let emote = Emote::ALL[wheel.selected];
+        println!("🎭 Player emotes: {:?}", emote);
+        social_events.write(SocialEvent {
+            emote,
+            position: player.translation,
+        });
+    }
+}
+
+fn update_emote_wheel(
+    wheel: Res<EmoteWheel>,
+    mut root: Single<&mut Node, With<EmoteWheelRoot>>,
+    mut options: Query<(&EmoteOption, &mut TextColor)>,
+) {
+    if !wheel.is_changed() {
+        return;
+    }
+
+    root.display = if wheel.open {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    for (option, mut color) in options.iter_mut() {
+        color.0 = if option.0 == wheel.selected {
+            SELECTED_COLOR
+        } else {
+            IDLE_COLOR
+        };
+    }
+}
@@ -0,0 +1,166 @@
+use std::{f32::consts::TAU, time::Duration};
+
+use bevy::{
+    platform::collections::HashMap,
+    prelude::*,
+    render::primitives::{Frustum, Sphere as BoundingSphere},
+    time::common_conditions::on_timer,
+};
+use rand::prelude::*;
+
+use crate::game::{
+    core_mechanics::{
+        enemy_ai::{encounters::spawn_encounters, o_insan::components::OInsanAI},
+        oz_devinimli_yaratim::{
+            cells::GenerationSettings,
+            grid::{cell_center, world_to_grid},
+        },
+        pause::simulation_running,
+        walkability::WalkabilityGrid,
+    },
+    spawn::player::Player,
+};
+
+const DIRECTOR_INTERVAL_MS: u64 = 4000;
+const SPAWN_RADIUS: f32 = 2.0; // Bounding sphere used for the frustum test
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<AiDirector>()
+        .add_event::<SpawnEnemyEvent>()
+        .add_systems(
+            Update,
+            (
+                pick_spawn_point.run_if(on_timer(Duration::from_millis(DIRECTOR_INTERVAL_MS))),
+                spawn_encounters,
+            )
+                .chain()
+                .run_if(simulation_running),
+        );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpawnRejection {
+    TooClose,
+    InView,
+    Unresolved, // Cell not loaded or not collapsed yet
+    NotWalkable,
+}
+
+#[derive(Resource, Debug)]
+pub struct AiDirector {
+    pub max_enemies: usize,
+    pub min_distance: f32,
+    pub max_distance: f32,
+    pub attempts_per_tick: u32,
+    pub enemy_health: f32,
+    pub spawn_density: f32, // From the difficulty settings
+
+    pub spawned: u32,
+    pub rejections: HashMap<SpawnRejection, u32>,
+}
+
+impl Default for AiDirector {
+    fn default() -> Self {
+        Self {
+            max_enemies: 4,
+            min_distance: 35.0,
+            max_distance: 70.0,
+            attempts_per_tick: 12,
+            enemy_health: 100.0,
+            spawn_density: 1.0,
+
+            spawned: 0,
+            rejections: HashMap::new(),
+        }
+    }
+}
+
+impl AiDirector {
+    pub fn enemy_cap(&self) -> usize {
+        (self.max_enemies as f32 * self.spawn_density).round() as usize
+    }
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SpawnEnemyEvent {
+    pub position: Vec3,
+}
+
+pub fn validate_spawn_point(
+    point: Vec3,
+    player_position: Vec3,
+    frustum: &Frustum,
+    walkability: &WalkabilityGrid,
+    director: &AiDirector,
+    cell_edge_length: f32,
+) -> Result<(), SpawnRejection> {
+    if point.with_y(0.0).distance(player_position.with_y(0.0)) < director.min_distance {
+        return Err(SpawnRejection::TooClose);
+    }
+
+    let sphere = BoundingSphere {
+        center: point.into(),
+        radius: SPAWN_RADIUS,
+    };
+    if frustum.intersects_sphere(&sphere, true) {
+        return Err(SpawnRejection::InView);
+    }
+
+    match walkability.get(world_to_grid(point, cell_edge_length)) {
+        None => Err(SpawnRejection::Unresolved),
+        Some(false) => Err(SpawnRejection::NotWalkable),
+        Some(true) => Ok(()),
+    }
+}
+
+fn pick_spawn_point(
+    mut director: ResMut<AiDirector>,
+    mut spawn_events: EventWriter<SpawnEnemyEvent>,
+    enemies: Query<(), With<OInsanAI>>,
+    player: Single<&Transform, With<Player>>,
+    camera: Single<&Frustum, With<Camera3d>>,
+    walkability: Res<WalkabilityGrid>,
+    settings: Res<GenerationSettings>,
+) {
+    if enemies.iter().count() >= director.enemy_cap() {
+        return;
+    }
+
+    let mut rng = rand::rng();
+    let cell_edge_length = settings.cell_edge_length as f32;
+
+    for _ in 0..director.attempts_per_tick {
+        let angle = rng.random_range(0.0..TAU);
+        let distance = rng.random_range(director.min_distance..director.max_distance);
+        // Snapped so the spawn lands on the same cell whose walkability gets checked
+        let point = cell_center(
+            player.translation.with_y(0.0)
+                + Vec3::new(angle.cos() * distance, 0.0, angle.sin() * distance),
+            cell_edge_length,
+        );
+
+        match validate_spawn_point(
+            point,
+            player.translation,
+            &camera,
+            &walkability,
+            &director,
+            cell_edge_length,
+        ) {
+            Ok(()) => {
+                director.spawned += 1;
+                spawn_events.write(SpawnEnemyEvent { position: point });
+                return;
+            }
+            Err(rejection) => {
+                debug!("AI spawn at {:?} rejected: {:?}", point, rejection);
+                *director.rejections.entry(rejection).or_insert(0) += 1;
+            }
+        }
+    }
+
+    println!(
+        "🚫 AI director found no valid spawn point ({} attempts), rejections so far: {:?}",
+        director.attempts_per_tick, director.rejections
+    );
+}
@@ -0,0 +1,364 @@
+use std::f32::consts::TAU;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use rand::prelude::*;
+use serde::Deserialize;
+
+use crate::game::core_mechanics::{
+    enemy_ai::{
+        director::{AiDirector, SpawnEnemyEvent},
+        o_insan::{
+            components::{AIBehavior, Leash, Morale, OInsanAI},
+            spawn::{MONSTER_MODEL, spawn_o_insan},
+        },
+        spawn_tables::LocalSpawns,
+    },
+    health::Health,
+    interactions::{Interactable, InteractionEvent, LookSubject},
+    inventory::{Inventory, Item, ItemStack},
+    loot::{LootAssets, LootKind, spawn_loot_drop},
+    pause::simulation_running,
+    reputation::{Faction, Reputation},
+};
+
+const BRUTE_HEALTH: f32 = 2.0; // Times the director's enemy health
+const BRUTE_SCALE: f32 = 1.3;
+const COWARD_HEALTH: f32 = 0.6;
+const STALKER_HEALTH: f32 = 0.8;
+const STALKER_SPEED: f32 = 13.0;
+const STALKER_DETECTION: f32 = 60.0;
+const WANDERER_SPEED: f32 = 6.0;
+const WANDERER_DETECTION: f32 = 20.0;
+const GROUP_SPACING: f32 = 5.0; // Between the copies of an encounter that spawn together
+const ESCORT_RADIUS: f32 = 25.0; // How far an escort strays from its trader
+const TRADER_SPEED: f32 = 2.5;
+const TRADER_TURN_SECS: f32 = 6.0;
+const TRADE_RANGE: f32 = 6.0;
+const WEAPON_PRICE: f32 = 30.0; // In health, before the Caravan's opinion of the player
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            (wander_traders, follow_escorted_traders)
+                .chain()
+                .run_if(simulation_running),
+            trade_with_traders,
+        ),
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum EncounterTemplate {
+    Lone,
+    BruteAndCowards,
+    GuardedCache,
+    TraderWithEscort,
+    Stalker,
+    Wanderer,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EncounterRole {
+    Enemy,
+    Brute,
+    Coward,
+    Guard, // Posted on the spawn point, with a loot cache to watch
+    Trader,
+    Escort,
+    Stalker,  // Quick and sharp-eyed, but frail
+    Wanderer, // Slow and easily missed
+}
+
+impl EncounterTemplate {
+    // Offsets are from the validated spawn point, in world units on the ground plane
+    fn members(self) -> &'static [(EncounterRole, (f32, f32))] {
+        match self {
+            EncounterTemplate::Lone => &[(EncounterRole::Enemy, (0.0, 0.0))],
+            EncounterTemplate::BruteAndCowards => &[
+                (EncounterRole::Brute, (0.0, 0.0)),
+                (EncounterRole::Coward, (3.0, 2.0)),
+                (EncounterRole::Coward, (-3.0, 2.0)),
+            ],
+            EncounterTemplate::GuardedCache => &[(EncounterRole::Guard, (2.0, 0.0))],
+            EncounterTemplate::TraderWithEscort => &[
+                (EncounterRole::Trader, (0.0, 0.0)),
+                (EncounterRole::Escort, (2.5, -2.0)),
+            ],
+            EncounterTemplate::Stalker => &[(EncounterRole::Stalker, (0.0, 0.0))],
+            EncounterTemplate::Wanderer => &[(EncounterRole::Wanderer, (0.0, 0.0))],
+        }
+    }
+
+    fn enemy_count(self) -> usize {
+        self.members()
+            .iter()
+            .filter(|(role, _)| *role != EncounterRole::Trader)
+            .count()
+    }
+
+    // Scales the spawn table's odds; denser difficulty settings favour the groups that bring
+    // more fighters
+    fn difficulty_bias(self, spawn_density: f32) -> f32 {
+        match self {
+            EncounterTemplate::BruteAndCowards => spawn_density,
+            EncounterTemplate::TraderWithEscort => 1.0 / spawn_density,
+            _ => 1.0,
+        }
+    }
+}
+
+// Walks around on its own and never fights; escorts keep it company
+#[derive(Component, Debug)]
+pub struct Trader {
+    heading: Vec3,
+    turn_timer: Timer,
+}
+
+#[derive(Component, Debug)]
+pub struct Escort {
+    pub trader: Entity,
+}
+
+#[derive(SystemParam)]
+pub struct EncounterAssets<'w> {
+    asset_server: Res<'w, AssetServer>,
+    loot: Res<'w, LootAssets>,
+}
+
+// Picks an entry of the local spawn table for each spawn point the director found, among
+// those that fit under the biome's enemy cap, then a group size that still fits
+pub fn spawn_encounters(
+    mut commands: Commands,
+    mut spawn_events: EventReader<SpawnEnemyEvent>,
+    director: Res<AiDirector>,
+    spawns: LocalSpawns,
+    enemies: Query<(), With<OInsanAI>>,
+    assets: EncounterAssets,
+) {
+    let mut alive = enemies.iter().count();
+    let mut rng = rand::rng();
+
+    for event in spawn_events.read() {
+        let biome = spawns.biome_at(event.position);
+        let cap = (director.enemy_cap() as f32 * spawns.density(biome)).round() as usize;
+        let room = cap.saturating_sub(alive);
+
+        let Ok(entry) = spawns
+            .entries(biome)
+            .filter(|entry| entry.encounter.enemy_count() * entry.group.0 <= room)
+            .collect::<Vec<_>>()
+            .choose_weighted(&mut rng, |entry| {
+                entry.weight * entry.encounter.difficulty_bias(director.spawn_density)
+            })
+            .copied()
+        else {
+            continue;
+        };
+        let template = entry.encounter;
+        let fits = room
+            .checked_div(template.enemy_count())
+            .unwrap_or(usize::MAX);
+        let group = rng.random_range(entry.group.0..=entry.group.1.max(entry.group.0));
+        let group = group.min(fits);
+        alive += template.enemy_count() * group;
+
+        // The first copy stands on the spawn point, the rest in a ring around it
+        for index in 0..group {
+            let angle = TAU * index as f32 / (group - 1).max(1) as f32;
+            let offset = if index == 0 {
+                Vec3::ZERO
+            } else {
+                Vec3::new(angle.cos(), 0.0, angle.sin()) * GROUP_SPACING
+            };
+            spawn_encounter(
+                &mut commands,
+                template,
+                event.position + offset,
+                &director,
+                &assets,
+            );
+        }
+        println!(
+            "🤖 AI director spawned {} {:?} encounter(s) in the {:?} at {:?}",
+            group, template, biome, event.position
+        );
+    }
+}
+
+fn spawn_encounter(
+    commands: &mut Commands,
+    template: EncounterTemplate,
+    origin: Vec3,
+    director: &AiDirector,
+    assets: &EncounterAssets,
+) {
+    let health = director.enemy_health;
+    let mut trader = None;
+
+    for (role, (dx, dz)) in template.members() {
+        let position = origin + Vec3::new(*dx, 0.0, *dz);
+        if *role == EncounterRole::Trader {
+            trader = Some(spawn_trader(commands, position, &assets.asset_server));
+            continue;
+        }
+
+        let entity = spawn_o_insan(commands, position, health, &assets.asset_server);
+        let mut ai = commands.entity(entity);
+        match role {
+            EncounterRole::Brute => {
+                ai.insert((
+                    OInsanAI {
+                        health: health * BRUTE_HEALTH,
+                        max_health: health * BRUTE_HEALTH,
+                        movement_speed: 8.0,
+                        ..Default::default()
+                    },
+                    Transform::from_translation(position).with_scale(Vec3::splat(BRUTE_SCALE)),
+                ));
+            }
+            EncounterRole::Coward => {
+                ai.insert((
+                    OInsanAI {
+                        health: health * COWARD_HEALTH,
+                        max_health: health * COWARD_HEALTH,
+                        ..Default::default()
+                    },
+                    Morale::coward(),
+                ));
+            }
+            EncounterRole::Guard => {
+                ai.insert((
+                    OInsanAI {
+                        health,
+                        max_health: health,
+                        current_behavior: AIBehavior::Guarding,
+                        guard_post: Some(origin),
+                        ..Default::default()
+                    },
+                    Faction::Wardens,
+                ));
+                spawn_loot_drop(
+                    commands,
+                    &assets.loot,
+                    LootKind::Item(ItemStack::new(Item::Blade)),
+                    origin,
+                );
+            }
+            EncounterRole::Escort => {
+                if let Some(trader) = trader {
+                    ai.insert((
+                        Escort { trader },
+                        Leash {
+                            radius: ESCORT_RADIUS,
+                            ..Leash::new(position)
+                        },
+                        Faction::Caravan,
+                    ));
+                }
+            }
+            EncounterRole::Stalker => {
+                ai.insert(OInsanAI {
+                    health: health * STALKER_HEALTH,
+                    max_health: health * STALKER_HEALTH,
+                    movement_speed: STALKER_SPEED,
+                    detection_range: STALKER_DETECTION,
+                    ..Default::default()
+                });
+            }
+            EncounterRole::Wanderer => {
+                ai.insert(OInsanAI {
+                    health,
+                    max_health: health,
+                    movement_speed: WANDERER_SPEED,
+                    detection_range: WANDERER_DETECTION,
+                    ..Default::default()
+                });
+            }
+            EncounterRole::Enemy | EncounterRole::Trader => {}
+        }
+    }
+}
+
+fn spawn_trader(commands: &mut Commands, position: Vec3, asset_server: &AssetServer) -> Entity {
+    commands
+        .spawn((
+            Name::new("Trader"),
+            Trader {
+                heading: Vec3::X,
+                turn_timer: Timer::from_seconds(TRADER_TURN_SECS, TimerMode::Repeating),
+            },
+            Interactable {
+                verb: "Trade",
+                range: TRADE_RANGE,
+            },
+            SceneRoot(asset_server.load(GltfAssetLabel::Scene(0).from_asset(MONSTER_MODEL))),
+            Transform::from_translation(position),
+        ))
+        .id()
+}
+
+fn wander_traders(mut traders: Query<(&mut Trader, &mut Transform)>, time: Res<Time>) {
+    let mut rng = rand::rng();
+
+    for (mut trader, mut transform) in traders.iter_mut() {
+        if trader.turn_timer.tick(time.delta()).just_finished() {
+            let angle = rng.random_range(0.0..std::f32::consts::TAU);
+            trader.heading = Vec3::new(angle.cos(), 0.0, angle.sin());
+        }
+        transform.translation += trader.heading * TRADER_SPEED * time.delta_secs();
+        let look_target = transform.translation + trader.heading;
+        transform.look_at(look_target, Vec3::Y);
+    }
+}
+
+// An escort's leash is tied to its trader, so it defends the trader instead of a fixed spot
+fn follow_escorted_traders(
+    mut escorts: Query<(&Escort, &mut Leash)>,
+    traders: Query<&Transform, With<Trader>>,
+) {
+    for (escort, mut leash) in escorts.iter_mut() {
+        if let Ok(trader) = traders.get(escort.trader) {
+            leash.home = trader.translation;
+        }
+    }
+}
+
+// Traders sell a blade for some of the player's health; the Caravan's opinion sets the price
+fn trade_with_traders(
+    mut interactions: EventReader<InteractionEvent>,
+    mut buyers: Query<(&mut Inventory, &mut Health)>,
+    traders: Query<(), With<Trader>>,
+    reputation: Res<Reputation>,
+) {
+    // Only as far away as the trader's Interactable reaches
+    let Some(buyer) = interactions.read().find_map(|interaction| {
+        matches!(interaction.subject, LookSubject::Entity(entity) if traders.contains(entity))
+            .then_some(interaction.user)
+    }) else {
+        return;
+    };
+    let Ok((mut inventory, mut health)) = buyers.get_mut(buyer) else {
+        return;
+    };
+
+    let Some(price) = reputation.price(Faction::Caravan, WEAPON_PRICE) else {
+        println!("🚫 The trader won't deal with you");
+        return;
+    };
+    if inventory.contains(Item::Blade) {
+        println!("🧳 The trader has nothing you need");
+        return;
+    }
+    if health.current <= price {
+        println!("🧳 The trader wants {:.0} health for a blade", price);
+        return;
+    }
+    if inventory.add(ItemStack::new(Item::Blade)).is_some() {
+        println!("🧳 You have no room for a blade");
+        return;
+    }
+
+    health.current -= price;
+    println!("🧳 Bought a blade for {:.0} health", price);
+}
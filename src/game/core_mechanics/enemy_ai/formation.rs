@@ -0,0 +1,112 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::game::{
+    core_mechanics::{
+        enemy_ai::o_insan::{
+            components::{AIBehavior, Dying, OInsanAI},
+            systems::ai_movement_system,
+        },
+        movement::PlayerVelocity,
+        pause::simulation_running,
+    },
+    spawn::player::Player,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<FormationSettings>().add_systems(
+        Update,
+        assign_surround_slots
+            .before(ai_movement_system)
+            .run_if(simulation_running),
+    );
+}
+
+#[derive(Resource, Debug)]
+pub struct FormationSettings {
+    pub slot_radius: f32,
+    pub prediction_secs: f32, // How far ahead the player's position is predicted
+}
+
+impl Default for FormationSettings {
+    fn default() -> Self {
+        Self {
+            slot_radius: 4.0, // Inside AI melee reach
+            prediction_secs: 0.6,
+        }
+    }
+}
+
+// Where this pack member should stand; only present while two or more AIs chase together
+#[derive(Component, Debug, Clone, Copy)]
+pub struct FormationSlot {
+    pub target: Vec3,
+}
+
+fn assign_surround_slots(
+    mut commands: Commands,
+    ais: Query<(Entity, &OInsanAI, &Transform, Has<FormationSlot>), Without<Dying>>,
+    player: Single<&Transform, (With<Player>, Without<OInsanAI>)>,
+    velocity: Res<PlayerVelocity>,
+    settings: Res<FormationSettings>,
+) {
+    let mut chasers = Vec::new();
+    for (entity, ai, transform, has_slot) in ais.iter() {
+        if ai.current_behavior == AIBehavior::Chasing {
+            chasers.push((entity, transform.translation));
+        } else if has_slot {
+            commands.entity(entity).remove::<FormationSlot>();
+        }
+    }
+
+    if chasers.len() < 2 {
+        for (entity, _) in chasers {
+            commands.entity(entity).remove::<FormationSlot>();
+        }
+        return;
+    }
+
+    let predicted = (player.translation + **velocity * settings.prediction_secs).with_y(0.0);
+
+    // Start the ring on the side the pack approaches from so nobody has to run around the player
+    let centroid = chasers.iter().map(|(_, p)| *p).sum::<Vec3>() / chasers.len() as f32;
+    let approach = (centroid - predicted).with_y(0.0);
+    let base_angle = approach.z.atan2(approach.x);
+
+    let slots: Vec<Vec3> = (0..chasers.len())
+        .map(|i| {
+            let angle = base_angle + TAU * i as f32 / chasers.len() as f32;
+            predicted + Vec3::new(angle.cos(), 0.0, angle.sin()) * settings.slot_radius
+        })
+        .collect();
+
+    // Greedy assignment: closest chaser/slot pairs first
+    let mut pairs = Vec::new();
+    for (chaser_index, (_, position)) in chasers.iter().enumerate() {
+        for (slot_index, slot) in slots.iter().enumerate() {
+            pairs.push((
+                position.with_y(0.0).distance(*slot),
+                chaser_index,
+                slot_index,
+            ));
+        }
+    }
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut chaser_taken = vec![false; chasers.len()];
+    let mut slot_taken = vec![false; slots.len()];
+    for (_, chaser_index, slot_index) in pairs {
+        if chaser_taken[chaser_index] || slot_taken[slot_index] {
+            continue;
+        }
+        chaser_taken[chaser_index] = true;
+        slot_taken[slot_index] = true;
+
+        commands
+            .entity(chasers[chaser_index].0)
+            .insert(FormationSlot {
+                target: slots[slot_index],
+            });
+    }
+}
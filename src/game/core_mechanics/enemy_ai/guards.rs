@@ -0,0 +1,87 @@
+use bevy::{platform::collections::HashSet, prelude::*};
+use rand::prelude::*;
+
+use crate::game::core_mechanics::{
+    enemy_ai::{
+        director::AiDirector,
+        o_insan::{
+            components::{AIBehavior, OInsanAI},
+            spawn::spawn_o_insan,
+        },
+    },
+    oz_devinimli_yaratim::{
+        cells::{Cell, CellSpatialIndex},
+        odycore::regions::RegionComplete,
+        odyrules::commons::TileType,
+    },
+    pause::simulation_running,
+    reputation::Faction,
+};
+
+const GUARD_CHANCE: f64 = 0.35; // Of a freshly generated chest getting a guard
+const GUARD_OFFSET: f32 = 3.0; // Beside the chest rather than inside it
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<RolledGuardPosts>()
+        .add_systems(Update, bind_chest_guards.run_if(simulation_running));
+}
+
+// Posts already rolled for a guard, so a chest that unloads and comes back doesn't get another
+#[derive(Resource, Default)]
+struct RolledGuardPosts(HashSet<(i32, i32)>);
+
+// Chests are only guarded once the region around them has settled, so the guard stands on
+// finished ground
+fn bind_chest_guards(
+    mut commands: Commands,
+    mut rolled: ResMut<RolledGuardPosts>,
+    mut regions: EventReader<RegionComplete>,
+    cells: Query<(&Cell, &Transform)>,
+    spatial_index: Res<CellSpatialIndex>,
+    director: Res<AiDirector>,
+    asset_server: Res<AssetServer>,
+) {
+    let mut rng = rand::rng();
+
+    for region in regions.read() {
+        let ((min_x, min_z), (max_x, max_z)) = region.bounds;
+        for position in (min_x..=max_x).flat_map(|x| (min_z..=max_z).map(move |z| (x, z))) {
+            let Some((cell, transform)) = spatial_index
+                .ground(&position)
+                .and_then(|entity| cells.get(*entity).ok())
+            else {
+                continue;
+            };
+            if cell.tile_type != Some(TileType::Chest)
+                || !rolled.0.insert(position)
+                || !rng.random_bool(GUARD_CHANCE)
+            {
+                continue;
+            }
+
+            let post = transform.translation.with_y(0.0);
+            let angle = rng.random_range(0.0..std::f32::consts::TAU);
+            let position = post + Vec3::new(angle.cos(), 0.0, angle.sin()) * GUARD_OFFSET;
+            let entity = spawn_o_insan(
+                &mut commands,
+                position,
+                director.enemy_health,
+                &asset_server,
+            );
+            commands.entity(entity).insert((
+                OInsanAI {
+                    health: director.enemy_health,
+                    max_health: director.enemy_health,
+                    current_behavior: AIBehavior::Guarding,
+                    guard_post: Some(post),
+                    ..Default::default()
+                },
+                Faction::Wardens,
+            ));
+            println!(
+                "🛡️ A guard takes up its post by the chest at {:?}",
+                cell.position
+            );
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use rand::prelude::*;
+
+use crate::game::core_mechanics::{
+    enemy_ai::o_insan::components::{AIBehavior, Dying, OInsanAI},
+    pause::simulation_running,
+};
+
+// Bones of monster.glb; the model has no head bone, so the upper spine does the looking
+const TORSO_BONE: &str = "CC_Base_Spine02";
+const WAIST_BONE: &str = "CC_Base_Waist";
+
+const STATIONARY_SPEED: f32 = 1.5; // Below this net speed the AI counts as standing still
+const VELOCITY_SMOOTHING: f32 = 4.0; // Wandering jitters every frame, so judge the average
+const BLEND_SPEED: f32 = 3.0; // Idle motion fades in and out instead of snapping
+const MAX_LOOK_YAW: f32 = 0.6;
+const LOOK_TURN_SPEED: f32 = 1.5;
+const FIDGET_SECS: f32 = 1.2;
+const FIDGET_ROLL: f32 = 0.15;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (attach_idle_animators, find_idle_bones, animate_idle)
+            .chain()
+            .run_if(simulation_running),
+    );
+}
+
+// Procedural idle layer: looking around and the odd weight shift while the AI stands still
+#[derive(Component, Debug)]
+pub struct IdleAnimator {
+    torso: Option<(Entity, Quat)>, // Bone and its rest rotation
+    waist: Option<(Entity, Quat)>,
+    last_position: Vec3,
+    velocity: Vec3,
+    blend: f32,
+    look_yaw: f32,
+    look_target: f32,
+    look_timer: Timer,
+    fidget: Option<Timer>,
+    fidget_timer: Timer,
+}
+
+impl IdleAnimator {
+    fn new(position: Vec3) -> Self {
+        let mut rng = rand::rng();
+        Self {
+            torso: None,
+            waist: None,
+            last_position: position,
+            velocity: Vec3::ZERO,
+            blend: 0.0,
+            look_yaw: 0.0,
+            look_target: 0.0,
+            look_timer: Timer::from_seconds(rng.random_range(1.5..4.0), TimerMode::Once),
+            fidget: None,
+            fidget_timer: Timer::from_seconds(rng.random_range(4.0..9.0), TimerMode::Once),
+        }
+    }
+}
+
+fn attach_idle_animators(
+    mut commands: Commands,
+    new_ais: Query<(Entity, &Transform), Added<OInsanAI>>,
+) {
+    for (entity, transform) in new_ais.iter() {
+        commands
+            .entity(entity)
+            .insert(IdleAnimator::new(transform.translation));
+    }
+}
+
+// The model's scene spawns asynchronously, so keep looking until its bones show up
+fn find_idle_bones(
+    mut animators: Query<(Entity, &mut IdleAnimator)>,
+    children: Query<&Children>,
+    bones: Query<(&Name, &Transform)>,
+) {
+    for (entity, mut animator) in animators.iter_mut() {
+        if animator.torso.is_some() && animator.waist.is_some() {
+            continue;
+        }
+
+        for descendant in children.iter_descendants(entity) {
+            let Ok((name, transform)) = bones.get(descendant) else {
+                continue;
+            };
+            match name.as_str() {
+                TORSO_BONE => animator.torso = Some((descendant, transform.rotation)),
+                WAIST_BONE => animator.waist = Some((descendant, transform.rotation)),
+                _ => {}
+            }
+        }
+    }
+}
+
+type IdleAiQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static OInsanAI,
+        &'static Transform,
+        &'static mut IdleAnimator,
+    ),
+    Without<Dying>,
+>;
+
+fn animate_idle(
+    mut ais: IdleAiQuery,
+    mut bones: Query<&mut Transform, Without<OInsanAI>>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+    if dt <= 0.0 {
+        return;
+    }
+    let mut rng = rand::rng();
+
+    for (ai, transform, mut animator) in ais.iter_mut() {
+        let frame_velocity = (transform.translation - animator.last_position).with_y(0.0) / dt;
+        animator.last_position = transform.translation;
+        animator.velocity = animator
+            .velocity
+            .lerp(frame_velocity, (VELOCITY_SMOOTHING * dt).min(1.0));
+
+        let calm = matches!(
+            ai.current_behavior,
+            AIBehavior::Wandering | AIBehavior::Begging | AIBehavior::Guarding
+        );
+        let target_blend = if calm && animator.velocity.length() < STATIONARY_SPEED {
+            1.0
+        } else {
+            0.0
+        };
+        animator.blend +=
+            (target_blend - animator.blend).clamp(-BLEND_SPEED * dt, BLEND_SPEED * dt);
+
+        // Pick a new direction to glance at every few seconds
+        animator.look_timer.tick(time.delta());
+        if animator.look_timer.finished() {
+            animator.look_target = rng.random_range(-MAX_LOOK_YAW..MAX_LOOK_YAW);
+            animator.look_timer = Timer::from_seconds(rng.random_range(1.5..4.0), TimerMode::Once);
+        }
+        let look_step = LOOK_TURN_SPEED * dt;
+        animator.look_yaw +=
+            (animator.look_target - animator.look_yaw).clamp(-look_step, look_step);
+
+        // A short weight shift from one leg to the other now and then
+        animator.fidget_timer.tick(time.delta());
+        if animator.fidget.is_none() && animator.fidget_timer.finished() && animator.blend > 0.9 {
+            animator.fidget = Some(Timer::from_seconds(FIDGET_SECS, TimerMode::Once));
+            animator.fidget_timer =
+                Timer::from_seconds(rng.random_range(4.0..9.0), TimerMode::Once);
+        }
+        let mut roll = 0.0;
+        if let Some(fidget) = animator.fidget.as_mut() {
+            fidget.tick(time.delta());
+            roll = (fidget.fraction() * PI).sin() * FIDGET_ROLL;
+            if fidget.finished() {
+                animator.fidget = None;
+            }
+        }
+
+        if let Some((torso, rest)) = animator.torso
+            && let Ok(mut bone) = bones.get_mut(torso)
+        {
+            bone.rotation = rest * Quat::from_rotation_y(animator.look_yaw * animator.blend);
+        }
+        if let Some((waist, rest)) = animator.waist
+            && let Ok(mut bone) = bones.get_mut(waist)
+        {
+            bone.rotation = rest * Quat::from_rotation_z(roll * animator.blend);
+        }
+    }
+}
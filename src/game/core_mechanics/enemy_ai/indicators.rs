@@ -0,0 +1,297 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    platform::collections::HashMap,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+use crate::game::{
+    core_mechanics::enemy_ai::o_insan::{
+        components::{AIBehavior, EmotionalState, OInsanAI},
+        events::{BehaviorChangedEvent, EmotionChangedEvent},
+    },
+    ui::palette::Palette,
+};
+
+const ICON_SIZE_PX: u32 = 32;
+const ICON_HEIGHT: f32 = 3.5; // Above the AI origin
+const ICON_SCALE: f32 = 1.0;
+const ALERT_SECS: f32 = 1.5;
+const ICONS: [IndicatorIcon; 4] = [
+    IndicatorIcon::Alert,
+    IndicatorIcon::Heart,
+    IndicatorIcon::BrokenHeart,
+    IndicatorIcon::Fire,
+];
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Startup, setup_indicator_assets)
+        .add_systems(
+            Update,
+            (
+                repaint_icons.run_if(resource_changed::<Palette>),
+                attach_indicators,
+                react_to_emotion_changes,
+                react_to_behavior_changes,
+                update_indicator_icons,
+                face_camera,
+            )
+                .chain(),
+        );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndicatorIcon {
+    Alert,       // Just spotted the player
+    Heart,       // Begging
+    BrokenHeart, // Depressed
+    Fire,        // Angry
+}
+
+// On the AI; the icon itself lives on a child billboard
+#[derive(Component, Debug)]
+pub struct EmotionIndicator {
+    pub billboard: Entity,
+    pub mood: Option<IndicatorIcon>,
+    pub begging: bool,
+    pub alert: Option<Timer>,
+}
+
+impl EmotionIndicator {
+    fn current_icon(&self) -> Option<IndicatorIcon> {
+        if self.alert.is_some() {
+            Some(IndicatorIcon::Alert)
+        } else if self.begging {
+            Some(IndicatorIcon::Heart)
+        } else {
+            self.mood
+        }
+    }
+}
+
+#[derive(Component)]
+struct IndicatorBillboard;
+
+#[derive(Resource)]
+struct IndicatorAssets {
+    mesh: Handle<Mesh>,
+    materials: HashMap<IndicatorIcon, Handle<StandardMaterial>>,
+    images: HashMap<IndicatorIcon, Handle<Image>>,
+}
+
+fn setup_indicator_assets(
+    mut commands: Commands,
+    mut mesh_assets: ResMut<Assets<Mesh>>,
+    mut material_assets: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    palette: Res<Palette>,
+) {
+    let images: HashMap<_, _> = ICONS
+        .into_iter()
+        .map(|icon| (icon, images.add(draw_icon(icon, &palette))))
+        .collect();
+    let materials = images
+        .iter()
+        .map(|(icon, image)| {
+            let material = material_assets.add(StandardMaterial {
+                base_color_texture: Some(image.clone()),
+                alpha_mode: AlphaMode::Mask(0.5),
+                unlit: true,
+                ..Default::default()
+            });
+            (*icon, material)
+        })
+        .collect();
+
+    commands.insert_resource(IndicatorAssets {
+        mesh: mesh_assets.add(Rectangle::new(ICON_SCALE, ICON_SCALE)),
+        materials,
+        images,
+    });
+}
+
+// Redrawn in place, so every billboard picks up the new colors without touching its material
+fn repaint_icons(
+    assets: Res<IndicatorAssets>,
+    mut images: ResMut<Assets<Image>>,
+    palette: Res<Palette>,
+) {
+    for (icon, handle) in assets.images.iter() {
+        if let Some(image) = images.get_mut(handle) {
+            *image = draw_icon(*icon, &palette);
+        }
+    }
+}
+
+// Icons are drawn procedurally so no texture assets are needed
+fn draw_icon(icon: IndicatorIcon, palette: &Palette) -> Image {
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: ICON_SIZE_PX,
+            height: ICON_SIZE_PX,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::all(),
+    );
+
+    for px in 0..ICON_SIZE_PX {
+        for py in 0..ICON_SIZE_PX {
+            // -1..1 with y pointing up
+            let u = (px as f32 + 0.5) / ICON_SIZE_PX as f32 * 2.0 - 1.0;
+            let v = 1.0 - (py as f32 + 0.5) / ICON_SIZE_PX as f32 * 2.0;
+
+            if let Some(color) = icon_pixel(icon, u, v, palette) {
+                let _ = image.set_color_at(px, py, color);
+            }
+        }
+    }
+
+    image
+}
+
+fn icon_pixel(icon: IndicatorIcon, u: f32, v: f32, palette: &Palette) -> Option<Color> {
+    let in_heart = |u: f32, v: f32| {
+        let (x, y) = (u * 1.25, v * 1.25 + 0.25);
+        (x * x + y * y - 1.0).powi(3) - x * x * y.powi(3) <= 0.0
+    };
+
+    match icon {
+        IndicatorIcon::Alert => {
+            let bar = u.abs() < 0.15 && (-0.2..0.9).contains(&v);
+            let dot = u * u + (v + 0.65).powi(2) < 0.04;
+            (bar || dot).then_some(palette.alert)
+        }
+        IndicatorIcon::Heart => in_heart(u, v).then_some(palette.affection),
+        IndicatorIcon::BrokenHeart => {
+            let zigzag = 0.12 * ((v * 6.0).sin()) + 0.05;
+            let crack = (u - zigzag).abs() < 0.07;
+            (in_heart(u, v) && !crack).then_some(palette.sorrow)
+        }
+        IndicatorIcon::Fire => {
+            let flame = |scale: f32| {
+                let y = v / scale;
+                let x = u / scale;
+                let bulb = x * x + (y + 0.35).powi(2) < 0.4;
+                let tip = y > -0.35 && y < 0.95 && x.abs() < 0.63 * (0.95 - y) / 1.3;
+                bulb || tip
+            };
+            if flame(0.55) {
+                Some(palette.anger[0])
+            } else if flame(0.95) {
+                Some(palette.anger[1])
+            } else {
+                None
+            }
+        }
+    }
+}
+
+fn attach_indicators(
+    mut commands: Commands,
+    new_ais: Query<Entity, Added<OInsanAI>>,
+    assets: Res<IndicatorAssets>,
+) {
+    for entity in new_ais.iter() {
+        let billboard = commands
+            .spawn((
+                IndicatorBillboard,
+                Mesh3d(assets.mesh.clone()),
+                MeshMaterial3d(assets.materials[&IndicatorIcon::Alert].clone()),
+                Transform::from_xyz(0.0, ICON_HEIGHT, 0.0),
+                Visibility::Hidden,
+            ))
+            .id();
+
+        commands
+            .entity(entity)
+            .add_child(billboard)
+            .insert(EmotionIndicator {
+                billboard,
+                mood: None,
+                begging: false,
+                alert: None,
+            });
+    }
+}
+
+fn react_to_emotion_changes(
+    mut events: EventReader<EmotionChangedEvent>,
+    mut indicators: Query<&mut EmotionIndicator>,
+) {
+    for event in events.read() {
+        let Ok(mut indicator) = indicators.get_mut(event.entity) else {
+            continue;
+        };
+        indicator.mood = match event.new {
+            EmotionalState::Depressed => Some(IndicatorIcon::BrokenHeart),
+            EmotionalState::Angry => Some(IndicatorIcon::Fire),
+            EmotionalState::Neutral => None,
+        };
+    }
+}
+
+fn react_to_behavior_changes(
+    mut events: EventReader<BehaviorChangedEvent>,
+    mut indicators: Query<&mut EmotionIndicator>,
+) {
+    for event in events.read() {
+        let Ok(mut indicator) = indicators.get_mut(event.entity) else {
+            continue;
+        };
+        indicator.begging = event.new == AIBehavior::Begging;
+        if event.new == AIBehavior::Chasing && event.old != AIBehavior::Chasing {
+            indicator.alert = Some(Timer::from_seconds(ALERT_SECS, TimerMode::Once));
+        }
+    }
+}
+
+fn update_indicator_icons(
+    mut indicators: Query<&mut EmotionIndicator>,
+    mut billboards: Query<
+        (&mut MeshMaterial3d<StandardMaterial>, &mut Visibility),
+        With<IndicatorBillboard>,
+    >,
+    assets: Res<IndicatorAssets>,
+    time: Res<Time>,
+) {
+    for mut indicator in indicators.iter_mut() {
+        if let Some(alert) = indicator.alert.as_mut() {
+            alert.tick(time.delta());
+            if alert.finished() {
+                indicator.alert = None;
+            }
+        }
+
+        let Ok((mut material, mut visibility)) = billboards.get_mut(indicator.billboard) else {
+            continue;
+        };
+        match indicator.current_icon() {
+            Some(icon) => {
+                if material.0 != assets.materials[&icon] {
+                    material.0 = assets.materials[&icon].clone();
+                }
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
+
+// Counteracts the AI's own rotation so the icon always faces the camera
+fn face_camera(
+    camera: Single<&GlobalTransform, With<Camera3d>>,
+    mut billboards: Query<(&ChildOf, &mut Transform), With<IndicatorBillboard>>,
+    parents: Query<&GlobalTransform>,
+) {
+    let camera_rotation = camera.rotation();
+
+    for (child_of, mut transform) in billboards.iter_mut() {
+        let Ok(parent) = parents.get(child_of.parent()) else {
+            continue;
+        };
+        transform.rotation = parent.rotation().inverse() * camera_rotation;
+    }
+}
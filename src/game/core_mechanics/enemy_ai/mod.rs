@@ -1,7 +1,21 @@
 use bevy::prelude::*;
 
+pub mod director;
+pub mod encounters;
+pub mod formation;
+pub mod guards;
+pub mod idle;
+pub mod indicators;
 pub mod o_insan;
+pub mod spawn_tables;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins(o_insan::SimpleAIPlugin); 
+    app.add_plugins(director::plugin);
+    app.add_plugins(encounters::plugin);
+    app.add_plugins(formation::plugin);
+    app.add_plugins(guards::plugin);
+    app.add_plugins(indicators::plugin);
+    app.add_plugins(idle::plugin);
+    app.add_plugins(spawn_tables::plugin);
 }
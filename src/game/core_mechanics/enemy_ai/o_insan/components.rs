@@ -1,18 +1,22 @@
+use std::collections::VecDeque;
+
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EmotionalState {
     Depressed, 
     Angry,     
     Neutral,   
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AIBehavior {
     Wandering, 
     Chasing,   
     Escaping, 
     Begging,   
+    Guarding, // Holding its guard post
 }
 
 #[derive(Component)]
@@ -28,11 +32,139 @@ pub struct OInsanAI {
     pub last_player_position: Option<Vec3>, 
     pub time_since_seen_player: f32,      
 
+    pub guard_post: Option<Vec3>, // Chest or other point of interest this AI is bound to
+    pub pursuit_secs: f32,        // Spent chasing a player who has left the guarded radius
 
     pub movement_speed: f32,
     pub detection_range: f32,
 }
 
+// Where the AI spawned and how far it will follow the player from there. Outside the radius
+// aggression drains until the AI gives up and walks home; back inside it recovers.
+#[derive(Component, Debug)]
+pub struct Leash {
+    pub home: Vec3,
+    pub radius: f32,
+    pub aggression: f32, // 0..=1, scales how far the AI notices the player
+}
+
+impl Leash {
+    pub fn new(home: Vec3) -> Self {
+        Self {
+            home,
+            radius: 80.0,
+            aggression: 1.0,
+        }
+    }
+
+    pub fn is_outside(&self, position: Vec3) -> bool {
+        position.with_y(0.0).distance(self.home.with_y(0.0)) > self.radius
+    }
+}
+
+// Shaken by packmates dying close by; while broken the AI is Depressed whatever its health
+#[derive(Component, Debug, Default)]
+pub struct Morale {
+    pub broken_secs: f32, // Left until the AI pulls itself together
+}
+
+impl Morale {
+    // Never pulls itself together, so it flees whenever it sees the player
+    pub fn coward() -> Self {
+        Self {
+            broken_secs: f32::INFINITY,
+        }
+    }
+
+    pub fn is_broken(&self) -> bool {
+        self.broken_secs > 0.0
+    }
+}
+
+// How the AI feels about the player, moved by the player's emotes. Below -HOSTILE a calm AI
+// turns angry; above FRIENDLY a calm AI stops begging and leaves the player be.
+#[derive(Component, Debug, Default)]
+pub struct Disposition {
+    pub friendliness: f32, // -1..=1, drifts back to the baseline
+    pub baseline: f32,     // Set by the player's reputation with the AI's faction
+}
+
+impl Disposition {
+    pub const FRIENDLY: f32 = 0.5;
+    pub const HOSTILE: f32 = 0.5;
+
+    pub fn is_friendly(&self) -> bool {
+        self.friendliness >= Self::FRIENDLY
+    }
+
+    pub fn is_hostile(&self) -> bool {
+        self.friendliness <= -Self::HOSTILE
+    }
+}
+
+// Cell centers a chasing or escaping AI walks through to get round whatever's in its way,
+// nearest first. Searched again on a timer, since whoever it's after keeps moving.
+#[derive(Component, Debug)]
+pub struct Path {
+    pub waypoints: VecDeque<Vec3>,
+    pub goal: Option<(i32, i32)>, // Cell the waypoints lead to; None while not pathing
+    pub repath_timer: Timer,
+}
+
+impl Default for Path {
+    fn default() -> Self {
+        Self {
+            waypoints: VecDeque::new(),
+            goal: None,
+            repath_timer: Timer::from_seconds(0.5, TimerMode::Repeating),
+        }
+    }
+}
+
+impl Path {
+    // Drops the waypoints already within `reach` and returns the next one
+    pub fn next_waypoint(&mut self, position: Vec3, reach: f32) -> Option<Vec3> {
+        while let Some(waypoint) = self.waypoints.front() {
+            if waypoint.with_y(0.0).distance(position.with_y(0.0)) > reach {
+                return Some(*waypoint);
+            }
+            self.waypoints.pop_front();
+        }
+        None
+    }
+
+    pub fn clear(&mut self) {
+        self.waypoints.clear();
+        self.goal = None;
+    }
+}
+
+// Inserted by the death pipeline; all other AI systems skip entities that have it
+#[derive(Component)]
+pub struct Dying {
+    pub timer: Timer,
+    pub materials: Vec<Handle<StandardMaterial>>,
+}
+
+impl Default for Dying {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(1.5, TimerMode::Once),
+            materials: Vec::new(),
+        }
+    }
+}
+
+// Steered by hand through the debug tools. The systems that decide and move for an AI skip it,
+// while everything that reacts to AIs still sees an ordinary one.
+#[derive(Component, Debug)]
+pub struct Possessed;
+
+// The player walks with WASD unless an AI has taken over those keys
+pub fn player_in_control(possessed: Query<(), With<Possessed>>) -> bool {
+    possessed.is_empty()
+}
+
 impl Default for OInsanAI {
     fn default() -> Self {
         Self {
@@ -47,6 +179,9 @@ impl Default for OInsanAI {
             last_player_position: None,
             time_since_seen_player: 0.0,
 
+            guard_post: None,
+            pursuit_secs: 0.0,
+
             movement_speed: 10.0,
             detection_range: 40.0,
         }
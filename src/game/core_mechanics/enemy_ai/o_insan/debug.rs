@@ -1,46 +1,73 @@
 use super::components::OInsanAI;
-use crate::game::spawn::player::Player;
+use crate::game::{
+    core_mechanics::{
+        health::{DamageEvent, DamageSource, HealEvent, HealSource},
+        inventory::{Inventory, Item, ItemStack},
+    },
+    input::{Action, Actions},
+    spawn::player::Player,
+};
 use bevy::prelude::*;
 
 pub fn ai_debug_system(
-    mut ai_query: Query<&mut OInsanAI>,
-    mut player_query: Query<&mut Player>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    ai_query: Query<(Entity, &OInsanAI)>,
+    mut player_query: Query<&mut Inventory, With<Player>>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut heal_events: EventWriter<HealEvent>,
+    actions: Actions,
 ) {
-    if keyboard_input.just_pressed(KeyCode::Digit1) {
-        for mut ai in ai_query.iter_mut() {
-            ai.health -= 20.0;
-            ai.health = ai.health.max(0.0);
+    // Through the same events as a landed hit or a bandage, so everything that reacts to damage
+    // and healing sees it
+    if actions.just_pressed(Action::DamageAI) {
+        for (entity, ai) in ai_query.iter() {
+            damage_events.write(DamageEvent {
+                target: entity,
+                amount: 20.0,
+                source: DamageSource::Debug,
+            });
             println!(
                 "🩸 AI damaged! Health: {:.0}/{:.0}",
-                ai.health, ai.max_health
+                (ai.health - 20.0).max(0.0),
+                ai.max_health
             );
         }
     }
 
-    if keyboard_input.just_pressed(KeyCode::Digit2) {
-        for mut ai in ai_query.iter_mut() {
-            ai.health += 20.0;
-            ai.health = ai.health.min(ai.max_health);
+    if actions.just_pressed(Action::HealAI) {
+        for (entity, ai) in ai_query.iter() {
+            heal_events.write(HealEvent {
+                target: entity,
+                amount: 20.0,
+                source: HealSource::Debug,
+            });
             println!(
                 "❤️ AI healed! Health: {:.0}/{:.0}",
-                ai.health, ai.max_health
+                (ai.health + 20.0).min(ai.max_health),
+                ai.max_health
             );
         }
     }
 
-    if keyboard_input.just_pressed(KeyCode::Digit3) {
-        for mut player in player_query.iter_mut() {
-            player.has_weapon = !player.has_weapon;
-            println!(
-                "🗡️ Player weapon: {}",
-                if player.has_weapon { "ON" } else { "OFF" }
-            );
+    if actions.just_pressed(Action::ToggleWeapon) {
+        // Takes away every blade, or puts one in hand in place of whatever was there
+        for mut inventory in player_query.iter_mut() {
+            let armed = inventory.contains(Item::Blade);
+            if armed {
+                for slot in inventory.slots.iter_mut() {
+                    if slot.is_some_and(|stack| stack.item == Item::Blade) {
+                        *slot = None;
+                    }
+                }
+            } else {
+                let selected = inventory.selected;
+                inventory.slots[selected] = Some(ItemStack::new(Item::Blade));
+            }
+            println!("🗡️ Player weapon: {}", if armed { "OFF" } else { "ON" });
         }
     }
 
-    if keyboard_input.just_pressed(KeyCode::Digit4) {
-        for ai in ai_query.iter() {
+    if actions.just_pressed(Action::InspectAI) {
+        for (_, ai) in ai_query.iter() {
             println!("📊 AI STATE:");
             println!(
                 "   Health: {:.0}/{:.0} ({:.0}%)",
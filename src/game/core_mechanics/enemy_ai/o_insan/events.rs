@@ -0,0 +1,15 @@
+use super::components::{AIBehavior, EmotionalState};
+use bevy::prelude::*;
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct EmotionChangedEvent {
+    pub entity: Entity,
+    pub new: EmotionalState,
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct BehaviorChangedEvent {
+    pub entity: Entity,
+    pub old: AIBehavior,
+    pub new: AIBehavior,
+}
@@ -1,9 +1,13 @@
 use bevy::prelude::*;
 
-use crate::game::core_mechanics::enemy_ai::o_insan::spawn::spawn_o_insan;
+use crate::game::core_mechanics::pause::simulation_running;
 
 pub mod components;
+#[cfg(feature = "debug-tools")]
 pub mod debug;
+pub mod events;
+#[cfg(feature = "debug-tools")]
+pub mod possession;
 pub mod spawn;
 pub mod systems;
 
@@ -12,46 +16,33 @@ pub struct SimpleAIPlugin;
 impl Plugin for SimpleAIPlugin {
     fn build(&self, app: &mut App) {
         app
+            .add_event::<events::EmotionChangedEvent>()
+            .add_event::<events::BehaviorChangedEvent>()
             .add_systems(Startup, setup_ai_demo)
             .add_systems(
                 Update,
                 (
+                    systems::ai_morale_system,
+                    systems::ai_social_system,
                     systems::ai_emotion_system,  
                     systems::ai_behavior_system, 
+                    systems::ai_pathfinding_system,
                     systems::ai_movement_system, 
+                    systems::ai_separation_system,
                     systems::ai_speech_system,   
                     systems::ai_health_system,   
-                    debug::ai_debug_system,      
+                    systems::ai_death_system,
+                    systems::ai_dissolve_system,
                 )
-                    .chain(),
+                    .chain()
+                    .run_if(simulation_running),
             ); 
     }
 }
 
-fn setup_ai_demo(
-    mut commands: Commands,
-    mesh_assets: ResMut<Assets<Mesh>>,
-    material_assets: ResMut<Assets<StandardMaterial>>,
-    asset_server: Res<AssetServer>,
-) {
-    let _ai_entity = spawn_o_insan(
-        &mut commands,
-        Vec3::new(0.0, 0.0, 0.0),
-        100.0,
-        mesh_assets,
-        material_assets,
-        asset_server,
-    );
-
+fn setup_ai_demo() {
     println!("🎮 SIMPLE AI DEMO STARTED!");
-    println!("🤖 AI spawned at (0, 0, 0) with full health (angry)");
-    println!("👤 Player spawned at (5, 0, 0)");
-    println!();
-    println!("🎮 DEBUG CONTROLS:");
-    println!("   Press '1' to damage AI (-20 health)");
-    println!("   Press '2' to heal AI (+20 health)");
-    println!("   Press '3' to toggle player weapon");
-    println!("   Press '4' to print AI state");
+    println!("🤖 Enemies are spawned by the AI director, out of sight of the player");
     println!();
     println!("🎯 EXPECTED BEHAVIOR:");
     println!("   High Health (70-100%): AI will be ANGRY and CHASE player");
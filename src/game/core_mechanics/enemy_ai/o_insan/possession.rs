@@ -0,0 +1,114 @@
+use super::components::{Dying, OInsanAI, Path, Possessed};
+use crate::game::{
+    core_mechanics::{movement::PlayerVelocity, walkability::TerrainSpeed},
+    input::{Action, Actions},
+    spawn::player::Player,
+};
+use bevy::prelude::*;
+
+const POSSESS_RANGE: f32 = 60.0; // Only AIs the player can see from where they stand
+
+type PossessableAiQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static mut Path,
+        Has<Possessed>,
+    ),
+    (With<OInsanAI>, Without<Dying>),
+>;
+
+type PossessedAiQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static mut Transform, &'static OInsanAI),
+    (With<Possessed>, Without<Dying>, Without<Player>),
+>;
+
+// Takes over the AI nearest the player, or hands the possessed one back to its systems. It picks
+// up from whatever behavior it was left in, with a fresh path.
+pub fn toggle_possession(
+    mut commands: Commands,
+    mut ai_query: PossessableAiQuery,
+    player: Single<&Transform, With<Player>>,
+    mut velocity: ResMut<PlayerVelocity>,
+) {
+    if let Some((entity, ..)) = ai_query.iter().find(|(.., possessed)| *possessed) {
+        commands.entity(entity).remove::<Possessed>();
+        println!("👻 Released the possessed AI");
+        return;
+    }
+
+    let distance_to_player =
+        |transform: &Transform| transform.translation.distance(player.translation);
+    let Some((entity, position)) = ai_query
+        .iter()
+        .filter(|(_, transform, ..)| distance_to_player(transform) <= POSSESS_RANGE)
+        .min_by(|(_, a, ..), (_, b, ..)| distance_to_player(a).total_cmp(&distance_to_player(b)))
+        .map(|(entity, transform, ..)| (entity, transform.translation))
+    else {
+        println!("👻 No AI within {:.0} to possess", POSSESS_RANGE);
+        return;
+    };
+
+    if let Ok((.., mut path, _)) = ai_query.get_mut(entity) {
+        path.clear();
+    }
+    commands.entity(entity).insert(Possessed);
+    // The player stands still until the AI is released, and the systems reading their speed see it
+    velocity.0 = Vec3::ZERO;
+    println!(
+        "👻 Possessed the AI at ({:.0}, {:.0}); WASD steers it relative to the camera",
+        position.x, position.z
+    );
+}
+
+// Dying ends the possession, so the player gets the keys back
+pub fn release_dying_possessed(
+    mut commands: Commands,
+    dying: Query<Entity, (With<Possessed>, Added<Dying>)>,
+) {
+    for entity in dying.iter() {
+        commands.entity(entity).remove::<Possessed>();
+        println!("👻 The possessed AI died");
+    }
+}
+
+// Walks at the AI's own speed and the terrain's, along the player's view flattened to the ground
+pub fn drive_possessed(
+    mut possessed: PossessedAiQuery,
+    player: Single<&Transform, With<Player>>,
+    actions: Actions,
+    terrain: TerrainSpeed,
+    time: Res<Time>,
+) {
+    let mut intent = Vec2::ZERO;
+    if actions.pressed(Action::MoveForward) {
+        intent.y += 1.0;
+    }
+    if actions.pressed(Action::MoveBack) {
+        intent.y -= 1.0;
+    }
+    if actions.pressed(Action::MoveLeft) {
+        intent.x -= 1.0;
+    }
+    if actions.pressed(Action::MoveRight) {
+        intent.x += 1.0;
+    }
+
+    let forward = player.forward().as_vec3().with_y(0.0).normalize_or_zero();
+    let right = player.right().as_vec3().with_y(0.0).normalize_or_zero();
+    let direction = (forward * intent.y + right * intent.x).normalize_or_zero();
+    if direction == Vec3::ZERO {
+        return;
+    }
+
+    for (mut transform, ai) in possessed.iter_mut() {
+        let distance = ai.movement_speed * terrain.at(transform.translation) * time.delta_secs();
+        transform.translation += direction * distance;
+        let look_target = transform.translation + direction;
+        transform.look_at(look_target, Vec3::Y);
+    }
+}
@@ -1,29 +1,34 @@
-use super::components::OInsanAI;
+use super::components::{Disposition, Leash, Morale, OInsanAI, Path};
+use crate::game::core_mechanics::{
+    combat::{MeleeCooldown, TransformHistory},
+    reputation::Faction,
+};
 use bevy::prelude::*;
 
+pub const MONSTER_MODEL: &str = "models/monster.glb"; // Shared by every humanoid, traders included
+
 pub fn spawn_o_insan(
     commands: &mut Commands,
     position: Vec3,
     health: f32,
-    mut mesh_assets: ResMut<Assets<Mesh>>,
-    mut material_assets: ResMut<Assets<StandardMaterial>>,
-    asset_server: Res<AssetServer>,
+    asset_server: &AssetServer,
 ) -> Entity {
-    let _ball_mesh = mesh_assets.add(Cuboid::default());
-    let color = Color::srgb(0.8, 0.5, 0.6);
-    let _ball_material = material_assets.add(StandardMaterial {
-        base_color: color,
-        ..Default::default()
-    });
     commands
         .spawn((
-            SceneRoot(asset_server.load(GltfAssetLabel::Scene(0).from_asset("models/monster.glb"))),
+            SceneRoot(asset_server.load(GltfAssetLabel::Scene(0).from_asset(MONSTER_MODEL))),
             OInsanAI {
                 health,
                 max_health: health,
                 ..Default::default()
             },
             Transform::from_translation(position),
+            Leash::new(position),
+            Path::default(),
+            Morale::default(),
+            Disposition::default(),
+            Faction::default(),
+            TransformHistory::default(),
+            MeleeCooldown::default(),
             Name::new("TheHuman"),
         ))
         .id()
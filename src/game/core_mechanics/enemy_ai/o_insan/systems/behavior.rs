@@ -1,35 +1,79 @@
-use super::super::components::{AIBehavior, EmotionalState, OInsanAI};
-use crate::game::spawn::player::Player;
+use super::super::{
+    components::{AIBehavior, Disposition, Dying, EmotionalState, Leash, OInsanAI, Possessed},
+    events::BehaviorChangedEvent,
+};
+use crate::game::{
+    core_mechanics::{inventory::Inventory, status_effects::StatusEffects},
+    spawn::player::Player,
+};
 use bevy::prelude::*;
 
+const AGGRESSION_DRAIN_PER_SEC: f32 = 0.15; // Outside the leash; gives up after ~7 seconds
+const AGGRESSION_RECOVERY_PER_SEC: f32 = 0.25;
+const GUARD_RADIUS: f32 = 15.0; // Players inside it are always worth chasing
+const GUARD_PURSUIT_SECS: f32 = 4.0; // After the player leaves the guarded radius
+
+type BehaviorAiQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut OInsanAI,
+        &'static Transform,
+        Option<&'static mut Leash>,
+        Option<&'static Disposition>,
+        Option<&'static StatusEffects>,
+    ),
+    (Without<Dying>, Without<Possessed>),
+>;
+
 pub fn ai_behavior_system(
-    mut ai_query: Query<&mut OInsanAI>,
-    player_query: Query<(&Transform, &Player), Without<OInsanAI>>,
-    ai_transform_query: Query<&Transform, (With<OInsanAI>, Without<Player>)>,
+    mut ai_query: BehaviorAiQuery,
+    player_query: Query<(&Transform, &Player, &Inventory), Without<OInsanAI>>,
+    mut behavior_events: EventWriter<BehaviorChangedEvent>,
     time: Res<Time>,
 ) {
-    for mut ai in ai_query.iter_mut() {
+    let _span = info_span!("ai_behavior", ais = ai_query.iter().len()).entered();
+
+    for (entity, mut ai, ai_transform, leash, disposition, effects) in ai_query.iter_mut() {
         ai.behavior_update_timer.tick(time.delta());
         ai.time_since_seen_player += time.delta_secs();
 
-        if !ai.behavior_update_timer.just_finished() {
+        let aggression = leash.map_or(1.0, |mut leash| {
+            update_aggression(&mut leash, ai_transform.translation, time.delta_secs())
+        });
+
+        // Stunned AIs hold whatever they were doing until they come round
+        if !ai.behavior_update_timer.just_finished()
+            || effects.is_some_and(StatusEffects::is_stunned)
+        {
             continue;
         }
 
-        let context = gather_situational_context(
+        let mut context = gather_situational_context(
             &player_query,
-            &ai_transform_query,
+            ai_transform.translation,
+            aggression,
             &mut ai,
         );
+        context.friendly = disposition.is_some_and(Disposition::is_friendly);
 
-        let new_behavior = decide_behavior_from_emotion_and_context(
+        let mut new_behavior = decide_behavior_from_emotion_and_context(
             ai.emotional_state,
             ai.current_behavior,
             &context,
         );
+        if let Some(post) = ai.guard_post {
+            new_behavior = decide_guard_behavior(new_behavior, post, &mut ai, &context);
+        }
 
         if new_behavior != ai.current_behavior {
             log_behavior_change(&ai.current_behavior, &new_behavior);
+            behavior_events.write(BehaviorChangedEvent {
+                entity,
+                old: ai.current_behavior,
+                new: new_behavior,
+            });
             ai.current_behavior = new_behavior;
         }
     }
@@ -39,50 +83,65 @@ pub fn ai_behavior_system(
 struct SituationalContext {
     player_position: Option<Vec3>,
     player_has_weapon: bool,
+    player_focused: bool, // Focusing with a weapon in hand reads as taking aim
     ai_position: Vec3,
     distance_to_player: Option<f32>,
     can_see_player: bool,
+    friendly: bool,
+}
+
+fn update_aggression(leash: &mut Leash, ai_position: Vec3, delta_secs: f32) -> f32 {
+    if leash.is_outside(ai_position) {
+        let was_aggressive = leash.aggression > 0.0;
+        leash.aggression = (leash.aggression - AGGRESSION_DRAIN_PER_SEC * delta_secs).max(0.0);
+        if was_aggressive && leash.aggression == 0.0 {
+            println!("🪢 AI strayed too far from home and gives up the chase");
+        }
+    } else {
+        leash.aggression =
+            (leash.aggression + AGGRESSION_RECOVERY_PER_SEC * delta_secs).min(1.0);
+    }
+    leash.aggression
 }
 
 fn gather_situational_context(
-    player_query: &Query<(&Transform, &Player), Without<OInsanAI>>,
-    ai_transform_query: &Query<&Transform, (With<OInsanAI>, Without<Player>)>,
+    player_query: &Query<(&Transform, &Player, &Inventory), Without<OInsanAI>>,
+    ai_position: Vec3,
+    aggression: f32,
     ai: &mut OInsanAI,
 ) -> SituationalContext {
-    let ai_position = if let Ok(ai_transform) = ai_transform_query.single() {
-        ai_transform.translation
-    } else {
-        return SituationalContext {
-            player_position: None,
-            player_has_weapon: false,
-            ai_position: Vec3::ZERO,
-            distance_to_player: None,
-            can_see_player: false,
-        };
-    };
 
-    let (player_position, player_has_weapon, distance_to_player, can_see_player) =
-        if let Ok((player_transform, player)) = player_query.single() {
+    let (player_position, player_has_weapon, player_focused, distance_to_player, can_see_player) =
+        if let Ok((player_transform, player, inventory)) = player_query.single() {
             let player_pos = player_transform.translation;
             let distance = ai_position.distance(player_pos);
-            let can_see = distance <= ai.detection_range;
+            // A leashed AI far from home only notices a player right next to it
+            let can_see = distance <= ai.detection_range * aggression;
             
             if can_see {
                 ai.last_player_position = Some(player_pos);
                 ai.time_since_seen_player = 0.0;
             }
             
-            (Some(player_pos), player.has_weapon, Some(distance), can_see)
+            (
+                Some(player_pos),
+                inventory.holds_weapon(),
+                player.focused,
+                Some(distance),
+                can_see,
+            )
         } else {
-            (None, false, None, false)
+            (None, false, false, None, false)
         };
 
     SituationalContext {
         player_position,
         player_has_weapon,
+        player_focused,
         ai_position,
         distance_to_player,
         can_see_player,
+        friendly: false,
     }
 }
 
@@ -111,8 +170,12 @@ fn decide_angry_behavior(
     context: &SituationalContext,
 ) -> AIBehavior {
     if context.can_see_player {
-        if context.player_has_weapon
-            && (current_behavior == AIBehavior::Chasing || current_behavior == AIBehavior::Begging)
+        // A blade only frightens a chaser, unless it's being aimed, which breaks them on sight
+        let aimed_at = context.player_has_weapon && context.player_focused;
+        if aimed_at
+            || context.player_has_weapon
+                && (current_behavior == AIBehavior::Chasing
+                    || current_behavior == AIBehavior::Begging)
         {
             AIBehavior::Begging
         } else {
@@ -123,8 +186,44 @@ fn decide_angry_behavior(
     }
 }
 
-fn decide_neutral_behavior(_context: &SituationalContext) -> AIBehavior {
-    AIBehavior::Begging
+fn decide_neutral_behavior(context: &SituationalContext) -> AIBehavior {
+    if context.friendly {
+        AIBehavior::Wandering
+    } else {
+        AIBehavior::Begging
+    }
+}
+
+// Guards only start chases for players near their post, and give up shortly after the
+// player leaves it
+fn decide_guard_behavior(
+    preferred: AIBehavior,
+    post: Vec3,
+    ai: &mut OInsanAI,
+    context: &SituationalContext,
+) -> AIBehavior {
+    if preferred != AIBehavior::Chasing {
+        ai.pursuit_secs = 0.0;
+        return if preferred == AIBehavior::Wandering {
+            AIBehavior::Guarding
+        } else {
+            preferred
+        };
+    }
+
+    let player_near_post = context.player_position.is_some_and(|position| {
+        position.with_y(0.0).distance(post.with_y(0.0)) <= GUARD_RADIUS
+    });
+    if player_near_post {
+        ai.pursuit_secs = 0.0;
+        return AIBehavior::Chasing;
+    }
+
+    if ai.current_behavior == AIBehavior::Chasing && ai.pursuit_secs < GUARD_PURSUIT_SECS {
+        ai.pursuit_secs += ai.behavior_update_timer.duration().as_secs_f32();
+        return AIBehavior::Chasing;
+    }
+    AIBehavior::Guarding
 }
 
 fn log_behavior_change(old_behavior: &AIBehavior, new_behavior: &AIBehavior) {
@@ -0,0 +1,100 @@
+use super::super::components::{Dying, OInsanAI};
+use crate::game::core_mechanics::{
+    inventory::{Item, ItemStack},
+    loot::{LootAssets, LootKind, spawn_loot_drop},
+};
+use bevy::prelude::*;
+use rand::{prelude::*, rng};
+
+const SINK_SPEED: f32 = 0.8;
+
+pub fn ai_death_system(
+    mut commands: Commands,
+    ai_query: Query<(Entity, &OInsanAI), Without<Dying>>,
+) {
+    for (entity, ai) in ai_query.iter() {
+        if ai.health <= 0.0 {
+            commands.entity(entity).insert(Dying::default());
+        }
+    }
+}
+
+pub fn ai_dissolve_system(
+    mut commands: Commands,
+    mut dying_query: Query<(Entity, &mut Dying, &mut Transform)>,
+    children: Query<&Children>,
+    mesh_materials: Query<&MeshMaterial3d<StandardMaterial>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    loot_assets: Res<LootAssets>,
+    time: Res<Time>,
+) {
+    for (entity, mut dying, mut transform) in dying_query.iter_mut() {
+        if dying.materials.is_empty() {
+            dying.materials = make_materials_fadeable(
+                &mut commands,
+                entity,
+                &children,
+                &mesh_materials,
+                &mut materials,
+            );
+        }
+
+        dying.timer.tick(time.delta());
+        let alpha = 1.0 - dying.timer.fraction();
+
+        for handle in dying.materials.iter() {
+            if let Some(material) = materials.get_mut(handle) {
+                material.base_color.set_alpha(alpha);
+            }
+        }
+        transform.translation.y -= SINK_SPEED * time.delta_secs();
+
+        if dying.timer.finished() {
+            drop_loot(&mut commands, &loot_assets, transform.translation);
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+// Gives every mesh of the model its own blended copy of its material so only this AI fades
+fn make_materials_fadeable(
+    commands: &mut Commands,
+    entity: Entity,
+    children: &Query<&Children>,
+    mesh_materials: &Query<&MeshMaterial3d<StandardMaterial>>,
+    materials: &mut Assets<StandardMaterial>,
+) -> Vec<Handle<StandardMaterial>> {
+    let mut handles = Vec::new();
+
+    for descendant in children.iter_descendants(entity) {
+        let Ok(mesh_material) = mesh_materials.get(descendant) else {
+            continue;
+        };
+        let Some(original) = materials.get(&mesh_material.0) else {
+            continue;
+        };
+
+        let mut fading = original.clone();
+        fading.alpha_mode = AlphaMode::Blend;
+        let handle = materials.add(fading);
+
+        commands
+            .entity(descendant)
+            .insert(MeshMaterial3d(handle.clone()));
+        handles.push(handle);
+    }
+
+    handles
+}
+
+fn drop_loot(commands: &mut Commands, loot_assets: &LootAssets, position: Vec3) {
+    let mut rng = rng();
+
+    let kind = if rng.random_bool(0.3) {
+        LootKind::Item(ItemStack::new(Item::Blade))
+    } else {
+        LootKind::Health(25.0)
+    };
+
+    spawn_loot_drop(commands, loot_assets, kind, position);
+}
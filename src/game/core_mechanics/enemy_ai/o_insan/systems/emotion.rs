@@ -1,22 +1,59 @@
-use super::super::components::{EmotionalState, OInsanAI};
+use super::super::{
+    components::{Disposition, Dying, EmotionalState, Morale, OInsanAI, Possessed},
+    events::EmotionChangedEvent,
+};
+use crate::game::core_mechanics::status_effects::StatusEffects;
 use bevy::prelude::*;
 
-pub fn ai_emotion_system(mut ai_query: Query<&mut OInsanAI>, _time: Res<Time>) {
-    for mut ai in ai_query.iter_mut() {
-        let new_emotion = calculate_emotion_from_health(ai.health, ai.max_health);
+type EmotionAiQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut OInsanAI,
+        Option<&'static Morale>,
+        Option<&'static Disposition>,
+        Option<&'static StatusEffects>,
+    ),
+    (Without<Dying>, Without<Possessed>),
+>;
+
+pub fn ai_emotion_system(
+    mut ai_query: EmotionAiQuery,
+    mut emotion_events: EventWriter<EmotionChangedEvent>,
+    _time: Res<Time>,
+) {
+    for (entity, mut ai, morale, disposition, effects) in ai_query.iter_mut() {
+        let new_emotion = if morale.is_some_and(Morale::is_broken) {
+            EmotionalState::Depressed
+        } else {
+            let burning = effects.is_some_and(StatusEffects::is_burning);
+            match calculate_emotion_from_health(ai.health, ai.max_health, burning) {
+                EmotionalState::Neutral if disposition.is_some_and(Disposition::is_hostile) => {
+                    EmotionalState::Angry
+                }
+                emotion => emotion,
+            }
+        };
 
         if new_emotion != ai.emotional_state {
             log_emotion_change(&ai.emotional_state, &new_emotion, ai.health, ai.max_health);
+            emotion_events.write(EmotionChangedEvent {
+                entity,
+                new: new_emotion,
+            });
             ai.emotional_state = new_emotion;
         }
     }
 }
 
-fn calculate_emotion_from_health(health: f32, max_health: f32) -> EmotionalState {
+// Burning AIs lose heart well before their health is low
+fn calculate_emotion_from_health(health: f32, max_health: f32, burning: bool) -> EmotionalState {
     let health_percent = health / max_health;
+    let depressed_below = if burning { 0.5 } else { 0.3 };
     
     match health_percent {
-        hp if hp <= 0.3 => EmotionalState::Depressed,
+        hp if hp <= depressed_below => EmotionalState::Depressed,
         hp if hp >= 0.7 => EmotionalState::Angry,
         _ => EmotionalState::Neutral,
     }
@@ -1,7 +1,7 @@
-use super::super::components::OInsanAI;
+use super::super::components::{Dying, OInsanAI};
 use bevy::prelude::*;
 
-pub fn ai_health_system(mut ai_query: Query<&mut OInsanAI>) {
+pub fn ai_health_system(mut ai_query: Query<&mut OInsanAI, Without<Dying>>) {
     for mut ai in ai_query.iter_mut() {
         let previous_health = ai.health;
         
@@ -1,11 +1,19 @@
+pub mod death;
 pub mod emotion;    
+pub mod morale;
+pub mod social;
 pub mod behavior; 
 pub mod movement;   
+pub mod pathfinding;
 pub mod speech;    
 pub mod health;    
 
+pub use death::{ai_death_system, ai_dissolve_system};
 pub use emotion::ai_emotion_system;
+pub use morale::ai_morale_system;
+pub use social::ai_social_system;
 pub use behavior::ai_behavior_system;
-pub use movement::ai_movement_system;
+pub use movement::{ai_movement_system, ai_separation_system};
+pub use pathfinding::ai_pathfinding_system;
 pub use speech::ai_speech_system;
 pub use health::ai_health_system;
@@ -0,0 +1,40 @@
+use super::super::components::{Dying, Morale};
+use bevy::prelude::*;
+
+const MORALE_RADIUS: f32 = 50.0; // How far away a death still shakes the pack
+const LOSS_WINDOW_SECS: f32 = 8.0; // Deaths this close together count towards the same rout
+const BREAKING_LOSSES: usize = 2;
+const BROKEN_SECS: f32 = 20.0;
+
+// Every OInsanAI counts as one pack: survivors near enough recent deaths lose heart together
+pub fn ai_morale_system(
+    newly_dying: Query<&Transform, Added<Dying>>,
+    mut ai_query: Query<(&Transform, &mut Morale), Without<Dying>>,
+    mut losses: Local<Vec<(Vec3, f32)>>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_secs();
+    losses.extend(newly_dying.iter().map(|transform| (transform.translation, now)));
+    losses.retain(|(_, died_at)| now - died_at <= LOSS_WINDOW_SECS);
+
+    let mut broken = 0;
+    for (transform, mut morale) in ai_query.iter_mut() {
+        if morale.is_broken() {
+            morale.broken_secs = (morale.broken_secs - time.delta_secs()).max(0.0);
+            continue;
+        }
+
+        let nearby_losses = losses
+            .iter()
+            .filter(|(position, _)| position.distance(transform.translation) <= MORALE_RADIUS)
+            .count();
+        if nearby_losses >= BREAKING_LOSSES {
+            morale.broken_secs = BROKEN_SECS;
+            broken += 1;
+        }
+    }
+
+    if broken > 0 {
+        println!("💔 The pack is breaking: {} AI lose heart and flee", broken);
+    }
+}
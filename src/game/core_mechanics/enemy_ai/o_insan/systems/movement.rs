@@ -1,37 +1,114 @@
-use super::super::components::{AIBehavior, OInsanAI};
-use crate::game::spawn::player::Player;
+use super::super::components::{AIBehavior, Dying, Leash, OInsanAI, Path, Possessed};
+use crate::game::{
+    core_mechanics::{
+        combat::{AI_RADIUS, PLAYER_RADIUS},
+        enemy_ai::formation::FormationSlot,
+        status_effects::StatusEffects,
+        walkability::TerrainSpeed,
+    },
+    spawn::player::Player,
+};
 use bevy::prelude::*;
 use rand::{prelude::*, rng};
 
+const GUARD_STANDING_DISTANCE: f32 = 3.0; // From the post
+const WAYPOINT_REACH: f32 = 2.0; // Close enough to a path cell's center to head for the next
+
+type MovingAiQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Transform,
+        &'static OInsanAI,
+        &'static mut Path,
+        Option<&'static FormationSlot>,
+        Option<&'static Leash>,
+        Option<&'static StatusEffects>,
+        Has<Dying>,
+    ),
+    (Without<Possessed>, Without<Player>),
+>;
+
 pub fn ai_movement_system(
-    mut ai_query: Query<(&mut Transform, &OInsanAI), Without<Player>>,
+    mut ai_query: MovingAiQuery,
     player_query: Query<&Transform, (With<Player>, Without<OInsanAI>)>,
+    terrain: TerrainSpeed,
     time: Res<Time>,
 ) {
-    for (mut ai_transform, ai) in ai_query.iter_mut() {
+    let _span = info_span!("ai_movement", ais = ai_query.iter().len()).entered();
+
+    for (mut ai_transform, ai, mut path, slot, leash, effects, is_dying) in ai_query.iter_mut() {
+        if is_dying {
+            continue;
+        }
+
         let delta_time = time.delta_secs();
-        let base_movement_distance = ai.movement_speed * delta_time;
+        let base_movement_distance = ai.movement_speed
+            * terrain.at(ai_transform.translation)
+            * effects.map_or(1.0, StatusEffects::speed_factor)
+            * delta_time;
 
         execute_behavior_movement(
             &mut ai_transform,
             ai,
+            &mut path,
+            slot,
+            leash,
             &player_query,
             base_movement_distance,
         );
     }
 }
 
+type SeparatedAiQuery<'w, 's> =
+    Query<'w, 's, &'static mut Transform, (With<OInsanAI>, Without<Dying>, Without<Player>)>;
+
+// Soft collision with the player: an AI that ends up overlapping them, whether it walked in
+// or the player walked into it, is pushed back out to the minimum separation
+pub fn ai_separation_system(
+    mut ai_query: SeparatedAiQuery,
+    player: Single<&Transform, (With<Player>, Without<OInsanAI>)>,
+) {
+    let min_separation = PLAYER_RADIUS + AI_RADIUS;
+
+    for mut ai_transform in ai_query.iter_mut() {
+        let offset = (ai_transform.translation - player.translation).with_y(0.0);
+        let distance = offset.length();
+        if distance >= min_separation {
+            continue;
+        }
+
+        // Exactly on top of the player: push out along the AI's facing instead
+        let direction = offset
+            .try_normalize()
+            .unwrap_or(ai_transform.back().as_vec3().with_y(0.0).normalize_or(Vec3::X));
+        ai_transform.translation += direction * (min_separation - distance);
+    }
+}
+
 fn execute_behavior_movement(
     ai_transform: &mut Transform,
     ai: &OInsanAI,
+    path: &mut Path,
+    slot: Option<&FormationSlot>,
+    leash: Option<&Leash>,
     player_query: &Query<&Transform, (With<Player>, Without<OInsanAI>)>,
     base_movement_distance: f32,
 ) {
+    // Wandering outside the leash means the AI gave up on the player and heads home
+    if ai.current_behavior == AIBehavior::Wandering
+        && let Some(leash) = leash.filter(|leash| leash.is_outside(ai_transform.translation))
+    {
+        execute_returning_movement(ai_transform, leash.home, base_movement_distance);
+        return;
+    }
+
     match ai.current_behavior {
         AIBehavior::Wandering => execute_wandering_movement(ai_transform, base_movement_distance),
-        AIBehavior::Chasing => execute_chasing_movement(ai_transform, player_query, base_movement_distance),
-        AIBehavior::Escaping => execute_escaping_movement(ai_transform, player_query, ai, base_movement_distance),
+        AIBehavior::Chasing => execute_chasing_movement(ai_transform, path, slot, player_query, base_movement_distance),
+        AIBehavior::Escaping => execute_escaping_movement(ai_transform, path, player_query, ai, base_movement_distance),
         AIBehavior::Begging => execute_begging_movement(ai_transform, player_query),
+        AIBehavior::Guarding => execute_guarding_movement(ai_transform, ai, base_movement_distance),
     }
 }
 
@@ -48,16 +125,52 @@ fn execute_wandering_movement(ai_transform: &mut Transform, base_movement_distan
     ai_transform.translation += random_direction * base_movement_distance * wandering_speed_multiplier;
 }
 
+fn execute_returning_movement(
+    ai_transform: &mut Transform,
+    home: Vec3,
+    base_movement_distance: f32,
+) {
+    let to_home = (home - ai_transform.translation).with_y(0.0);
+    let direction = to_home.normalize_or_zero();
+    ai_transform.translation += direction * base_movement_distance.min(to_home.length());
+
+    if direction != Vec3::ZERO {
+        let look_target = ai_transform.translation + direction;
+        ai_transform.look_at(look_target, Vec3::Y);
+    }
+}
+
+// Walks back to the post and stands watch next to it
+fn execute_guarding_movement(
+    ai_transform: &mut Transform,
+    ai: &OInsanAI,
+    base_movement_distance: f32,
+) {
+    let Some(post) = ai.guard_post else {
+        return;
+    };
+    if ai_transform.translation.with_y(0.0).distance(post.with_y(0.0)) > GUARD_STANDING_DISTANCE {
+        execute_returning_movement(ai_transform, post, base_movement_distance);
+    }
+}
+
 fn execute_chasing_movement(
     ai_transform: &mut Transform,
+    path: &mut Path,
+    slot: Option<&FormationSlot>,
     player_query: &Query<&Transform, (With<Player>, Without<OInsanAI>)>,
     base_movement_distance: f32,
 ) {
     if let Ok(player_transform) = player_query.single() {
-        let direction = (player_transform.translation - ai_transform.translation)
-            .normalize_or_zero();
+        // Pack members run to their surround slot, lone chasers to the player, either way round
+        // obstacles along the path; the last stretch inside the target's cell is straight
+        let target = path
+            .next_waypoint(ai_transform.translation, WAYPOINT_REACH)
+            .unwrap_or(slot.map_or(player_transform.translation, |slot| slot.target));
+        let to_target = (target - ai_transform.translation).with_y(0.0);
+        let direction = to_target.normalize_or_zero();
         
-        ai_transform.translation += direction * base_movement_distance;
+        ai_transform.translation += direction * base_movement_distance.min(to_target.length());
         
         if direction != Vec3::ZERO {
             ai_transform.look_at(player_transform.translation, Vec3::Y);
@@ -67,11 +180,19 @@ fn execute_chasing_movement(
 
 fn execute_escaping_movement(
     ai_transform: &mut Transform,
+    path: &mut Path,
     player_query: &Query<&Transform, (With<Player>, Without<OInsanAI>)>,
     ai: &OInsanAI,
     base_movement_distance: f32,
 ) {
-    let escape_direction = calculate_escape_direction(ai_transform, player_query, ai);
+    let escape_direction = match path.next_waypoint(ai_transform.translation, WAYPOINT_REACH) {
+        Some(waypoint) => Some(
+            (waypoint - ai_transform.translation)
+                .with_y(0.0)
+                .normalize_or_zero(),
+        ),
+        None => calculate_escape_direction(ai_transform, player_query, ai),
+    };
     
     if let Some(direction) = escape_direction {
         let escape_speed_multiplier = 1.5;
@@ -0,0 +1,82 @@
+use super::super::components::{AIBehavior, Dying, OInsanAI, Path, Possessed};
+use crate::game::{
+    core_mechanics::{
+        enemy_ai::formation::FormationSlot,
+        oz_devinimli_yaratim::{
+            cells::GenerationSettings,
+            grid::{grid_to_world, world_to_grid},
+        },
+        walkability::{PathSearchTraces, WalkabilityGrid},
+    },
+    spawn::player::Player,
+};
+use bevy::prelude::*;
+
+const ESCAPE_LOOKAHEAD: f32 = 40.0; // How far from the player an escaping AI aims to get
+
+type PathingAiQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static OInsanAI,
+        &'static mut Path,
+        Option<&'static FormationSlot>,
+    ),
+    (Without<Dying>, Without<Possessed>, Without<Player>),
+>;
+
+// Chasers path to their target and escapers to a point away from the player; everyone else
+// walks without one
+pub fn ai_pathfinding_system(
+    mut ai_query: PathingAiQuery,
+    player_query: Query<&Transform, (With<Player>, Without<OInsanAI>)>,
+    grid: Res<WalkabilityGrid>,
+    mut traces: Option<ResMut<PathSearchTraces>>,
+    settings: Res<GenerationSettings>,
+    time: Res<Time>,
+) {
+    let _span = info_span!("ai_pathfinding").entered();
+    let edge = settings.cell_edge_length as f32;
+    let player = player_query
+        .single()
+        .ok()
+        .map(|transform| transform.translation);
+
+    for (entity, transform, ai, mut path, slot) in ai_query.iter_mut() {
+        let position = transform.translation;
+        let target = match ai.current_behavior {
+            AIBehavior::Chasing => player.map(|player| slot.map_or(player, |slot| slot.target)),
+            AIBehavior::Escaping => player.or(ai.last_player_position).map(|threat| {
+                let away = (position - threat).with_y(0.0).normalize_or(Vec3::X);
+                position + away * ESCAPE_LOOKAHEAD
+            }),
+            _ => None,
+        };
+        let Some(target) = target else {
+            if let Some(traces) = traces.as_mut() {
+                traces.searches.remove(&entity);
+            }
+            path.clear();
+            continue;
+        };
+
+        path.repath_timer.tick(time.delta());
+        if path.goal.is_some() && !path.repath_timer.just_finished() {
+            continue;
+        }
+
+        let goal = world_to_grid(target, edge);
+        let search = grid.search(world_to_grid(position, edge), goal);
+        path.waypoints = search
+            .path
+            .iter()
+            .map(|cell| grid_to_world(*cell, edge))
+            .collect();
+        path.goal = Some(goal);
+        if let Some(traces) = traces.as_mut() {
+            traces.searches.insert(entity, search);
+        }
+    }
+}
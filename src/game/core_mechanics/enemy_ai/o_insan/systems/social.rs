@@ -0,0 +1,71 @@
+use super::super::components::{Disposition, Dying, EmotionalState, Leash, OInsanAI};
+use crate::game::core_mechanics::emotes::{Emote, SocialEvent};
+use bevy::prelude::*;
+use rand::prelude::*;
+
+const EMOTE_RANGE: f32 = 30.0;
+const THREATEN_SHIFT: f32 = 0.5;
+const BOW_SHIFT: f32 = 0.35;
+const BOW_SWAY_CHANCE: f64 = 0.6; // A bow doesn't always land
+const WAVE_SHIFT: f32 = 0.1;
+const DISPOSITION_DRIFT_PER_SEC: f32 = 0.01; // Back towards the faction's baseline
+
+type SocialAiQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static OInsanAI,
+        &'static Transform,
+        &'static mut Disposition,
+        Option<&'static mut Leash>,
+    ),
+    Without<Dying>,
+>;
+
+pub fn ai_social_system(
+    mut social_events: EventReader<SocialEvent>,
+    mut ai_query: SocialAiQuery,
+    time: Res<Time>,
+) {
+    let drift = DISPOSITION_DRIFT_PER_SEC * time.delta_secs();
+    for (_, _, mut disposition, _) in ai_query.iter_mut() {
+        let offset = disposition.friendliness - disposition.baseline;
+        disposition.friendliness -= offset.signum() * drift.min(offset.abs());
+    }
+
+    let mut rng = rand::rng();
+    for event in social_events.read() {
+        for (ai, transform, mut disposition, leash) in ai_query.iter_mut() {
+            if transform.translation.distance(event.position) > EMOTE_RANGE {
+                continue;
+            }
+
+            let shift = match event.emote {
+                // Provoked: notices the player from its full range again, even far from home
+                Emote::Threaten => {
+                    if let Some(mut leash) = leash {
+                        leash.aggression = 1.0;
+                    }
+                    -THREATEN_SHIFT
+                }
+                Emote::Bow
+                    if ai.emotional_state == EmotionalState::Neutral
+                        && rng.random_bool(BOW_SWAY_CHANCE) =>
+                {
+                    BOW_SHIFT
+                }
+                Emote::Wave if ai.emotional_state != EmotionalState::Angry => WAVE_SHIFT,
+                _ => continue,
+            };
+
+            let was_friendly = disposition.is_friendly();
+            let was_hostile = disposition.is_hostile();
+            disposition.friendliness = (disposition.friendliness + shift).clamp(-1.0, 1.0);
+            if !was_friendly && disposition.is_friendly() {
+                println!("🤝 AI warms to the player");
+            } else if !was_hostile && disposition.is_hostile() {
+                println!("😡 AI takes the threat personally");
+            }
+        }
+    }
+}
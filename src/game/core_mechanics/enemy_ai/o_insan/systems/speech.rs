@@ -1,22 +1,37 @@
-use super::super::components::{AIBehavior, EmotionalState, OInsanAI};
+use super::super::components::{AIBehavior, Dying, EmotionalState, OInsanAI, Possessed};
+use crate::game::audio::{PlaySfx, SfxKind};
 use bevy::prelude::*;
 use rand::{prelude::*, rng};
 
-pub fn ai_speech_system(mut ai_query: Query<&mut OInsanAI>, time: Res<Time>) {
-    for mut ai in ai_query.iter_mut() {
+type SpeakingAiQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static mut OInsanAI, &'static Transform),
+    (Without<Dying>, Without<Possessed>),
+>;
+
+pub fn ai_speech_system(
+    mut ai_query: SpeakingAiQuery,
+    time: Res<Time>,
+    mut sfx: EventWriter<PlaySfx>,
+) {
+    for (mut ai, transform) in ai_query.iter_mut() {
         ai.speech_timer.tick(time.delta());
 
-        if ai.speech_timer.just_finished() {
-            attempt_speech(&ai);
+        if ai.speech_timer.just_finished() && attempt_speech(&ai) {
+            sfx.write(PlaySfx {
+                kind: SfxKind::Voice,
+                position: transform.translation,
+            });
         }
     }
 }
 
-fn attempt_speech(ai: &OInsanAI) {
+fn attempt_speech(ai: &OInsanAI) -> bool {
     let mut rng = rng();
     
     if !rng.random_bool(0.8) {
-        return;
+        return false;
     }
 
     let dialogue_options = select_dialogue_for_state(ai.emotional_state, ai.current_behavior);
@@ -24,7 +39,10 @@ fn attempt_speech(ai: &OInsanAI) {
     if !dialogue_options.is_empty() {
         let selected_line = dialogue_options[rng.random_range(0..dialogue_options.len())];
         println!("🤖 AI says: '{}'", selected_line);
+        return true;
     }
+
+    false
 }
 
 fn select_dialogue_for_state(
@@ -85,6 +103,17 @@ fn select_dialogue_for_state(
             "Stay with me...",
         ],
 
+        (EmotionalState::Angry, AIBehavior::Guarding) => vec![
+            "Stay away from it!",
+            "Nobody touches this.",
+            "I'm watching you...",
+        ],
+
+        (EmotionalState::Neutral, AIBehavior::Guarding) => vec![
+            "Someone has to keep watch...",
+            "Move along.",
+        ],
+
         _ => vec!["..."],
     }
 }
@@ -0,0 +1,136 @@
+use std::{collections::HashMap, fs};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::Deserialize;
+
+use crate::game::core_mechanics::{
+    day_night::WorldClock,
+    enemy_ai::encounters::EncounterTemplate,
+    oz_devinimli_yaratim::{
+        cells::GenerationSettings,
+        grid::world_to_grid,
+        odyrules::biomes::{BIOMES, Biome, BiomeMap},
+    },
+};
+
+pub const SPAWN_TABLES_PATH: &str = "assets/spawn_tables.ron";
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(SpawnTables::load_or_default());
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpawnTime {
+    #[default]
+    Always,
+    Day,
+    Night,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SpawnEntry {
+    pub encounter: EncounterTemplate,
+    pub weight: f32,
+    #[serde(default)]
+    pub time: SpawnTime,
+    #[serde(default = "single_group")]
+    pub group: (usize, usize), // How many of the encounter turn up together, inclusive
+}
+
+fn single_group() -> (usize, usize) {
+    (1, 1)
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BiomeSpawns {
+    #[serde(default = "full_density")]
+    pub density: f32, // Scales the enemy cap while spawning here
+    pub entries: Vec<SpawnEntry>,
+}
+
+fn full_density() -> f32 {
+    1.0
+}
+
+// Which encounters the director may spawn in each biome, and when
+#[derive(Resource, Deserialize, Debug, Clone)]
+pub struct SpawnTables {
+    pub biomes: HashMap<Biome, BiomeSpawns>,
+}
+
+impl Default for SpawnTables {
+    // Only used when the asset is missing or broken: lone enemies everywhere
+    fn default() -> Self {
+        let lone = BiomeSpawns {
+            density: 1.0,
+            entries: vec![SpawnEntry {
+                encounter: EncounterTemplate::Lone,
+                weight: 1.0,
+                time: SpawnTime::Always,
+                group: single_group(),
+            }],
+        };
+        Self {
+            biomes: BIOMES
+                .into_iter()
+                .map(|biome| (biome, lone.clone()))
+                .collect(),
+        }
+    }
+}
+
+impl SpawnTables {
+    pub fn load() -> Result<Self, String> {
+        let text = fs::read_to_string(SPAWN_TABLES_PATH)
+            .map_err(|error| format!("{}: {}", SPAWN_TABLES_PATH, error))?;
+        ron::from_str(&text).map_err(|error| format!("{}: {}", SPAWN_TABLES_PATH, error))
+    }
+
+    fn load_or_default() -> Self {
+        Self::load().unwrap_or_else(|error| {
+            warn!(
+                "Failed to load spawn tables, spawning lone enemies only: {}",
+                error
+            );
+            Self::default()
+        })
+    }
+}
+
+// The spawn table that applies at a point right now
+#[derive(SystemParam)]
+pub struct LocalSpawns<'w> {
+    tables: Res<'w, SpawnTables>,
+    biomes: Res<'w, BiomeMap>,
+    settings: Res<'w, GenerationSettings>,
+    clock: Res<'w, WorldClock>,
+}
+
+impl LocalSpawns<'_> {
+    pub fn biome_at(&self, point: Vec3) -> Biome {
+        self.biomes
+            .biome_at(world_to_grid(point, self.settings.cell_edge_length as f32))
+    }
+
+    pub fn density(&self, biome: Biome) -> f32 {
+        self.tables
+            .biomes
+            .get(&biome)
+            .map_or(0.0, |spawns| spawns.density)
+    }
+
+    // Entries of the biome's table that are active at this hour
+    pub fn entries(&self, biome: Biome) -> impl Iterator<Item = &SpawnEntry> {
+        let night = self.clock.is_night();
+        self.tables
+            .biomes
+            .get(&biome)
+            .into_iter()
+            .flat_map(|spawns| spawns.entries.iter())
+            .filter(move |entry| match entry.time {
+                SpawnTime::Always => true,
+                SpawnTime::Day => !night,
+                SpawnTime::Night => night,
+            })
+    }
+}
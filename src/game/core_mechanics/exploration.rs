@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::core_mechanics::oz_devinimli_yaratim::{cells::Cell, odyrules::commons::TileType};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ExploredCells>()
+        .add_systems(Update, record_explored_cells);
+}
+
+// Every collapsed cell the player has ever had loaded, kept after the cell despawns.
+// Saved with the world, so std's HashMap for serde.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ExploredCells {
+    pub tiles: std::collections::HashMap<(i32, i32), TileType>,
+}
+
+fn record_explored_cells(
+    mut explored: ResMut<ExploredCells>,
+    changed_cells: Query<&Cell, Changed<Cell>>,
+) {
+    for cell in changed_cells.iter() {
+        if let (true, Some(tile)) = (cell.is_collapsed, cell.tile_type) {
+            explored.tiles.insert(cell.position, tile);
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use bevy::prelude::*;
+
+use crate::game::{
+    core_mechanics::{
+        fountains::{DRINK_RANGE, drink_from_fountain},
+        interactions::{InteractionStore, nearest_tile},
+        movement::PlayerTeleported,
+        naming::{PlaceKind, place_name},
+        oz_devinimli_yaratim::{
+            cells::{Cell, CellSpatialIndex, GenerationSettings},
+            grid::grid_to_world,
+            odyrules::commons::TileType,
+        },
+    },
+    input::{Action, Actions, action_just_pressed},
+    spawn::player::Player,
+    ui::{console::console_closed, world_map::world_map_closed},
+};
+
+const GATES_TO_LINK: usize = 3; // Awakened springs needed before any of them lets the player through
+const GATE_MIN_DISTANCE: i32 = 40; // In cells; closer springs are walked to
+const MAX_DESTINATIONS: usize = 9; // One per number key
+const ARRIVAL_OFFSET: i32 = 3; // Cells south of the spring, clear of the basin
+
+pub const DESTINATION_ACTIONS: [Action; MAX_DESTINATIONS] = [
+    Action::TravelTo1,
+    Action::TravelTo2,
+    Action::TravelTo3,
+    Action::TravelTo4,
+    Action::TravelTo5,
+    Action::TravelTo6,
+    Action::TravelTo7,
+    Action::TravelTo8,
+    Action::TravelTo9,
+];
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<GateMenu>().add_systems(
+        Update,
+        (
+            use_gate.after(drink_from_fountain).run_if(
+                action_just_pressed(Action::Interact)
+                    .and(console_closed)
+                    .and(world_map_closed),
+            ),
+            travel_through_gate.run_if(gate_menu_open),
+        )
+            .chain(),
+    );
+}
+
+#[derive(Debug, Clone)]
+pub struct GateDestination {
+    pub name: String,
+    pub position: (i32, i32), // The spring's center cell
+    pub distance: f32,        // World units from the gate it was listed at
+}
+
+// The destination list shown while standing at an awakened spring
+#[derive(Resource, Debug, Default)]
+pub struct GateMenu {
+    pub open: bool,
+    pub destinations: Vec<GateDestination>,
+}
+
+pub fn gate_menu_closed(menu: Res<GateMenu>) -> bool {
+    !menu.open
+}
+
+fn gate_menu_open(menu: Res<GateMenu>) -> bool {
+    menu.open
+}
+
+// A spring is awakened the first time the player uses it. It's kept as the center cell's
+// `opened` flag in the interaction store, so the network is saved with the world.
+fn awakened_springs(store: &InteractionStore) -> impl Iterator<Item = (i32, i32)> + '_ {
+    store
+        .tiles
+        .iter()
+        .filter(|(_, state)| state.tile == TileType::FountainCenter && state.opened)
+        .map(|(position, _)| *position)
+}
+
+fn use_gate(
+    mut menu: ResMut<GateMenu>,
+    mut store: ResMut<InteractionStore>,
+    player: Single<&Transform, With<Player>>,
+    cells: Query<(&Cell, &Transform)>,
+    spatial_index: Res<CellSpatialIndex>,
+    settings: Res<GenerationSettings>,
+) {
+    if menu.open {
+        menu.open = false;
+        return;
+    }
+
+    let edge = settings.cell_edge_length as f32;
+    let Some((_, spring, _)) = nearest_tile(
+        player.translation,
+        DRINK_RANGE,
+        TileType::FountainCenter,
+        &cells,
+        &spatial_index,
+        edge,
+    ) else {
+        return;
+    };
+    let here = spring.position;
+
+    let state = store.state_mut(here, TileType::FountainCenter);
+    if !state.opened {
+        state.opened = true;
+        println!("⛲ You awakened {}", place_name(PlaceKind::Fountain, here));
+    }
+
+    let awakened = awakened_springs(&store).count();
+    if awakened < GATES_TO_LINK {
+        println!(
+            "⛲ The springs stay quiet, {} more must awaken",
+            GATES_TO_LINK - awakened
+        );
+        return;
+    }
+
+    let origin = grid_to_world(here, edge);
+    let mut destinations: Vec<GateDestination> = awakened_springs(&store)
+        .filter(|position| {
+            (position.0 - here.0).abs().max((position.1 - here.1).abs()) >= GATE_MIN_DISTANCE
+        })
+        .map(|position| GateDestination {
+            name: place_name(PlaceKind::Fountain, position),
+            position,
+            distance: grid_to_world(position, edge).distance(origin),
+        })
+        .collect();
+    if destinations.is_empty() {
+        println!("⛲ No awakened spring is far enough away to travel to");
+        return;
+    }
+
+    destinations.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+    destinations.truncate(MAX_DESTINATIONS);
+    menu.destinations = destinations;
+    menu.open = true;
+}
+
+fn travel_through_gate(
+    mut menu: ResMut<GateMenu>,
+    mut teleports: EventWriter<PlayerTeleported>,
+    mut player: Single<&mut Transform, With<Player>>,
+    actions: Actions,
+    settings: Res<GenerationSettings>,
+) {
+    let Some(destination) = DESTINATION_ACTIONS
+        .iter()
+        .position(|action| actions.just_pressed(*action))
+        .and_then(|index| menu.destinations.get(index))
+        .cloned()
+    else {
+        return;
+    };
+
+    let edge = settings.cell_edge_length as f32;
+    let (x, z) = destination.position;
+    let from = player.translation;
+    player.translation = grid_to_world((x, z - ARRIVAL_OFFSET), edge).with_y(from.y);
+    player.look_at(
+        grid_to_world(destination.position, edge).with_y(from.y),
+        Vec3::Y,
+    );
+    teleports.write(PlayerTeleported {
+        from,
+        to: player.translation,
+    });
+
+    println!("🌀 Stepped through the gate to {}", destination.name);
+    menu.open = false;
+}
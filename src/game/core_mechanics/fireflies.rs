@@ -0,0 +1,146 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::common_conditions::on_timer};
+use rand::prelude::*;
+
+use crate::game::core_mechanics::{
+    day_night::WorldClock,
+    oz_devinimli_yaratim::{
+        cells::Cell,
+        odyrules::biomes::{Biome, BiomeMap},
+    },
+    pause::simulation_running,
+};
+
+const SWARM_INTERVAL_MS: u64 = 1000;
+const SWARM_CHANCE: f64 = 0.3; // Of forest cells getting fireflies on a given night
+const FIREFLIES_PER_SWARM: usize = 5;
+const DRIFT_RADIUS: f32 = 2.5;
+const MIN_HEIGHT: f32 = 1.0;
+const MAX_HEIGHT: f32 = 3.5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Startup, setup_firefly_assets).add_systems(
+        Update,
+        (
+            update_firefly_swarms.run_if(on_timer(Duration::from_millis(SWARM_INTERVAL_MS))),
+            animate_fireflies,
+        )
+            .chain()
+            .run_if(simulation_running),
+    );
+}
+
+// One shared mesh and material, so every firefly is drawn in the same instanced batch
+#[derive(Resource)]
+struct FireflyAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+// Kept in world space rather than under the scaled cell, and dropped once the cell is gone
+#[derive(Component)]
+struct Firefly {
+    cell: Entity,
+    origin: Vec3,
+    phase: f32,
+}
+
+// Cells already rolled for fireflies tonight, whether or not they got any
+#[derive(Component)]
+struct FireflyCell;
+
+fn setup_firefly_assets(
+    mut commands: Commands,
+    mut mesh_assets: ResMut<Assets<Mesh>>,
+    mut material_assets: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(FireflyAssets {
+        mesh: mesh_assets.add(Sphere::new(0.06)),
+        material: material_assets.add(StandardMaterial {
+            base_color: Color::srgb(0.8, 1.0, 0.3),
+            emissive: LinearRgba::rgb(6.0, 8.0, 1.5),
+            unlit: true,
+            ..Default::default()
+        }),
+    });
+}
+
+fn update_firefly_swarms(
+    mut commands: Commands,
+    clock: Res<WorldClock>,
+    biomes: Res<BiomeMap>,
+    assets: Res<FireflyAssets>,
+    new_cells: Query<(Entity, &Cell, &Transform), Without<FireflyCell>>,
+    rolled_cells: Query<Entity, With<FireflyCell>>,
+    fireflies: Query<Entity, With<Firefly>>,
+) {
+    if !clock.is_night() {
+        for entity in fireflies.iter() {
+            commands.entity(entity).despawn();
+        }
+        for entity in rolled_cells.iter() {
+            commands.entity(entity).try_remove::<FireflyCell>();
+        }
+        return;
+    }
+
+    let mut rng = rand::rng();
+    for (entity, cell, transform) in new_cells.iter() {
+        if !cell.is_collapsed || biomes.biome_at(cell.position) != Biome::Forest {
+            continue;
+        }
+        // The cell can be unloaded by the time this is applied
+        commands.entity(entity).try_insert(FireflyCell);
+        if !rng.random_bool(SWARM_CHANCE) {
+            continue;
+        }
+
+        for _ in 0..FIREFLIES_PER_SWARM {
+            let origin = transform.translation
+                + Vec3::new(
+                    rng.random_range(-DRIFT_RADIUS..DRIFT_RADIUS),
+                    rng.random_range(MIN_HEIGHT..MAX_HEIGHT),
+                    rng.random_range(-DRIFT_RADIUS..DRIFT_RADIUS),
+                );
+            commands.spawn((
+                Name::new("Firefly"),
+                Firefly {
+                    cell: entity,
+                    origin,
+                    phase: rng.random_range(0.0..std::f32::consts::TAU),
+                },
+                Mesh3d(assets.mesh.clone()),
+                MeshMaterial3d(assets.material.clone()),
+                Transform::from_translation(origin),
+            ));
+        }
+    }
+}
+
+// Slow looping drift with a blink, all from the phase so there's no per-firefly state to step
+fn animate_fireflies(
+    mut commands: Commands,
+    mut fireflies: Query<(Entity, &Firefly, &mut Transform)>,
+    cells: Query<(), With<Cell>>,
+    time: Res<Time>,
+) {
+    let t = time.elapsed_secs();
+
+    for (entity, firefly, mut transform) in fireflies.iter_mut() {
+        if !cells.contains(firefly.cell) {
+            commands.entity(entity).try_despawn();
+            continue;
+        }
+
+        let phase = firefly.phase;
+        transform.translation = firefly.origin
+            + Vec3::new(
+                (t * 0.4 + phase).sin() * DRIFT_RADIUS * 0.5,
+                (t * 0.9 + phase * 2.0).sin() * 0.4,
+                (t * 0.3 + phase * 1.5).cos() * DRIFT_RADIUS * 0.5,
+            );
+        let blink = ((t * 2.0 + phase * 3.0).sin() * 0.5 + 0.5).powi(3);
+        transform.scale = Vec3::splat(0.3 + blink);
+    }
+}
@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+
+use crate::game::{
+    core_mechanics::inventory::Inventory,
+    input::{Action, Actions},
+    spawn::player::Player,
+    ui::{console::ConsoleState, world_map::WorldMapState},
+};
+
+const DEFAULT_FOV: f32 = std::f32::consts::FRAC_PI_4; // Bevy's own default
+const FOCUS_FOV: f32 = DEFAULT_FOV * 0.75;
+const FOCUS_RATE: f32 = 10.0; // How quickly the view and the weapon settle, per second
+
+// Where the held weapon sits in front of the camera, lowered and raised
+const LOWERED_OFFSET: Vec3 = Vec3::new(0.45, -0.4, -0.9);
+const RAISED_OFFSET: Vec3 = Vec3::new(0.12, -0.2, -0.8);
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(PostStartup, spawn_held_weapon)
+        .add_systems(Update, (update_focus, ease_focus_view).chain());
+}
+
+#[derive(Component)]
+struct HeldWeapon;
+
+// Startup spawns the camera, so the blade is attached once it exists
+fn spawn_held_weapon(
+    mut commands: Commands,
+    camera: Single<Entity, With<Camera3d>>,
+    mut mesh_assets: ResMut<Assets<Mesh>>,
+    mut material_assets: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.entity(*camera).with_child((
+        Name::new("HeldWeapon"),
+        HeldWeapon,
+        Mesh3d(mesh_assets.add(Cuboid::new(0.05, 0.05, 0.7))),
+        MeshMaterial3d(material_assets.add(StandardMaterial {
+            base_color: Color::srgb(0.75, 0.75, 0.8),
+            metallic: 0.8,
+            perceptual_roughness: 0.3,
+            ..Default::default()
+        })),
+        Transform::from_translation(LOWERED_OFFSET),
+        Visibility::Hidden,
+    ));
+}
+
+// Focus lasts while its button is held, and not through menus
+fn update_focus(
+    mut player: Single<&mut Player>,
+    actions: Actions,
+    console: Res<ConsoleState>,
+    world_map: Res<WorldMapState>,
+) {
+    let focused = actions.pressed(Action::Focus) && !console.open && !world_map.open;
+    if player.focused != focused {
+        player.focused = focused;
+    }
+}
+
+// Zooms in a little and brings the blade up to the middle of the view
+fn ease_focus_view(
+    player: Single<(&Player, &Inventory)>,
+    mut projection: Single<&mut Projection, With<Camera3d>>,
+    mut weapon: Single<(&mut Transform, &mut Visibility), With<HeldWeapon>>,
+    time: Res<Time>,
+) {
+    let (player, inventory) = *player;
+    let t = (FOCUS_RATE * time.delta_secs()).min(1.0);
+
+    if let Projection::Perspective(perspective) = projection.as_mut() {
+        let target = if player.focused {
+            FOCUS_FOV
+        } else {
+            DEFAULT_FOV
+        };
+        perspective.fov = perspective.fov.lerp(target, t);
+    }
+
+    let (transform, visibility) = &mut *weapon;
+    visibility.set_if_neq(if inventory.holds_weapon() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    });
+    let (offset, pitch) = if player.focused && inventory.holds_weapon() {
+        (RAISED_OFFSET, 0.0)
+    } else {
+        (LOWERED_OFFSET, -0.5)
+    };
+    transform.translation = transform.translation.lerp(offset, t);
+    transform.rotation = transform.rotation.slerp(Quat::from_rotation_x(pitch), t);
+}
@@ -0,0 +1,216 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use rand::prelude::*;
+
+use crate::game::{
+    audio::{PlaySfx, SfxKind},
+    core_mechanics::{
+        fast_travel::gate_menu_closed,
+        health::{HealEvent, HealSource},
+        interactions::{InteractionStore, RegisterInteractionHook, TileUse, nearest_tile},
+        oz_devinimli_yaratim::{
+            cells::{Cell, CellSpatialIndex, GenerationSettings},
+            odyrules::commons::TileType,
+        },
+    },
+    input::{Action, action_just_pressed},
+    spawn::player::Player,
+    ui::{console::console_closed, world_map::world_map_closed},
+};
+
+pub const DRINK_RANGE: f32 = 10.0;
+const DRINK_COOLDOWN_SECS: f64 = 60.0; // Per fountain
+const HEAL_AMOUNT: f32 = 40.0;
+const HEAL_DURATION_SECS: f32 = 4.0;
+const WELL_HEAL_AMOUNT: f32 = 15.0; // A bucket of well water is less than a fountain
+const SPLASH_PARTICLES: usize = 16;
+const PARTICLE_GRAVITY: f32 = 9.8;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_interaction_hook(TileType::Well, drink_from_well)
+        .add_systems(Startup, setup_fountain_assets)
+        .add_systems(
+            Update,
+            (
+                // Pressing use again at a gate closes its list instead
+                drink_from_fountain.run_if(
+                    action_just_pressed(Action::Interact)
+                        .and(console_closed)
+                        .and(world_map_closed)
+                        .and(gate_menu_closed),
+                ),
+                apply_regeneration,
+                update_splash_particles,
+            ),
+        );
+}
+
+// Heals `per_second` until the timer runs out
+#[derive(Component, Debug, Clone)]
+pub struct Regenerating {
+    pub per_second: f32,
+    pub timer: Timer,
+}
+
+#[derive(Component)]
+struct SplashParticle {
+    velocity: Vec3,
+    lifetime: Timer,
+}
+
+#[derive(Resource)]
+struct FountainAssets {
+    particle_mesh: Handle<Mesh>,
+    particle_material: Handle<StandardMaterial>,
+}
+
+// The sound and spray of a drink
+#[derive(SystemParam)]
+pub struct Splash<'w> {
+    sfx: EventWriter<'w, PlaySfx>,
+    assets: Res<'w, FountainAssets>,
+}
+
+impl Splash<'_> {
+    fn play(&mut self, commands: &mut Commands, origin: Vec3) {
+        self.sfx.write(PlaySfx {
+            kind: SfxKind::Drink,
+            position: origin,
+        });
+
+        let mut rng = rand::rng();
+        for _ in 0..SPLASH_PARTICLES {
+            let velocity = Vec3::new(
+                rng.random_range(-1.5..1.5),
+                rng.random_range(3.0..6.0),
+                rng.random_range(-1.5..1.5),
+            );
+            commands.spawn((
+                SplashParticle {
+                    velocity,
+                    lifetime: Timer::from_seconds(rng.random_range(0.6..1.2), TimerMode::Once),
+                },
+                Mesh3d(self.assets.particle_mesh.clone()),
+                MeshMaterial3d(self.assets.particle_material.clone()),
+                Transform::from_translation(origin),
+            ));
+        }
+    }
+}
+
+fn setup_fountain_assets(
+    mut commands: Commands,
+    mut mesh_assets: ResMut<Assets<Mesh>>,
+    mut material_assets: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(FountainAssets {
+        particle_mesh: mesh_assets.add(Sphere::new(0.15)),
+        particle_material: material_assets.add(StandardMaterial {
+            base_color: Color::srgba(0.5, 0.8, 1.0, 0.8),
+            emissive: LinearRgba::rgb(0.2, 0.4, 0.8),
+            alpha_mode: AlphaMode::Blend,
+            ..Default::default()
+        }),
+    });
+}
+
+pub fn drink_from_fountain(
+    mut commands: Commands,
+    mut splash: Splash,
+    mut store: ResMut<InteractionStore>,
+    player: Single<(Entity, &Transform), With<Player>>,
+    cells: Query<(&Cell, &Transform)>,
+    spatial_index: Res<CellSpatialIndex>,
+    settings: Res<GenerationSettings>,
+) {
+    let (player_entity, player_transform) = *player;
+
+    let Some((_, fountain, fountain_transform)) = nearest_tile(
+        player_transform.translation,
+        DRINK_RANGE,
+        TileType::FountainCenter,
+        &cells,
+        &spatial_index,
+        settings.cell_edge_length as f32,
+    ) else {
+        return;
+    };
+
+    if let Some(remaining) = store.cooldown_remaining(
+        fountain.position,
+        TileType::FountainCenter,
+        DRINK_COOLDOWN_SECS,
+    ) {
+        println!("💧 The fountain is still settling ({:.0}s)", remaining);
+        return;
+    }
+
+    store.mark_used(fountain.position, TileType::FountainCenter);
+    commands.entity(player_entity).insert(Regenerating {
+        per_second: HEAL_AMOUNT / HEAL_DURATION_SECS,
+        timer: Timer::from_seconds(HEAL_DURATION_SECS, TimerMode::Once),
+    });
+
+    splash.play(&mut commands, fountain_transform.translation.with_y(1.0));
+
+    println!("💧 Drank from the fountain");
+}
+
+fn drink_from_well(
+    In(tile_use): In<TileUse>,
+    mut commands: Commands,
+    mut store: ResMut<InteractionStore>,
+) {
+    if let Some(remaining) =
+        store.cooldown_remaining(tile_use.position, tile_use.tile, DRINK_COOLDOWN_SECS)
+    {
+        println!("🪣 The bucket is still coming up ({:.0}s)", remaining);
+        return;
+    }
+
+    store.mark_used(tile_use.position, tile_use.tile);
+    commands.entity(tile_use.user).insert(Regenerating {
+        per_second: WELL_HEAL_AMOUNT / HEAL_DURATION_SECS,
+        timer: Timer::from_seconds(HEAL_DURATION_SECS, TimerMode::Once),
+    });
+
+    println!("🪣 Drank from the well");
+}
+
+fn apply_regeneration(
+    mut commands: Commands,
+    mut regenerating: Query<(Entity, &mut Regenerating)>,
+    mut heals: EventWriter<HealEvent>,
+    time: Res<Time>,
+) {
+    for (entity, mut regen) in regenerating.iter_mut() {
+        regen.timer.tick(time.delta());
+        heals.write(HealEvent {
+            target: entity,
+            amount: regen.per_second * time.delta_secs(),
+            source: HealSource::Fountain,
+        });
+
+        if regen.timer.finished() {
+            commands.entity(entity).remove::<Regenerating>();
+        }
+    }
+}
+
+fn update_splash_particles(
+    mut commands: Commands,
+    mut particles: Query<(Entity, &mut SplashParticle, &mut Transform)>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, mut particle, mut transform) in particles.iter_mut() {
+        particle.lifetime.tick(time.delta());
+        if particle.lifetime.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        particle.velocity.y -= PARTICLE_GRAVITY * dt;
+        transform.translation += particle.velocity * dt;
+    }
+}
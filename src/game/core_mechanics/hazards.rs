@@ -0,0 +1,113 @@
+use bevy::{prelude::*, time::common_conditions::on_timer};
+use std::time::Duration;
+
+use crate::game::core_mechanics::{
+    day_night::WorldClock,
+    enemy_ai::o_insan::components::OInsanAI,
+    health::{DamageEvent, DamageSource, Health},
+    oz_devinimli_yaratim::{
+        cells::{Cell, CellSpatialIndex, GenerationSettings},
+        grid::world_to_grid,
+        odyrules::commons::{HazardKind, TileType},
+    },
+    pause::simulation_running,
+};
+
+const HAZARD_TICK_MS: u64 = 500;
+const COLD_DAMAGE_PER_SECOND: f32 = 0.25;
+const FOUNTAIN_LIGHT_RADIUS: f32 = 25.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            attach_fountain_lights,
+            apply_environmental_hazards.run_if(on_timer(Duration::from_millis(HAZARD_TICK_MS))),
+        )
+            .run_if(simulation_running),
+    );
+}
+
+type DamageableQuery<'w, 's> =
+    Query<'w, 's, (Entity, &'static Transform), Or<(With<Health>, With<OInsanAI>)>>;
+
+// Keeps the night cold away within `radius`
+#[derive(Component, Debug, Clone, Copy)]
+pub struct LightSource {
+    pub radius: f32,
+}
+
+fn attach_fountain_lights(
+    mut commands: Commands,
+    changed_cells: Query<(Entity, &Cell, Has<LightSource>), Changed<Cell>>,
+) {
+    for (entity, cell, has_light) in changed_cells.iter() {
+        if has_light || cell.tile_type != Some(TileType::FountainCenter) {
+            continue;
+        }
+
+        commands
+            .entity(entity)
+            .insert(LightSource {
+                radius: FOUNTAIN_LIGHT_RADIUS,
+            })
+            .with_children(|parent| {
+                parent.spawn((
+                    PointLight {
+                        color: Color::srgb(0.6, 0.8, 1.0),
+                        intensity: 400_000.0,
+                        range: FOUNTAIN_LIGHT_RADIUS,
+                        ..Default::default()
+                    },
+                    Transform::from_xyz(0.0, 0.3, 0.0),
+                ));
+            });
+    }
+}
+
+fn apply_environmental_hazards(
+    mut damage_events: EventWriter<DamageEvent>,
+    targets: DamageableQuery,
+    lights: Query<(&GlobalTransform, &LightSource)>,
+    cells: Query<&Cell>,
+    spatial_index: Res<CellSpatialIndex>,
+    settings: Res<GenerationSettings>,
+    clock: Res<WorldClock>,
+) {
+    let tick_secs = HAZARD_TICK_MS as f32 / 1000.0;
+
+    for (entity, transform) in targets.iter() {
+        let grid_position = world_to_grid(transform.translation, settings.cell_edge_length as f32);
+
+        let tile_hazard = spatial_index
+            .ground(&grid_position)
+            .and_then(|cell_entity| cells.get(*cell_entity).ok())
+            .and_then(|cell| cell.tile_type)
+            .and_then(|tile| tile.metadata().hazard);
+
+        if let Some(hazard) = tile_hazard {
+            damage_events.write(DamageEvent {
+                target: entity,
+                amount: hazard.damage_per_second * tick_secs,
+                source: DamageSource::Hazard(hazard.kind),
+            });
+        }
+
+        if clock.is_night() {
+            let is_lit = lights.iter().any(|(light_transform, light)| {
+                light_transform
+                    .translation()
+                    .distance(transform.translation)
+                    <= light.radius
+            });
+
+            if !is_lit {
+                damage_events.write(DamageEvent {
+                    target: entity,
+                    amount: COLD_DAMAGE_PER_SECOND * tick_secs,
+                    source: DamageSource::Hazard(HazardKind::Cold),
+                });
+            }
+        }
+    }
+}
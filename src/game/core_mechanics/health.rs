@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+
+use crate::game::core_mechanics::{
+    difficulty::Difficulty, enemy_ai::o_insan::components::OInsanAI,
+    oz_devinimli_yaratim::odyrules::commons::HazardKind,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_event::<DamageEvent>()
+        .add_event::<HealEvent>()
+        .add_systems(Update, (apply_damage, apply_healing));
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Self { current: max, max }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DamageSource {
+    Hazard(HazardKind),
+    Attack {
+        attacker: Entity,
+    },
+    Burning, // The burn status effect
+    #[cfg(feature = "debug-tools")]
+    Debug, // Cheat keys
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DamageEvent {
+    pub target: Entity,
+    pub amount: f32,
+    pub source: DamageSource,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealSource {
+    Bandage,
+    Pickup,
+    Fountain, // Regeneration after drinking, a little every frame
+    #[cfg(feature = "debug-tools")]
+    Debug, // Cheat keys
+}
+
+// The other half of DamageEvent: never takes anyone past their max
+#[derive(Event, Debug, Clone, Copy)]
+pub struct HealEvent {
+    pub target: Entity,
+    pub amount: f32,
+    pub source: HealSource,
+}
+
+// Player and other Health owners, plus o_insan which keeps its own health field
+fn apply_damage(
+    mut events: EventReader<DamageEvent>,
+    mut healths: Query<&mut Health>,
+    mut ais: Query<&mut OInsanAI>,
+    difficulty: Res<Difficulty>,
+) {
+    for event in events.read() {
+        // Enemy hits are scaled by difficulty, everything else lands as-is
+        let amount = match event.source {
+            DamageSource::Attack { attacker } if ais.contains(attacker) => {
+                event.amount * difficulty.enemy_damage
+            }
+            _ => event.amount,
+        };
+        debug!(
+            "{:?} took {:.1} damage from {:?}",
+            event.target, amount, event.source
+        );
+
+        if let Ok(mut health) = healths.get_mut(event.target) {
+            health.current = (health.current - amount).max(0.0);
+        } else if let Ok(mut ai) = ais.get_mut(event.target) {
+            ai.health = (ai.health - amount).max(0.0);
+        }
+    }
+}
+
+fn apply_healing(
+    mut events: EventReader<HealEvent>,
+    mut healths: Query<&mut Health>,
+    mut ais: Query<&mut OInsanAI>,
+) {
+    for event in events.read() {
+        debug!(
+            "{:?} healed {:.1} from {:?}",
+            event.target, event.amount, event.source
+        );
+
+        if let Ok(mut health) = healths.get_mut(event.target) {
+            health.current = (health.current + event.amount).min(health.max);
+        } else if let Ok(mut ai) = ais.get_mut(event.target) {
+            ai.health = (ai.health + event.amount).min(ai.max_health);
+        }
+    }
+}
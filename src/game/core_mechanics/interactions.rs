@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+
+use bevy::{ecs::system::SystemId, prelude::*};
+use bevy_rapier3d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    core_mechanics::{
+        combat::HitProbe,
+        enemy_ai::o_insan::components::OInsanAI,
+        oz_devinimli_yaratim::{
+            cells::{Cell, CellSpatialIndex, GenerationSettings},
+            grid::cells_in_radius,
+            odyrules::commons::TileType,
+        },
+        pause::simulation_running,
+        physics::{GROUND_GROUP, SOLID_GROUP},
+    },
+    input::{Action, action_just_pressed},
+    spawn::player::Player,
+    ui::{console::console_closed, world_map::world_map_closed},
+};
+
+const HOOK_RANGE: f32 = 8.0;
+const LOOK_RANGE: f32 = 8.0; // For tiles and enemies; interactable entities bring their own
+const LOOK_WIDTH: f32 = 2.0; // How far off the view line an entity still counts as looked at
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<InteractionStore>()
+        .init_resource::<InteractionHooks>()
+        .init_resource::<LookTarget>()
+        .add_event::<InteractionEvent>()
+        .add_systems(
+            Update,
+            (
+                advance_play_time.run_if(simulation_running),
+                (
+                    update_look_target,
+                    (send_interaction_event, run_interaction_hooks).run_if(
+                        action_just_pressed(Action::Interact)
+                            .and(console_closed)
+                            .and(world_map_closed)
+                            .and(simulation_running),
+                    ),
+                )
+                    .chain(),
+            ),
+        );
+}
+
+// Handlers run when the player uses a tile, so plugins can give any tile an interaction
+// without writing their own input system
+#[derive(Resource, Default)]
+pub struct InteractionHooks {
+    handlers: HashMap<TileType, Vec<SystemId<In<TileUse>>>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TileUse {
+    pub position: (i32, i32),
+    pub tile: TileType,
+    pub user: Entity, // Whoever pressed use; the player for now
+}
+
+pub trait RegisterInteractionHook {
+    fn register_interaction_hook<M>(
+        &mut self,
+        tile: TileType,
+        handler: impl IntoSystem<In<TileUse>, (), M> + 'static,
+    ) -> &mut Self;
+}
+
+impl RegisterInteractionHook for App {
+    fn register_interaction_hook<M>(
+        &mut self,
+        tile: TileType,
+        handler: impl IntoSystem<In<TileUse>, (), M> + 'static,
+    ) -> &mut Self {
+        let id = self.world_mut().register_system(handler);
+        self.init_resource::<InteractionHooks>();
+        self.world_mut()
+            .resource_mut::<InteractionHooks>()
+            .handlers
+            .entry(tile)
+            .or_default()
+            .push(id);
+        self
+    }
+}
+
+// An entity the player can use, such as a trader; tiles declare theirs in TileMetadata
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Interactable {
+    pub verb: &'static str,
+    pub range: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LookSubject {
+    Tile {
+        position: (i32, i32),
+        tile: TileType,
+    },
+    Entity(Entity),
+}
+
+// Whatever is under the crosshair, what to call it and what pressing use would do to it
+#[derive(Resource, Debug, Default, PartialEq)]
+pub struct LookTarget {
+    pub subject: Option<LookSubject>,
+    pub label: Option<String>, // Left out for plain ground
+    pub verb: Option<&'static str>,
+}
+
+// Sent when the player presses use while looking at something, whether or not it has a verb
+#[derive(Event, Debug, Clone, Copy)]
+pub struct InteractionEvent {
+    pub user: Entity,
+    pub subject: LookSubject,
+}
+
+// State of chests, fountains and doors, keyed by cell so it outlives the cell entity
+// and is saved with the world
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct InteractionStore {
+    pub play_time: f64, // Seconds the world has been simulated; timestamps below use it
+    pub tiles: HashMap<(i32, i32), InteractionState>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct InteractionState {
+    pub tile: TileType, // A different tile generated on the same cell later starts fresh
+    pub opened: bool,
+    pub looted: bool,
+    pub last_used: Option<f64>,
+}
+
+impl InteractionState {
+    fn new(tile: TileType) -> Self {
+        Self {
+            tile,
+            opened: false,
+            looted: false,
+            last_used: None,
+        }
+    }
+}
+
+impl InteractionStore {
+    pub fn state(&self, position: (i32, i32), tile: TileType) -> InteractionState {
+        self.tiles
+            .get(&position)
+            .filter(|state| state.tile == tile)
+            .copied()
+            .unwrap_or(InteractionState::new(tile))
+    }
+
+    pub fn state_mut(&mut self, position: (i32, i32), tile: TileType) -> &mut InteractionState {
+        let state = self
+            .tiles
+            .entry(position)
+            .or_insert(InteractionState::new(tile));
+        if state.tile != tile {
+            *state = InteractionState::new(tile);
+        }
+        state
+    }
+
+    // Seconds left before the object can be used again, if any
+    pub fn cooldown_remaining(
+        &self,
+        position: (i32, i32),
+        tile: TileType,
+        cooldown_secs: f64,
+    ) -> Option<f64> {
+        let last_used = self.state(position, tile).last_used?;
+        let remaining = last_used + cooldown_secs - self.play_time;
+        (remaining > 0.0).then_some(remaining)
+    }
+
+    pub fn mark_used(&mut self, position: (i32, i32), tile: TileType) {
+        let now = self.play_time;
+        self.state_mut(position, tile).last_used = Some(now);
+    }
+}
+
+fn advance_play_time(mut store: ResMut<InteractionStore>, time: Res<Time>) {
+    store.play_time += time.delta_secs_f64();
+}
+
+// Runs the handlers of the tile under the crosshair, or failing that of the closest hooked tile
+// in range, so a chest at the player's feet still opens
+fn run_interaction_hooks(
+    mut commands: Commands,
+    hooks: Res<InteractionHooks>,
+    target: Res<LookTarget>,
+    player: Single<(Entity, &Transform), With<Player>>,
+    cells: Query<(&Cell, &Transform)>,
+    spatial_index: Res<CellSpatialIndex>,
+    settings: Res<GenerationSettings>,
+) {
+    let (player_entity, player_transform) = *player;
+    let origin = player_transform.translation.with_y(0.0);
+
+    let looked_at = match target.subject {
+        Some(LookSubject::Tile { position, tile }) if hooks.handlers.contains_key(&tile) => {
+            Some((position, tile))
+        }
+        _ => None,
+    };
+    let Some((position, tile)) = looked_at.or_else(|| {
+        let (_, cell, _) = hooks
+            .handlers
+            .keys()
+            .filter_map(|tile| {
+                nearest_tile(
+                    origin,
+                    HOOK_RANGE,
+                    *tile,
+                    &cells,
+                    &spatial_index,
+                    settings.cell_edge_length as f32,
+                )
+            })
+            .min_by(|a, b| {
+                let distance =
+                    |transform: &Transform| transform.translation.with_y(0.0).distance(origin);
+                distance(a.2).total_cmp(&distance(b.2))
+            })?;
+        Some((cell.position, cell.tile_type?))
+    }) else {
+        return;
+    };
+
+    let tile_use = TileUse {
+        position,
+        tile,
+        user: player_entity,
+    };
+    for handler in hooks.handlers.get(&tile).into_iter().flatten() {
+        commands.run_system_with(*handler, tile_use);
+    }
+}
+
+fn send_interaction_event(
+    target: Res<LookTarget>,
+    player: Single<Entity, With<Player>>,
+    mut interactions: EventWriter<InteractionEvent>,
+) {
+    if let Some(subject) = target.subject {
+        interactions.write(InteractionEvent {
+            user: *player,
+            subject,
+        });
+    }
+}
+
+type LookableEntities<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        Option<&'static Interactable>,
+        Option<&'static OInsanAI>,
+        Option<&'static Name>,
+    ),
+    Or<(With<Interactable>, With<OInsanAI>)>,
+>;
+
+// Casts the view ray against the tile colliders, then tests enemies and interactable entities
+// against the same line, keeping whichever is closest
+fn update_look_target(
+    mut target: ResMut<LookTarget>,
+    player: Single<&Transform, With<Player>>,
+    cells: Query<&Cell>,
+    entities: LookableEntities,
+    rapier: ReadRapierContext,
+    store: Res<InteractionStore>,
+) {
+    let eye = player.translation;
+    let direction = player.forward().as_vec3();
+
+    let filter =
+        QueryFilter::default().groups(CollisionGroups::new(Group::ALL, GROUND_GROUP | SOLID_GROUP));
+    let tile = rapier
+        .single()
+        .ok()
+        .and_then(|context| context.cast_ray(eye, direction, LOOK_RANGE, true, filter))
+        .and_then(|(entity, distance)| {
+            let cell = cells.get(entity).ok()?;
+            let tile = cell.tile_type?;
+            let metadata = tile.metadata();
+            let verb = match metadata.interaction {
+                Some("Open")
+                    if tile == TileType::Door && store.state(cell.position, tile).opened =>
+                {
+                    Some("Close")
+                }
+                // Nothing left to take
+                Some("Open")
+                    if tile == TileType::Chest && store.state(cell.position, tile).looted =>
+                {
+                    None
+                }
+                verb => verb,
+            };
+            let named = verb.is_some() || !metadata.walkable && tile != TileType::Covered;
+            // Along the ground, to compare with the entities
+            let along = distance * direction.xz().length();
+            Some((
+                along,
+                LookTarget {
+                    subject: Some(LookSubject::Tile {
+                        position: cell.position,
+                        tile,
+                    }),
+                    label: named.then(|| tile.display_name().to_string()),
+                    verb,
+                },
+            ))
+        });
+
+    let entities = entities
+        .iter()
+        .filter_map(|(entity, transform, interactable, enemy, name)| {
+            let probe = HitProbe {
+                origin: eye.with_y(0.0),
+                direction,
+                reach: interactable.map_or(LOOK_RANGE, |interactable| interactable.range),
+            };
+            let along = probe.test(transform.translation, LOOK_WIDTH)?;
+            let label = match (enemy, name) {
+                (Some(enemy), _) => format!("Enemy {:.0}/{:.0}", enemy.health, enemy.max_health),
+                (None, Some(name)) => name.to_string(),
+                (None, None) => String::new(),
+            };
+            Some((
+                along,
+                LookTarget {
+                    subject: Some(LookSubject::Entity(entity)),
+                    label: Some(label).filter(|label| !label.is_empty()),
+                    verb: interactable.map(|interactable| interactable.verb),
+                },
+            ))
+        });
+
+    let looked_at = tile
+        .into_iter()
+        .chain(entities)
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, looked_at)| looked_at)
+        .unwrap_or_default();
+    target.set_if_neq(looked_at);
+}
+
+// Closest cell of `tile` within `range`, looked up through the spatial index
+pub fn nearest_tile<'a>(
+    origin: Vec3,
+    range: f32,
+    tile: TileType,
+    cells: &'a Query<(&Cell, &Transform)>,
+    spatial_index: &CellSpatialIndex,
+    cell_edge_length: f32,
+) -> Option<(Entity, &'a Cell, &'a Transform)> {
+    let origin = origin.with_y(0.0);
+
+    cells_in_radius(origin, range, cell_edge_length)
+        .filter_map(|position| spatial_index.ground(&position))
+        .filter_map(|entity| {
+            cells
+                .get(*entity)
+                .ok()
+                .map(|(cell, transform)| (*entity, cell, transform))
+        })
+        .filter(|(_, cell, _)| cell.tile_type == Some(tile))
+        .min_by(|a, b| {
+            let distance =
+                |transform: &Transform| transform.translation.with_y(0.0).distance(origin);
+            distance(a.2).total_cmp(&distance(b.2))
+        })
+}
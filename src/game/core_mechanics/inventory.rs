@@ -0,0 +1,312 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    core_mechanics::{
+        health::{HealEvent, HealSource, Health},
+        loot::{LootAssets, LootKind, PICKUP_RADIUS, spawn_loot_drop},
+        pause::simulation_running,
+    },
+    input::{Action, action_just_pressed},
+    spawn::player::Player,
+    ui::{console::console_closed, world_map::world_map_closed},
+};
+
+pub const HOTBAR_SLOTS: usize = 5;
+const BLADE_DURABILITY: u32 = 15; // Swings a blade lasts, landed or not
+const BANDAGE_STACK: u32 = 5;
+const ARROW_STACK: u32 = 20;
+const BANDAGE_HEAL: f32 = 30.0;
+const DROP_DISTANCE: f32 = PICKUP_RADIUS + 1.5; // Past the pickup radius, or it comes straight back
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_event::<ItemPickedUp>()
+        .add_event::<ItemDropped>()
+        .add_systems(
+            Update,
+            (
+                select_next_slot.run_if(action_just_pressed(Action::NextSlot)),
+                drop_held_item.run_if(action_just_pressed(Action::DropItem)),
+                use_held_item.run_if(action_just_pressed(Action::Attack).and(simulation_running)),
+            )
+                .run_if(console_closed.and(world_map_closed)),
+        );
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Item {
+    Blade,
+    Bow, // Fires the arrows carried anywhere in the hotbar
+    Arrow,
+    Bandage,
+}
+
+impl Item {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Item::Blade => "Blade",
+            Item::Bow => "Bow",
+            Item::Arrow => "Arrow",
+            Item::Bandage => "Bandage",
+        }
+    }
+
+    pub fn max_stack(self) -> u32 {
+        match self {
+            Item::Blade | Item::Bow => 1,
+            Item::Arrow => ARROW_STACK,
+            Item::Bandage => BANDAGE_STACK,
+        }
+    }
+
+    // Uses before it breaks, for items that wear out
+    pub fn durability(self) -> Option<u32> {
+        match self {
+            Item::Blade => Some(BLADE_DURABILITY),
+            Item::Bow | Item::Arrow | Item::Bandage => None,
+        }
+    }
+
+    pub fn is_weapon(self) -> bool {
+        self.is_melee() || self.is_ranged()
+    }
+
+    pub fn is_melee(self) -> bool {
+        self == Item::Blade
+    }
+
+    pub fn is_ranged(self) -> bool {
+        self == Item::Bow
+    }
+}
+
+fn one() -> u32 {
+    1
+}
+
+// Worn items never stack, so `wear` always belongs to the single item in the stack
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct ItemStack {
+    pub item: Item,
+    #[serde(default = "one")]
+    pub count: u32,
+    #[serde(default)]
+    pub wear: u32,
+}
+
+impl ItemStack {
+    pub fn new(item: Item) -> Self {
+        Self {
+            item,
+            count: 1,
+            wear: 0,
+        }
+    }
+
+    pub fn uses_left(&self) -> Option<u32> {
+        self.item
+            .durability()
+            .map(|durability| durability.saturating_sub(self.wear))
+    }
+}
+
+// What the player carries: a hotbar and nothing else. The selected slot is what's in hand.
+#[derive(Component, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Inventory {
+    pub slots: [Option<ItemStack>; HOTBAR_SLOTS],
+    pub selected: usize,
+}
+
+impl Inventory {
+    // Tops up stacks of the same item first, then fills empty slots; hands back what didn't fit
+    pub fn add(&mut self, mut stack: ItemStack) -> Option<ItemStack> {
+        let max = stack.item.max_stack();
+        for slot in self.slots.iter_mut().flatten() {
+            if slot.item == stack.item && slot.count < max {
+                let moved = stack.count.min(max - slot.count);
+                slot.count += moved;
+                stack.count -= moved;
+            }
+        }
+
+        for slot in self.slots.iter_mut().filter(|slot| slot.is_none()) {
+            if stack.count == 0 {
+                break;
+            }
+            let moved = stack.count.min(max);
+            *slot = Some(ItemStack {
+                count: moved,
+                ..stack
+            });
+            stack.count -= moved;
+        }
+
+        (stack.count > 0).then_some(stack)
+    }
+
+    pub fn held(&self) -> Option<&ItemStack> {
+        self.slots[self.selected].as_ref()
+    }
+
+    pub fn held_mut(&mut self) -> Option<&mut ItemStack> {
+        self.slots[self.selected].as_mut()
+    }
+
+    pub fn holds_weapon(&self) -> bool {
+        self.held().is_some_and(|stack| stack.item.is_weapon())
+    }
+
+    pub fn holds_melee_weapon(&self) -> bool {
+        self.held().is_some_and(|stack| stack.item.is_melee())
+    }
+
+    pub fn contains(&self, item: Item) -> bool {
+        self.slots.iter().flatten().any(|stack| stack.item == item)
+    }
+
+    // Uses up one of the held item, emptying the slot with the last of the stack
+    pub fn consume_held(&mut self) {
+        take_one(&mut self.slots[self.selected]);
+    }
+
+    // Uses up one of `item` from wherever it is, for ammo; false if there's none left
+    pub fn consume(&mut self, item: Item) -> bool {
+        let Some(slot) = self
+            .slots
+            .iter_mut()
+            .find(|slot| slot.is_some_and(|stack| stack.item == item))
+        else {
+            return false;
+        };
+        take_one(slot);
+        true
+    }
+
+    pub fn take_held(&mut self) -> Option<ItemStack> {
+        self.slots[self.selected].take()
+    }
+}
+
+fn take_one(slot: &mut Option<ItemStack>) {
+    if let Some(stack) = slot {
+        stack.count -= 1;
+        if stack.count == 0 {
+            *slot = None;
+        }
+    }
+}
+
+// Something went from the ground into the player's inventory
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ItemPickedUp {
+    pub item: Item,
+    pub count: u32,
+}
+
+// The player put down the stack they were holding
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ItemDropped {
+    pub stack: ItemStack,
+}
+
+fn select_next_slot(mut inventory: Single<&mut Inventory, With<Player>>) {
+    inventory.selected = (inventory.selected + 1) % HOTBAR_SLOTS;
+}
+
+// Lands in front of the player as an ordinary loot drop, wear and all
+fn drop_held_item(
+    mut commands: Commands,
+    player: Single<(&Transform, &mut Inventory), With<Player>>,
+    loot_assets: Res<LootAssets>,
+    mut dropped: EventWriter<ItemDropped>,
+) {
+    let (transform, mut inventory) = player.into_inner();
+    let Some(stack) = inventory.take_held() else {
+        return;
+    };
+
+    let forward = transform
+        .forward()
+        .as_vec3()
+        .with_y(0.0)
+        .normalize_or_zero();
+    let position = transform.translation + forward * DROP_DISTANCE;
+    spawn_loot_drop(&mut commands, &loot_assets, LootKind::Item(stack), position);
+    dropped.write(ItemDropped { stack });
+    println!("🎒 Dropped {} x{}", stack.item.display_name(), stack.count);
+}
+
+// Attack with something other than a weapon in hand uses it; swinging and shooting are handled
+// by combat and projectiles
+fn use_held_item(
+    player: Single<(Entity, &mut Inventory, &Health), With<Player>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut heals: EventWriter<HealEvent>,
+) {
+    let (entity, mut inventory, health) = player.into_inner();
+    if !window.focused {
+        return;
+    }
+
+    match inventory.held().map(|stack| stack.item) {
+        Some(Item::Bandage) => {
+            if health.current >= health.max {
+                println!("🩹 You're not hurt");
+                return;
+            }
+            heals.write(HealEvent {
+                target: entity,
+                amount: BANDAGE_HEAL,
+                source: HealSource::Bandage,
+            });
+            inventory.consume_held();
+            println!("🩹 Applied a bandage (+{:.0})", BANDAGE_HEAL);
+        }
+        Some(Item::Blade | Item::Bow | Item::Arrow) | None => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_tops_up_stacks_before_empty_slots() {
+        let mut inventory = Inventory::default();
+        inventory.slots[2] = Some(ItemStack {
+            count: 3,
+            ..ItemStack::new(Item::Bandage)
+        });
+
+        let leftover = inventory.add(ItemStack {
+            count: 4,
+            ..ItemStack::new(Item::Bandage)
+        });
+
+        assert_eq!(leftover, None);
+        assert_eq!(inventory.slots[2].map(|stack| stack.count), Some(5));
+        assert_eq!(inventory.slots[0].map(|stack| stack.count), Some(2));
+    }
+
+    #[test]
+    fn add_hands_back_what_does_not_fit() {
+        let mut inventory = Inventory::default();
+        for _ in 0..HOTBAR_SLOTS {
+            assert_eq!(inventory.add(ItemStack::new(Item::Blade)), None);
+        }
+
+        let leftover = inventory.add(ItemStack::new(Item::Blade));
+
+        assert_eq!(leftover, Some(ItemStack::new(Item::Blade)));
+    }
+
+    #[test]
+    fn consume_empties_the_slot_with_the_last_one() {
+        let mut inventory = Inventory::default();
+        inventory.slots[3] = Some(ItemStack::new(Item::Arrow));
+
+        assert!(inventory.consume(Item::Arrow));
+        assert_eq!(inventory.slots[3], None);
+        assert!(!inventory.consume(Item::Arrow));
+    }
+}
@@ -0,0 +1,152 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::game::{
+    core_mechanics::{
+        health::{HealEvent, HealSource},
+        inventory::{Inventory, ItemPickedUp, ItemStack},
+    },
+    spawn::player::Player,
+};
+
+pub const PICKUP_RADIUS: f32 = 3.0;
+const SPIN_SPEED: f32 = 2.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Startup, setup_loot_assets)
+        .add_systems(Update, (spin_loot_drops, pick_up_loot));
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum LootKind {
+    Item(ItemStack), // Goes into the inventory; the rest are used up on the spot
+    Health(f32),
+    Currency(u32),
+}
+
+impl LootKind {
+    pub fn is_weapon(&self) -> bool {
+        matches!(self, LootKind::Item(stack) if stack.item.is_weapon())
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct LootDrop {
+    pub kind: LootKind,
+}
+
+#[derive(Resource)]
+pub struct LootAssets {
+    pub mesh: Handle<Mesh>,
+    pub weapon_material: Handle<StandardMaterial>,
+    pub health_material: Handle<StandardMaterial>,
+    pub currency_material: Handle<StandardMaterial>,
+}
+
+fn setup_loot_assets(
+    mut commands: Commands,
+    mut mesh_assets: ResMut<Assets<Mesh>>,
+    mut material_assets: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(LootAssets {
+        mesh: mesh_assets.add(Cuboid::new(0.6, 0.6, 0.6)),
+        weapon_material: material_assets.add(StandardMaterial {
+            base_color: Color::srgb(0.8, 0.8, 0.9),
+            emissive: LinearRgba::rgb(0.4, 0.4, 0.6),
+            ..Default::default()
+        }),
+        health_material: material_assets.add(StandardMaterial {
+            base_color: Color::srgb(0.9, 0.1, 0.2),
+            emissive: LinearRgba::rgb(0.6, 0.0, 0.1),
+            ..Default::default()
+        }),
+        currency_material: material_assets.add(StandardMaterial {
+            base_color: Color::srgb(1.0, 0.8, 0.2),
+            emissive: LinearRgba::rgb(0.6, 0.45, 0.0),
+            metallic: 0.8,
+            ..Default::default()
+        }),
+    });
+}
+
+pub fn spawn_loot_drop(
+    commands: &mut Commands,
+    assets: &LootAssets,
+    kind: LootKind,
+    position: Vec3,
+) -> Entity {
+    let material = match kind {
+        _ if kind.is_weapon() => assets.weapon_material.clone(),
+        LootKind::Item(_) | LootKind::Health(_) => assets.health_material.clone(),
+        LootKind::Currency(_) => assets.currency_material.clone(),
+    };
+
+    commands
+        .spawn((
+            Name::new(format!("Loot_{:?}", kind)),
+            LootDrop { kind },
+            Mesh3d(assets.mesh.clone()),
+            MeshMaterial3d(material),
+            Transform::from_translation(position.with_y(0.8)),
+        ))
+        .id()
+}
+
+fn spin_loot_drops(mut drops: Query<&mut Transform, With<LootDrop>>, time: Res<Time>) {
+    for mut transform in drops.iter_mut() {
+        transform.rotate_y(SPIN_SPEED * time.delta_secs());
+    }
+}
+
+// A drop that doesn't fit in the inventory stays on the ground with whatever is left of it
+fn pick_up_loot(
+    mut commands: Commands,
+    mut drops: Query<(Entity, &mut LootDrop, &Transform), Without<Player>>,
+    player: Single<(Entity, &Transform, &mut Player, &mut Inventory)>,
+    mut picked_up: EventWriter<ItemPickedUp>,
+    mut heals: EventWriter<HealEvent>,
+) {
+    let (player_entity, player_transform, mut player, mut inventory) = player.into_inner();
+
+    for (entity, mut drop, transform) in drops.iter_mut() {
+        let distance = transform
+            .translation
+            .with_y(0.0)
+            .distance(player_transform.translation.with_y(0.0));
+        if distance > PICKUP_RADIUS {
+            continue;
+        }
+
+        match drop.kind {
+            LootKind::Item(stack) => {
+                let leftover = inventory.add(stack);
+                let count = stack.count - leftover.map_or(0, |left| left.count);
+                if count > 0 {
+                    picked_up.write(ItemPickedUp {
+                        item: stack.item,
+                        count,
+                    });
+                    println!("🎒 Picked up {} x{}", stack.item.display_name(), count);
+                }
+                if let Some(left) = leftover {
+                    drop.kind = LootKind::Item(left);
+                    continue;
+                }
+            }
+            LootKind::Health(amount) => {
+                heals.write(HealEvent {
+                    target: player_entity,
+                    amount,
+                    source: HealSource::Pickup,
+                });
+                println!("❤️ Picked up health (+{:.0})", amount);
+            }
+            LootKind::Currency(amount) => {
+                player.coins += amount;
+                println!("🪙 Picked up {} coins ({} total)", amount, player.coins);
+            }
+        }
+
+        commands.entity(entity).despawn();
+    }
+}
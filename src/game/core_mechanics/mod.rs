@@ -1,11 +1,71 @@
 use bevy::prelude::*;
 
+pub mod ai_lod;
+pub mod blob_shadows;
+pub mod chests;
+pub mod combat;
+pub mod day_night;
+pub mod difficulty;
+pub mod doors;
+pub mod emotes;
 pub mod enemy_ai; 
+pub mod exploration;
+pub mod fast_travel;
+pub mod fireflies;
+pub mod focus;
+pub mod fountains;
+pub mod hazards;
+pub mod health;
+pub mod interactions;
+pub mod inventory;
+pub mod loot;
 pub mod movement; 
+pub mod naming;
 pub mod oz_devinimli_yaratim; 
+#[cfg(feature = "debug-tools")]
+pub mod path_overlay;
+pub mod pause;
+pub mod permadeath;
+pub mod physics;
+pub mod play_stats;
+pub mod projectiles;
+pub mod reputation;
+pub mod status_effects;
+pub mod triggers;
+pub mod walkability;
+pub mod wildlife;
 
 pub(super) fn plugin(app: &mut App) {
+    app.add_plugins(pause::plugin);
+    app.add_plugins(difficulty::plugin);
     app.add_plugins(movement::plugin); 
     app.add_plugins(oz_devinimli_yaratim::plugin); 
-    //app.add_plugins(enemy_ai::plugin); // AI behavior systems
+    app.add_plugins(exploration::plugin);
+    app.add_plugins(naming::plugin);
+    app.add_plugins(ai_lod::plugin);
+    app.add_plugins(wildlife::plugin);
+    app.add_plugins(day_night::plugin);
+    app.add_plugins(fireflies::plugin);
+    app.add_plugins(blob_shadows::plugin);
+    app.add_plugins(health::plugin);
+    app.add_plugins(status_effects::plugin);
+    app.add_plugins(hazards::plugin);
+    app.add_plugins(loot::plugin);
+    app.add_plugins(inventory::plugin);
+    app.add_plugins(walkability::plugin);
+    app.add_plugins(physics::plugin);
+    app.add_plugins(enemy_ai::plugin); // AI behavior systems
+    app.add_plugins(interactions::plugin);
+    app.add_plugins(fountains::plugin);
+    app.add_plugins(fast_travel::plugin);
+    app.add_plugins(emotes::plugin);
+    app.add_plugins(reputation::plugin);
+    app.add_plugins(chests::plugin);
+    app.add_plugins(doors::plugin);
+    app.add_plugins(combat::plugin);
+    app.add_plugins(projectiles::plugin);
+    app.add_plugins(focus::plugin);
+    app.add_plugins(play_stats::plugin);
+    app.add_plugins(permadeath::plugin);
+    app.add_plugins(triggers::plugin);
 }
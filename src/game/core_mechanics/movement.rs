@@ -1,46 +1,170 @@
-use bevy::{
-    input::{common_conditions::input_just_released, mouse::AccumulatedMouseMotion},
-    prelude::*,
-    window::PrimaryWindow,
+use bevy::{input::mouse::AccumulatedMouseMotion, prelude::*, window::PrimaryWindow};
+use bevy_rapier3d::control::KinematicCharacterController;
+
+use crate::game::{
+    audio::{PlaySfx, SfxKind},
+    core_mechanics::{
+        difficulty::Difficulty, enemy_ai::o_insan::components::player_in_control,
+        status_effects::StatusEffects, walkability::TerrainSpeed,
+    },
+    input::{Action, Actions, action_just_released},
+    spawn::player::Player,
+    ui::{
+        console::console_closed, difficulty_panel::difficulty_panel_closed,
+        world_map::world_map_closed,
+    },
 };
-
-use crate::game::spawn::player::Player;
-const MOVEMENT_SPEED: f32 = 23.0;
+const FOOTSTEP_DISTANCE: f32 = 4.0;
+const STAMINA_REGEN: f32 = 15.0; // Per second, while nothing is spending it
+const STAMINA_RECOVERED: f32 = 0.25; // Of the max, before sprint works again after running out
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(
-        Update,
-        (
-            player_movement.after(player_look), 
-            player_look,                      
-            focus_event,                       
-            toggle_grab.run_if(input_just_released(KeyCode::Escape)), 
-        ),
-    )
-    .add_observer(apply_grab); 
+    app.init_resource::<PlayerVelocity>()
+        .add_event::<PlayerTeleported>()
+        .add_systems(
+            Update,
+            (
+                (update_movement_state, player_movement)
+                    .chain()
+                    .after(player_look)
+                    .run_if(
+                        console_closed
+                            .and(world_map_closed)
+                            .and(difficulty_panel_closed)
+                            .and(player_in_control),
+                    ),
+                player_look.run_if(world_map_closed),
+                focus_event,                       
+                toggle_grab.run_if(action_just_released(Action::ReleaseCursor)), 
+            ),
+        )
+        .add_observer(apply_grab); 
 }
 
 #[derive(Event, Deref)]
 struct GrabEvent(bool);
 
-fn player_movement(
-    mut player: Single<&mut Transform, With<Player>>,
-    input: Res<ButtonInput<KeyCode>>,
+// World units per second from walking input, teleports excluded
+#[derive(Resource, Debug, Default, Clone, Copy, Deref)]
+pub struct PlayerVelocity(pub Vec3);
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PlayerTeleported {
+    pub from: Vec3,
+    pub to: Vec3,
+}
+
+// Sprint and Crouch apply while held; sprinting falls back to walking when out of stamina, until
+// some comes back or sprint is let go
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MovementState {
+    #[default]
+    Walk,
+    Sprint,
+    Crouch,
+}
+
+impl MovementState {
+    pub fn speed(self) -> f32 {
+        match self {
+            MovementState::Walk => 23.0,
+            MovementState::Sprint => 38.0,
+            MovementState::Crouch => 11.0,
+        }
+    }
+
+    // Stamina per second spent while moving in this state, before difficulty
+    pub fn stamina_cost(self) -> Option<f32> {
+        match self {
+            MovementState::Sprint => Some(20.0),
+            MovementState::Walk | MovementState::Crouch => None,
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Stamina {
+    pub current: f32,
+    pub max: f32,
+    pub exhausted: bool, // Ran out, so holding sprint doesn't flicker between sprint and walk
+}
+
+impl Stamina {
+    pub fn new(max: f32) -> Self {
+        Self {
+            current: max,
+            max,
+            exhausted: false,
+        }
+    }
+}
+
+// Last frame's velocity says whether the player is moving; close enough for spending stamina
+fn update_movement_state(
+    player: Single<(&mut MovementState, &mut Stamina), With<Player>>,
+    actions: Actions,
+    velocity: Res<PlayerVelocity>,
+    difficulty: Res<Difficulty>,
     time: Res<Time>,
 ) {
+    let (mut state, mut stamina) = player.into_inner();
+    let sprint_held = actions.pressed(Action::Sprint);
+    if stamina.current <= 0.0 {
+        stamina.exhausted = true;
+    } else if stamina.current >= stamina.max * STAMINA_RECOVERED || !sprint_held {
+        stamina.exhausted = false;
+    }
+
+    let wanted = if actions.pressed(Action::Crouch) {
+        MovementState::Crouch
+    } else if sprint_held && !stamina.exhausted {
+        MovementState::Sprint
+    } else {
+        MovementState::Walk
+    };
+    state.set_if_neq(wanted);
+
+    let spending = state
+        .stamina_cost()
+        .filter(|_| velocity.length_squared() > 0.0);
+    stamina.current = match spending {
+        Some(cost) => stamina.current - cost * difficulty.stamina_drain * time.delta_secs(),
+        None => stamina.current + STAMINA_REGEN * time.delta_secs(),
+    }
+    .clamp(0.0, stamina.max);
+}
+
+pub fn player_movement(
+    player: Single<
+        (
+            &Transform,
+            &mut KinematicCharacterController,
+            &MovementState,
+            Option<&StatusEffects>,
+        ),
+        With<Player>,
+    >,
+    actions: Actions,
+    time: Res<Time>,
+    mut sfx: EventWriter<PlaySfx>,
+    mut velocity: ResMut<PlayerVelocity>,
+    mut distance_since_step: Local<f32>,
+    terrain: TerrainSpeed,
+) {
+    let (player, mut controller, state, effects) = player.into_inner();
     let mut intent = Vec3::ZERO;
 
-    if input.pressed(KeyCode::KeyW) || input.pressed(KeyCode::ArrowUp) {
+    if actions.pressed(Action::MoveForward) {
         intent.z += 1.0; 
     }
-    if input.pressed(KeyCode::KeyS) || input.pressed(KeyCode::ArrowDown) {
+    if actions.pressed(Action::MoveBack) {
         intent.z -= 1.0; 
     }
 
-    if input.pressed(KeyCode::KeyA) || input.pressed(KeyCode::ArrowLeft) {
+    if actions.pressed(Action::MoveLeft) {
         intent.x -= 1.0; 
     }
-    if input.pressed(KeyCode::KeyD) || input.pressed(KeyCode::ArrowRight) {
+    if actions.pressed(Action::MoveRight) {
         intent.x += 1.0; 
     }
 
@@ -49,7 +173,22 @@ fn player_movement(
     let mut to_move = forward + right; 
     to_move.y = 0.0; 
 
-    player.translation += to_move.normalize_or_zero() * time.delta_secs() * MOVEMENT_SPEED;
+    let speed = state.speed()
+        * terrain.at(player.translation)
+        * effects.map_or(1.0, StatusEffects::speed_factor);
+    // The controller moves the player in PostUpdate, sliding along anything solid
+    let step = to_move.normalize_or_zero() * time.delta_secs() * speed;
+    controller.translation = Some(step);
+    velocity.0 = to_move.normalize_or_zero() * speed;
+
+    *distance_since_step += step.length();
+    if *distance_since_step >= FOOTSTEP_DISTANCE {
+        *distance_since_step = 0.0;
+        sfx.write(PlaySfx {
+            kind: SfxKind::Footstep,
+            position: player.translation.with_y(0.0),
+        });
+    }
 }
 
 fn player_look(
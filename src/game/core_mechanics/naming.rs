@@ -0,0 +1,211 @@
+use std::time::Duration;
+
+use bevy::{platform::collections::HashSet, prelude::*, time::common_conditions::on_timer};
+use rand::{SeedableRng, prelude::*, rngs::StdRng};
+
+use crate::game::{
+    core_mechanics::oz_devinimli_yaratim::{
+        cells::{Cell, CellSpatialIndex, GenerationSettings},
+        grid::{cells_in_radius, world_to_grid},
+        odyrules::commons::TileType,
+        prefabs::SettlementCompleted,
+    },
+    spawn::player::Player,
+};
+
+const NAME_SEED: u64 = 0x5EED_7A1E;
+const REGION_CELLS: i32 = 12; // Regions are squares of this many cells
+const POI_DISCOVERY_RANGE: f32 = 30.0;
+const POI_MIN_SPACING: i32 = 4; // Cells; neighboring fountain centers belong to one fountain
+
+const ONSETS: [&str; 16] = [
+    "E", "Ka", "Mor", "Tal", "Vey", "Sa", "Dun", "Il", "Ar", "Ze", "Bel", "Or", "Nu", "Hra", "Yas",
+    "Go",
+];
+const MIDDLES: [&str; 10] = [
+    "ge", "ra", "lin", "do", "va", "mir", "ke", "su", "the", "an",
+];
+const ENDINGS: [&str; 8] = ["", "n", "th", "ra", "k", "s", "el", "or"];
+
+const REGION_TEMPLATES: [&str; 6] = [
+    "the Hollow of {}",
+    "the {} Wilds",
+    "{} Vale",
+    "the Marches of {}",
+    "{} Moor",
+    "the Reach of {}",
+];
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<DiscoveredPlaces>()
+        .add_event::<PlaceDiscovered>()
+        .add_systems(
+            Update,
+            (
+                (discover_region, discover_points_of_interest)
+                    .run_if(on_timer(Duration::from_millis(250))),
+                name_settlements,
+            ),
+        );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaceKind {
+    Region,
+    Fountain,
+    Ruins,
+    Settlement,
+}
+
+#[derive(Debug, Clone)]
+pub struct Place {
+    pub name: String,
+    pub kind: PlaceKind,
+    pub position: (i32, i32), // Grid cell the name is shown at on the map
+}
+
+#[derive(Resource, Default)]
+pub struct DiscoveredPlaces {
+    pub places: Vec<Place>,
+    regions: HashSet<(i32, i32)>,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct PlaceDiscovered {
+    pub name: String,
+    pub kind: PlaceKind,
+}
+
+// Same name for the same place every time, without storing it anywhere
+fn place_rng(kind: PlaceKind, position: (i32, i32)) -> StdRng {
+    let mut hash = NAME_SEED ^ kind as u64;
+    for value in [position.0, position.1] {
+        hash = (hash ^ value as u32 as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        hash ^= hash >> 31;
+    }
+    StdRng::seed_from_u64(hash)
+}
+
+fn generate_word(rng: &mut impl Rng) -> String {
+    let mut word = ONSETS.choose(rng).unwrap().to_string();
+    for _ in 0..rng.random_range(0..=1) {
+        word.push_str(MIDDLES.choose(rng).unwrap());
+    }
+    word.push_str(ENDINGS.choose(rng).unwrap());
+    word
+}
+
+pub fn place_name(kind: PlaceKind, position: (i32, i32)) -> String {
+    let mut rng = place_rng(kind, position);
+    let word = generate_word(&mut rng);
+
+    match kind {
+        PlaceKind::Region => REGION_TEMPLATES
+            .choose(&mut rng)
+            .unwrap()
+            .replace("{}", &word),
+        PlaceKind::Fountain => format!("the Spring of {}", word),
+        PlaceKind::Ruins => format!("the Ruins of {}", word),
+        PlaceKind::Settlement => format!("the village of {}", word),
+    }
+}
+
+fn region_of(grid: (i32, i32)) -> (i32, i32) {
+    (
+        grid.0.div_euclid(REGION_CELLS),
+        grid.1.div_euclid(REGION_CELLS),
+    )
+}
+
+fn discover_region(
+    mut discovered: ResMut<DiscoveredPlaces>,
+    mut events: EventWriter<PlaceDiscovered>,
+    player: Single<&Transform, With<Player>>,
+    settings: Res<GenerationSettings>,
+) {
+    let region = region_of(world_to_grid(
+        player.translation,
+        settings.cell_edge_length as f32,
+    ));
+    if !discovered.regions.insert(region) {
+        return;
+    }
+
+    let name = place_name(PlaceKind::Region, region);
+    println!("🗺️ You have entered {}", name);
+    discovered.places.push(Place {
+        name: name.clone(),
+        kind: PlaceKind::Region,
+        position: (
+            region.0 * REGION_CELLS + REGION_CELLS / 2,
+            region.1 * REGION_CELLS + REGION_CELLS / 2,
+        ),
+    });
+    events.write(PlaceDiscovered {
+        name,
+        kind: PlaceKind::Region,
+    });
+}
+
+fn discover_points_of_interest(
+    mut discovered: ResMut<DiscoveredPlaces>,
+    mut events: EventWriter<PlaceDiscovered>,
+    player: Single<&Transform, With<Player>>,
+    cells: Query<&Cell>,
+    spatial_index: Res<CellSpatialIndex>,
+    settings: Res<GenerationSettings>,
+) {
+    let nearby = cells_in_radius(
+        player.translation,
+        POI_DISCOVERY_RANGE,
+        settings.cell_edge_length as f32,
+    )
+    .filter_map(|position| spatial_index.ground(&position))
+    .filter_map(|entity| cells.get(*entity).ok());
+
+    for cell in nearby {
+        let kind = match cell.tile_type {
+            Some(TileType::FountainCenter) => PlaceKind::Fountain,
+            Some(TileType::RuinEntrance) => PlaceKind::Ruins,
+            _ => continue,
+        };
+        let already_named = discovered.places.iter().any(|place| {
+            place.kind == kind
+                && (place.position.0 - cell.position.0).abs() <= POI_MIN_SPACING
+                && (place.position.1 - cell.position.1).abs() <= POI_MIN_SPACING
+        });
+        if already_named {
+            continue;
+        }
+
+        let name = place_name(kind, cell.position);
+        println!("📍 Discovered {}", name);
+        discovered.places.push(Place {
+            name: name.clone(),
+            kind,
+            position: cell.position,
+        });
+        events.write(PlaceDiscovered { name, kind });
+    }
+}
+
+// Settlements are named once they have finished generating around the player
+fn name_settlements(
+    mut discovered: ResMut<DiscoveredPlaces>,
+    mut completed: EventReader<SettlementCompleted>,
+    mut events: EventWriter<PlaceDiscovered>,
+) {
+    for settlement in completed.read() {
+        let name = place_name(PlaceKind::Settlement, settlement.origin);
+        println!("🏘️ {} has finished generating", name);
+        discovered.places.push(Place {
+            name: name.clone(),
+            kind: PlaceKind::Settlement,
+            position: settlement.origin,
+        });
+        events.write(PlaceDiscovered {
+            name,
+            kind: PlaceKind::Settlement,
+        });
+    }
+}
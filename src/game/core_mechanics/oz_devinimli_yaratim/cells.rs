@@ -1,21 +1,35 @@
 use bevy::{platform::collections::HashMap, prelude::*};
-use std::{collections::HashSet, time::Duration};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 use crate::game::{
-    core_mechanics::oz_devinimli_yaratim::odyrules::{
-        commons::TileType, open_space_rules::OpenSpaceRules,
+    core_mechanics::{
+        movement::PlayerVelocity,
+        oz_devinimli_yaratim::{
+            grid::{
+                GROUND_FLOOR, GridKey, cell_to_chunk, chunk_cells, grid_to_world, ground_key,
+                world_to_grid,
+            },
+            odyrules::{biomes::BiomeRules, commons::TileType},
+        },
+        pause::simulation_running,
     },
     spawn::player::Player,
 };
 
 const UPDATE_INTERVAL_MS: u64 = 200;
 const DESPAWN_INTERVAL_MS: u64 = 200;
+const ENTROPY_NOISE: f32 = 1e-3; // Well below the gap between two different domains
 
 #[derive(Resource)]
 pub struct GenerationSettings {
     pub cell_edge_length: i32,
     pub total_cells_on_edge: i32,
     pub spawn_distance: f32,
+    pub prefetch_secs: f32, // Seconds of movement the cell square is pushed ahead by
+    pub max_prefetch_cells: i32, // Cap on that lead so cells behind the player stay loaded
+    pub cells_per_chunk: i32, // Cells per edge of a chunk, the unit cells are loaded in
+    pub spawn_batch: usize, // Most cells spawned per update, nearest chunks first
 }
 
 impl Default for GenerationSettings {
@@ -24,44 +38,134 @@ impl Default for GenerationSettings {
             cell_edge_length: 9,     
             total_cells_on_edge: 17, 
             spawn_distance: 0.7,    
+            prefetch_secs: 1.2,
+            max_prefetch_cells: 3,
+            cells_per_chunk: 4,
+            spawn_batch: 512,
         }
     }
 }
 
+impl GenerationSettings {
+    // Cells further than this from the player are despawned
+    pub fn despawn_distance(&self) -> f32 {
+        (self.total_cells_on_edge as f32 * self.cell_edge_length as f32) * self.spawn_distance
+    }
+
+    // Chunks covering the cell square around `center`, `margin` chunks wider on every side
+    pub fn chunk_range(&self, center: (i32, i32), margin: i32) -> ((i32, i32), (i32, i32)) {
+        let half_size = self.total_cells_on_edge / 2;
+        let (min_x, min_z) = cell_to_chunk(
+            (center.0 - half_size, center.1 - half_size),
+            self.cells_per_chunk,
+        );
+        let (max_x, max_z) = cell_to_chunk(
+            (center.0 + half_size, center.1 + half_size),
+            self.cells_per_chunk,
+        );
+        (
+            (min_x - margin, min_z - margin),
+            (max_x + margin, max_z + margin),
+        )
+    }
+}
+
+// Where the cell square is centered: the player, pushed ahead along their movement
+#[derive(Resource, Debug, Default, Clone, Copy, Deref)]
+pub struct GenerationFocus(pub Vec3);
+
 #[derive(Resource, Default)]
 pub struct CellSpatialIndex {
-    pub grid: HashMap<(i32, i32), Entity>,
+    pub grid: HashMap<GridKey, Entity>,
+    pub chunks: HashMap<(i32, i32), Entity>,
+    pub footprints: HashMap<(i32, i32), (i32, i32)>, // Covered position -> anchor of its multi-cell tile
+}
+
+impl CellSpatialIndex {
+    // Everything outside generation itself only looks at the open world
+    pub fn ground(&self, position: &(i32, i32)) -> Option<&Entity> {
+        self.grid.get(&ground_key(*position))
+    }
+}
+
+// Tiles of collapsed cells that were unloaded, so walking back finds the same world.
+// Saved with the world, so std's HashMap for serde.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct CollapsedTileCache {
+    pub tiles: std::collections::HashMap<(i32, i32), TileType>,
 }
 
 pub(super) fn plugin(app: &mut App) {
     app.init_resource::<GenerationSettings>() 
         .init_resource::<CellSpatialIndex>() 
-        .add_systems(Update, (create_cells, destroy_cells).chain()); 
+        .init_resource::<GenerationFocus>()
+        .init_resource::<CollapsedTileCache>()
+        .add_systems(
+            Update,
+            (
+                update_generation_focus,
+                attach_cells_to_chunks,
+                create_cells,
+                destroy_cells,
+            )
+                .chain()
+                .run_if(simulation_running),
+        )
+        .add_observer(cache_despawned_cell);
 }
 
 #[derive(Component, Debug)]
 pub struct Cell {
     pub is_collapsed: bool,
     pub tile_type: Option<TileType>,
-    pub entropy: i32,
+    pub entropy: f32,
     pub valid_tiles: Vec<TileType>,
     pub position: (i32, i32),
+    pub floor: i32,
+    pub height: i32, // In levels of elevation::LEVEL_HEIGHT, set once the cell is spawned
 }
 
 impl Cell {
+    // Unweighted to start with; weigh_new_cells applies the rule weights once it's spawned
     pub fn new(all_tiles: &[TileType], position: (i32, i32)) -> Self {
-        Self {
+        let mut cell = Self {
             is_collapsed: false,
             tile_type: None,
-            entropy: all_tiles.len() as i32, 
+            entropy: 0.0,
             valid_tiles: all_tiles.to_vec(), 
             position,
-        }
+            floor: GROUND_FLOOR,
+            height: 0,
+        };
+        cell.update_entropy(&HashMap::new());
+        cell
     }
-    pub fn update_entropy(&mut self) {
-        if !self.is_collapsed {
-            self.entropy = self.valid_tiles.len() as i32;
+    // Shannon entropy of the weighted tile distribution, so a domain dominated by one heavy
+    // tile counts as nearly decided. The noise breaks ties between equal domains at random.
+    pub fn update_entropy(&mut self, weights: &HashMap<TileType, f32>) {
+        if self.is_collapsed {
+            return;
         }
+
+        let weight = |tile: &TileType| weights.get(tile).copied().unwrap_or(1.0);
+        let total: f32 = self.valid_tiles.iter().map(weight).sum();
+        let weighted_log: f32 = self
+            .valid_tiles
+            .iter()
+            .map(weight)
+            .filter(|weight| *weight > 0.0)
+            .map(|weight| weight * weight.ln())
+            .sum();
+        let entropy = if total > 0.0 {
+            total.ln() - weighted_log / total
+        } else {
+            0.0
+        };
+
+        self.entropy = entropy + rand::random::<f32>() * ENTROPY_NOISE;
+    }
+    pub fn key(&self) -> GridKey {
+        (self.position.0, self.position.1, self.floor)
     }
     pub fn is_contradicted(&mut self) -> bool {
         self.valid_tiles.is_empty()
@@ -71,13 +175,40 @@ impl Cell {
 #[derive(Component)]
 pub struct Tile;
 
+// An N×N block of cells that is spawned and despawned as one; the cells keep world space
+// transforms and are tied to it by relationship rather than parented
+#[derive(Component, Debug)]
+pub struct Chunk {
+    pub position: (i32, i32),
+}
+
+#[derive(Component, Debug)]
+#[relationship(relationship_target = ChunkCells)]
+pub struct InChunk(pub Entity);
+
+// Despawning the chunk despawns its cells
+#[derive(Component, Debug, Default)]
+#[relationship_target(relationship = InChunk, linked_spawn)]
+pub struct ChunkCells(Vec<Entity>);
+
+fn update_generation_focus(
+    mut focus: ResMut<GenerationFocus>,
+    player: Single<&Transform, With<Player>>,
+    velocity: Res<PlayerVelocity>,
+    settings: Res<GenerationSettings>,
+) {
+    let max_lead = (settings.max_prefetch_cells * settings.cell_edge_length) as f32;
+    let lead = (velocity.with_y(0.0) * settings.prefetch_secs).clamp_length_max(max_lead);
+    focus.0 = player.translation + lead;
+}
+
 fn create_cells(
     mut commands: Commands,
-    player_pos: Single<&Transform, With<Player>>,
-    existing_cells: Query<&Transform, With<Cell>>,
+    focus: Res<GenerationFocus>,
+    mut spatial_index: ResMut<CellSpatialIndex>,
     mut last_update: Local<Duration>,
     time: Res<Time>,
-    wfc_rules: Res<OpenSpaceRules>,
+    biome_rules: BiomeRules,
     settings: Res<GenerationSettings>,
 ) {
     let now = time.elapsed();
@@ -86,50 +217,106 @@ fn create_cells(
     }
     *last_update = now;
 
-    let player_grid_x =
-        (player_pos.translation.x / settings.cell_edge_length as f32).round() as i32;
-    let player_grid_z =
-        (player_pos.translation.z / settings.cell_edge_length as f32).round() as i32;
-
-    let existing_positions: HashSet<(i32, i32)> = existing_cells
-        .iter()
-        .map(|transform| {
-            let grid_x =
-                (transform.translation.x / settings.cell_edge_length as f32).round() as i32;
-            let grid_z =
-                (transform.translation.z / settings.cell_edge_length as f32).round() as i32;
-            (grid_x, grid_z)
-        })
+    let focus_cell = world_to_grid(**focus, settings.cell_edge_length as f32);
+    let ((min_x, min_z), (max_x, max_z)) = settings.chunk_range(focus_cell, 0);
+    let focus_chunk = cell_to_chunk(focus_cell, settings.cells_per_chunk);
+
+    let mut chunk_positions: Vec<_> = (min_x..=max_x)
+        .flat_map(|chunk_x| (min_z..=max_z).map(move |chunk_z| (chunk_x, chunk_z)))
         .collect();
+    chunk_positions.sort_by_key(|(chunk_x, chunk_z)| {
+        (chunk_x - focus_chunk.0).pow(2) + (chunk_z - focus_chunk.1).pow(2)
+    });
 
-    let half_size = settings.total_cells_on_edge / 2;
+    let mut spawned = 0;
+    for chunk_position in chunk_positions {
+        if spawned >= settings.spawn_batch {
+            break; // The rest come with the next update
+        }
+        let chunk = match spatial_index.chunks.get(&chunk_position) {
+            Some(chunk) => *chunk,
+            None => spawn_chunk(&mut commands, &mut spatial_index, chunk_position),
+        };
 
-    for grid_x in (player_grid_x - half_size)..=(player_grid_x + half_size) {
-        for grid_z in (player_grid_z - half_size)..=(player_grid_z + half_size) {
-            if existing_positions.contains(&(grid_x, grid_z)) {
+        // Chunks rebuilt from a save can be missing cells that were never loaded
+        for position in chunk_cells(chunk_position, settings.cells_per_chunk) {
+            if spatial_index.ground(&position).is_some() {
                 continue;
             }
+            let cell = Cell::new(&biome_rules.tiles_at(position), position);
+            let entity = spawn_cell(&mut commands, cell, &settings);
+            commands.entity(entity).insert(InChunk(chunk));
+            spawned += 1;
+        }
+    }
+}
+
+fn spawn_chunk(
+    commands: &mut Commands,
+    spatial_index: &mut CellSpatialIndex,
+    position: (i32, i32),
+) -> Entity {
+    let chunk = commands
+        .spawn((
+            Name::new(format!("Chunk_{}_{}", position.0, position.1)),
+            Chunk { position },
+        ))
+        .id();
+    spatial_index.chunks.insert(position, chunk);
+    chunk
+}
+
+// Cells spawned on their own (restored from a save, switched by backtracking) join the chunk
+// they fall in, which is created if it isn't loaded
+fn attach_cells_to_chunks(
+    mut commands: Commands,
+    mut spatial_index: ResMut<CellSpatialIndex>,
+    loose_cells: Query<(Entity, &Cell), Without<InChunk>>,
+    settings: Res<GenerationSettings>,
+) {
+    for (entity, cell) in loose_cells.iter() {
+        let chunk_position = cell_to_chunk(cell.position, settings.cells_per_chunk);
+        let chunk = match spatial_index.chunks.get(&chunk_position) {
+            Some(chunk) => *chunk,
+            None => spawn_chunk(&mut commands, &mut spatial_index, chunk_position),
+        };
+        commands.entity(entity).insert(InChunk(chunk));
+    }
+}
 
-            let world_x = grid_x as f32 * settings.cell_edge_length as f32;
-            let world_z = grid_z as f32 * settings.cell_edge_length as f32;
-            let position = (grid_x, grid_z);
+pub fn spawn_cell(commands: &mut Commands, cell: Cell, settings: &GenerationSettings) -> Entity {
+    let (grid_x, grid_z) = cell.position;
+    let translation = grid_to_world(cell.position, settings.cell_edge_length as f32);
 
-            let cell = Cell::new(&wfc_rules.all_tiles, position);
+    commands
+        .spawn((
+            Name::new(format!("Cell_{}_{}", grid_x, grid_z)), 
+            cell,
+            Transform::from_translation(translation),
+            Tile, 
+        ))
+        .id()
+}
 
-            commands.spawn((
-                Name::new(format!("Cell_{}_{}", grid_x, grid_z)), 
-                cell,
-                Transform::from_translation(Vec3::new(world_x, 0.0, world_z)),
-                Tile, 
-            ));
-        }
+// Runs before the component is gone, for every way a cell can be despawned
+fn cache_despawned_cell(
+    trigger: Trigger<OnRemove, Cell>,
+    cells: Query<&Cell>,
+    mut cache: ResMut<CollapsedTileCache>,
+) {
+    // Only the open world is cached; floors of structures are generated afresh
+    if let Ok(cell) = cells.get(trigger.target())
+        && let (true, Some(tile)) = (cell.is_collapsed, cell.tile_type)
+        && cell.floor == GROUND_FLOOR
+    {
+        cache.tiles.insert(cell.position, tile);
     }
 }
 
 fn destroy_cells(
     mut commands: Commands,
-    player_pos: Single<&Transform, With<Player>>,
-    cells: Query<(Entity, &Transform), With<Cell>>,
+    focus: Res<GenerationFocus>,
+    chunks: Query<(Entity, &Chunk)>,
     mut last_update: Local<Duration>,
     time: Res<Time>,
     settings: Res<GenerationSettings>,
@@ -141,18 +328,31 @@ fn destroy_cells(
     }
     *last_update = now;
 
-    let despawn_distance = (settings.total_cells_on_edge as f32 * settings.cell_edge_length as f32)
-        * settings.spawn_distance;
+    // Measured from the focus too, or prefetched chunks would be despawned right away.
+    // One chunk of slack past the loaded square keeps chunks on its edge from churning.
+    let focus_cell = world_to_grid(**focus, settings.cell_edge_length as f32);
+    let ((min_x, min_z), (max_x, max_z)) = settings.chunk_range(focus_cell, 1);
 
-    for (entity, transform) in cells.iter() {
-        if player_pos.translation.distance(transform.translation) > despawn_distance {
-            spatial_index.grid.remove(&(
-                transform.translation.x as i32,
-                transform.translation.z as i32,
-            ));
-
-            // Despawn the entity
-            commands.entity(entity).despawn();
+    for (entity, chunk) in chunks.iter() {
+        let (chunk_x, chunk_z) = chunk.position;
+        if (min_x..=max_x).contains(&chunk_x) && (min_z..=max_z).contains(&chunk_z) {
+            continue;
         }
+
+        spatial_index.chunks.remove(&chunk.position);
+        let in_chunk = |position: &(i32, i32)| {
+            cell_to_chunk(*position, settings.cells_per_chunk) == chunk.position
+        };
+        // Every floor of the chunk goes with it
+        spatial_index
+            .grid
+            .retain(|(x, z, _), _| !in_chunk(&(*x, *z)));
+        // A footprint is released once either its anchor or the covered cell is gone
+        spatial_index
+            .footprints
+            .retain(|covered, anchor| !in_chunk(covered) && !in_chunk(anchor));
+
+        // Takes the chunk's cells with it
+        commands.entity(entity).despawn();
     }
 }
@@ -0,0 +1,140 @@
+use bevy::prelude::*;
+
+use crate::game::core_mechanics::{
+    enemy_ai::{encounters::Trader, o_insan::components::OInsanAI},
+    oz_devinimli_yaratim::{
+        cells::{Cell, GenerationSettings},
+        grid::{FLOOR_HEIGHT, GROUND_FLOOR, world_to_grid},
+        odycore::open_space::weigh_new_cells,
+        odyrules::commons::TileType,
+    },
+};
+
+const ELEVATION_SEED: u64 = 0x5EED_4111;
+pub const LEVEL_HEIGHT: f32 = 2.0; // World units between two heights
+const FOLLOW_RATE: f32 = 8.0; // How quickly walkers settle onto a new height, per second
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ElevationMap>().add_systems(
+        Update,
+        (raise_new_cells.before(weigh_new_cells), follow_terrain),
+    );
+}
+
+// Tiles that make up the step where the ground drops by one height
+pub const STEP_TILES: [TileType; 3] = [TileType::Cliff, TileType::Slope, TileType::Stairs];
+
+// Smooth noise on a lattice of `spacing` cells, cut into `levels` heights. Between lattice
+// points the noise moves by at most 1 / spacing per cell, so while `levels` stays below
+// `spacing`, neighboring cells are never more than one height apart.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct ElevationMap {
+    pub seed: u64,
+    pub spacing: i32,
+    pub levels: i32,
+}
+
+impl Default for ElevationMap {
+    fn default() -> Self {
+        Self {
+            seed: ELEVATION_SEED,
+            spacing: 16,
+            levels: 3,
+        }
+    }
+}
+
+impl ElevationMap {
+    fn lattice_value(&self, point: (i32, i32)) -> f32 {
+        let mut hash = self.seed;
+        for value in [point.0, point.1] {
+            hash = (hash ^ value as u32 as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+            hash ^= hash >> 31;
+        }
+        (hash % 1024) as f32 / 1024.0
+    }
+
+    pub fn height_at(&self, position: (i32, i32)) -> i32 {
+        let (lattice_x, lattice_z) = (
+            position.0.div_euclid(self.spacing),
+            position.1.div_euclid(self.spacing),
+        );
+        let fraction =
+            |coordinate: i32| coordinate.rem_euclid(self.spacing) as f32 / self.spacing as f32;
+        let (fx, fz) = (fraction(position.0), fraction(position.1));
+
+        let near = self
+            .lattice_value((lattice_x, lattice_z))
+            .lerp(self.lattice_value((lattice_x + 1, lattice_z)), fx);
+        let far = self
+            .lattice_value((lattice_x, lattice_z + 1))
+            .lerp(self.lattice_value((lattice_x + 1, lattice_z + 1)), fx);
+        (near.lerp(far, fz) * self.levels as f32) as i32
+    }
+
+    // The offset to a neighbor a height below, if the ground steps down from here
+    pub fn downhill(&self, position: (i32, i32)) -> Option<(i32, i32)> {
+        let height = self.height_at(position);
+        [(1, 0), (-1, 0), (0, 1), (0, -1)]
+            .into_iter()
+            .find(|(dx, dz)| self.height_at((position.0 + dx, position.1 + dz)) < height)
+    }
+
+    // A cell above any of its neighbors is where the ground steps down
+    pub fn is_step(&self, position: (i32, i32)) -> bool {
+        self.downhill(position).is_some()
+    }
+}
+
+// Where a cell's tile stands: its ground height, plus the floors it's stacked on
+pub fn cell_lift(cell: &Cell) -> f32 {
+    cell.height as f32 * LEVEL_HEIGHT + cell.floor as f32 * FLOOR_HEIGHT
+}
+
+// Cells on a step can only be a step tile, and only they can. Rule sets without any step tiles
+// leave the domain alone, so learned rules still work on a flat world.
+fn raise_new_cells(
+    elevation: Res<ElevationMap>,
+    mut added_cells: Query<(&mut Cell, &mut Transform), Added<Cell>>,
+) {
+    for (mut cell, mut transform) in added_cells.iter_mut() {
+        cell.height = elevation.height_at(cell.position);
+        transform.translation.y = cell_lift(&cell);
+        if cell.is_collapsed || cell.floor != GROUND_FLOOR {
+            continue;
+        }
+
+        let step = elevation.is_step(cell.position);
+        let narrowed: Vec<TileType> = cell
+            .valid_tiles
+            .iter()
+            .copied()
+            .filter(|tile| STEP_TILES.contains(tile) == step)
+            .collect();
+        if !narrowed.is_empty() {
+            cell.valid_tiles = narrowed;
+        }
+    }
+}
+
+type WalkerQuery<'w, 's> =
+    Query<'w, 's, &'static mut Transform, Or<(With<OInsanAI>, With<Trader>)>>;
+
+// The AI and traders walk on the ground's height, smoothed so a step reads as a climb. The player
+// falls and climbs under physics::apply_gravity instead.
+fn follow_terrain(
+    elevation: Res<ElevationMap>,
+    settings: Res<GenerationSettings>,
+    mut walkers: WalkerQuery,
+    time: Res<Time>,
+) {
+    let edge = settings.cell_edge_length as f32;
+    for mut transform in walkers.iter_mut() {
+        let ground = elevation.height_at(world_to_grid(transform.translation, edge)) as f32;
+        let target = ground * LEVEL_HEIGHT;
+        transform.translation.y = transform
+            .translation
+            .y
+            .lerp(target, (FOLLOW_RATE * time.delta_secs()).min(1.0));
+    }
+}
@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+
+// Cell (x, z) is centered on (x * edge, z * edge), so it spans half an edge either side
+
+// Where a cell sits in the spatial index: (x, z, floor). The open world is the ground floor,
+// and only it is generated so far; a cell's neighbors are on its own floor.
+pub type GridKey = (i32, i32, i32);
+pub const GROUND_FLOOR: i32 = 0;
+pub const FLOOR_HEIGHT: f32 = 4.0; // World units between two floors
+
+pub fn ground_key(position: (i32, i32)) -> GridKey {
+    (position.0, position.1, GROUND_FLOOR)
+}
+
+pub fn neighbor_key(key: GridKey, (dx, dz): (i32, i32)) -> GridKey {
+    (key.0 + dx, key.1 + dz, key.2)
+}
+
+pub fn world_to_grid(point: Vec3, cell_edge_length: f32) -> (i32, i32) {
+    (
+        (point.x / cell_edge_length).round() as i32,
+        (point.z / cell_edge_length).round() as i32,
+    )
+}
+
+pub fn grid_to_world(position: (i32, i32), cell_edge_length: f32) -> Vec3 {
+    Vec3::new(
+        position.0 as f32 * cell_edge_length,
+        0.0,
+        position.1 as f32 * cell_edge_length,
+    )
+}
+
+// Snaps a world point to the center of the cell it falls in, keeping its height
+pub fn cell_center(point: Vec3, cell_edge_length: f32) -> Vec3 {
+    grid_to_world(world_to_grid(point, cell_edge_length), cell_edge_length).with_y(point.y)
+}
+
+// Grid positions whose cell centers are within `radius` world units of `center` on the ground plane
+pub fn cells_in_radius(
+    center: Vec3,
+    radius: f32,
+    cell_edge_length: f32,
+) -> impl Iterator<Item = (i32, i32)> {
+    let (center_x, center_z) = world_to_grid(center, cell_edge_length);
+    let reach = (radius / cell_edge_length).ceil() as i32;
+    let ground_center = center.with_y(0.0);
+
+    (center_x - reach..=center_x + reach)
+        .flat_map(move |x| (center_z - reach..=center_z + reach).map(move |z| (x, z)))
+        .filter(move |position| {
+            grid_to_world(*position, cell_edge_length).distance(ground_center) <= radius
+        })
+}
+
+// Chunk (x, z) owns cells x * size ..= x * size + size - 1 on each axis
+pub fn cell_to_chunk(position: (i32, i32), cells_per_chunk: i32) -> (i32, i32) {
+    (
+        position.0.div_euclid(cells_per_chunk),
+        position.1.div_euclid(cells_per_chunk),
+    )
+}
+
+pub fn chunk_cells(chunk: (i32, i32), cells_per_chunk: i32) -> impl Iterator<Item = (i32, i32)> {
+    let (origin_x, origin_z) = (chunk.0 * cells_per_chunk, chunk.1 * cells_per_chunk);
+
+    (origin_x..origin_x + cells_per_chunk)
+        .flat_map(move |x| (origin_z..origin_z + cells_per_chunk).map(move |z| (x, z)))
+}
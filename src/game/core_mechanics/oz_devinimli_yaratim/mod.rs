@@ -1,14 +1,27 @@
 use bevy::prelude::*;
 
 pub mod cells; 
+pub mod elevation;
+pub mod grid;
 pub mod odycore; 
 pub mod odyrules; 
+pub mod prefabs;
+pub mod roads;
+pub mod structures;
+pub mod templates;
 pub mod tiles_meshes_models; 
+pub mod world_snapshot;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
         cells::plugin,               
+        elevation::plugin,
         tiles_meshes_models::plugin, 
         odycore::plugin,             
+        prefabs::plugin,
+        roads::plugin,
+        structures::plugin,
+        templates::plugin,
+        world_snapshot::plugin,
     ));
 }
@@ -0,0 +1,308 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    ecs::{
+        entity::Entity,
+        event::EventWriter,
+        system::{Commands, Query, Res, ResMut},
+    },
+    log::debug,
+};
+
+use crate::game::core_mechanics::oz_devinimli_yaratim::{
+    cells::{Cell, CellSpatialIndex, GenerationSettings, spawn_cell},
+    grid::{GridKey, neighbor_key},
+    odycore::history::GenerationHistory,
+    odycore::open_space::{
+        CellCollapsed, OpenSpacePropagationQueue, constrained_tiles, get_random_tile,
+    },
+    odyrules::{
+        commons::{DIRECTION_VECTORS, Rules, TileType},
+        open_space_rules::OpenSpaceRules,
+    },
+};
+
+// Recent free collapse choices, so a contradiction next to one of them can be solved by
+// picking that cell a different tile instead of forcing Ground
+#[derive(Debug)]
+pub struct CollapseStack {
+    pub decisions: VecDeque<CollapseDecision>,
+    pub max_depth: usize, // Decisions kept; contradictions can't reach back further than this
+    pub retries: Vec<CellRetry>, // Decisions switched this frame, waiting for a fresh cell entity
+}
+
+impl Default for CollapseStack {
+    fn default() -> Self {
+        Self {
+            decisions: VecDeque::new(),
+            max_depth: 16,
+            retries: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CollapseDecision {
+    pub key: GridKey,
+    pub tile: TileType,
+    pub tried: Vec<TileType>, // Never picked again for this decision
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CellRetry {
+    pub entity: Entity, // The cell entity still showing the old tile
+    pub key: GridKey,
+    pub tile: TileType,
+}
+
+impl CollapseStack {
+    pub fn record(&mut self, key: GridKey, tile: TileType) {
+        self.decisions.push_back(CollapseDecision {
+            key,
+            tile,
+            tried: vec![tile],
+        });
+        while self.decisions.len() > self.max_depth {
+            self.decisions.pop_front();
+        }
+    }
+
+    // Newest decision next to the contradicted cell with an untried tile that leaves every
+    // open cell around it at least one option; returns its index and the new tile
+    pub fn find_retry(
+        &self,
+        contradicted: GridKey,
+        cells: &Query<&mut Cell>,
+        spatial_index: &CellSpatialIndex,
+        rules: &OpenSpaceRules,
+    ) -> Option<(usize, TileType)> {
+        let tile_at = |key| collapsed_tile_at(key, cells, spatial_index);
+
+        self.decisions
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, decision)| is_adjacent(decision.key, contradicted))
+            // A decision whose cell was since unloaded or restamped can't be revisited
+            .filter(|(_, decision)| tile_at(decision.key) == Some(decision.tile))
+            .find_map(|(index, decision)| {
+                let open_neighbors = open_neighbors(decision.key, cells, spatial_index);
+
+                let mut options = constrained_tiles(decision.key, &rules.all_tiles, rules, tile_at);
+                options.retain(|tile| {
+                    !decision.tried.contains(tile)
+                        && rules.footprint(*tile) == (1, 1)
+                        && rules.weights.get(tile).is_none_or(|weight| *weight > 0.0)
+                });
+                options.retain(|tile| {
+                    open_neighbors.iter().all(|(_, neighbor)| {
+                        let with_retry = |key| {
+                            if key == decision.key {
+                                Some(*tile)
+                            } else {
+                                tile_at(key)
+                            }
+                        };
+                        !constrained_tiles(*neighbor, &rules.all_tiles, rules, with_retry)
+                            .is_empty()
+                    })
+                });
+
+                (!options.is_empty()).then(|| (index, get_random_tile(&rules.weights, &options)))
+            })
+    }
+
+    // Switches the decision to `tile` and recomputes the open cells around it from scratch
+    pub fn apply_retry(
+        &mut self,
+        index: usize,
+        tile: TileType,
+        cells: &mut Query<&mut Cell>,
+        spatial_index: &CellSpatialIndex,
+        rules: &OpenSpaceRules,
+    ) {
+        let decision = &mut self.decisions[index];
+        debug!(
+            "Backtracking {:?} from {:?} to {:?}",
+            decision.key, decision.tile, tile
+        );
+        decision.tile = tile;
+        decision.tried.push(tile);
+        let key = decision.key;
+
+        for (neighbor_entity, neighbor) in open_neighbors(key, cells, spatial_index) {
+            let valid_tiles = constrained_tiles(neighbor, &rules.all_tiles, rules, |at| {
+                if at == key {
+                    Some(tile)
+                } else {
+                    collapsed_tile_at(at, cells, spatial_index)
+                }
+            });
+            if let Ok(mut cell) = cells.get_mut(neighbor_entity) {
+                cell.valid_tiles = valid_tiles;
+                cell.update_entropy(rules.weights());
+            }
+        }
+
+        if let Some(entity) = spatial_index.grid.get(&key) {
+            self.retries.push(CellRetry {
+                entity: *entity,
+                key,
+                tile,
+            });
+        }
+    }
+}
+
+// Cells switched by backtracking get a fresh entity, so everything built for the old tile
+// (models, lights, door leaves) is despawned along with the old one
+pub fn respawn_retried_cells(
+    mut commands: Commands,
+    mut history: ResMut<GenerationHistory>,
+    mut open_space: ResMut<OpenSpacePropagationQueue>,
+    mut collapsed: EventWriter<CellCollapsed>,
+    mut spatial_index: ResMut<CellSpatialIndex>,
+    settings: Res<GenerationSettings>,
+) {
+    for retry in history.backtrack.retries.drain(..) {
+        commands.entity(retry.entity).despawn();
+
+        let cell = Cell {
+            is_collapsed: true,
+            tile_type: Some(retry.tile),
+            entropy: 0.0,
+            valid_tiles: vec![retry.tile],
+            position: (retry.key.0, retry.key.1),
+            floor: retry.key.2,
+            height: 0,
+        };
+        let entity = spawn_cell(&mut commands, cell, &settings);
+        spatial_index.grid.insert(retry.key, entity);
+        open_space.queue.push_back(entity);
+        collapsed.write(CellCollapsed {
+            position: (retry.key.0, retry.key.1),
+            tile: retry.tile,
+        });
+    }
+}
+
+fn collapsed_tile_at(
+    key: GridKey,
+    cells: &Query<&mut Cell>,
+    spatial_index: &CellSpatialIndex,
+) -> Option<TileType> {
+    spatial_index
+        .grid
+        .get(&key)
+        .and_then(|entity| cells.get(*entity).ok())
+        .filter(|cell| cell.is_collapsed)
+        .and_then(|cell| cell.tile_type)
+}
+
+fn open_neighbors(
+    key: GridKey,
+    cells: &Query<&mut Cell>,
+    spatial_index: &CellSpatialIndex,
+) -> Vec<(Entity, GridKey)> {
+    DIRECTION_VECTORS
+        .iter()
+        .map(|(_, offset)| neighbor_key(key, *offset))
+        .filter_map(|neighbor| {
+            let entity = *spatial_index.grid.get(&neighbor)?;
+            let cell = cells.get(entity).ok()?;
+            (!cell.is_collapsed).then_some((entity, neighbor))
+        })
+        .collect()
+}
+
+fn is_adjacent(a: GridKey, b: GridKey) -> bool {
+    a.2 == b.2 && (a.0 - b.0).abs() + (a.1 - b.1).abs() == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        ecs::{system::SystemState, world::World},
+        platform::collections::HashMap,
+    };
+
+    use super::*;
+    use crate::game::core_mechanics::oz_devinimli_yaratim::{
+        grid::ground_key, odyrules::commons::DIRECTIONS,
+    };
+
+    const ALL_TILES: [TileType; 3] = [TileType::Ground, TileType::Tree, TileType::Chest];
+
+    // Same on every side: a tile may only sit next to the tiles listed for it
+    fn rules(pairs: &[(TileType, TileType)]) -> OpenSpaceRules {
+        let mut allowed_neighbors: HashMap<TileType, HashMap<_, Vec<TileType>>> = HashMap::new();
+        for (a, b) in pairs.iter().flat_map(|(a, b)| [(a, b), (b, a)]) {
+            for direction in DIRECTIONS {
+                let allowed = allowed_neighbors
+                    .entry(*a)
+                    .or_default()
+                    .entry(direction)
+                    .or_default();
+                if !allowed.contains(b) {
+                    allowed.push(*b);
+                }
+            }
+        }
+        OpenSpaceRules {
+            allowed_neighbors,
+            all_tiles: ALL_TILES.to_vec(),
+            weights: HashMap::new(),
+            footprints: HashMap::new(),
+        }
+    }
+
+    fn spawn(
+        world: &mut World,
+        spatial_index: &mut CellSpatialIndex,
+        position: (i32, i32),
+        tile: Option<TileType>,
+    ) -> Entity {
+        let mut cell = Cell::new(&ALL_TILES, position);
+        cell.is_collapsed = tile.is_some();
+        cell.tile_type = tile;
+        let entity = world.spawn(cell).id();
+        spatial_index.grid.insert(ground_key(position), entity);
+        entity
+    }
+
+    #[test]
+    fn retry_picks_a_tile_its_neighbors_can_live_with() {
+        use TileType::{Chest, Ground, Tree, Water};
+
+        let rules = rules(&[
+            (Ground, Ground),
+            (Ground, Water),
+            (Tree, Tree),
+            (Chest, Chest),
+            (Water, Water),
+        ]);
+
+        // The Tree decision left the cell between it and the Water with nothing to pick;
+        // Chest fits the decision's cell too but would leave that cell just as stuck
+        let mut world = World::new();
+        let mut spatial_index = CellSpatialIndex::default();
+        let decided = spawn(&mut world, &mut spatial_index, (0, 0), Some(Tree));
+        let stuck = spawn(&mut world, &mut spatial_index, (1, 0), None);
+        spawn(&mut world, &mut spatial_index, (2, 0), Some(Water));
+
+        let mut stack = CollapseStack::default();
+        stack.record(ground_key((0, 0)), Tree);
+
+        let mut state = SystemState::<Query<&mut Cell>>::new(&mut world);
+        let mut cells = state.get_mut(&mut world);
+        let retry = stack.find_retry(ground_key((1, 0)), &cells, &spatial_index, &rules);
+        assert_eq!(retry, Some((0, Ground)));
+
+        stack.apply_retry(0, Ground, &mut cells, &spatial_index, &rules);
+        assert_eq!(stack.decisions[0].tried, vec![Tree, Ground]);
+        assert_eq!(cells.get(stuck).unwrap().valid_tiles, vec![Ground]);
+        assert_eq!(stack.retries.len(), 1);
+        assert_eq!(stack.retries[0].entity, decided);
+        assert_eq!(stack.retries[0].tile, Ground);
+    }
+}
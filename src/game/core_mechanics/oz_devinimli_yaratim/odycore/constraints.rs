@@ -0,0 +1,169 @@
+use bevy::{ecs::resource::Resource, platform::collections::HashMap};
+
+use crate::game::core_mechanics::oz_devinimli_yaratim::{
+    odycore::open_space::get_random_tile, odyrules::commons::TileType,
+};
+
+const FOUNTAIN_PIECES: &[TileType] = &[
+    TileType::FountainCorner1,
+    TileType::FountainCorner2,
+    TileType::FountainCorner3,
+    TileType::FountainCorner4,
+    TileType::FountainEdge1,
+    TileType::FountainEdge2,
+    TileType::FountainEdge3,
+    TileType::FountainEdge4,
+];
+
+// Counts over a stretch of the world, which adjacency between neighbors can't express
+#[derive(Debug, Clone, Copy)]
+pub enum GlobalConstraint {
+    // No more than `count` in the square of `area` cells centered on a new one. `parts` only
+    // make sense around the tile, so they're kept out along with it.
+    AtMost {
+        tile: TileType,
+        count: usize,
+        area: i32,
+        parts: &'static [TileType],
+    },
+    // At least `count` in every grid-aligned square of `area` cells, forced onto the last
+    // cells that could still take one
+    AtLeast {
+        tile: TileType,
+        count: usize,
+        area: i32,
+    },
+}
+
+// How a cell stands towards some tile, whether it's loaded or not
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Prospect {
+    Placed,
+    Possible, // Still open to it, or not generated yet
+    RuledOut,
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct GlobalConstraints {
+    pub constraints: Vec<GlobalConstraint>,
+}
+
+impl Default for GlobalConstraints {
+    fn default() -> Self {
+        Self {
+            constraints: vec![
+                GlobalConstraint::AtMost {
+                    tile: TileType::FountainCenter,
+                    count: 1,
+                    area: 30,
+                    parts: FOUNTAIN_PIECES,
+                },
+                GlobalConstraint::AtLeast {
+                    tile: TileType::Chest,
+                    count: 1,
+                    area: 8,
+                },
+            ],
+        }
+    }
+}
+
+impl GlobalConstraints {
+    // Weighted pick for `position` that keeps within every constraint, narrowing `valid_tiles`
+    // as it goes. Limits are only counted for the tile picked, and waived when the cell would
+    // be left with nothing else, since its neighbors already demand it.
+    pub fn pick(
+        &self,
+        position: (i32, i32),
+        valid_tiles: &mut Vec<TileType>,
+        weights: &HashMap<TileType, f32>,
+        prospect: impl Fn((i32, i32), TileType) -> Prospect,
+    ) -> TileType {
+        for constraint in self.constraints.iter() {
+            if let Some(tile) = constraint.required(position, valid_tiles, &prospect) {
+                valid_tiles.retain(|valid| *valid == tile);
+            }
+        }
+
+        let mut tile = get_random_tile(weights, valid_tiles);
+        while let Some(banned) = self
+            .constraints
+            .iter()
+            .find_map(|constraint| constraint.limited(position, tile, &prospect))
+        {
+            if valid_tiles.iter().all(|valid| banned.contains(valid)) {
+                break;
+            }
+            valid_tiles.retain(|valid| !banned.contains(valid));
+            tile = get_random_tile(weights, valid_tiles);
+        }
+        tile
+    }
+}
+
+impl GlobalConstraint {
+    // The tile `position` has to take, when no other cell of its square is left to take it
+    fn required(
+        &self,
+        position: (i32, i32),
+        valid_tiles: &[TileType],
+        prospect: &impl Fn((i32, i32), TileType) -> Prospect,
+    ) -> Option<TileType> {
+        let GlobalConstraint::AtLeast { tile, count, area } = *self else {
+            return None;
+        };
+        if !valid_tiles.contains(&tile) {
+            return None;
+        }
+
+        let origin = (
+            position.0.div_euclid(area) * area,
+            position.1.div_euclid(area) * area,
+        );
+        let mut placed = 0;
+        let mut possible = 0;
+        for at in square(origin, area).filter(|at| *at != position) {
+            match prospect(at, tile) {
+                Prospect::Placed => placed += 1,
+                Prospect::Possible => possible += 1,
+                Prospect::RuledOut => {}
+            }
+        }
+        (placed + possible < count).then_some(tile)
+    }
+
+    // Tiles to keep out of `position` if picking `tile` there would go over this limit
+    fn limited(
+        &self,
+        position: (i32, i32),
+        tile: TileType,
+        prospect: &impl Fn((i32, i32), TileType) -> Prospect,
+    ) -> Option<Vec<TileType>> {
+        let GlobalConstraint::AtMost {
+            tile: limited,
+            count,
+            area,
+            parts,
+        } = *self
+        else {
+            return None;
+        };
+        if tile != limited && !parts.contains(&tile) {
+            return None;
+        }
+
+        let half = area / 2;
+        let corner = (position.0 - half, position.1 - half);
+        let crowded = square(corner, area)
+            .filter(|at| prospect(*at, limited) == Prospect::Placed)
+            .take(count)
+            .count()
+            >= count;
+        crowded.then(|| [limited].into_iter().chain(parts.iter().copied()).collect())
+    }
+}
+
+// Grid positions of the square of `size` cells from `corner` towards +X and +Z
+fn square(corner: (i32, i32), size: i32) -> impl Iterator<Item = (i32, i32)> {
+    (corner.0..corner.0 + size).flat_map(move |x| (corner.1..corner.1 + size).map(move |z| (x, z)))
+}
@@ -0,0 +1,134 @@
+use std::{cmp::Ordering, cmp::Reverse, collections::BinaryHeap};
+
+use bevy::ecs::{
+    entity::Entity,
+    query::Changed,
+    resource::Resource,
+    system::{Query, ResMut},
+};
+
+use crate::game::core_mechanics::oz_devinimli_yaratim::cells::Cell;
+
+const REBUILD_FACTOR: usize = 4; // Stale entries allowed per live cell before the heap is rebuilt
+
+// Uncollapsed cells ordered by entropy. Changed cells get a fresh entry rather than having
+// the old one removed; entries that no longer match their cell are dropped when they surface.
+#[derive(Resource, Debug, Default)]
+pub struct EntropyQueue {
+    heap: BinaryHeap<Reverse<EntropyEntry>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct EntropyEntry {
+    entropy: f32,
+    entity: Entity,
+}
+
+impl PartialEq for EntropyEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for EntropyEntry {}
+
+impl PartialOrd for EntropyEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for EntropyEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.entropy
+            .total_cmp(&other.entropy)
+            .then(self.entity.cmp(&other.entity))
+    }
+}
+
+impl EntropyQueue {
+    pub fn push(&mut self, entity: Entity, entropy: f32) {
+        self.heap.push(Reverse(EntropyEntry { entropy, entity }));
+    }
+
+    // Lowest entropy cell whose entry still matches it, per `current_entropy`
+    pub fn pop_lowest(
+        &mut self,
+        current_entropy: impl Fn(Entity) -> Option<f32>,
+    ) -> Option<Entity> {
+        while let Some(Reverse(entry)) = self.heap.pop() {
+            if current_entropy(entry.entity)
+                .is_some_and(|entropy| entropy.to_bits() == entry.entropy.to_bits())
+            {
+                return Some(entry.entity);
+            }
+        }
+        None
+    }
+
+    // Starts over from the live cells once stale entries far outnumber them
+    pub fn compact<I>(&mut self, cell_count: usize, uncollapsed_cells: impl FnOnce() -> I)
+    where
+        I: Iterator<Item = (Entity, f32)>,
+    {
+        if self.heap.len() <= cell_count.max(1) * REBUILD_FACTOR {
+            return;
+        }
+        self.heap = uncollapsed_cells()
+            .map(|(entity, entropy)| Reverse(EntropyEntry { entropy, entity }))
+            .collect();
+    }
+}
+
+// Runs after propagation has written its results, so every narrowed cell is queued again
+pub fn queue_changed_entropies(
+    mut entropy_queue: ResMut<EntropyQueue>,
+    changed_cells: Query<(Entity, &Cell), Changed<Cell>>,
+) {
+    for (entity, cell) in changed_cells.iter() {
+        if !cell.is_collapsed {
+            entropy_queue.push(entity, cell.entropy);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn stale_entries_are_skipped() {
+        let (narrowed, collapsed, untouched) = (
+            Entity::from_raw(1),
+            Entity::from_raw(2),
+            Entity::from_raw(3),
+        );
+        let mut queue = EntropyQueue::default();
+        queue.push(narrowed, 2.0);
+        queue.push(collapsed, 0.5);
+        queue.push(untouched, 1.5);
+        queue.push(narrowed, 1.0);
+
+        // The narrowed cell's first entry is out of date and the collapsed cell has none left
+        let current = HashMap::from([(narrowed, 1.0), (untouched, 1.5)]);
+        let entropy_of = |entity| current.get(&entity).copied();
+        assert_eq!(queue.pop_lowest(entropy_of), Some(narrowed));
+        assert_eq!(queue.pop_lowest(entropy_of), Some(untouched));
+        assert_eq!(queue.pop_lowest(entropy_of), None);
+    }
+
+    #[test]
+    fn compacting_keeps_only_live_cells() {
+        let live = Entity::from_raw(1);
+        let mut queue = EntropyQueue::default();
+        for step in 0..=REBUILD_FACTOR {
+            queue.push(live, step as f32);
+        }
+
+        queue.compact(1, || [(live, 0.25)].into_iter());
+        assert_eq!(queue.heap.len(), 1);
+        assert_eq!(queue.pop_lowest(|_| Some(0.25)), Some(live));
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::VecDeque;
+
+use bevy::{ecs::resource::Resource, platform::collections::HashMap};
+
+#[cfg(feature = "debug-tools")]
+use crate::game::core_mechanics::oz_devinimli_yaratim::grid::cell_to_chunk;
+use crate::game::core_mechanics::oz_devinimli_yaratim::{
+    odycore::backtrack::CollapseStack, odyrules::commons::TileType,
+};
+
+// Recent collapses compared against the long-run mix, so streaks get damped and droughts broken
+#[derive(Resource, Debug)]
+pub struct GenerationHistory {
+    pub recent: VecDeque<TileType>,
+    pub totals: HashMap<TileType, u64>,
+    pub total_collapsed: u64,
+    pub contradictions: u64, // Cells left with no valid tile, whether backtracking saved them or not
+    pub fallbacks: HashMap<(i32, i32), u32>, // Cells backtracking couldn't save -> times it gave up
+    pub window: usize,
+    pub strength: f32,
+    pub min_factor: f32,
+    pub max_factor: f32,
+    pub backtrack: CollapseStack,
+}
+
+impl Default for GenerationHistory {
+    fn default() -> Self {
+        Self {
+            recent: VecDeque::new(),
+            totals: HashMap::new(),
+            total_collapsed: 0,
+            contradictions: 0,
+            fallbacks: HashMap::new(),
+            window: 64,
+            strength: 1.0,
+            min_factor: 0.25,
+            max_factor: 3.0,
+            backtrack: CollapseStack::default(),
+        }
+    }
+}
+
+impl GenerationHistory {
+    pub fn record(&mut self, tile: TileType) {
+        self.recent.push_back(tile);
+        *self.totals.entry(tile).or_insert(0) += 1;
+        self.total_collapsed += 1;
+        while self.recent.len() > self.window {
+            self.recent.pop_front();
+        }
+    }
+
+    // A cell backtracking couldn't save, which was left as rubble
+    pub fn record_fallback(&mut self, position: (i32, i32)) {
+        *self.fallbacks.entry(position).or_insert(0) += 1;
+    }
+
+    pub fn total_fallbacks(&self) -> u64 {
+        self.fallbacks.values().map(|count| *count as u64).sum()
+    }
+
+    // Fallbacks summed per chunk, to find where generation keeps failing
+    #[cfg(feature = "debug-tools")]
+    pub fn failed_chunks(&self, cells_per_chunk: i32) -> HashMap<(i32, i32), u32> {
+        let mut chunks = HashMap::new();
+        for (position, count) in self.fallbacks.iter() {
+            *chunks
+                .entry(cell_to_chunk(*position, cells_per_chunk))
+                .or_insert(0) += count;
+        }
+        chunks
+    }
+
+    // Tiles seen more often recently than over the whole run get scaled down, rare ones up
+    pub fn adjusted_weights(&self, base: &HashMap<TileType, f32>) -> HashMap<TileType, f32> {
+        if self.recent.len() < self.window {
+            return base.clone();
+        }
+
+        let mut counts: HashMap<TileType, usize> = HashMap::new();
+        for tile in self.recent.iter() {
+            *counts.entry(*tile).or_insert(0) += 1;
+        }
+
+        let samples = self.recent.len() as f32;
+        base.iter()
+            .map(|(tile, weight)| {
+                let Some(total) = self.totals.get(tile) else {
+                    return (*tile, *weight);
+                };
+                let expected = *total as f32 / self.total_collapsed as f32;
+                // Half a sample keeps tiles missing from the window from producing an infinite boost
+                let observed = (*counts.get(tile).unwrap_or(&0) as f32).max(0.5) / samples;
+                let factor = (expected / observed)
+                    .powf(self.strength)
+                    .clamp(self.min_factor, self.max_factor);
+                (*tile, weight * factor)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,76 @@
+use bevy::{platform::collections::HashSet, prelude::*};
+
+use crate::game::core_mechanics::oz_devinimli_yaratim::{
+    cells::{Cell, CellSpatialIndex},
+    grid::{GridKey, neighbor_key},
+    odycore::open_space::filter_valid_tiles,
+    odyrules::{
+        commons::{DIRECTION_VECTORS, Direction},
+        open_space_rules::OpenSpaceRules,
+    },
+};
+
+// What the last scan of the loaded cells found
+#[derive(Resource, Debug, Default)]
+pub struct AdjacencyAudit {
+    pub scans: u32,
+    pub checked_sides: usize,
+    pub violations: usize,
+    pub reported: usize, // Every violation logged so far, each one once
+    last_violations: HashSet<(GridKey, Direction)>,
+}
+
+// Goes over every collapsed cell and checks its tile against each collapsed neighbor the same
+// way propagation would have. Cells generated before a `rules learn` are judged by the new
+// rules, so expect a burst right after switching.
+pub fn check_adjacency_invariants(
+    mut audit: ResMut<AdjacencyAudit>,
+    rules: Res<OpenSpaceRules>,
+    spatial_index: Res<CellSpatialIndex>,
+    cells: Query<&Cell>,
+) {
+    let tile_at = |key: &GridKey| {
+        spatial_index
+            .grid
+            .get(key)
+            .and_then(|entity| cells.get(*entity).ok())
+            .filter(|cell| cell.is_collapsed)
+            .and_then(|cell| cell.tile_type)
+    };
+
+    let mut checked_sides = 0;
+    let mut violations = HashSet::new();
+    for cell in cells.iter().filter(|cell| cell.is_collapsed) {
+        let Some(tile) = cell.tile_type else {
+            continue;
+        };
+        for (direction, offset) in DIRECTION_VECTORS.iter() {
+            let Some(neighbor) = tile_at(&neighbor_key(cell.key(), *offset)) else {
+                continue;
+            };
+            checked_sides += 1;
+
+            let mut allowed = vec![tile];
+            filter_valid_tiles(&mut allowed, neighbor, *direction, rules.as_ref());
+            if !allowed.is_empty() {
+                continue;
+            }
+            violations.insert((cell.key(), *direction));
+            if !audit.last_violations.contains(&(cell.key(), *direction)) {
+                audit.reported += 1;
+                warn!(
+                    "Adjacency violated: {:?} at {:?} next to {:?} on its {:?} side",
+                    tile,
+                    cell.key(),
+                    neighbor,
+                    direction
+                );
+            }
+        }
+    }
+
+    audit.scans += 1;
+    audit.checked_sides = checked_sides;
+    audit.violations = violations.len();
+    audit.last_violations = violations;
+}
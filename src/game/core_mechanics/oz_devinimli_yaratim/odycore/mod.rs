@@ -8,33 +8,82 @@ use bevy::{
 
 use crate::game::core_mechanics::oz_devinimli_yaratim::{
     cells::CellSpatialIndex,
+    odycore::backtrack::respawn_retried_cells,
+    odycore::constraints::GlobalConstraints,
+    odycore::entropy_queue::{EntropyQueue, queue_changed_entropies},
+    odycore::history::GenerationHistory,
     odycore::open_space::{
-        OpenSpacePropagationQueue, collapse_lowest_entropy_open_space_cell, initialize_new_cells,
-        propagate_open_space_constraints, update_spatial_index,
+        CellCollapsed, CollapseBudget, OpenSpacePropagationQueue, apply_propagation_results,
+        cancel_stale_propagation, collapse_lowest_entropy_open_space_cells, initialize_new_cells,
+        restore_cached_cells, stamp_footprint_cells, update_spatial_index, weigh_new_cells,
     },
-    odyrules::open_space_rules::OpenSpaceRules,
+    odycore::propagation_tasks::{
+        PropagationTasks, poll_propagation_task, start_propagation_task,
+    },
+    odycore::regions::{RegionComplete, report_complete_regions},
+    odycore::throttle::{GenerationThrottle, match_refresh_rate, throttle_generation},
+    odyrules::{biomes::BiomeMap, example_rules::rules_command, open_space_rules::OpenSpaceRules},
 };
+use crate::game::core_mechanics::pause::simulation_running;
 
+pub mod backtrack;
+pub mod constraints;
+pub mod entropy_queue;
+pub mod history;
+#[cfg(feature = "debug-tools")]
+pub mod invariants;
 pub mod open_space; 
+pub mod propagation_tasks;
+pub mod regions;
+#[cfg(feature = "debug-tools")]
+pub mod resolve;
+pub mod snapshot;
+pub mod throttle;
 
 pub fn plugin(app: &mut App) {
     app.init_resource::<OpenSpaceRules>() 
+        .init_resource::<BiomeMap>()
         .init_resource::<OpenSpacePropagationQueue>() 
+        .init_resource::<GenerationHistory>()
+        .init_resource::<PropagationTasks>()
+        .init_resource::<EntropyQueue>()
+        .init_resource::<CollapseBudget>()
+        .init_resource::<GlobalConstraints>()
+        .init_resource::<GenerationThrottle>()
+        .add_event::<CellCollapsed>()
+        .add_event::<RegionComplete>()
         .add_systems(Startup, setup_wfc_rules) 
         .add_systems(
             Update,
             (
-                update_spatial_index,             
-                initialize_new_cells,             
-                propagate_open_space_constraints, 
-                collapse_lowest_entropy_open_space_cell.run_if(propagation_queue_empty),
+                // Outside the pause gate so teleport events are never missed while a menu is open
+                cancel_stale_propagation,
+                rules_command,
+                (
+                    match_refresh_rate,
+                    throttle_generation,
+                    update_spatial_index,             
+                    restore_cached_cells,
+                    stamp_footprint_cells,
+                    initialize_new_cells,             
+                    weigh_new_cells,
+                    start_propagation_task,
+                    poll_propagation_task,
+                    apply_propagation_results,
+                    respawn_retried_cells,
+                    queue_changed_entropies,
+                    collapse_lowest_entropy_open_space_cells.run_if(propagation_idle),
+                    report_complete_regions,
+                )
+                    .chain()
+                    .run_if(simulation_running),
             )
                 .chain(), 
         );
 }
 
-fn propagation_queue_empty(queue: Res<OpenSpacePropagationQueue>) -> bool {
-    queue.queue.is_empty() 
+fn propagation_idle(queue: Res<OpenSpacePropagationQueue>, tasks: Res<PropagationTasks>) -> bool {
+    queue.queue.is_empty() && tasks.is_idle()
 }
 
 fn setup_wfc_rules(mut commands: Commands) {
@@ -1,18 +1,38 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, time::Duration};
 
-use bevy::ecs::{
-    entity::Entity,
-    query::Added,
-    resource::Resource,
-    system::{Query, Res, ResMut},
+use bevy::{
+    ecs::{
+        entity::Entity,
+        event::{Event, EventReader, EventWriter},
+        query::{Added, With},
+        resource::Resource,
+        system::{Query, Res, ResMut, SystemParam},
+    },
+    log::{debug, info_span},
+    platform::{
+        collections::{HashMap, HashSet},
+        time::Instant,
+    },
+    transform::components::Transform,
 };
-use rand::seq::IteratorRandom;
 
-use crate::game::core_mechanics::oz_devinimli_yaratim::{
-    cells::{Cell, CellSpatialIndex},
-    odyrules::{
-        commons::{DIRECTION_VECTORS, Direction, Rules, TileType},
-        open_space_rules::OpenSpaceRules,
+use crate::game::core_mechanics::{
+    movement::PlayerTeleported,
+    oz_devinimli_yaratim::{
+        cells::{Cell, CellSpatialIndex, CollapsedTileCache, GenerationSettings},
+        grid::{GROUND_FLOOR, GridKey, ground_key, neighbor_key},
+        odycore::{
+            constraints::{GlobalConstraints, Prospect},
+            entropy_queue::EntropyQueue,
+            history::GenerationHistory,
+            propagation_tasks::PropagationTasks,
+        },
+        prefabs::PrefabReservations,
+        odyrules::{
+            biomes::BiomeRules,
+            commons::{DIRECTION_VECTORS, Direction, Rules, TileType},
+            open_space_rules::OpenSpaceRules,
+        },
     },
 };
 
@@ -21,12 +41,95 @@ pub struct OpenSpacePropagationQueue {
     pub queue: VecDeque<Entity>,
 }
 
+// A cell settled on its final tile, whether picked, forced by a contradiction or stamped
+#[derive(Event, Debug, Clone, Copy)]
+pub struct CellCollapsed {
+    pub position: (i32, i32),
+    pub tile: TileType,
+}
+
+// Drops queue entries whose cells are gone, or about to be after the player teleported away
+pub fn cancel_stale_propagation(
+    mut wfc_queue: ResMut<OpenSpacePropagationQueue>,
+    mut tasks: ResMut<PropagationTasks>,
+    mut teleports: EventReader<PlayerTeleported>,
+    cells: Query<&Transform, With<Cell>>,
+    settings: Res<GenerationSettings>,
+) {
+    let teleport = teleports.read().last().copied();
+    let keep_around = teleport.map(|teleport| teleport.to);
+    // The batch in flight goes back in the queue, so its cells far from the player go too
+    if teleport.is_some() {
+        tasks.restart(&mut wfc_queue);
+    }
+    let despawn_distance = settings.despawn_distance();
+    let before = wfc_queue.queue.len();
+
+    wfc_queue
+        .queue
+        .retain(|entity| match (cells.get(*entity), keep_around) {
+            (Err(_), _) => false,
+            (Ok(transform), Some(center)) => {
+                transform.translation.distance(center) <= despawn_distance
+            }
+            (Ok(_), None) => true,
+        });
+
+    let dropped = before - wfc_queue.queue.len();
+    if let Some(teleport) = teleport {
+        debug!(
+            "Teleport {:?} -> {:?}: cancelled propagation for {} cells",
+            teleport.from, teleport.to, dropped
+        );
+    } else if dropped > 0 {
+        debug!("Cancelled propagation for {} despawned cells", dropped);
+    }
+}
+
 pub fn update_spatial_index(
     mut spatial_index: ResMut<CellSpatialIndex>,
     added_cells: Query<(Entity, &Cell), Added<Cell>>,
 ) {
     for (entity, cell) in added_cells.iter() {
-        spatial_index.grid.insert(cell.position, entity);
+        spatial_index.grid.insert(cell.key(), entity);
+    }
+}
+
+// Cells coming back into range take the tile they had when they were unloaded
+pub fn restore_cached_cells(
+    mut open_space: ResMut<OpenSpacePropagationQueue>,
+    mut collapsed: EventWriter<CellCollapsed>,
+    mut spatial_index: ResMut<CellSpatialIndex>,
+    cache: Res<CollapsedTileCache>,
+    rules: Res<OpenSpaceRules>,
+    mut added_cells: Query<(Entity, &mut Cell), Added<Cell>>,
+) {
+    for (entity, mut cell) in added_cells.iter_mut() {
+        if cell.is_collapsed || cell.floor != GROUND_FLOOR {
+            continue;
+        }
+        let Some(tile) = cache.tiles.get(&cell.position).copied() else {
+            continue;
+        };
+
+        // Parts of a restored multi-cell tile that were never loaded get covered on spawn
+        for covered in footprint_cells(cell.position, rules.footprint(tile))
+            .into_iter()
+            .skip(1)
+            .filter(|covered| !cache.tiles.contains_key(covered))
+        {
+            spatial_index.footprints.insert(covered, cell.position);
+        }
+
+        cell.valid_tiles = vec![tile];
+        cell.tile_type = Some(tile);
+        cell.is_collapsed = true;
+        cell.entropy = 0.0;
+        open_space.queue.push_back(entity);
+        collapsed.write(CellCollapsed {
+            position: cell.position,
+            tile,
+        });
     }
 }
 
@@ -37,9 +140,9 @@ pub fn initialize_new_cells(
     cells: Query<&Cell>,
 ) {
     for cell in added_cells.iter() {
-        for (_, (dx, dz)) in DIRECTION_VECTORS.iter() {
-            let neighbor_pos = (cell.position.0 + dx, cell.position.1 + dz);
-            if let Some(neighbor_entity) = spatial_index.grid.get(&neighbor_pos) {
+        for (_, offset) in DIRECTION_VECTORS.iter() {
+            let neighbor = neighbor_key(cell.key(), *offset);
+            if let Some(neighbor_entity) = spatial_index.grid.get(&neighbor) {
                 if let Ok(neighbor_cell) = cells.get(*neighbor_entity) {
                     if neighbor_cell.is_collapsed {
                         wfc_queue.queue.push_front(*neighbor_entity);
@@ -50,6 +153,13 @@ pub fn initialize_new_cells(
     }
 }
 
+// Cells start out with unweighted entropy, as the rules aren't at hand where they're built
+pub fn weigh_new_cells(rules: Res<OpenSpaceRules>, mut added_cells: Query<&mut Cell, Added<Cell>>) {
+    for mut cell in added_cells.iter_mut() {
+        cell.update_entropy(rules.weights());
+    }
+}
+
 pub fn filter_valid_tiles<T>(
     valid_tiles: &mut Vec<TileType>,
     neighbor_tile: TileType,
@@ -65,66 +175,93 @@ pub fn filter_valid_tiles<T>(
     }
 }
 
-pub fn get_opposite_direction(direction: Direction) -> Direction {
-    match direction {
-        Direction::Front => Direction::Back,
-        Direction::Back => Direction::Front,
-        Direction::Right => Direction::Left,
-        Direction::Left => Direction::Right,
+// Tiles a cell could take given only its collapsed neighbors, as reported by `tile_at`
+pub fn constrained_tiles<T>(
+    key: GridKey,
+    all_tiles: &[TileType],
+    rules: &T,
+    tile_at: impl Fn(GridKey) -> Option<TileType>,
+) -> Vec<TileType>
+where
+    T: Rules,
+{
+    let mut valid_tiles = all_tiles.to_vec();
+    for (direction, offset) in DIRECTION_VECTORS.iter() {
+        // Same pairing as propagation: the rule is looked up from the neighbor's side
+        if let Some(neighbor_tile) = tile_at(neighbor_key(key, *offset)) {
+            filter_valid_tiles(&mut valid_tiles, neighbor_tile, *direction, rules);
+        }
     }
+    valid_tiles
 }
 
-pub fn propagate_open_space_constraints(
+// Writes finished propagation results back to the cells, up to the per-frame budget
+pub fn apply_propagation_results(
+    mut tasks: ResMut<PropagationTasks>,
     mut open_space: ResMut<OpenSpacePropagationQueue>,
+    mut collapsed: EventWriter<CellCollapsed>,
+    mut history: ResMut<GenerationHistory>,
     rules: Res<OpenSpaceRules>,
     spatial_index: Res<CellSpatialIndex>,
     mut cells: Query<&mut Cell>,
 ) {
-    while let Some(entity) = open_space.queue.pop_front() {
-        let (is_collapsed, tile_type, position) = {
-            if let Ok(cell) = cells.get_mut(entity) {
-                (cell.is_collapsed, cell.tile_type, cell.position)
-            } else {
-                continue;
-            }
+    let mut contradicted = Vec::new();
+    for update in tasks.next_updates() {
+        let Ok(mut cell) = cells.get_mut(update.entity) else {
+            continue;
         };
+        if cell.is_collapsed {
+            continue;
+        }
+        // Filtering only ever narrows, so a result computed on an older domain still holds
+        cell.valid_tiles
+            .retain(|tile| update.valid_tiles.contains(tile));
+        cell.update_entropy(rules.weights());
 
-        if is_collapsed {
-            if let Some(tile) = tile_type {
-                for (direction, (dx, dz)) in DIRECTION_VECTORS.iter() {
-                    let neighbor_pos = (position.0 + dx, position.1 + dz);
-
-                    if let Some(neighbor_entity) = spatial_index.grid.get(&neighbor_pos) {
-                        if let Ok(mut neighbor_cell) = cells.get_mut(*neighbor_entity) {
-                            if neighbor_cell.is_collapsed {
-                                continue;
-                            }
-                            filter_valid_tiles(
-                                &mut neighbor_cell.valid_tiles,
-                                tile,
-                                get_opposite_direction(*direction),
-                                rules.as_ref(),
-                            );
-                            neighbor_cell.update_entropy();
-
-                            if neighbor_cell.is_contradicted() {
-                                neighbor_cell.tile_type = Some(TileType::Ground);
-                                neighbor_cell.is_collapsed = true;
-                                neighbor_cell.entropy = 0;
-                                open_space.queue.push_back(*neighbor_entity);
-                            }
-                        }
-                    }
-                }
-            }
+        if cell.is_contradicted() {
+            contradicted.push((update.entity, cell.key()));
         }
     }
+
+    history.contradictions += contradicted.len() as u64;
+
+    // Switch a recent neighboring choice if one can make room; rubble is the last resort
+    let stack = &mut history.backtrack;
+    let mut retried = false;
+    let mut fallbacks = Vec::new();
+    for (neighbor_entity, key) in contradicted {
+        if !retried
+            && let Some((index, tile)) = stack.find_retry(key, &cells, &spatial_index, &rules)
+        {
+            stack.apply_retry(index, tile, &mut cells, &spatial_index, &rules);
+            retried = true;
+        }
+
+        if let Ok(mut neighbor_cell) = cells.get_mut(neighbor_entity)
+            && neighbor_cell.is_contradicted()
+        {
+            neighbor_cell.tile_type = Some(TileType::Rubble);
+            neighbor_cell.is_collapsed = true;
+            neighbor_cell.entropy = 0.0;
+            open_space.queue.push_back(neighbor_entity);
+            collapsed.write(CellCollapsed {
+                position: (key.0, key.1),
+                tile: TileType::Rubble,
+            });
+            fallbacks.push((key.0, key.1));
+        }
+    }
+    for position in fallbacks {
+        history.record_fallback(position);
+    }
+
+    // Results still out were worked out against the switched cell's old tile
+    if retried {
+        tasks.restart(&mut open_space);
+    }
 }
 
-pub fn get_random_tile<T>(rules: &T, valid_tiles: &[TileType]) -> TileType
-where
-    T: Rules,
-{
+pub fn get_random_tile(weights: &HashMap<TileType, f32>, valid_tiles: &[TileType]) -> TileType {
     use rand::prelude::*;
 
     if valid_tiles.is_empty() {
@@ -134,12 +271,12 @@ where
     let mut rng = rand::rng();
     let total_weight: f32 = valid_tiles
         .iter()
-        .map(|t| *rules.weights().get(t).unwrap_or(&1.0))
+        .map(|t| *weights.get(t).unwrap_or(&1.0))
         .sum();
 
     let mut random = rng.random_range(0.0..total_weight);
     for &tile in valid_tiles {
-        let weight = *rules.weights().get(&tile).unwrap_or(&1.0);
+        let weight = *weights.get(&tile).unwrap_or(&1.0);
         random -= weight;
         if random <= 0.0 {
             return tile;
@@ -149,40 +286,260 @@ where
     valid_tiles[0]
 }
 
-pub fn collapse_lowest_entropy_open_space_cell(
+// How much collapsing a frame may do; propagation then narrows around the whole batch at once
+#[derive(Resource, Debug)]
+pub struct CollapseBudget {
+    pub cells_per_frame: usize,
+    pub max_frame_time: Duration,
+}
+
+impl Default for CollapseBudget {
+    fn default() -> Self {
+        Self {
+            cells_per_frame: 32,
+            max_frame_time: Duration::from_millis(2),
+        }
+    }
+}
+
+// Which cells collapse this frame, and how many
+#[derive(SystemParam)]
+pub struct CollapseOrder<'w> {
+    pub entropy_queue: ResMut<'w, EntropyQueue>,
+    pub budget: Res<'w, CollapseBudget>,
+}
+
+// Where tiles can go: what's loaded or already covered, prefab plots, what was generated
+// before and the limits on the world as a whole
+#[derive(SystemParam)]
+pub struct TileSpace<'w> {
+    pub spatial_index: ResMut<'w, CellSpatialIndex>,
+    pub reservations: Res<'w, PrefabReservations>,
+    pub cache: Res<'w, CollapsedTileCache>,
+    pub constraints: Res<'w, GlobalConstraints>,
+}
+
+impl TileSpace<'_> {
+    fn prospect(
+        &self,
+        cells: &Query<(Entity, &mut Cell)>,
+        position: (i32, i32),
+        tile: TileType,
+    ) -> Prospect {
+        let settled = match self
+            .spatial_index
+            .ground(&position)
+            .and_then(|entity| cells.get(*entity).ok())
+        {
+            Some((_, cell)) if cell.is_collapsed => cell.tile_type,
+            Some((_, cell)) if cell.valid_tiles.contains(&tile) => return Prospect::Possible,
+            Some(_) => return Prospect::RuledOut,
+            None if self.spatial_index.footprints.contains_key(&position) => {
+                Some(TileType::Covered)
+            }
+            None => self
+                .reservations
+                .tiles
+                .get(&position)
+                .or(self.cache.tiles.get(&position))
+                .copied(),
+        };
+        match settled {
+            Some(settled) if settled == tile => Prospect::Placed,
+            Some(_) => Prospect::RuledOut,
+            None => Prospect::Possible,
+        }
+    }
+}
+
+pub fn collapse_lowest_entropy_open_space_cells(
     mut open_space: ResMut<OpenSpacePropagationQueue>,
+    mut collapsed: EventWriter<CellCollapsed>,
     mut cells: Query<(Entity, &mut Cell)>,
-    open_space_rules: Res<OpenSpaceRules>,
+    biome_rules: BiomeRules,
+    mut history: ResMut<GenerationHistory>,
+    mut order: CollapseOrder,
+    mut space: TileSpace,
 ) {
-    let mut candidates = cells
-        .iter_mut()
-        .filter(|(_, cell)| !cell.is_collapsed)
-        .collect::<Vec<_>>();
+    let _span = info_span!("wfc_collapse").entered();
+    let open_space_rules = &biome_rules.rules;
+    let started = Instant::now();
 
-    if candidates.is_empty() {
-        return;
-    }
+    order.entropy_queue.compact(cells.iter().len(), || {
+        cells
+            .iter()
+            .filter(|(_, cell)| !cell.is_collapsed)
+            .map(|(entity, cell)| (entity, cell.entropy))
+    });
 
-    let min_entropy = candidates
-        .iter()
-        .map(|(_, cell)| cell.entropy)
-        .min()
-        .unwrap();
+    // Narrowing is left to the propagation task, so cells next to this frame's picks have stale
+    // domains; they wait for the next frame, after the task has caught up
+    let mut stale = HashSet::new();
+    let mut deferred = Vec::new();
+    for _ in 0..order.budget.cells_per_frame {
+        if started.elapsed() >= order.budget.max_frame_time {
+            break;
+        }
+
+        // Entropy carries its own tie-breaking noise
+        let Some((entity, key, entropy, mut valid_tiles)) = order
+            .entropy_queue
+            .pop_lowest(|entity| {
+                cells
+                    .get(entity)
+                    .ok()
+                    .filter(|(_, cell)| !cell.is_collapsed)
+                    .map(|(_, cell)| cell.entropy)
+            })
+            .and_then(|entity| cells.get(entity).ok())
+            .map(|(e, c)| (e, c.key(), c.entropy, c.valid_tiles.clone()))
+        else {
+            break;
+        };
+        if stale.contains(&key) {
+            deferred.push((entity, entropy));
+            continue;
+        }
+        if valid_tiles.is_empty() {
+            continue;
+        }
+        let position = (key.0, key.1);
 
-    candidates.retain(|(_, cell)| cell.entropy == min_entropy);
+        let weights = history.adjusted_weights(&biome_rules.weights_at(position));
+        let prospect = |at, tile| space.prospect(&cells, at, tile);
+        let mut tile = space
+            .constraints
+            .pick(position, &mut valid_tiles, &weights, prospect);
+        let mut footprint = footprint_cells(position, open_space_rules.footprint(tile));
+
+        // Multi-cell tiles only spread out over the open world
+        if key.2 != GROUND_FLOOR
+            || !footprint_fits(
+                &footprint,
+                &cells,
+                &space.spatial_index,
+                &space.reservations,
+                open_space_rules.as_ref(),
+            )
+        {
+            let mut single_cell_tiles = valid_tiles
+                .into_iter()
+                .filter(|tile| open_space_rules.footprint(*tile) == (1, 1))
+                .collect::<Vec<_>>();
+            tile = space
+                .constraints
+                .pick(position, &mut single_cell_tiles, &weights, prospect);
+            footprint.truncate(1);
+        }
+        history.record(tile);
+        // Multi-cell tiles cover their neighbors, which a retry couldn't take back
+        if footprint.len() == 1 {
+            history.backtrack.record(key, tile);
+        }
 
-    if let Some((entity, cell)) = candidates
-        .into_iter()
-        .choose(&mut rand::rng())
-        .map(|(e, c)| (e, c.into_inner()))
-    {
-        if !cell.valid_tiles.is_empty() {
-            let tile = get_random_tile(open_space_rules.as_ref(), &cell.valid_tiles);
+        let mut settled = vec![key];
+        // Loaded cells of the footprint are covered now, the rest as soon as they spawn
+        for covered in footprint.iter().skip(1) {
+            space.spatial_index.footprints.insert(*covered, position);
+            if let Some(covered_entity) = space.spatial_index.ground(covered)
+                && let Ok((_, mut cell)) = cells.get_mut(*covered_entity)
+            {
+                cover_cell(&mut cell);
+                open_space.queue.push_back(*covered_entity);
+                settled.push(ground_key(*covered));
+                collapsed.write(CellCollapsed {
+                    position: *covered,
+                    tile: TileType::Covered,
+                });
+            }
+        }
 
+        if let Ok((_, mut cell)) = cells.get_mut(entity) {
             cell.tile_type = Some(tile);
             cell.is_collapsed = true;
-            cell.entropy = 0;
+            cell.entropy = 0.0;
             open_space.queue.push_back(entity);
+            collapsed.write(CellCollapsed { position, tile });
         }
+
+        for settled_key in settled.iter() {
+            for (_, offset) in DIRECTION_VECTORS.iter() {
+                stale.insert(neighbor_key(*settled_key, *offset));
+            }
+        }
+    }
+
+    for (entity, entropy) in deferred {
+        order.entropy_queue.push(entity, entropy);
     }
 }
+
+// Cells spawned inside an already placed footprint take their share of the multi-cell tile
+pub fn stamp_footprint_cells(
+    mut open_space: ResMut<OpenSpacePropagationQueue>,
+    mut collapsed: EventWriter<CellCollapsed>,
+    spatial_index: Res<CellSpatialIndex>,
+    mut added_cells: Query<(Entity, &mut Cell), Added<Cell>>,
+) {
+    for (entity, mut cell) in added_cells.iter_mut() {
+        if cell.is_collapsed || !spatial_index.footprints.contains_key(&cell.position) {
+            continue;
+        }
+        cover_cell(&mut cell);
+        open_space.queue.push_back(entity);
+        collapsed.write(CellCollapsed {
+            position: cell.position,
+            tile: TileType::Covered,
+        });
+    }
+}
+
+// Anchor first, extending towards +X and +Z
+fn footprint_cells(anchor: (i32, i32), (width, depth): (i32, i32)) -> Vec<(i32, i32)> {
+    (0..width)
+        .flat_map(|dx| (0..depth).map(move |dz| (anchor.0 + dx, anchor.1 + dz)))
+        .collect()
+}
+
+fn footprint_fits(
+    footprint: &[(i32, i32)],
+    cells: &Query<(Entity, &mut Cell)>,
+    spatial_index: &CellSpatialIndex,
+    reservations: &PrefabReservations,
+    rules: &OpenSpaceRules,
+) -> bool {
+    let cell_at = |key: &GridKey| {
+        spatial_index
+            .grid
+            .get(key)
+            .and_then(|entity| cells.get(*entity).ok())
+            .map(|(_, cell)| cell)
+    };
+
+    footprint.iter().skip(1).all(|covered| {
+        if spatial_index.footprints.contains_key(covered)
+            || reservations.tiles.contains_key(covered)
+            || cell_at(&ground_key(*covered)).is_some_and(|cell| cell.is_collapsed)
+        {
+            return false;
+        }
+
+        // Collapsed cells around the footprint have to accept Covered next to them
+        let around = |neighbor: GridKey| {
+            if neighbor.2 == GROUND_FLOOR && footprint.contains(&(neighbor.0, neighbor.1)) {
+                return None;
+            }
+            cell_at(&neighbor)
+                .filter(|cell| cell.is_collapsed)
+                .and_then(|cell| cell.tile_type)
+        };
+        !constrained_tiles(ground_key(*covered), &[TileType::Covered], rules, around).is_empty()
+    })
+}
+
+fn cover_cell(cell: &mut Cell) {
+    cell.valid_tiles = vec![TileType::Covered];
+    cell.tile_type = Some(TileType::Covered);
+    cell.is_collapsed = true;
+    cell.entropy = 0.0;
+}
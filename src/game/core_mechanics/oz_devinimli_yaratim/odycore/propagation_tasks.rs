@@ -0,0 +1,174 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use bevy::{
+    ecs::{
+        change_detection::DetectChanges,
+        entity::Entity,
+        resource::Resource,
+        system::{Query, Res, ResMut},
+    },
+    log::info_span,
+    platform::collections::HashMap,
+    tasks::{AsyncComputeTaskPool, Task, block_on},
+};
+
+use crate::game::core_mechanics::oz_devinimli_yaratim::{
+    cells::{Cell, CellSpatialIndex},
+    grid::{GridKey, neighbor_key},
+    odycore::open_space::{OpenSpacePropagationQueue, filter_valid_tiles},
+    odyrules::{
+        commons::{DIRECTION_VECTORS, TileType},
+        open_space_rules::OpenSpaceRules,
+    },
+};
+
+// Propagation runs on the async compute pool: a batch of queued cells and the domains around
+// them go out as plain data, and the narrowed domains come back to be applied a few per frame
+#[derive(Resource)]
+pub struct PropagationTasks {
+    pub apply_budget: usize, // Domain updates written back to cells per frame
+    running: Option<Task<Vec<DomainUpdate>>>,
+    pending: VecDeque<DomainUpdate>,
+    sources: Vec<Entity>, // Queue entries of the batch in flight, requeued if it's thrown away
+    rules: Option<Arc<OpenSpaceRules>>, // Shared with the tasks, refreshed when the rules change
+}
+
+impl Default for PropagationTasks {
+    fn default() -> Self {
+        Self {
+            apply_budget: 256,
+            running: None,
+            pending: VecDeque::new(),
+            sources: Vec::new(),
+            rules: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DomainUpdate {
+    pub entity: Entity,
+    pub valid_tiles: Vec<TileType>,
+}
+
+impl PropagationTasks {
+    pub fn is_idle(&self) -> bool {
+        self.running.is_none() && self.pending.is_empty()
+    }
+
+    pub fn next_updates(&mut self) -> Vec<DomainUpdate> {
+        let count = self.apply_budget.min(self.pending.len());
+        let updates = self.pending.drain(..count).collect();
+        if self.is_idle() {
+            self.sources.clear();
+        }
+        updates
+    }
+
+    // Drops the batch in flight and queues its cells again; propagating twice is harmless
+    pub fn restart(&mut self, queue: &mut OpenSpacePropagationQueue) {
+        self.running = None;
+        self.pending.clear();
+        queue.queue.extend(self.sources.drain(..));
+    }
+}
+
+pub fn start_propagation_task(
+    mut tasks: ResMut<PropagationTasks>,
+    mut open_space: ResMut<OpenSpacePropagationQueue>,
+    rules: Res<OpenSpaceRules>,
+    spatial_index: Res<CellSpatialIndex>,
+    cells: Query<&Cell>,
+) {
+    if !tasks.is_idle() || open_space.queue.is_empty() {
+        return;
+    }
+    if tasks.rules.is_none() || rules.is_changed() {
+        tasks.rules = Some(Arc::new(rules.clone()));
+    }
+
+    let mut sources = Vec::new();
+    let mut domains = HashMap::new();
+    for entity in open_space.queue.drain(..) {
+        let Ok(cell) = cells.get(entity) else {
+            continue;
+        };
+        let (true, Some(tile)) = (cell.is_collapsed, cell.tile_type) else {
+            continue;
+        };
+        sources.push((cell.key(), tile));
+        tasks.sources.push(entity);
+
+        for (_, offset) in DIRECTION_VECTORS.iter() {
+            let neighbor_at = neighbor_key(cell.key(), *offset);
+            let Some(neighbor_entity) = spatial_index.grid.get(&neighbor_at) else {
+                continue;
+            };
+            if let Ok(neighbor) = cells.get(*neighbor_entity)
+                && !neighbor.is_collapsed
+            {
+                domains
+                    .entry(neighbor_at)
+                    .or_insert_with(|| (*neighbor_entity, neighbor.valid_tiles.clone()));
+            }
+        }
+    }
+
+    if sources.is_empty() {
+        tasks.sources.clear();
+        return;
+    }
+
+    let rules = tasks.rules.clone().unwrap();
+    tasks.running = Some(
+        AsyncComputeTaskPool::get()
+            .spawn(async move { narrow_domains(&sources, domains, rules.as_ref()) }),
+    );
+}
+
+// On exit the batch in flight is dropped rather than waited on, and its cells go back in the
+// queue so the save picks them up
+pub fn stop_propagation_tasks(
+    mut tasks: ResMut<PropagationTasks>,
+    mut open_space: ResMut<OpenSpacePropagationQueue>,
+) {
+    if !tasks.is_idle() {
+        tasks.restart(&mut open_space);
+    }
+}
+
+pub fn poll_propagation_task(mut tasks: ResMut<PropagationTasks>) {
+    if let Some(task) = tasks.running.take_if(|task| task.is_finished()) {
+        let updates = block_on(task);
+        tasks.pending.extend(updates);
+    }
+}
+
+fn narrow_domains(
+    sources: &[(GridKey, TileType)],
+    mut domains: HashMap<GridKey, (Entity, Vec<TileType>)>,
+    rules: &OpenSpaceRules,
+) -> Vec<DomainUpdate> {
+    let _span = info_span!("wfc_propagate", sources = sources.len()).entered();
+
+    for (key, tile) in sources.iter() {
+        for (direction, offset) in DIRECTION_VECTORS.iter() {
+            if let Some((_, valid_tiles)) = domains.get_mut(&neighbor_key(*key, *offset)) {
+                filter_valid_tiles(
+                    valid_tiles,
+                    *tile,
+                    direction.opposite(),
+                    rules,
+                );
+            }
+        }
+    }
+
+    domains
+        .into_values()
+        .map(|(entity, valid_tiles)| DomainUpdate {
+            entity,
+            valid_tiles,
+        })
+        .collect()
+}
@@ -0,0 +1,56 @@
+use bevy::{
+    ecs::{
+        event::{Event, EventReader, EventWriter},
+        system::{Local, Query, Res},
+    },
+    platform::collections::HashSet,
+};
+
+use crate::game::core_mechanics::oz_devinimli_yaratim::{
+    cells::{Cell, CellSpatialIndex, GenerationSettings},
+    grid::{cell_to_chunk, chunk_cells},
+    odycore::open_space::CellCollapsed,
+};
+
+// Every cell of a loaded chunk has settled. Bounds are the inclusive min and max grid positions.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct RegionComplete {
+    pub bounds: ((i32, i32), (i32, i32)),
+}
+
+// Chunks are checked from the first collapse in them until they finish or unload, so a
+// cell that was still being spawned when the last event came in is caught a frame later
+pub fn report_complete_regions(
+    mut collapsed: EventReader<CellCollapsed>,
+    mut completed: EventWriter<RegionComplete>,
+    mut pending: Local<HashSet<(i32, i32)>>,
+    cells: Query<&Cell>,
+    spatial_index: Res<CellSpatialIndex>,
+    settings: Res<GenerationSettings>,
+) {
+    let size = settings.cells_per_chunk;
+    pending.extend(
+        collapsed
+            .read()
+            .map(|event| cell_to_chunk(event.position, size)),
+    );
+
+    pending.retain(|chunk| {
+        if !spatial_index.chunks.contains_key(chunk) {
+            return false;
+        }
+        let settled = chunk_cells(*chunk, size).all(|position| {
+            spatial_index
+                .ground(&position)
+                .and_then(|entity| cells.get(*entity).ok())
+                .is_some_and(|cell| cell.is_collapsed)
+        });
+        if settled {
+            let min = (chunk.0 * size, chunk.1 * size);
+            completed.write(RegionComplete {
+                bounds: (min, (min.0 + size - 1, min.1 + size - 1)),
+            });
+        }
+        !settled
+    });
+}
@@ -0,0 +1,113 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::game::{
+    core_mechanics::oz_devinimli_yaratim::{
+        cells::{Cell, CellSpatialIndex, CollapsedTileCache, GenerationSettings, spawn_cell},
+        grid::{chunk_cells, ground_key},
+        odycore::history::GenerationHistory,
+        odyrules::{biomes::BiomeRules, commons::TileType},
+    },
+    ui::console::{ConsoleCommand, ConsoleState},
+};
+
+const LISTED_CHUNKS: usize = 10;
+
+// The loaded world, for swapping cells out from under generation
+#[derive(SystemParam)]
+pub struct LoadedCells<'w, 's> {
+    cells: Query<'w, 's, &'static mut Cell>,
+    spatial_index: ResMut<'w, CellSpatialIndex>,
+    cache: ResMut<'w, CollapsedTileCache>,
+    settings: Res<'w, GenerationSettings>,
+}
+
+// `resolve` lists the chunks generation gave up on most, `resolve <chunk_x> <chunk_z>` puts
+// fresh cells in place of that chunk's rubble so the solver has another go at them. The
+// fallbacks stay on record either way.
+pub fn resolve_command(
+    mut commands: Commands,
+    mut commands_in: EventReader<ConsoleCommand>,
+    mut console: ResMut<ConsoleState>,
+    history: Res<GenerationHistory>,
+    mut world: LoadedCells,
+    biome_rules: BiomeRules,
+) {
+    for command in commands_in.read().filter(|c| c.name == "resolve") {
+        let coordinates: Vec<i32> = command
+            .args
+            .iter()
+            .filter_map(|arg| arg.parse().ok())
+            .collect();
+        match coordinates[..] {
+            [] if command.args.is_empty() => {
+                let mut chunks: Vec<_> = history
+                    .failed_chunks(world.settings.cells_per_chunk)
+                    .into_iter()
+                    .collect();
+                if chunks.is_empty() {
+                    console.print("no generation fallbacks yet");
+                    continue;
+                }
+                chunks.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+                console.print(format!(
+                    "{} fallbacks in {} chunks:",
+                    history.total_fallbacks(),
+                    chunks.len()
+                ));
+                for ((chunk_x, chunk_z), count) in chunks.into_iter().take(LISTED_CHUNKS) {
+                    console.print(format!("  chunk ({}, {}): {}", chunk_x, chunk_z, count));
+                }
+            }
+            [chunk_x, chunk_z] => {
+                let resolved =
+                    resolve_chunk(&mut commands, &mut world, &biome_rules, (chunk_x, chunk_z));
+                if resolved == 0 {
+                    console.print(format!(
+                        "no rubble loaded in chunk ({}, {})",
+                        chunk_x, chunk_z
+                    ));
+                } else {
+                    console.print(format!(
+                        "re-solving {} cells in chunk ({}, {})",
+                        resolved, chunk_x, chunk_z
+                    ));
+                }
+            }
+            _ => console.print("usage: resolve [<chunk_x> <chunk_z>]"),
+        }
+    }
+}
+
+// Like a backtracking retry, each rubble cell gets a fresh entity so its stones go with the old
+// one; the new cell starts undecided and is constrained by its neighbors as it's spawned
+fn resolve_chunk(
+    commands: &mut Commands,
+    world: &mut LoadedCells,
+    biome_rules: &BiomeRules,
+    chunk: (i32, i32),
+) -> usize {
+    let mut resolved = 0;
+    for position in chunk_cells(chunk, world.settings.cells_per_chunk) {
+        let key = ground_key(position);
+        let Some(entity) = world.spatial_index.grid.get(&key).copied() else {
+            continue;
+        };
+        let Ok(mut cell) = world.cells.get_mut(entity) else {
+            continue;
+        };
+        if cell.tile_type != Some(TileType::Rubble) {
+            continue;
+        }
+
+        // Uncollapsed first, so despawning it doesn't put the rubble straight back in the cache
+        cell.is_collapsed = false;
+        world.cache.tiles.remove(&position);
+        commands.entity(entity).despawn();
+
+        let cell = Cell::new(&biome_rules.tiles_at(position), position);
+        let entity = spawn_cell(commands, cell, &world.settings);
+        world.spatial_index.grid.insert(key, entity);
+        resolved += 1;
+    }
+    resolved
+}
@@ -0,0 +1,86 @@
+use bevy::{
+    ecs::{entity::Entity, system::Commands},
+    platform::collections::HashMap,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::game::core_mechanics::oz_devinimli_yaratim::{
+    cells::{Cell, GenerationSettings, spawn_cell},
+    grid::GROUND_FLOOR,
+    odycore::open_space::OpenSpacePropagationQueue,
+    odyrules::commons::TileType,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CellSnapshot {
+    pub position: (i32, i32),
+    pub tile_type: Option<TileType>,
+    pub valid_tiles: Vec<TileType>, // By name, so reordering TileType can't corrupt saves
+}
+
+// Everything needed to resume a half-finished solve
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PropagationSnapshot {
+    pub cells: Vec<CellSnapshot>,
+    pub queue: Vec<(i32, i32)>,
+}
+
+impl PropagationSnapshot {
+    pub fn capture<'a>(
+        cells: impl Iterator<Item = (Entity, &'a Cell)>,
+        queue: &OpenSpacePropagationQueue,
+    ) -> Self {
+        let mut positions = HashMap::new();
+        let mut snapshot = PropagationSnapshot::default();
+
+        // Like the tile cache, saves hold the open world; floors of structures are generated again
+        for (entity, cell) in cells.filter(|(_, cell)| cell.floor == GROUND_FLOOR) {
+            positions.insert(entity, cell.position);
+            snapshot.cells.push(CellSnapshot {
+                position: cell.position,
+                tile_type: if cell.is_collapsed {
+                    cell.tile_type
+                } else {
+                    None
+                },
+                valid_tiles: cell.valid_tiles.clone(),
+            });
+        }
+
+        snapshot.queue = queue
+            .queue
+            .iter()
+            .filter_map(|entity| positions.get(entity).copied())
+            .collect();
+
+        snapshot
+    }
+
+    // Spawns the saved cells and refills the queue in its original order
+    pub fn restore(
+        &self,
+        commands: &mut Commands,
+        queue: &mut OpenSpacePropagationQueue,
+        settings: &GenerationSettings,
+    ) {
+        let mut entities = HashMap::new();
+
+        for saved in self.cells.iter() {
+            let mut cell = Cell::new(&saved.valid_tiles, saved.position);
+            if saved.tile_type.is_some() {
+                cell.tile_type = saved.tile_type;
+                cell.is_collapsed = true;
+                cell.entropy = 0.0;
+            }
+
+            entities.insert(saved.position, spawn_cell(commands, cell, settings));
+        }
+
+        queue.queue.clear();
+        queue.queue.extend(
+            self.queue
+                .iter()
+                .filter_map(|position| entities.get(position).copied()),
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use bevy::{
+    ecs::{
+        query::{Changed, With},
+        resource::Resource,
+        system::{Query, Res, ResMut},
+    },
+    log::debug,
+    time::{Time, Timer, TimerMode},
+    window::{Monitor, PrimaryMonitor},
+};
+
+use crate::game::core_mechanics::oz_devinimli_yaratim::{
+    cells::GenerationSettings,
+    odycore::{open_space::CollapseBudget, propagation_tasks::PropagationTasks},
+};
+
+const SMOOTHING: f32 = 0.1; // Weight of the newest frame in the running average
+const SLOW_FACTOR: f32 = 1.1; // Over target by this much and budgets shrink
+const KEEPING_UP_FACTOR: f32 = 1.02; // Within this of target and they grow back, for vsync jitter
+const MIN_SCALE: f32 = 0.125;
+
+// Scales the generation budgets down while frames run over the target time and back up to
+// their configured sizes once frames keep up with it again. With vsync, frames never come in
+// under the refresh interval, so that's the target.
+#[derive(Resource, Debug)]
+pub struct GenerationThrottle {
+    pub target_frame_ms: f32, // The primary monitor's refresh interval once it's known
+    pub average_frame_ms: f32,
+    pub scale: f32, // MIN_SCALE..=1 of the full budgets below
+    full_apply_budget: usize,
+    full_cells_per_frame: usize,
+    full_spawn_batch: usize,
+    adjust_timer: Timer,
+}
+
+impl Default for GenerationThrottle {
+    fn default() -> Self {
+        Self {
+            target_frame_ms: 1000.0 / 60.0,
+            average_frame_ms: 1000.0 / 60.0,
+            scale: 1.0,
+            full_apply_budget: PropagationTasks::default().apply_budget,
+            full_cells_per_frame: CollapseBudget::default().cells_per_frame,
+            full_spawn_batch: GenerationSettings::default().spawn_batch,
+            adjust_timer: Timer::from_seconds(0.5, TimerMode::Repeating),
+        }
+    }
+}
+
+fn scaled(full: usize, scale: f32) -> usize {
+    ((full as f32 * scale).round() as usize).max(1)
+}
+
+pub fn match_refresh_rate(
+    mut throttle: ResMut<GenerationThrottle>,
+    monitors: Query<&Monitor, (With<PrimaryMonitor>, Changed<Monitor>)>,
+) {
+    if let Some(millihertz) = monitors
+        .iter()
+        .find_map(|monitor| monitor.refresh_rate_millihertz)
+    {
+        throttle.target_frame_ms = 1_000_000.0 / millihertz as f32;
+    }
+}
+
+pub fn throttle_generation(
+    mut throttle: ResMut<GenerationThrottle>,
+    mut tasks: ResMut<PropagationTasks>,
+    mut budget: ResMut<CollapseBudget>,
+    mut settings: ResMut<GenerationSettings>,
+    time: Res<Time>,
+) {
+    let frame_ms = time.delta_secs() * 1000.0;
+    throttle.average_frame_ms += (frame_ms - throttle.average_frame_ms) * SMOOTHING;
+
+    throttle.adjust_timer.tick(time.delta());
+    if !throttle.adjust_timer.just_finished() {
+        return;
+    }
+
+    let scale = if throttle.average_frame_ms > throttle.target_frame_ms * SLOW_FACTOR {
+        (throttle.scale * 0.75).max(MIN_SCALE)
+    } else if throttle.average_frame_ms <= throttle.target_frame_ms * KEEPING_UP_FACTOR {
+        (throttle.scale * 1.25).min(1.0)
+    } else {
+        return;
+    };
+    if scale == throttle.scale {
+        return;
+    }
+    // Whatever the budgets were set to while unthrottled is what they grow back to
+    if throttle.scale == 1.0 {
+        throttle.full_apply_budget = tasks.apply_budget;
+        throttle.full_cells_per_frame = budget.cells_per_frame;
+        throttle.full_spawn_batch = settings.spawn_batch;
+    }
+
+    debug!(
+        "Generation budgets at {:.0}% ({:.1} ms per frame)",
+        scale * 100.0,
+        throttle.average_frame_ms
+    );
+    throttle.scale = scale;
+    tasks.apply_budget = scaled(throttle.full_apply_budget, scale);
+    budget.cells_per_frame = scaled(throttle.full_cells_per_frame, scale);
+    settings.spawn_batch = scaled(throttle.full_spawn_batch, scale);
+}
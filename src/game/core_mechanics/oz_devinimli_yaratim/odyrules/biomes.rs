@@ -0,0 +1,145 @@
+use bevy::{
+    ecs::{resource::Resource, system::Res, system::SystemParam},
+    platform::collections::HashMap,
+};
+
+use serde::Deserialize;
+
+use crate::game::core_mechanics::oz_devinimli_yaratim::odyrules::{
+    commons::{Rules, TileType},
+    open_space_rules::OpenSpaceRules,
+};
+
+const BIOME_SEED: u64 = 0x5EED_B10E;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Deserialize)]
+pub enum Biome {
+    Plains,
+    Forest,
+    Ruins,
+}
+
+pub const BIOMES: [Biome; 3] = [Biome::Plains, Biome::Forest, Biome::Ruins];
+
+impl Biome {
+    // Multiplier on the base rule weight; 0 keeps the tile out of the biome
+    fn weight_scale(self, tile: TileType) -> f32 {
+        match (self, tile) {
+            (Biome::Plains, TileType::Tree) => 0.5,
+            (Biome::Plains, TileType::ThornBush) => 0.6,
+            (Biome::Plains, TileType::DeepWater) => 1.4,
+            (Biome::Plains, TileType::LargeTree | TileType::RuinEntrance) => 0.0,
+
+            (Biome::Forest, TileType::Ground) => 0.7,
+            (Biome::Forest, TileType::Tree) => 3.0,
+            (Biome::Forest, TileType::LargeTree) => 3.0,
+            (Biome::Forest, TileType::ThornBush) => 1.5,
+            (Biome::Forest, TileType::DeepWater) => 0.4,
+            (Biome::Forest, TileType::FountainCenter) => 0.3,
+            (Biome::Forest, TileType::RuinEntrance) => 0.0,
+
+            (Biome::Ruins, TileType::Tree) => 0.6,
+            (Biome::Ruins, TileType::Chest) => 2.0,
+            (Biome::Ruins, TileType::FountainCenter) => 1.5,
+            (Biome::Ruins, TileType::RuinEntrance) => 4.0,
+            (Biome::Ruins, TileType::LargeTree) => 0.0,
+
+            _ => 1.0,
+        }
+    }
+}
+
+// Splits the grid into square regions with one biome each. Cells within `border_cells` of a
+// region edge blend the biomes on both sides, so forests thin out instead of stopping dead.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct BiomeMap {
+    pub seed: u64,
+    pub region_cells: i32,
+    pub border_cells: f32,
+}
+
+impl Default for BiomeMap {
+    fn default() -> Self {
+        Self {
+            seed: BIOME_SEED,
+            region_cells: 24,
+            border_cells: 4.0,
+        }
+    }
+}
+
+impl BiomeMap {
+    // Hashed rather than stored, so regions far from anything loaded can be asked about too
+    pub fn region_biome(&self, region: (i32, i32)) -> Biome {
+        let mut hash = self.seed;
+        for value in [region.0, region.1] {
+            hash = (hash ^ value as u32 as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+            hash ^= hash >> 31;
+        }
+        BIOMES[(hash % BIOMES.len() as u64) as usize]
+    }
+
+    // Biomes of the four regions around `position` with how much each one counts
+    pub fn blend_at(&self, position: (i32, i32)) -> [(Biome, f32); 4] {
+        let region_cells = self.region_cells as f32;
+        // 0.5 is a region center, so the edge between two regions sits at a fraction of 0.5
+        let axis = |coordinate: i32| {
+            let scaled = (coordinate as f32 + 0.5) / region_cells - 0.5;
+            let lower = scaled.floor();
+            let band = self.border_cells / region_cells;
+            let blend = ((scaled - lower - 0.5) / (2.0 * band) + 0.5).clamp(0.0, 1.0);
+            (lower as i32, blend)
+        };
+        let (x, blend_x) = axis(position.0);
+        let (z, blend_z) = axis(position.1);
+
+        [
+            (self.region_biome((x, z)), (1.0 - blend_x) * (1.0 - blend_z)),
+            (self.region_biome((x + 1, z)), blend_x * (1.0 - blend_z)),
+            (self.region_biome((x, z + 1)), (1.0 - blend_x) * blend_z),
+            (self.region_biome((x + 1, z + 1)), blend_x * blend_z),
+        ]
+    }
+
+    pub fn biome_at(&self, position: (i32, i32)) -> Biome {
+        self.blend_at(position)
+            .into_iter()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(biome, _)| biome)
+            .unwrap()
+    }
+
+    fn weight_scale_at(&self, position: (i32, i32), tile: TileType) -> f32 {
+        self.blend_at(position)
+            .into_iter()
+            .map(|(biome, share)| biome.weight_scale(tile) * share)
+            .sum()
+    }
+}
+
+// The rule set as it applies at a given cell: the base rules shaped by the local biomes
+#[derive(SystemParam)]
+pub struct BiomeRules<'w> {
+    pub rules: Res<'w, OpenSpaceRules>,
+    pub biomes: Res<'w, BiomeMap>,
+}
+
+impl BiomeRules<'_> {
+    // Tiles a new cell starts out with; on a border, anything either side allows
+    pub fn tiles_at(&self, position: (i32, i32)) -> Vec<TileType> {
+        self.rules
+            .all_tiles
+            .iter()
+            .copied()
+            .filter(|tile| self.biomes.weight_scale_at(position, *tile) > 0.0)
+            .collect()
+    }
+
+    pub fn weights_at(&self, position: (i32, i32)) -> HashMap<TileType, f32> {
+        self.rules
+            .weights()
+            .iter()
+            .map(|(tile, weight)| (*tile, weight * self.biomes.weight_scale_at(position, *tile)))
+            .collect()
+    }
+}
@@ -1,7 +1,9 @@
 use bevy::platform::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, EnumIter)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, EnumIter, Serialize, Deserialize)]
 pub enum TileType {
     Ground, 
     Tree,   
@@ -16,6 +18,337 @@ pub enum TileType {
     FountainEdge2,   
     FountainEdge3,   
     FountainEdge4,   
+
+    ThornBush,
+    DeepWater,
+    Water, // Shallow, wadeable
+
+    // River pieces, turned like the fountain ones; the flow runs along the unturned front
+    RiverStraight1,
+    RiverStraight2,
+    RiverBend1,
+    RiverBend2,
+    RiverBend3,
+    RiverBend4,
+    Bridge1,
+    Bridge2,
+
+    // Where the ground steps down to the next height
+    Cliff,
+    Slope,
+    Stairs,
+
+    // Only placed by prefabs, never picked by the solver
+    Wall,
+    Door,
+    Floor,
+    Path,
+    Well,
+
+    // Only placed where the solver ran out of tiles, in place of plain ground
+    Rubble,
+
+    // Multi-cell tiles: placed on their anchor cell (lowest x and z of the footprint)
+    LargeTree,
+    RuinEntrance,
+    // The rest of a multi-cell footprint
+    Covered,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HazardKind {
+    Thorns,
+    DeepWater,
+    Cold,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Hazard {
+    pub kind: HazardKind,
+    pub damage_per_second: f32,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AmbientSound {
+    FountainGurgle,
+    TreeWind,
+}
+
+// A loop the tile plays while the listener is within `radius`, fading out towards the edge
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct AmbientEmitter {
+    pub sound: AmbientSound,
+    pub radius: f32,
+    pub volume: f32,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TileMetadata {
+    pub walkable: bool,
+    pub blocks_sight: bool,
+    pub hazard: Option<Hazard>,
+    pub ambient: Option<AmbientEmitter>,
+    pub speed: f32, // Multiplies how fast anything walks, wades or swims across the tile
+    pub interaction: Option<&'static str>, // Verb for pressing use on the tile, if it has one
+}
+
+impl TileType {
+    pub fn metadata(self) -> TileMetadata {
+        match self {
+            TileType::Ground => TileMetadata {
+                walkable: true,
+                blocks_sight: false,
+                hazard: None,
+                ambient: None,
+                speed: 1.0,
+                interaction: None,
+            },
+            TileType::FountainCenter => TileMetadata {
+                walkable: false,
+                blocks_sight: false,
+                hazard: None,
+                ambient: Some(AmbientEmitter {
+                    sound: AmbientSound::FountainGurgle,
+                    radius: 60.0,
+                    volume: 0.6,
+                }),
+                speed: 1.0,
+                interaction: Some("Drink"),
+            },
+            TileType::Tree | TileType::LargeTree => TileMetadata {
+                walkable: false,
+                blocks_sight: true,
+                hazard: None,
+                ambient: Some(AmbientEmitter {
+                    sound: AmbientSound::TreeWind,
+                    radius: 15.0,
+                    volume: 0.25,
+                }),
+                speed: 1.0,
+                interaction: None,
+            },
+            TileType::ThornBush => TileMetadata {
+                walkable: true,
+                blocks_sight: false,
+                hazard: Some(Hazard {
+                    kind: HazardKind::Thorns,
+                    damage_per_second: 6.0,
+                }),
+                ambient: None,
+                speed: 0.6, // Pushing through brambles
+                interaction: None,
+            },
+            TileType::DeepWater => TileMetadata {
+                walkable: false,
+                blocks_sight: false,
+                hazard: Some(Hazard {
+                    kind: HazardKind::DeepWater,
+                    damage_per_second: 3.0, // Drowning
+                }),
+                ambient: None,
+                speed: 0.45, // Swimming
+                interaction: None,
+            },
+            TileType::Water => TileMetadata {
+                walkable: true,
+                blocks_sight: false,
+                hazard: None,
+                ambient: None,
+                speed: 0.7, // Wading
+                interaction: None,
+            },
+            TileType::RiverStraight1
+            | TileType::RiverStraight2
+            | TileType::RiverBend1
+            | TileType::RiverBend2
+            | TileType::RiverBend3
+            | TileType::RiverBend4 => TileMetadata {
+                walkable: false,
+                blocks_sight: false,
+                hazard: None,
+                ambient: None,
+                speed: 0.5, // Wading against the current
+                interaction: None,
+            },
+            TileType::Bridge1 | TileType::Bridge2 => TileMetadata {
+                walkable: true,
+                blocks_sight: false,
+                hazard: None,
+                ambient: None,
+                speed: 1.0,
+                interaction: None,
+            },
+            TileType::Cliff => TileMetadata {
+                walkable: false,
+                blocks_sight: true,
+                hazard: None,
+                ambient: None,
+                speed: 1.0,
+                interaction: None,
+            },
+            TileType::Slope => TileMetadata {
+                walkable: true,
+                blocks_sight: false,
+                hazard: None,
+                ambient: None,
+                speed: 0.75, // Scrambling up the bank
+                interaction: None,
+            },
+            TileType::Stairs => TileMetadata {
+                walkable: true,
+                blocks_sight: false,
+                hazard: None,
+                ambient: None,
+                speed: 0.9,
+                interaction: None,
+            },
+            TileType::Door => TileMetadata {
+                walkable: true,
+                blocks_sight: false,
+                hazard: None,
+                ambient: None,
+                speed: 1.0,
+                interaction: Some("Open"),
+            },
+            TileType::Floor => TileMetadata {
+                walkable: true,
+                blocks_sight: false,
+                hazard: None,
+                ambient: None,
+                speed: 1.0,
+                interaction: None,
+            },
+            TileType::Path => TileMetadata {
+                walkable: true,
+                blocks_sight: false,
+                hazard: None,
+                ambient: None,
+                speed: 1.2, // Packed earth road
+                interaction: None,
+            },
+            TileType::Well => TileMetadata {
+                walkable: false,
+                blocks_sight: false,
+                hazard: None,
+                ambient: None,
+                speed: 1.0,
+                interaction: None,
+            },
+            TileType::Chest => TileMetadata {
+                walkable: false,
+                blocks_sight: true,
+                hazard: None,
+                ambient: None,
+                speed: 1.0,
+                interaction: Some("Open"),
+            },
+            TileType::Rubble => TileMetadata {
+                walkable: true,
+                blocks_sight: false,
+                hazard: None,
+                ambient: None,
+                speed: 0.85, // Picking a way over the stones
+                interaction: None,
+            },
+            // Fountain walls, house walls and multi-cell footprints
+            _ => TileMetadata {
+                walkable: false,
+                blocks_sight: true,
+                hazard: None,
+                ambient: None,
+                speed: 1.0,
+                interaction: None,
+            },
+        }
+    }
+
+    pub fn blocks_sound(self) -> bool {
+        self.metadata().blocks_sight
+    }
+
+    // For the player's eyes; the pieces of one structure share a name
+    pub fn display_name(self) -> &'static str {
+        match self {
+            TileType::Ground | TileType::Covered => "Ground",
+            TileType::Tree => "Tree",
+            TileType::LargeTree => "Large tree",
+            TileType::Chest => "Chest",
+            TileType::FountainCenter
+            | TileType::FountainCorner1
+            | TileType::FountainCorner2
+            | TileType::FountainCorner3
+            | TileType::FountainCorner4
+            | TileType::FountainEdge1
+            | TileType::FountainEdge2
+            | TileType::FountainEdge3
+            | TileType::FountainEdge4 => "Fountain",
+            TileType::ThornBush => "Thorn bush",
+            TileType::DeepWater => "Deep water",
+            TileType::Water => "Shallow water",
+            TileType::RiverStraight1
+            | TileType::RiverStraight2
+            | TileType::RiverBend1
+            | TileType::RiverBend2
+            | TileType::RiverBend3
+            | TileType::RiverBend4 => "River",
+            TileType::Bridge1 | TileType::Bridge2 => "Bridge",
+            TileType::Cliff => "Cliff",
+            TileType::Slope => "Slope",
+            TileType::Stairs => "Stairs",
+            TileType::Wall => "Wall",
+            TileType::Door => "Door",
+            TileType::Floor => "Floor",
+            TileType::Path => "Path",
+            TileType::Well => "Well",
+            TileType::Rubble => "Rubble",
+            TileType::RuinEntrance => "Ruin entrance",
+        }
+    }
+
+    // One character per tile for text dumps of the world; every tile has its own
+    pub fn glyph(self) -> char {
+        match self {
+            TileType::Ground => '.',
+            TileType::Tree => 't',
+            TileType::Chest => 'c',
+            TileType::FountainCenter => 'F',
+            TileType::FountainCorner1 => '1',
+            TileType::FountainCorner2 => '2',
+            TileType::FountainCorner3 => '3',
+            TileType::FountainCorner4 => '4',
+            TileType::FountainEdge1 => '5',
+            TileType::FountainEdge2 => '6',
+            TileType::FountainEdge3 => '7',
+            TileType::FountainEdge4 => '8',
+            TileType::ThornBush => '*',
+            TileType::DeepWater => 'W',
+            TileType::Water => 'w',
+            TileType::RiverStraight1 => '|',
+            TileType::RiverStraight2 => '-',
+            TileType::RiverBend1 => 'q',
+            TileType::RiverBend2 => 'p',
+            TileType::RiverBend3 => 'b',
+            TileType::RiverBend4 => 'd',
+            TileType::Bridge1 => '=',
+            TileType::Bridge2 => 'H',
+            TileType::Cliff => '^',
+            TileType::Slope => '/',
+            TileType::Stairs => 's',
+            TileType::Wall => '#',
+            TileType::Door => 'D',
+            TileType::Floor => '_',
+            TileType::Path => ':',
+            TileType::Well => 'o',
+            TileType::Rubble => 'r',
+            TileType::LargeTree => 'T',
+            TileType::RuinEntrance => 'E',
+            TileType::Covered => '+',
+        }
+    }
+
+    pub fn from_glyph(glyph: char) -> Option<Self> {
+        TileType::iter().find(|tile| tile.glyph() == glyph)
+    }
 }
 
 #[derive(Clone, Copy, Hash, Eq, PartialEq, Debug)]
@@ -40,6 +373,203 @@ pub const DIRECTION_VECTORS: [(Direction, (i32, i32)); 4] = [
     (Direction::Left, (-1, 0)), // -X
 ];
 
+impl Direction {
+    pub fn counter_clockwise(self) -> Direction {
+        match self {
+            Direction::Front => Direction::Left,
+            Direction::Left => Direction::Back,
+            Direction::Back => Direction::Right,
+            Direction::Right => Direction::Front,
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Front => Direction::Back,
+            Direction::Back => Direction::Front,
+            Direction::Right => Direction::Left,
+            Direction::Left => Direction::Right,
+        }
+    }
+}
+
+// What a tile presents along one of its sides. Two tiles may sit side by side when the
+// sockets they turn towards each other fit, so a new tile only has to declare its own sides.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Socket {
+    Open,      // Bare ground, which borders almost anything
+    Growth,    // Trees, bushes and chests standing on the ground
+    Footprint, // Multi-cell tiles and the cells they cover
+    Water,
+    Shallow,
+    RiverFlow, // Where a river carries on into the next cell
+    Settlement,
+    FountainRim,
+    FountainInner, // Faces the middle of the fountain
+    // Runs along the border, with the rim one turn clockwise or counter-clockwise from the
+    // side. Only opposite turns meet, so the rims of neighboring pieces line up.
+    FountainBorderCw,
+    FountainBorderCcw,
+}
+
+impl Socket {
+    pub fn fits(self, other: Socket) -> bool {
+        self.accepts(other) || other.accepts(self)
+    }
+
+    // One half of the pairs; `fits` checks both orders
+    fn accepts(self, other: Socket) -> bool {
+        use Socket::*;
+
+        match self {
+            Open => matches!(
+                other,
+                Open | Growth | Footprint | Water | Shallow | Settlement | FountainRim
+            ),
+            Growth => matches!(other, Growth | Footprint | Settlement),
+            Footprint | Water | FountainInner => other == self,
+            // Rivers only stop where they run into shallows
+            Shallow => matches!(other, Shallow | Water | RiverFlow),
+            RiverFlow => other == RiverFlow,
+            FountainBorderCw => other == FountainBorderCcw,
+            Settlement | FountainRim | FountainBorderCcw => false,
+        }
+    }
+}
+
+// Pieces that share one model and one set of sockets, turned to fit where they're placed
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BaseTile {
+    FountainCorner, // Unturned: bottom-left
+    FountainEdge,   // Unturned: top
+    RiverStraight,  // Unturned: flowing front to back
+    RiverBend,      // Unturned: flowing in at the front and out to the right
+    Bridge,         // A straight stretch with a crossing from left to right
+}
+
+impl BaseTile {
+    // In DIRECTIONS order: front, back, right, left
+    fn sockets(self) -> [Socket; 4] {
+        use Socket::*;
+
+        match self {
+            BaseTile::FountainCorner => [
+                FountainBorderCcw,
+                FountainRim,
+                FountainBorderCw,
+                FountainRim,
+            ],
+            BaseTile::FountainEdge => [
+                FountainRim,
+                FountainInner,
+                FountainBorderCcw,
+                FountainBorderCw,
+            ],
+            BaseTile::RiverStraight | BaseTile::Bridge => [RiverFlow, RiverFlow, Open, Open],
+            BaseTile::RiverBend => [RiverFlow, Open, RiverFlow, Open],
+        }
+    }
+}
+
+// Each quarter turn carries what faced front to the right, as Quat::from_rotation_y does
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TileVariant {
+    pub base: BaseTile,
+    pub rotation: u8, // Quarter turns
+}
+
+impl TileVariant {
+    pub fn angle(self) -> f32 {
+        self.rotation as f32 * std::f32::consts::FRAC_PI_2
+    }
+
+    fn socket(self, direction: Direction) -> Socket {
+        // Undo the turn to find the side of the unturned piece now facing `direction`
+        let unturned = (0..self.rotation).fold(direction, |side, _| side.counter_clockwise());
+        side_socket(self.base.sockets(), unturned)
+    }
+}
+
+fn side_socket([front, back, right, left]: [Socket; 4], direction: Direction) -> Socket {
+    match direction {
+        Direction::Front => front,
+        Direction::Back => back,
+        Direction::Right => right,
+        Direction::Left => left,
+    }
+}
+
+impl TileType {
+    pub fn variant(self) -> Option<TileVariant> {
+        let (base, rotation) = match self {
+            TileType::FountainCorner3 => (BaseTile::FountainCorner, 0),
+            TileType::FountainCorner1 => (BaseTile::FountainCorner, 1),
+            TileType::FountainCorner2 => (BaseTile::FountainCorner, 2),
+            TileType::FountainCorner4 => (BaseTile::FountainCorner, 3),
+            TileType::FountainEdge1 => (BaseTile::FountainEdge, 0),
+            TileType::FountainEdge2 => (BaseTile::FountainEdge, 1),
+            TileType::FountainEdge4 => (BaseTile::FountainEdge, 2),
+            TileType::FountainEdge3 => (BaseTile::FountainEdge, 3),
+            TileType::RiverStraight1 => (BaseTile::RiverStraight, 0),
+            TileType::RiverStraight2 => (BaseTile::RiverStraight, 1),
+            TileType::RiverBend1 => (BaseTile::RiverBend, 0),
+            TileType::RiverBend2 => (BaseTile::RiverBend, 1),
+            TileType::RiverBend3 => (BaseTile::RiverBend, 2),
+            TileType::RiverBend4 => (BaseTile::RiverBend, 3),
+            TileType::Bridge1 => (BaseTile::Bridge, 0),
+            TileType::Bridge2 => (BaseTile::Bridge, 1),
+            _ => return None,
+        };
+        Some(TileVariant { base, rotation })
+    }
+
+    pub fn socket(self, direction: Direction) -> Socket {
+        use Socket::*;
+
+        if let Some(variant) = self.variant() {
+            return variant.socket(direction);
+        }
+
+        let sockets = match self {
+            // Rubble borders whatever ground would, so it never makes a contradiction worse
+            TileType::Ground
+            | TileType::Rubble
+            | TileType::Cliff
+            | TileType::Slope
+            | TileType::Stairs => [Open; 4],
+            TileType::Tree | TileType::Chest | TileType::ThornBush => [Growth; 4],
+            TileType::LargeTree | TileType::RuinEntrance | TileType::Covered => [Footprint; 4],
+            TileType::DeepWater => [Water; 4],
+            TileType::Water => [Shallow; 4],
+            TileType::FountainCenter => [FountainInner; 4],
+            // Prefab tiles, plus the turned pieces handled above
+            _ => [Settlement; 4],
+        };
+        side_socket(sockets, direction)
+    }
+}
+
+// For every tile and side, the tiles whose facing socket fits
+pub fn socket_adjacency() -> HashMap<TileType, HashMap<Direction, Vec<TileType>>> {
+    TileType::iter()
+        .map(|tile| {
+            let sides = DIRECTIONS
+                .iter()
+                .map(|direction| {
+                    let neighbors = TileType::iter()
+                        .filter(|neighbor| {
+                            tile.socket(*direction)
+                                .fits(neighbor.socket(direction.opposite()))
+                        })
+                        .collect();
+                    (*direction, neighbors)
+                })
+                .collect();
+            (tile, sides)
+        })
+        .collect()
+}
+
 pub trait Rules {
     fn allowed_neighbors<'a>(&'a self) -> &'a HashMap<TileType, HashMap<Direction, Vec<TileType>>>;
     fn weights<'a>(&'a self) -> &'a HashMap<TileType, f32>;
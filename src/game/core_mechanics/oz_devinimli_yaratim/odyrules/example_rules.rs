@@ -0,0 +1,150 @@
+use std::fs;
+
+use bevy::{
+    ecs::{
+        event::EventReader,
+        system::{Commands, ResMut},
+    },
+    platform::collections::HashMap,
+};
+use serde::Deserialize;
+use strum::IntoEnumIterator;
+
+use crate::game::{
+    core_mechanics::oz_devinimli_yaratim::odyrules::{
+        commons::{DIRECTION_VECTORS, TileType},
+        open_space_rules::OpenSpaceRules,
+    },
+    ui::console::{ConsoleCommand, ConsoleState},
+};
+
+pub const EXAMPLE_DIRECTORY: &str = "assets/rules";
+
+// A small hand-authored stretch of world to learn rules from. Row n is z = n, column n is x = n.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ExampleMap {
+    pub rows: Vec<Vec<TileType>>,
+    #[serde(default)]
+    pub periodic: bool, // Opposite edges count as touching, so border tiles still get neighbors
+}
+
+impl ExampleMap {
+    fn tile_at(&self, (x, z): (i32, i32)) -> Option<TileType> {
+        let (width, depth) = (self.rows[0].len() as i32, self.rows.len() as i32);
+        let (x, z) = if self.periodic {
+            (x.rem_euclid(width), z.rem_euclid(depth))
+        } else if (0..width).contains(&x) && (0..depth).contains(&z) {
+            (x, z)
+        } else {
+            return None;
+        };
+        Some(self.rows[z as usize][x as usize])
+    }
+}
+
+impl OpenSpaceRules {
+    // The overlapping model with patterns of two cells: every pair of tiles that sits side by
+    // side in the example is allowed, and nothing else is. Weights follow how often each tile
+    // shows up; footprints keep their usual sizes, so multi-cell tiles need their Covered cells
+    // drawn in.
+    pub fn from_example(example: &ExampleMap) -> Result<Self, String> {
+        let width = example.rows.first().map_or(0, Vec::len);
+        if width == 0 {
+            return Err("example map is empty".to_string());
+        }
+        if let Some(z) = example.rows.iter().position(|row| row.len() != width) {
+            return Err(format!("row {} has a different width than row 0", z));
+        }
+
+        let mut allowed_neighbors: HashMap<_, HashMap<_, Vec<TileType>>> = HashMap::new();
+        let mut counts: HashMap<TileType, usize> = HashMap::new();
+        for (z, row) in example.rows.iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                *counts.entry(*tile).or_default() += 1;
+
+                // Same pairing as propagation: the list under `direction` is for the cell on
+                // the other side of the tile from it
+                let sides = allowed_neighbors.entry(*tile).or_default();
+                for (direction, (dx, dz)) in DIRECTION_VECTORS.iter() {
+                    let Some(neighbor) = example.tile_at((x as i32 - dx, z as i32 - dz)) else {
+                        continue;
+                    };
+                    let allowed = sides.entry(*direction).or_default();
+                    if !allowed.contains(&neighbor) {
+                        allowed.push(neighbor);
+                    }
+                }
+            }
+        }
+
+        let total = (width * example.rows.len()) as f32;
+        let weights = TileType::iter()
+            .map(|tile| {
+                let share = *counts.get(&tile).unwrap_or(&0) as f32 / total;
+                // Still only placed as part of a footprint
+                let weight = if tile == TileType::Covered {
+                    0.0
+                } else {
+                    share
+                };
+                (tile, weight)
+            })
+            .collect();
+
+        Ok(OpenSpaceRules {
+            allowed_neighbors,
+            all_tiles: TileType::iter()
+                .filter(|tile| counts.contains_key(tile))
+                .collect(),
+            weights,
+            footprints: OpenSpaceRules::default().footprints,
+        })
+    }
+}
+
+pub fn load_example(name: &str) -> Result<OpenSpaceRules, String> {
+    let path = format!("{}/{}.ron", EXAMPLE_DIRECTORY, name);
+    let text = fs::read_to_string(&path).map_err(|error| format!("{}: {}", path, error))?;
+    let example: ExampleMap =
+        ron::from_str(&text).map_err(|error| format!("{}: {}", path, error))?;
+    OpenSpaceRules::from_example(&example).map_err(|error| format!("{}: {}", path, error))
+}
+
+// `rules learn <name>` switches to rules learned from assets/rules/<name>.ron, `rules default`
+// back to the hand-written ones. Cells already generated keep what they have.
+pub fn rules_command(
+    mut commands: Commands,
+    mut commands_in: EventReader<ConsoleCommand>,
+    mut console: ResMut<ConsoleState>,
+) {
+    for command in commands_in.read().filter(|c| c.name == "rules") {
+        let args: Vec<&str> = command.args.iter().map(String::as_str).collect();
+
+        let rules = match args.as_slice() {
+            ["learn", name] => match load_example(name) {
+                Ok(rules) => rules,
+                Err(error) => {
+                    console.print(format!("couldn't learn rules: {}", error));
+                    continue;
+                }
+            },
+            ["default"] => OpenSpaceRules::default(),
+            _ => {
+                console.print("usage: rules learn <example> | rules default");
+                continue;
+            }
+        };
+
+        console.print(format!(
+            "rules: {} tiles, {} allowed pairings",
+            rules.all_tiles.len(),
+            rules
+                .allowed_neighbors
+                .values()
+                .flat_map(|sides| sides.values())
+                .map(Vec::len)
+                .sum::<usize>()
+        ));
+        commands.insert_resource(rules);
+    }
+}
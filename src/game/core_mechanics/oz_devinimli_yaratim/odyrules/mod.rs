@@ -1,2 +1,4 @@
+pub mod biomes;
 pub mod commons; 
+pub mod example_rules;
 pub mod open_space_rules; 
@@ -2,13 +2,14 @@ use bevy::{ecs::resource::Resource, platform::collections::HashMap};
 use strum::IntoEnumIterator;
 
 use crate::game::core_mechanics::oz_devinimli_yaratim::odyrules::commons::{
-    DIRECTIONS, Direction, Rules, TileType,
+    Direction, Rules, TileType, socket_adjacency,
 };
-#[derive(Resource, Debug)]
+#[derive(Resource, Debug, Clone)]
 pub struct OpenSpaceRules {
     pub allowed_neighbors: HashMap<TileType, HashMap<Direction, Vec<TileType>>>,
     pub all_tiles: Vec<TileType>,
     pub weights: HashMap<TileType, f32>,
+    pub footprints: HashMap<TileType, (i32, i32)>, // Cells along x and z; tiles not listed take one cell
 }
 
 impl Rules for OpenSpaceRules {
@@ -20,220 +21,14 @@ impl Rules for OpenSpaceRules {
     }
 }
 impl OpenSpaceRules {
-    fn set_all_directions(rules_map: &mut HashMap<Direction, Vec<TileType>>, tiles: Vec<TileType>) {
-        for dir in DIRECTIONS.iter() {
-            rules_map.insert(*dir, tiles.clone());
-        }
+    pub fn footprint(&self, tile: TileType) -> (i32, i32) {
+        self.footprints.get(&tile).copied().unwrap_or((1, 1))
     }
 }
 
 impl Default for OpenSpaceRules {
     fn default() -> Self {
-        let mut allowed_neighbors = HashMap::new();
-        let mut rules_map = HashMap::new();
-
-        rules_map.insert(
-            Direction::Front,
-            vec![
-                TileType::Ground,          // More ground in front
-                TileType::Tree,            // Tree in front
-                TileType::Chest,           // Chest in front
-                TileType::FountainCorner3, // Bottom-left fountain corner
-                TileType::FountainCorner4, // Bottom-right fountain corner
-                TileType::FountainEdge4,   // Bottom fountain edge
-            ],
-        );
-        rules_map.insert(
-            Direction::Back,
-            vec![
-                TileType::Ground,
-                TileType::Tree,
-                TileType::Chest,
-                TileType::FountainCorner1, // Top-left fountain corner
-                TileType::FountainCorner2, // Top-right fountain corner
-                TileType::FountainEdge1,   // Top fountain edge
-            ],
-        );
-        rules_map.insert(
-            Direction::Right,
-            vec![
-                TileType::Ground,
-                TileType::Tree,
-                TileType::Chest,
-                TileType::FountainCorner1, // Top-left fountain corner
-                TileType::FountainCorner3, // Bottom-left fountain corner
-                TileType::FountainEdge3,   // Left fountain edge
-            ],
-        );
-        rules_map.insert(
-            Direction::Left,
-            vec![
-                TileType::Ground,
-                TileType::Tree,
-                TileType::Chest,
-                TileType::FountainCorner2, // Top-right fountain corner
-                TileType::FountainCorner4, // Bottom-right fountain corner
-                TileType::FountainEdge3,   // Left fountain edge
-            ],
-        );
-        allowed_neighbors.insert(TileType::Ground, rules_map.clone());
-        rules_map.clear();
-
-        OpenSpaceRules::set_all_directions(
-            &mut rules_map,
-            vec![TileType::Ground, TileType::Tree, TileType::Chest],
-        );
-        allowed_neighbors.insert(TileType::Tree, rules_map.clone());
-        rules_map.clear();
-
-        OpenSpaceRules::set_all_directions(
-            &mut rules_map,
-            vec![TileType::Ground, TileType::Tree, TileType::Chest],
-        );
-        allowed_neighbors.insert(TileType::Chest, rules_map.clone());
-        rules_map.clear();
-
-        rules_map.insert(
-            Direction::Front,
-            vec![TileType::FountainEdge1, TileType::FountainCenter], // Top edge or more center
-        );
-        rules_map.insert(
-            Direction::Back,
-            vec![TileType::FountainEdge4, TileType::FountainCenter], // Bottom edge or more center
-        );
-        rules_map.insert(
-            Direction::Right,
-            vec![TileType::FountainEdge2, TileType::FountainCenter], // Right edge or more center
-        );
-        rules_map.insert(
-            Direction::Left,
-            vec![TileType::FountainEdge3, TileType::FountainCenter], // Left edge or more center
-        );
-        allowed_neighbors.insert(TileType::FountainCenter, rules_map.clone());
-        rules_map.clear();
-
-        rules_map.insert(Direction::Front, vec![TileType::Ground]); // External side to ground
-        rules_map.insert(
-            Direction::Back,
-            vec![TileType::FountainEdge3, TileType::FountainCorner3], // Internal: connects to left edge or bottom-left corner
-        );
-        rules_map.insert(
-            Direction::Right,
-            vec![TileType::FountainEdge1, TileType::FountainCorner2], // Internal: connects to top edge or top-right corner
-        );
-        rules_map.insert(Direction::Left, vec![TileType::Ground]); // External side to ground
-        allowed_neighbors.insert(TileType::FountainCorner1, rules_map.clone());
-        rules_map.clear();
-
-        // FOUNTAIN CORNER2 (Top-right corner)
-        rules_map.insert(Direction::Front, vec![TileType::Ground]); // External side to ground
-        rules_map.insert(
-            Direction::Back,
-            vec![TileType::FountainEdge2, TileType::FountainCorner4], // Internal: connects to right edge or bottom-right corner
-        );
-        rules_map.insert(Direction::Right, vec![TileType::Ground]); // External side to ground
-        rules_map.insert(
-            Direction::Left,
-            vec![TileType::FountainEdge1, TileType::FountainCorner1], // Internal: connects to top edge or top-left corner
-        );
-        allowed_neighbors.insert(TileType::FountainCorner2, rules_map.clone());
-        rules_map.clear();
-
-        // FOUNTAIN CORNER3 (Bottom-left corner)
-        rules_map.insert(
-            Direction::Front,
-            vec![TileType::FountainEdge3, TileType::FountainCorner1], // Internal: connects to left edge or top-left corner
-        );
-        rules_map.insert(Direction::Back, vec![TileType::Ground]); // External side to ground
-        rules_map.insert(
-            Direction::Right,
-            vec![TileType::FountainEdge4, TileType::FountainCorner4], // Internal: connects to bottom edge or bottom-right corner
-        );
-        rules_map.insert(Direction::Left, vec![TileType::Ground]); // External side to ground
-        allowed_neighbors.insert(TileType::FountainCorner3, rules_map.clone());
-        rules_map.clear();
-
-        // FOUNTAIN CORNER4 (Bottom-right corner)
-        rules_map.insert(
-            Direction::Front,
-            vec![TileType::FountainEdge2, TileType::FountainCorner2], // Internal: connects to right edge or top-right corner
-        );
-        rules_map.insert(Direction::Back, vec![TileType::Ground]); // External side to ground
-        rules_map.insert(Direction::Right, vec![TileType::Ground]); // External side to ground
-        rules_map.insert(
-            Direction::Left,
-            vec![TileType::FountainEdge4, TileType::FountainCorner3], // Internal: connects to bottom edge or bottom-left corner
-        );
-        allowed_neighbors.insert(TileType::FountainCorner4, rules_map.clone());
-        rules_map.clear();
-
-        // FOUNTAIN EDGE1 (Top edge - expandable horizontally)
-        rules_map.insert(Direction::Front, vec![TileType::Ground]); // External: to ground
-        rules_map.insert(
-            Direction::Back,
-            vec![TileType::FountainCenter, TileType::FountainEdge4], // Internal: to center or opposite edge
-        );
-        rules_map.insert(
-            Direction::Right,
-            vec![TileType::FountainEdge1, TileType::FountainCorner2], // Expandable: more edge or corner
-        );
-        rules_map.insert(
-            Direction::Left,
-            vec![TileType::FountainEdge1, TileType::FountainCorner1], // Expandable: more edge or corner
-        );
-        allowed_neighbors.insert(TileType::FountainEdge1, rules_map.clone());
-        rules_map.clear();
-
-        // FOUNTAIN EDGE2 (Right edge - expandable vertically)
-        rules_map.insert(
-            Direction::Front,
-            vec![TileType::FountainEdge2, TileType::FountainCorner2], // Expandable: more edge or corner
-        );
-        rules_map.insert(
-            Direction::Back,
-            vec![TileType::FountainEdge2, TileType::FountainCorner4], // Expandable: more edge or corner
-        );
-        rules_map.insert(Direction::Right, vec![TileType::Ground]); // External: to ground
-        rules_map.insert(
-            Direction::Left,
-            vec![TileType::FountainCenter, TileType::FountainEdge3], // Internal: to center or opposite edge
-        );
-        allowed_neighbors.insert(TileType::FountainEdge2, rules_map.clone());
-        rules_map.clear();
-
-        // FOUNTAIN EDGE3 (Left edge - expandable vertically)
-        rules_map.insert(
-            Direction::Front,
-            vec![TileType::FountainEdge3, TileType::FountainCorner1], // Expandable: more edge or corner
-        );
-        rules_map.insert(
-            Direction::Back,
-            vec![TileType::FountainEdge3, TileType::FountainCorner3], // Expandable: more edge or corner
-        );
-        rules_map.insert(
-            Direction::Right,
-            vec![TileType::FountainCenter, TileType::FountainEdge2], // Internal: to center or opposite edge
-        );
-        rules_map.insert(Direction::Left, vec![TileType::Ground]); // External: to ground
-        allowed_neighbors.insert(TileType::FountainEdge3, rules_map.clone());
-        rules_map.clear();
-
-        // FOUNTAIN EDGE4 (Bottom edge - expandable horizontally)
-        rules_map.insert(
-            Direction::Front,
-            vec![TileType::FountainCenter, TileType::FountainEdge1], // Internal: to center or opposite edge
-        );
-        rules_map.insert(Direction::Back, vec![TileType::Ground]); // External: to ground
-        rules_map.insert(
-            Direction::Right,
-            vec![TileType::FountainEdge4, TileType::FountainCorner4], // Expandable: more edge or corner
-        );
-        rules_map.insert(
-            Direction::Left,
-            vec![TileType::FountainEdge4, TileType::FountainCorner3], // Expandable: more edge or corner
-        );
-        allowed_neighbors.insert(TileType::FountainEdge4, rules_map.clone());
-        rules_map.clear();
+        let allowed_neighbors = socket_adjacency();
 
         let mut weights = HashMap::new();
         for tile in TileType::iter() {
@@ -242,6 +37,13 @@ impl Default for OpenSpaceRules {
                 TileType::Tree => 0.2,           // Common: natural decoration
                 TileType::Chest => 0.1,          // Uncommon: special objects
                 TileType::FountainCenter => 0.5, // High: triggers fountain creation
+                TileType::ThornBush => 0.08,     // Uncommon: hazard
+                TileType::DeepWater => 0.15,     // Uncommon: hazard, clusters into ponds
+                TileType::Water => 0.01,         // Rare on its own: mostly where rivers end
+                TileType::LargeTree => 0.05,     // Rare: 2x2 landmark
+                TileType::RuinEntrance => 0.02,  // Very rare: 3x3 landmark
+                TileType::Covered => 0.0,        // Only placed as part of a footprint
+                TileType::Rubble => 0.0,         // Only placed when generation fails
 
                 // Fountain piece weights - slightly different for variety
                 TileType::FountainCorner1 => 0.34567,
@@ -252,27 +54,52 @@ impl Default for OpenSpaceRules {
                 TileType::FountainEdge2 => 0.338,
                 TileType::FountainEdge3 => 0.337,
                 TileType::FountainEdge4 => 0.336,
+
+                // River pieces are rare to start one, but a started river is far more
+                // likely to carry on than to end in shallows, so it runs a long way
+                TileType::RiverStraight1 | TileType::RiverStraight2 => 0.06,
+                TileType::RiverBend1
+                | TileType::RiverBend2
+                | TileType::RiverBend3
+                | TileType::RiverBend4 => 0.02,
+                TileType::Bridge1 | TileType::Bridge2 => 0.005,
+
+                // Only ever weighed against each other, on the cells where the ground steps
+                TileType::Cliff => 0.5,
+                TileType::Slope => 0.35,
+                TileType::Stairs => 0.15,
+
+                // Prefab-only tiles
+                TileType::Wall
+                | TileType::Door
+                | TileType::Floor
+                | TileType::Path
+                | TileType::Well => 0.0,
             };
             weights.insert(tile, weight);
         }
 
         OpenSpaceRules {
             allowed_neighbors,
-            all_tiles: vec![
-                TileType::Ground,
-                TileType::Tree,
-                TileType::Chest,
-                TileType::FountainCenter,
-                TileType::FountainCorner1,
-                TileType::FountainCorner2,
-                TileType::FountainCorner3,
-                TileType::FountainCorner4,
-                TileType::FountainEdge1,
-                TileType::FountainEdge2,
-                TileType::FountainEdge3,
-                TileType::FountainEdge4,
-            ],
+            // Everything the solver may pick, so a new tile cannot be missed or listed twice
+            all_tiles: TileType::iter()
+                .filter(|tile| {
+                    !matches!(
+                        tile,
+                        TileType::Wall
+                            | TileType::Door
+                            | TileType::Floor
+                            | TileType::Path
+                            | TileType::Well
+                            | TileType::Rubble
+                    )
+                })
+                .collect(),
             weights,
+            footprints: HashMap::from_iter([
+                (TileType::LargeTree, (2, 2)),
+                (TileType::RuinEntrance, (3, 3)),
+            ]),
         }
     }
 }
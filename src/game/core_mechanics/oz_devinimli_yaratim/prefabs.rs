@@ -0,0 +1,404 @@
+use std::time::Duration;
+
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+    time::common_conditions::on_timer,
+};
+use rand::prelude::*;
+
+use crate::game::{
+    core_mechanics::{
+        oz_devinimli_yaratim::{
+            cells::{Cell, CellSpatialIndex, GenerationSettings},
+            grid::world_to_grid,
+            odycore::open_space::{CellCollapsed, OpenSpacePropagationQueue, initialize_new_cells},
+            odyrules::commons::TileType,
+            roads::RoadNetwork,
+        },
+        pause::simulation_running,
+    },
+    spawn::player::Player,
+};
+
+const REGION_SIZE: i32 = 40; // In cells; each region can hold at most one settlement
+const SETTLEMENT_CHANCE: f64 = 0.35;
+const MAX_EXPANSION_DEPTH: usize = 8;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<StructureGrammar>()
+        .init_resource::<PrefabReservations>()
+        .add_event::<SettlementCompleted>()
+        .add_systems(
+            Update,
+            (
+                plan_settlements.run_if(on_timer(Duration::from_secs(1))),
+                stamp_reserved_cells.before(initialize_new_cells),
+                track_settlement_progress,
+            )
+                .run_if(simulation_running),
+        );
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum StructureSymbol {
+    Village,
+    House,
+    Walls,
+    Door,
+    Interior,
+    Well,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum StructurePart {
+    Symbol(StructureSymbol),
+    Tile(TileType),
+}
+
+// A part placed relative to the origin of the symbol being expanded
+#[derive(Clone, Copy, Debug)]
+pub struct Placement {
+    pub part: StructurePart,
+    pub offset: (i32, i32),
+}
+
+impl Placement {
+    pub fn symbol(symbol: StructureSymbol, offset: (i32, i32)) -> Self {
+        Self {
+            part: StructurePart::Symbol(symbol),
+            offset,
+        }
+    }
+
+    pub fn tile(tile: TileType, offset: (i32, i32)) -> Self {
+        Self {
+            part: StructurePart::Tile(tile),
+            offset,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Expansion {
+    pub weight: f32,
+    pub placements: Vec<Placement>, // Later placements overwrite earlier ones
+}
+
+#[derive(Resource, Debug)]
+pub struct StructureGrammar {
+    pub rules: HashMap<StructureSymbol, Vec<Expansion>>,
+}
+
+impl StructureGrammar {
+    // Expands a symbol into concrete tiles at grid positions
+    pub fn expand(
+        &self,
+        symbol: StructureSymbol,
+        origin: (i32, i32),
+        rng: &mut impl Rng,
+    ) -> HashMap<(i32, i32), TileType> {
+        let mut tiles = HashMap::new();
+        self.expand_into(symbol, origin, rng, 0, &mut tiles);
+        tiles
+    }
+
+    fn expand_into(
+        &self,
+        symbol: StructureSymbol,
+        origin: (i32, i32),
+        rng: &mut impl Rng,
+        depth: usize,
+        tiles: &mut HashMap<(i32, i32), TileType>,
+    ) {
+        if depth > MAX_EXPANSION_DEPTH {
+            warn!("Structure grammar too deep while expanding {:?}", symbol);
+            return;
+        }
+        let Some(expansion) = self
+            .rules
+            .get(&symbol)
+            .and_then(|expansions| expansions.choose_weighted(rng, |e| e.weight).ok())
+        else {
+            return;
+        };
+
+        for placement in expansion.placements.iter() {
+            let position = (origin.0 + placement.offset.0, origin.1 + placement.offset.1);
+            match placement.part {
+                StructurePart::Tile(tile) => {
+                    tiles.insert(position, tile);
+                }
+                StructurePart::Symbol(child) => {
+                    self.expand_into(child, position, rng, depth + 1, tiles);
+                }
+            }
+        }
+    }
+}
+
+fn square(tile: TileType, half_size: i32, ring_only: bool) -> Vec<Placement> {
+    let mut placements = Vec::new();
+    for x in -half_size..=half_size {
+        for z in -half_size..=half_size {
+            if ring_only && x.abs() != half_size && z.abs() != half_size {
+                continue;
+            }
+            placements.push(Placement::tile(tile, (x, z)));
+        }
+    }
+    placements
+}
+
+fn cross(tile: TileType, arm_length: i32) -> Vec<Placement> {
+    (1..=arm_length)
+        .flat_map(|i| [(i, 0), (-i, 0), (0, i), (0, -i)])
+        .map(|offset| Placement::tile(tile, offset))
+        .collect()
+}
+
+impl Default for StructureGrammar {
+    fn default() -> Self {
+        let mut rules = HashMap::new();
+
+        // VILLAGE - houses around a well, joined by a cross of paths
+        let mut hamlet = cross(TileType::Path, 8);
+        hamlet.extend([
+            Placement::symbol(StructureSymbol::Well, (0, 0)),
+            Placement::symbol(StructureSymbol::House, (-5, -5)),
+            Placement::symbol(StructureSymbol::House, (5, 5)),
+        ]);
+        let mut village = cross(TileType::Path, 8);
+        village.extend([
+            Placement::symbol(StructureSymbol::Well, (0, 0)),
+            Placement::symbol(StructureSymbol::House, (-5, -5)),
+            Placement::symbol(StructureSymbol::House, (5, -5)),
+            Placement::symbol(StructureSymbol::House, (-5, 5)),
+            Placement::symbol(StructureSymbol::House, (5, 5)),
+        ]);
+        rules.insert(
+            StructureSymbol::Village,
+            vec![
+                Expansion {
+                    weight: 2.0,
+                    placements: hamlet,
+                },
+                Expansion {
+                    weight: 1.0,
+                    placements: village,
+                },
+            ],
+        );
+
+        // HOUSE - walls first so the door can cut through them
+        rules.insert(
+            StructureSymbol::House,
+            vec![Expansion {
+                weight: 1.0,
+                placements: vec![
+                    Placement::symbol(StructureSymbol::Walls, (0, 0)),
+                    Placement::symbol(StructureSymbol::Interior, (0, 0)),
+                    Placement::symbol(StructureSymbol::Door, (0, 0)),
+                ],
+            }],
+        );
+
+        rules.insert(
+            StructureSymbol::Walls,
+            vec![Expansion {
+                weight: 1.0,
+                placements: square(TileType::Wall, 2, true),
+            }],
+        );
+
+        rules.insert(
+            StructureSymbol::Door,
+            [(0, 2), (0, -2), (2, 0), (-2, 0)]
+                .into_iter()
+                .map(|offset| Expansion {
+                    weight: 1.0,
+                    placements: vec![Placement::tile(TileType::Door, offset)],
+                })
+                .collect(),
+        );
+
+        let mut stocked = square(TileType::Floor, 1, false);
+        stocked.push(Placement::tile(TileType::Chest, (0, 0)));
+        rules.insert(
+            StructureSymbol::Interior,
+            vec![
+                Expansion {
+                    weight: 3.0,
+                    placements: square(TileType::Floor, 1, false),
+                },
+                Expansion {
+                    weight: 1.0,
+                    placements: stocked,
+                },
+            ],
+        );
+
+        let mut paved_well = square(TileType::Path, 1, true);
+        paved_well.push(Placement::tile(TileType::Well, (0, 0)));
+        rules.insert(
+            StructureSymbol::Well,
+            vec![
+                Expansion {
+                    weight: 1.0,
+                    placements: vec![Placement::tile(TileType::Well, (0, 0))],
+                },
+                Expansion {
+                    weight: 1.0,
+                    placements: paved_well,
+                },
+            ],
+        );
+
+        Self { rules }
+    }
+}
+
+// Tiles promised to prefabs ahead of time; cells spawned there start out collapsed
+#[derive(Resource, Default)]
+pub struct PrefabReservations {
+    pub tiles: HashMap<(i32, i32), TileType>,
+    pub planned_regions: HashSet<(i32, i32)>,
+    pub settlements: Vec<PlannedSettlement>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlannedSettlement {
+    pub origin: (i32, i32),
+    pub bounds: ((i32, i32), (i32, i32)), // Inclusive min and max grid positions
+    pub pending: HashSet<(i32, i32)>,     // Reserved cells not stamped yet
+}
+
+// Every cell of a planned settlement has been stamped
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SettlementCompleted {
+    pub origin: (i32, i32),
+    pub bounds: ((i32, i32), (i32, i32)),
+}
+
+pub fn plan_settlements(
+    mut reservations: ResMut<PrefabReservations>,
+    grammar: Res<StructureGrammar>,
+    spatial_index: Res<CellSpatialIndex>,
+    roads: Res<RoadNetwork>,
+    player: Single<&Transform, With<Player>>,
+    settings: Res<GenerationSettings>,
+) {
+    let player_grid = world_to_grid(player.translation, settings.cell_edge_length as f32);
+    let player_region = (
+        player_grid.0.div_euclid(REGION_SIZE),
+        player_grid.1.div_euclid(REGION_SIZE),
+    );
+    let mut rng = rand::rng();
+
+    for dx in -1..=1 {
+        for dz in -1..=1 {
+            let region = (player_region.0 + dx, player_region.1 + dz);
+            if !reservations.planned_regions.insert(region) {
+                continue;
+            }
+            if !rng.random_bool(SETTLEMENT_CHANCE) {
+                continue;
+            }
+
+            let origin = (
+                region.0 * REGION_SIZE + REGION_SIZE / 2,
+                region.1 * REGION_SIZE + REGION_SIZE / 2,
+            );
+            let mut tiles = grammar.expand(StructureSymbol::Village, origin, &mut rng);
+
+            // Never stamp over ground the player has already seen
+            if tiles
+                .keys()
+                .any(|position| spatial_index.ground(position).is_some())
+            {
+                continue;
+            }
+            // Roads running through keep going; walls make way for them
+            tiles.retain(|position, tile| {
+                tile.metadata().walkable || !roads.cells.contains(position)
+            });
+
+            println!(
+                "🏘️ Planned a settlement of {} tiles at {:?}",
+                tiles.len(),
+                origin
+            );
+            reservations.settlements.push(PlannedSettlement {
+                origin,
+                bounds: tile_bounds(tiles.keys()),
+                pending: tiles.keys().copied().collect(),
+            });
+            reservations.tiles.extend(tiles);
+        }
+    }
+}
+
+pub fn tile_bounds<'a>(
+    positions: impl IntoIterator<Item = &'a (i32, i32)>,
+) -> ((i32, i32), (i32, i32)) {
+    positions.into_iter().fold(
+        ((i32::MAX, i32::MAX), (i32::MIN, i32::MIN)),
+        |(min, max), position| {
+            (
+                (min.0.min(position.0), min.1.min(position.1)),
+                (max.0.max(position.0), max.1.max(position.1)),
+            )
+        },
+    )
+}
+
+pub fn stamp_reserved_cells(
+    reservations: Res<PrefabReservations>,
+    mut queue: ResMut<OpenSpacePropagationQueue>,
+    mut collapsed: EventWriter<CellCollapsed>,
+    mut added_cells: Query<(Entity, &mut Cell), Added<Cell>>,
+) {
+    for (entity, mut cell) in added_cells.iter_mut() {
+        let Some(tile) = reservations.tiles.get(&cell.position) else {
+            continue;
+        };
+
+        cell.valid_tiles = vec![*tile];
+        cell.tile_type = Some(*tile);
+        cell.is_collapsed = true;
+        cell.entropy = 0.0;
+        queue.queue.push_back(entity);
+        collapsed.write(CellCollapsed {
+            position: cell.position,
+            tile: *tile,
+        });
+    }
+}
+
+fn track_settlement_progress(
+    mut reservations: ResMut<PrefabReservations>,
+    mut collapsed: EventReader<CellCollapsed>,
+    mut completed: EventWriter<SettlementCompleted>,
+) {
+    let reservations = reservations.as_mut();
+    for event in collapsed.read() {
+        // Only the reserved tile counts; anything else landing there is not the settlement
+        if reservations.tiles.get(&event.position) != Some(&event.tile) {
+            continue;
+        }
+        for settlement in reservations.settlements.iter_mut() {
+            settlement.pending.remove(&event.position);
+        }
+    }
+
+    reservations.settlements.retain(|settlement| {
+        if settlement.pending.is_empty() {
+            completed.write(SettlementCompleted {
+                origin: settlement.origin,
+                bounds: settlement.bounds,
+            });
+            return false;
+        }
+        true
+    });
+}
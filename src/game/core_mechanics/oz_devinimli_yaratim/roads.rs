@@ -0,0 +1,311 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+use rand::{SeedableRng, prelude::*, rngs::StdRng};
+
+use crate::game::{
+    core_mechanics::{
+        oz_devinimli_yaratim::{
+            cells::{Cell, CellSpatialIndex, CollapsedTileCache, GenerationSettings},
+            grid::world_to_grid,
+            odyrules::commons::TileType,
+            prefabs::{PrefabReservations, stamp_reserved_cells},
+        },
+        pause::simulation_running,
+    },
+    spawn::player::Player,
+};
+
+const ROAD_SEED: u64 = 0x5EED_60AD;
+const ROAD_REGION: i32 = 20; // In cells; each region has one junction
+const JUNCTION_MARGIN: i32 = 4; // Junctions keep this far in from the edges of their region
+const PLAN_RADIUS: i32 = 2; // Regions around the player's whose roads are carved
+const DETOUR_MARGIN: i32 = ROAD_REGION; // How far past its two junctions a detour may stray
+const SIDE_OFFSETS: [(i32, i32); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<RoadNetwork>().add_systems(
+        Update,
+        plan_roads
+            .before(stamp_reserved_cells)
+            .run_if(simulation_running),
+    );
+}
+
+// Ground corridors reserved before the solver gets to them. Every junction is joined to the
+// ones in the regions east and north of it, so the roads form a single connected lattice no
+// matter how far out the world grows. Where the world was already generated, roads run over
+// the walkable cells already there and go around the rest.
+#[derive(Resource, Default)]
+pub struct RoadNetwork {
+    pub cells: HashSet<(i32, i32)>,
+    carved_regions: HashSet<(i32, i32)>, // Regions whose east and north roads are reserved
+    junctions: HashMap<(i32, i32), (i32, i32)>, // Where each region's roads meet, once settled
+}
+
+impl RoadNetwork {
+    // A junction buried under something generated moves to the nearest cell a road can
+    // reach, and stays there for every road that meets at it
+    fn settle_junction(
+        &mut self,
+        region: (i32, i32),
+        road_cell: impl Fn((i32, i32)) -> RoadCell,
+    ) -> (i32, i32) {
+        *self.junctions.entry(region).or_insert_with(|| {
+            let planned = junction(region);
+            let bounds = (
+                (region.0 * ROAD_REGION, region.1 * ROAD_REGION),
+                (
+                    (region.0 + 1) * ROAD_REGION - 1,
+                    (region.1 + 1) * ROAD_REGION - 1,
+                ),
+            );
+            shortest_route(
+                planned,
+                |position| road_cell(position) != RoadCell::Blocked,
+                bounds,
+                |_| RoadCell::Open,
+            )
+            .and_then(|route| route.last().copied())
+            .unwrap_or(planned)
+        })
+    }
+}
+
+// What a road can do with a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RoadCell {
+    Open,     // Nothing there yet; reserved as ground
+    Walkable, // Already walkable, so the road just runs over it
+    Blocked,  // Generated or promised as something that can't be walked
+}
+
+// Hashed rather than stored, so both ends of a road agree on where it starts and stops
+fn region_rng(region: (i32, i32)) -> StdRng {
+    let mut hash = ROAD_SEED;
+    for value in [region.0, region.1] {
+        hash = (hash ^ value as u32 as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        hash ^= hash >> 31;
+    }
+    StdRng::seed_from_u64(hash)
+}
+
+fn junction(region: (i32, i32)) -> (i32, i32) {
+    let mut rng = region_rng(region);
+    (
+        region.0 * ROAD_REGION + rng.random_range(JUNCTION_MARGIN..ROAD_REGION - JUNCTION_MARGIN),
+        region.1 * ROAD_REGION + rng.random_range(JUNCTION_MARGIN..ROAD_REGION - JUNCTION_MARGIN),
+    )
+}
+
+// A winding walk that only ever steps towards `to`, so consecutive cells share a side
+fn corridor(from: (i32, i32), to: (i32, i32), rng: &mut impl Rng) -> Vec<(i32, i32)> {
+    let mut position = from;
+    let mut cells = vec![position];
+    while position != to {
+        let (left_x, left_z) = ((to.0 - position.0).abs(), (to.1 - position.1).abs());
+        // Leaning towards the longer leg keeps the road from running out in an L
+        if rng.random_range(0..left_x + left_z) < left_x {
+            position.0 += (to.0 - position.0).signum();
+        } else {
+            position.1 += (to.1 - position.1).signum();
+        }
+        cells.push(position);
+    }
+    cells
+}
+
+// The first cell a search from `from` reaches that satisfies `goal`, by the shortest run of
+// side-sharing cells within `bounds` (lowest and highest corner)
+fn shortest_route(
+    from: (i32, i32),
+    goal: impl Fn((i32, i32)) -> bool,
+    bounds: ((i32, i32), (i32, i32)),
+    road_cell: impl Fn((i32, i32)) -> RoadCell,
+) -> Option<Vec<(i32, i32)>> {
+    let mut came_from = HashMap::from([(from, from)]);
+    let mut open = VecDeque::from([from]);
+    while let Some(position) = open.pop_front() {
+        if goal(position) {
+            let mut route = vec![position];
+            let mut position = position;
+            while position != from {
+                position = came_from[&position];
+                route.push(position);
+            }
+            route.reverse();
+            return Some(route);
+        }
+
+        for (dx, dz) in SIDE_OFFSETS {
+            let next = (position.0 + dx, position.1 + dz);
+            let inside = (bounds.0.0..=bounds.1.0).contains(&next.0)
+                && (bounds.0.1..=bounds.1.1).contains(&next.1);
+            if !inside || came_from.contains_key(&next) || road_cell(next) == RoadCell::Blocked {
+                continue;
+            }
+            came_from.insert(next, position);
+            open.push_back(next);
+        }
+    }
+    None
+}
+
+fn plan_roads(
+    mut network: ResMut<RoadNetwork>,
+    mut reservations: ResMut<PrefabReservations>,
+    spatial_index: Res<CellSpatialIndex>,
+    cache: Res<CollapsedTileCache>,
+    cells: Query<&Cell>,
+    player: Single<&Transform, With<Player>>,
+    settings: Res<GenerationSettings>,
+) {
+    let player_grid = world_to_grid(player.translation, settings.cell_edge_length as f32);
+    let player_region = (
+        player_grid.0.div_euclid(ROAD_REGION),
+        player_grid.1.div_euclid(ROAD_REGION),
+    );
+
+    // Cells that already exist can't be reserved any more, so only a collapsed walkable one
+    // will do; multi-cell tiles are landmarks in the way
+    let road_cell = |position: (i32, i32), reservations: &PrefabReservations| {
+        let tile = match spatial_index.ground(&position) {
+            Some(entity) => match cells.get(*entity) {
+                Ok(Cell {
+                    is_collapsed: true,
+                    tile_type: Some(tile),
+                    ..
+                }) => Some(*tile),
+                _ => return RoadCell::Blocked,
+            },
+            None => cache
+                .tiles
+                .get(&position)
+                .or_else(|| reservations.tiles.get(&position))
+                .copied(),
+        };
+        match tile {
+            _ if spatial_index.footprints.contains_key(&position) => RoadCell::Blocked,
+            Some(tile) if tile.metadata().walkable => RoadCell::Walkable,
+            Some(_) => RoadCell::Blocked,
+            None => RoadCell::Open,
+        }
+    };
+
+    for dx in -PLAN_RADIUS..=PLAN_RADIUS {
+        for dz in -PLAN_RADIUS..=PLAN_RADIUS {
+            let region = (player_region.0 + dx, player_region.1 + dz);
+            if !network.carved_regions.insert(region) {
+                continue;
+            }
+
+            let mut rng = region_rng(region);
+            let [start, east, north] = [region, (region.0 + 1, region.1), (region.0, region.1 + 1)]
+                .map(|at| {
+                    network.settle_junction(at, |position| road_cell(position, &reservations))
+                });
+            for end in [east, north] {
+                let winding = corridor(start, end, &mut rng);
+                let route = if winding
+                    .iter()
+                    .all(|position| road_cell(*position, &reservations) != RoadCell::Blocked)
+                {
+                    winding
+                } else {
+                    let bounds = (
+                        (
+                            start.0.min(end.0) - DETOUR_MARGIN,
+                            start.1.min(end.1) - DETOUR_MARGIN,
+                        ),
+                        (
+                            start.0.max(end.0) + DETOUR_MARGIN,
+                            start.1.max(end.1) + DETOUR_MARGIN,
+                        ),
+                    );
+                    shortest_route(
+                        start,
+                        |position| position == end,
+                        bounds,
+                        |position| road_cell(position, &reservations),
+                    )
+                    .unwrap_or_else(|| {
+                        warn!(
+                            "No way for a road from {:?} to {:?} around the generated world",
+                            start, end
+                        );
+                        winding
+                    })
+                };
+
+                for position in route {
+                    if road_cell(position, &reservations) == RoadCell::Open {
+                        reservations.tiles.insert(position, TileType::Ground);
+                    }
+                    network.cells.insert(position);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_route_out_of_a_walled_in_cell() {
+        let bounds = ((-10, -10), (10, 10));
+        let walled_in = |(x, z): (i32, i32)| {
+            if x.abs() + z.abs() == 1 {
+                RoadCell::Blocked
+            } else {
+                RoadCell::Open
+            }
+        };
+        assert_eq!(
+            shortest_route((0, 0), |position| position == (5, 0), bounds, walled_in),
+            None
+        );
+
+        // A single gap in a wall is enough to get around it
+        let gap = |(x, z): (i32, i32)| {
+            if x == 2 && z != 3 {
+                RoadCell::Blocked
+            } else {
+                RoadCell::Open
+            }
+        };
+        let route = shortest_route((0, 0), |position| position == (5, 0), bounds, gap).unwrap();
+        assert!(route.contains(&(2, 3)));
+        assert_eq!(route.last(), Some(&(5, 0)));
+    }
+
+    #[test]
+    fn neighboring_regions_meet_at_the_same_junction() {
+        let mut network = RoadNetwork::default();
+        let shared = (1, 0);
+        let planned = junction(shared);
+
+        // The west region plans its east road while the planned junction is built over
+        let buried = |position| {
+            if position == planned {
+                RoadCell::Blocked
+            } else {
+                RoadCell::Open
+            }
+        };
+        let from_west = network.settle_junction(shared, buried);
+        assert_ne!(from_west, planned);
+        assert_eq!(
+            (from_west.0 - planned.0).abs() + (from_west.1 - planned.1).abs(),
+            1
+        );
+
+        // By the time the region itself is planned the cell is clear again
+        let from_itself = network.settle_junction(shared, |_| RoadCell::Open);
+        assert_eq!(from_itself, from_west);
+    }
+}
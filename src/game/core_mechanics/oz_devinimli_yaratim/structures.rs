@@ -0,0 +1,179 @@
+use bevy::{platform::collections::HashSet, prelude::*};
+
+use crate::game::core_mechanics::{
+    oz_devinimli_yaratim::{
+        cells::{Cell, CellSpatialIndex},
+        odycore::open_space::CellCollapsed,
+        odyrules::commons::{DIRECTION_VECTORS, TileType},
+        prefabs::{SettlementCompleted, tile_bounds},
+    },
+    pause::simulation_running,
+};
+
+const MAX_REGION_CELLS: usize = 64; // Larger connected regions are left unannounced
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_event::<StructureCompleted>().add_systems(
+        Update,
+        (detect_completed_structures, forward_completed_settlements).run_if(simulation_running),
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StructureKind {
+    FountainPlaza,
+    Building, // A floor walled in all round, doors included
+    Village,
+}
+
+// A multi-cell structure has finished generating. Bounds are inclusive grid positions.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct StructureCompleted {
+    pub kind: StructureKind,
+    pub bounds: ((i32, i32), (i32, i32)),
+}
+
+// A structure recognized from its tiles: a connected region of `is_part` tiles, complete once
+// every cell around it has collapsed into something it `may_border`
+struct Shape {
+    kind: StructureKind,
+    is_part: fn(TileType) -> bool,
+    may_border: fn(TileType) -> bool,
+    walled: bool, // The border belongs to the structure, so the bounds take it in
+}
+
+const SHAPES: [Shape; 2] = [
+    Shape {
+        kind: StructureKind::FountainPlaza,
+        is_part: is_fountain,
+        may_border: |_| true,
+        walled: false,
+    },
+    Shape {
+        kind: StructureKind::Building,
+        is_part: |tile| matches!(tile, TileType::Floor | TileType::Chest),
+        may_border: |tile| matches!(tile, TileType::Wall | TileType::Door),
+        walled: true,
+    },
+];
+
+enum Region {
+    Closed(HashSet<(i32, i32)>),
+    Open, // Some edge is still uncollapsed or not loaded
+    Gone, // Not this structure any more, broken into, or too big to count as one
+}
+
+fn is_fountain(tile: TileType) -> bool {
+    matches!(
+        tile,
+        TileType::FountainCenter
+            | TileType::FountainCorner1
+            | TileType::FountainCorner2
+            | TileType::FountainCorner3
+            | TileType::FountainCorner4
+            | TileType::FountainEdge1
+            | TileType::FountainEdge2
+            | TileType::FountainEdge3
+            | TileType::FountainEdge4
+    )
+}
+
+// Pieces collapse one at a time, so a structure is done once its connected pieces are walled in
+// by collapsed cells. A fountain can border anything; a building only its own walls and doors,
+// so a road cutting through the wall leaves the house unannounced.
+fn detect_completed_structures(
+    mut collapsed: EventReader<CellCollapsed>,
+    mut completed: EventWriter<StructureCompleted>,
+    mut pending: Local<HashSet<(usize, (i32, i32))>>, // By index into SHAPES
+    mut announced: Local<HashSet<(StructureKind, (i32, i32))>>, // By the min corner of the bounds
+    cells: Query<&Cell>,
+    spatial_index: Res<CellSpatialIndex>,
+) {
+    for event in collapsed.read() {
+        for (index, shape) in SHAPES.iter().enumerate() {
+            if (shape.is_part)(event.tile) {
+                pending.insert((index, event.position));
+            }
+        }
+    }
+
+    let tile_at = |position: &(i32, i32)| {
+        spatial_index
+            .ground(position)
+            .and_then(|entity| cells.get(*entity).ok())
+            .map(|cell| cell.tile_type.filter(|_| cell.is_collapsed))
+    };
+
+    let starts: Vec<_> = pending.iter().copied().collect();
+    for (index, start) in starts {
+        if !pending.contains(&(index, start)) {
+            continue; // Part of a region already walked this frame
+        }
+        let shape = &SHAPES[index];
+        match walk_region(start, shape, &tile_at) {
+            Region::Open => {}
+            Region::Gone => {
+                pending.remove(&(index, start));
+            }
+            Region::Closed(region) => {
+                pending.retain(|(other, position)| *other != index || !region.contains(position));
+                let (min, max) = tile_bounds(region.iter());
+                let wall = i32::from(shape.walled);
+                let bounds = ((min.0 - wall, min.1 - wall), (max.0 + wall, max.1 + wall));
+                if announced.insert((shape.kind, bounds.0)) {
+                    completed.write(StructureCompleted {
+                        kind: shape.kind,
+                        bounds,
+                    });
+                }
+            }
+        }
+    }
+}
+
+// `tile_at` is None for unloaded cells and Some(None) for uncollapsed ones
+fn walk_region(
+    start: (i32, i32),
+    shape: &Shape,
+    tile_at: &impl Fn(&(i32, i32)) -> Option<Option<TileType>>,
+) -> Region {
+    if !tile_at(&start).flatten().is_some_and(shape.is_part) {
+        return Region::Gone;
+    }
+
+    let mut region = HashSet::from([start]);
+    let mut frontier = vec![start];
+    while let Some(position) = frontier.pop() {
+        for (_, (dx, dz)) in DIRECTION_VECTORS.iter() {
+            let neighbor = (position.0 + dx, position.1 + dz);
+            if region.contains(&neighbor) {
+                continue;
+            }
+            match tile_at(&neighbor) {
+                None | Some(None) => return Region::Open,
+                Some(Some(tile)) if (shape.is_part)(tile) => {
+                    if region.len() >= MAX_REGION_CELLS {
+                        return Region::Gone;
+                    }
+                    region.insert(neighbor);
+                    frontier.push(neighbor);
+                }
+                Some(Some(tile)) if (shape.may_border)(tile) => {}
+                Some(Some(_)) => return Region::Gone,
+            }
+        }
+    }
+    Region::Closed(region)
+}
+
+fn forward_completed_settlements(
+    mut settlements: EventReader<SettlementCompleted>,
+    mut completed: EventWriter<StructureCompleted>,
+) {
+    for settlement in settlements.read() {
+        completed.write(StructureCompleted {
+            kind: StructureKind::Village,
+            bounds: settlement.bounds,
+        });
+    }
+}
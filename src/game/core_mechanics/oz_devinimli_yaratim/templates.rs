@@ -0,0 +1,233 @@
+use std::{fs, time::Duration};
+
+use bevy::{
+    ecs::system::SystemParam, platform::collections::HashSet, prelude::*,
+    time::common_conditions::on_timer,
+};
+use rand::prelude::*;
+use serde::Deserialize;
+
+use crate::game::{
+    core_mechanics::{
+        oz_devinimli_yaratim::{
+            cells::{CellSpatialIndex, GenerationSettings},
+            grid::world_to_grid,
+            odyrules::{
+                biomes::{Biome, BiomeMap},
+                commons::TileType,
+            },
+            prefabs::{PrefabReservations, plan_settlements},
+            roads::RoadNetwork,
+        },
+        pause::simulation_running,
+    },
+    spawn::player::Player,
+    ui::console::{ConsoleCommand, ConsoleState},
+};
+
+pub const STRUCTURE_DIRECTORY: &str = "assets/structures";
+
+const SET_PIECE_REGION: i32 = 24; // In cells; each region gets at most one set piece
+const SET_PIECE_CHANCE: f64 = 0.3;
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(StructureTemplates::load_or_default())
+        .add_systems(
+            Update,
+            (
+                plan_set_pieces
+                    .after(plan_settlements)
+                    .run_if(on_timer(Duration::from_secs(1)).and(simulation_running)),
+                stamp_command,
+            ),
+        );
+}
+
+// A hand-drawn set piece, stamped into the world as is. Row n is z = n and column n is x = n
+// from the anchor; empty cells are left to the solver, which blends the piece into whatever
+// grows around it.
+#[derive(Deserialize, Debug, Clone)]
+pub struct StructureTemplate {
+    #[serde(skip)]
+    pub name: String, // The file stem
+    pub weight: f32,
+    #[serde(default)]
+    pub biomes: Vec<Biome>, // Where it can be planned; empty means anywhere
+    pub rows: Vec<Vec<Option<TileType>>>,
+}
+
+impl StructureTemplate {
+    // Grid positions and tiles of the piece with its anchor at `anchor`
+    pub fn tiles(&self, anchor: (i32, i32)) -> impl Iterator<Item = ((i32, i32), TileType)> {
+        self.rows.iter().enumerate().flat_map(move |(z, row)| {
+            row.iter().enumerate().filter_map(move |(x, tile)| {
+                tile.map(|tile| ((anchor.0 + x as i32, anchor.1 + z as i32), tile))
+            })
+        })
+    }
+
+    pub fn size(&self) -> (i32, i32) {
+        let width = self.rows.iter().map(Vec::len).max().unwrap_or(0);
+        (width as i32, self.rows.len() as i32)
+    }
+}
+
+#[derive(Resource, Debug, Clone, Default)]
+pub struct StructureTemplates {
+    pub templates: Vec<StructureTemplate>,
+}
+
+impl StructureTemplates {
+    pub fn load() -> Result<Self, String> {
+        let entries = fs::read_dir(STRUCTURE_DIRECTORY)
+            .map_err(|error| format!("{}: {}", STRUCTURE_DIRECTORY, error))?;
+
+        let mut templates = Vec::new();
+        for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+            if path.extension().is_none_or(|extension| extension != "ron") {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let text = fs::read_to_string(&path)
+                .map_err(|error| format!("{}: {}", path.display(), error))?;
+            let mut template: StructureTemplate =
+                ron::from_str(&text).map_err(|error| format!("{}: {}", path.display(), error))?;
+            template.name = name.to_string();
+            templates.push(template);
+        }
+        // read_dir has no set order; keeps the list `stamp` prints the same between runs
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(Self { templates })
+    }
+
+    fn load_or_default() -> Self {
+        Self::load().unwrap_or_else(|error| {
+            warn!(
+                "Failed to load structure templates, planning none: {}",
+                error
+            );
+            Self::default()
+        })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&StructureTemplate> {
+        self.templates.iter().find(|template| template.name == name)
+    }
+}
+
+// Ground a template can be stamped on: not generated yet, and not promised to another prefab
+#[derive(SystemParam)]
+pub struct StampSite<'w> {
+    pub reservations: ResMut<'w, PrefabReservations>,
+    pub spatial_index: Res<'w, CellSpatialIndex>,
+    pub roads: Res<'w, RoadNetwork>,
+}
+
+impl StampSite<'_> {
+    // Reserves the piece's cells so they spawn already collapsed; false if any are taken
+    pub fn reserve(&mut self, template: &StructureTemplate, anchor: (i32, i32)) -> bool {
+        let taken = |position: &(i32, i32)| {
+            self.spatial_index.ground(position).is_some()
+                || self.reservations.tiles.contains_key(position)
+        };
+        if template.tiles(anchor).any(|(position, _)| taken(&position)) {
+            return false;
+        }
+
+        // Like settlements, only walkable tiles are laid over roads
+        let roads = &self.roads.cells;
+        self.reservations.tiles.extend(
+            template
+                .tiles(anchor)
+                .filter(|(position, tile)| tile.metadata().walkable || !roads.contains(position)),
+        );
+        true
+    }
+}
+
+fn plan_set_pieces(
+    mut site: StampSite,
+    mut planned_regions: Local<HashSet<(i32, i32)>>,
+    templates: Res<StructureTemplates>,
+    biomes: Res<BiomeMap>,
+    player: Single<&Transform, With<Player>>,
+    settings: Res<GenerationSettings>,
+) {
+    let player_grid = world_to_grid(player.translation, settings.cell_edge_length as f32);
+    let player_region = (
+        player_grid.0.div_euclid(SET_PIECE_REGION),
+        player_grid.1.div_euclid(SET_PIECE_REGION),
+    );
+    let mut rng = rand::rng();
+
+    for dx in -1..=1 {
+        for dz in -1..=1 {
+            let region = (player_region.0 + dx, player_region.1 + dz);
+            if !planned_regions.insert(region) || !rng.random_bool(SET_PIECE_CHANCE) {
+                continue;
+            }
+
+            let corner = (region.0 * SET_PIECE_REGION, region.1 * SET_PIECE_REGION);
+            let biome = biomes.biome_at((
+                corner.0 + SET_PIECE_REGION / 2,
+                corner.1 + SET_PIECE_REGION / 2,
+            ));
+            let Ok(template) = templates
+                .templates
+                .iter()
+                .filter(|template| template.biomes.is_empty() || template.biomes.contains(&biome))
+                .collect::<Vec<_>>()
+                .choose_weighted(&mut rng, |template| template.weight)
+                .copied()
+            else {
+                continue;
+            };
+
+            let size = template.size();
+            let anchor = (
+                corner.0 + rng.random_range(0..=(SET_PIECE_REGION - size.0).max(0)),
+                corner.1 + rng.random_range(0..=(SET_PIECE_REGION - size.1).max(0)),
+            );
+            if site.reserve(template, anchor) {
+                println!("🏛️ Planned {} at {:?}", template.name, anchor);
+            }
+        }
+    }
+}
+
+// `stamp <template> <x> <z>` reserves a template with its anchor on the given grid cell
+fn stamp_command(
+    mut commands_in: EventReader<ConsoleCommand>,
+    mut console: ResMut<ConsoleState>,
+    mut site: StampSite,
+    templates: Res<StructureTemplates>,
+) {
+    for command in commands_in.read().filter(|c| c.name == "stamp") {
+        let args: Vec<&str> = command.args.iter().map(String::as_str).collect();
+        let [name, x, z] = args.as_slice() else {
+            console.print("usage: stamp <template> <x> <z>");
+            continue;
+        };
+        let (Ok(x), Ok(z)) = (x.parse::<i32>(), z.parse::<i32>()) else {
+            console.print("usage: stamp <template> <x> <z>");
+            continue;
+        };
+        let Some(template) = templates.get(name) else {
+            let names: Vec<&str> = templates
+                .templates
+                .iter()
+                .map(|t| t.name.as_str())
+                .collect();
+            console.print(format!("no template {}; have {}", name, names.join(", ")));
+            continue;
+        };
+
+        if site.reserve(template, (x, z)) {
+            console.print(format!("{} will generate at ({}, {})", name, x, z));
+        } else {
+            console.print("some of those cells are already generated or reserved");
+        }
+    }
+}
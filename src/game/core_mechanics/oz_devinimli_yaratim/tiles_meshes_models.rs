@@ -1,17 +1,47 @@
-use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+use bevy::{
+    asset::RenderAssetUsages,
+    image::{ImageAddressMode, ImageSampler, ImageSamplerDescriptor},
+    math::Affine2,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
 
 use crate::game::core_mechanics::oz_devinimli_yaratim::{
     cells::{Cell, GenerationSettings},
-    odyrules::commons::TileType,
+    elevation::{ElevationMap, LEVEL_HEIGHT, cell_lift},
+    odyrules::{
+        commons::{BaseTile, TileType},
+        open_space_rules::OpenSpaceRules,
+    },
 };
 
 pub const _GROUND: [f32; 3] = [4.8, 0.1, 4.8];
 pub const _CORNER: [f32; 3] = [4.8, 5.0, 4.8];
 pub const _CHEST: [f32; 3] = [1.5, 0.8, 1.0]; 
 
+const GROUND_MODEL: &str = "models/road.glb";
+const TREE_MODEL: &str = "models/tree.glb";
+const CHEST_MODEL: &str = "models/rockWide.glb";
+const FOUNTAIN_CENTER_MODEL: &str = "models/fountainCenter.glb";
+const FOUNTAIN_CORNER_MODEL: &str = "models/fountainCorner.glb";
+const FOUNTAIN_EDGE_MODEL: &str = "models/fountainEdge.glb";
+pub const TILE_MODEL_PATHS: [&str; 6] = [
+    GROUND_MODEL,
+    TREE_MODEL,
+    CHEST_MODEL,
+    FOUNTAIN_CENTER_MODEL,
+    FOUNTAIN_CORNER_MODEL,
+    FOUNTAIN_EDGE_MODEL,
+];
+
+const RIPPLE_TEXTURE_SIZE: u32 = 64;
+const WATER_FLOW_SPEED: f32 = 0.15; // Ripple texture lengths per second
+
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(Startup, setup_tile_resources) 
-        .add_systems(Update, update_tile_visuals);
+        .add_systems(Update, (update_tile_visuals, animate_water));
 }
 
 #[derive(Resource)]
@@ -24,32 +54,145 @@ pub struct TileModels {
     pub fountain_edge: Handle<Scene>,   
 }
 
-fn setup_tile_resources(mut commands: Commands, asset_server: Res<AssetServer>) {
+#[derive(Resource)]
+pub struct TileMeshes {
+    pub water_mesh: Handle<Mesh>,
+    pub water_material: Handle<StandardMaterial>,
+    pub block_mesh: Handle<Mesh>,
+    pub wall_material: Handle<StandardMaterial>,
+    pub door_material: Handle<StandardMaterial>,
+    pub floor_material: Handle<StandardMaterial>,
+    pub path_material: Handle<StandardMaterial>,
+    pub bridge_material: Handle<StandardMaterial>,
+    pub cliff_material: Handle<StandardMaterial>,
+    pub slope_material: Handle<StandardMaterial>,
+}
+
+fn setup_tile_resources(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut mesh_assets: ResMut<Assets<Mesh>>,
+    mut material_assets: ResMut<Assets<StandardMaterial>>,
+    mut image_assets: ResMut<Assets<Image>>,
+) {
     let tile_models = TileModels {
-        ground: asset_server.load(GltfAssetLabel::Scene(0).from_asset("models/road.glb")),
-        tree: asset_server.load(GltfAssetLabel::Scene(0).from_asset("models/tree.glb")),
-        chest: asset_server.load(GltfAssetLabel::Scene(0).from_asset("models/rockWide.glb")),
+        ground: asset_server.load(GltfAssetLabel::Scene(0).from_asset(GROUND_MODEL)),
+        tree: asset_server.load(GltfAssetLabel::Scene(0).from_asset(TREE_MODEL)),
+        chest: asset_server.load(GltfAssetLabel::Scene(0).from_asset(CHEST_MODEL)),
         fountain_center: asset_server
-            .load(GltfAssetLabel::Scene(0).from_asset("models/fountainCenter.glb")),
+            .load(GltfAssetLabel::Scene(0).from_asset(FOUNTAIN_CENTER_MODEL)),
         fountain_corner: asset_server
-            .load(GltfAssetLabel::Scene(0).from_asset("models/fountainCorner.glb")),
-        fountain_edge: asset_server
-            .load(GltfAssetLabel::Scene(0).from_asset("models/fountainEdge.glb")),
+            .load(GltfAssetLabel::Scene(0).from_asset(FOUNTAIN_CORNER_MODEL)),
+        fountain_edge: asset_server.load(GltfAssetLabel::Scene(0).from_asset(FOUNTAIN_EDGE_MODEL)),
     };
 
     commands.insert_resource(tile_models);
+
+    commands.insert_resource(TileMeshes {
+        water_mesh: mesh_assets.add(Plane3d::default().mesh().size(1.0, 1.0)),
+        water_material: material_assets.add(StandardMaterial {
+            base_color: Color::srgba(0.05, 0.2, 0.45, 0.85),
+            base_color_texture: Some(image_assets.add(ripple_texture())),
+            alpha_mode: AlphaMode::Blend,
+            perceptual_roughness: 0.1,
+            ..Default::default()
+        }),
+        block_mesh: mesh_assets.add(Cuboid::new(1.0, 1.0, 1.0)),
+        wall_material: material_assets.add(StandardMaterial {
+            base_color: Color::srgb(0.55, 0.5, 0.45),
+            ..Default::default()
+        }),
+        door_material: material_assets.add(StandardMaterial {
+            base_color: Color::srgb(0.35, 0.2, 0.1),
+            ..Default::default()
+        }),
+        floor_material: material_assets.add(StandardMaterial {
+            base_color: Color::srgb(0.5, 0.35, 0.2),
+            ..Default::default()
+        }),
+        path_material: material_assets.add(StandardMaterial {
+            base_color: Color::srgb(0.6, 0.5, 0.35),
+            ..Default::default()
+        }),
+        bridge_material: material_assets.add(StandardMaterial {
+            base_color: Color::srgb(0.45, 0.3, 0.15),
+            ..Default::default()
+        }),
+        cliff_material: material_assets.add(StandardMaterial {
+            base_color: Color::srgb(0.4, 0.37, 0.33),
+            perceptual_roughness: 0.95,
+            ..Default::default()
+        }),
+        slope_material: material_assets.add(StandardMaterial {
+            base_color: Color::srgb(0.42, 0.4, 0.25),
+            ..Default::default()
+        }),
+    });
+}
+
+// Tiling ripples for the water material to tint; whole periods on both axes so it wraps
+fn ripple_texture() -> Image {
+    let size = RIPPLE_TEXTURE_SIZE;
+    let mut data = Vec::with_capacity((size * size * 4) as usize);
+    for y in 0..size {
+        for x in 0..size {
+            let (u, v) = (x as f32 / size as f32 * TAU, y as f32 / size as f32 * TAU);
+            let ripple = 0.5 + 0.25 * (3.0 * v + u.sin()).sin() + 0.25 * (2.0 * u + v).sin();
+            let shade = (140.0 + 115.0 * ripple) as u8;
+            data.extend([shade, shade, 255, 255]);
+        }
+    }
+
+    let mut image = Image::new(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+        address_mode_u: ImageAddressMode::Repeat,
+        address_mode_v: ImageAddressMode::Repeat,
+        ..ImageSamplerDescriptor::linear()
+    });
+    image
+}
+
+// Scrolls the ripples along the texture's v axis, which river pieces turn to follow the flow
+fn animate_water(
+    time: Res<Time>,
+    tile_meshes: Option<Res<TileMeshes>>,
+    mut material_assets: ResMut<Assets<StandardMaterial>>,
+) {
+    let Some(material) =
+        tile_meshes.and_then(|meshes| material_assets.get_mut(&meshes.water_material))
+    else {
+        return;
+    };
+    let offset = (time.elapsed_secs() * WATER_FLOW_SPEED).fract();
+    material.uv_transform = Affine2::from_translation(Vec2::new(0.0, -offset));
 }
 
-fn update_tile_visuals(
+pub fn update_tile_visuals(
     mut commands: Commands,
     changed_cells: Query<(Entity, &Cell, &Transform), Changed<Cell>>, 
     tile_models: Res<TileModels>,
+    tile_meshes: Res<TileMeshes>,
     settings: Res<GenerationSettings>,
+    rules: Res<OpenSpaceRules>,
+    elevation: Res<ElevationMap>,
 ) {
+    let _span = info_span!("tile_visuals").entered();
+
     for (entity, cell, transform) in changed_cells.iter() {
+        let lift = cell_lift(cell);
         if let Some(tile_type) = cell.tile_type {
             match tile_type {
-                TileType::Ground => {
+                TileType::Ground | TileType::Covered => {
                     let transform = Transform::from_translation(Vec3::new(
                         0.0 + transform.translation.x, 
                         0.0,                           
@@ -65,10 +208,42 @@ fn update_tile_visuals(
                         .insert((SceneRoot(tile_models.ground.clone()), transform));
                 }
 
+                // Ground strewn with a few stones, turned per cell so fallback patches don't repeat
+                TileType::Rubble => {
+                    let transform = Transform::from_translation(transform.translation.with_y(0.0))
+                        .with_scale(Vec3::splat(settings.cell_edge_length as f32));
+                    let (x, z) = cell.position;
+                    let turn = (x.wrapping_mul(73_856_093) ^ z.wrapping_mul(19_349_663))
+                        .rem_euclid(360) as f32;
+                    let mut tile = commands.entity(entity);
+                    tile.insert((SceneRoot(tile_models.ground.clone()), transform));
+                    for (offset, size) in [
+                        (Vec2::new(-0.25, 0.2), 0.22),
+                        (Vec2::new(0.2, -0.15), 0.3),
+                        (Vec2::new(0.3, 0.3), 0.15),
+                    ] {
+                        let rotation = Quat::from_rotation_y((turn + size * 300.0).to_radians());
+                        tile.with_child((
+                            Mesh3d(tile_meshes.block_mesh.clone()),
+                            MeshMaterial3d(tile_meshes.wall_material.clone()),
+                            Transform::from_translation(
+                                Quat::from_rotation_y(turn.to_radians())
+                                    * Vec3::new(offset.x, size * 0.25, offset.y),
+                            )
+                            .with_rotation(rotation)
+                            .with_scale(Vec3::new(
+                                size,
+                                size * 0.5,
+                                size * 0.8,
+                            )),
+                        ));
+                    }
+                }
+
                 TileType::Tree => {
                     let transform = Transform::from_translation(Vec3::new(
                         0.0 + transform.translation.x,
-                        0.0,
+                        lift,
                         0.0 + transform.translation.z,
                     ))
                     .with_scale(Vec3 {
@@ -84,7 +259,7 @@ fn update_tile_visuals(
                 TileType::Chest => {
                     let transform = Transform::from_translation(Vec3::new(
                         0.0 + transform.translation.x,
-                        0.0,
+                        lift,
                         0.0 + transform.translation.z,
                     ))
                     .with_scale(Vec3 {
@@ -100,7 +275,7 @@ fn update_tile_visuals(
                 TileType::FountainCenter => {
                     let transform = Transform::from_translation(Vec3::new(
                         0.0 + transform.translation.x,
-                        0.0,
+                        lift,
                         0.0 + transform.translation.z,
                     ))
                     .with_scale(Vec3 {
@@ -113,10 +288,28 @@ fn update_tile_visuals(
                         .insert((SceneRoot(tile_models.fountain_center.clone()), transform));
                 }
 
-                TileType::FountainCorner1 => {
+                // One model per base piece, turned to the variant's rotation
+                TileType::FountainCorner1
+                | TileType::FountainCorner2
+                | TileType::FountainCorner3
+                | TileType::FountainCorner4
+                | TileType::FountainEdge1
+                | TileType::FountainEdge2
+                | TileType::FountainEdge3
+                | TileType::FountainEdge4 => {
+                    let Some(variant) = tile_type.variant() else {
+                        continue;
+                    };
+                    let model = match variant.base {
+                        BaseTile::FountainCorner => tile_models.fountain_corner.clone(),
+                        BaseTile::FountainEdge => tile_models.fountain_edge.clone(),
+                        BaseTile::RiverStraight | BaseTile::RiverBend | BaseTile::Bridge => {
+                            continue;
+                        }
+                    };
                     let transform = Transform::from_translation(Vec3::new(
                         0.0 + transform.translation.x,
-                        0.0,
+                        lift,
                         0.0 + transform.translation.z,
                     ))
                     .with_scale(Vec3 {
@@ -124,129 +317,241 @@ fn update_tile_visuals(
                         y: settings.cell_edge_length as f32,
                         z: settings.cell_edge_length as f32,
                     })
-                    .with_rotation(Quat::from_rotation_y(0.5 * std::f32::consts::PI)); // 90° rotation
+                    .with_rotation(Quat::from_rotation_y(variant.angle()));
                     commands
                         .entity(entity)
-                        .insert((SceneRoot(tile_models.fountain_corner.clone()), transform));
+                        .insert((SceneRoot(model), transform));
                 }
 
-                TileType::FountainCorner2 => {
+                TileType::ThornBush => {
                     let transform = Transform::from_translation(Vec3::new(
                         0.0 + transform.translation.x,
-                        0.0,
+                        lift,
                         0.0 + transform.translation.z,
                     ))
                     .with_scale(Vec3 {
-                        x: settings.cell_edge_length as f32,
-                        y: settings.cell_edge_length as f32,
-                        z: settings.cell_edge_length as f32,
-                    })
-                    .with_rotation(Quat::from_rotation_y(1.0 * std::f32::consts::PI)); // 180° rotation
+                        x: settings.cell_edge_length as f32 * 0.6, // Low, wide shrub
+                        y: settings.cell_edge_length as f32 * 0.3,
+                        z: settings.cell_edge_length as f32 * 0.6,
+                    });
                     commands
                         .entity(entity)
-                        .insert((SceneRoot(tile_models.fountain_corner.clone()), transform));
+                        .insert((SceneRoot(tile_models.tree.clone()), transform));
                 }
 
-                TileType::FountainCorner3 => {
+                TileType::DeepWater => {
                     let transform = Transform::from_translation(Vec3::new(
                         0.0 + transform.translation.x,
-                        0.0,
+                        lift + 0.05,
                         0.0 + transform.translation.z,
                     ))
                     .with_scale(Vec3 {
                         x: settings.cell_edge_length as f32,
-                        y: settings.cell_edge_length as f32,
+                        y: 1.0,
                         z: settings.cell_edge_length as f32,
-                    }); // No rotation (0°)
-                    commands
-                        .entity(entity)
-                        .insert((SceneRoot(tile_models.fountain_corner.clone()), transform));
+                    });
+                    commands.entity(entity).insert((
+                        Mesh3d(tile_meshes.water_mesh.clone()),
+                        MeshMaterial3d(tile_meshes.water_material.clone()),
+                        transform,
+                    ));
                 }
 
-                TileType::FountainCorner4 => {
+                // Rivers share the water plane, turned so the ripples run with the flow
+                TileType::Water
+                | TileType::RiverStraight1
+                | TileType::RiverStraight2
+                | TileType::RiverBend1
+                | TileType::RiverBend2
+                | TileType::RiverBend3
+                | TileType::RiverBend4
+                | TileType::Bridge1
+                | TileType::Bridge2 => {
+                    let angle = tile_type.variant().map_or(0.0, |variant| variant.angle());
                     let transform = Transform::from_translation(Vec3::new(
                         0.0 + transform.translation.x,
-                        0.0,
+                        lift + 0.05,
                         0.0 + transform.translation.z,
                     ))
                     .with_scale(Vec3 {
                         x: settings.cell_edge_length as f32,
-                        y: settings.cell_edge_length as f32,
+                        y: 1.0,
                         z: settings.cell_edge_length as f32,
                     })
-                    .with_rotation(Quat::from_rotation_y(1.5 * std::f32::consts::PI)); // 270° rotation
-                    commands
-                        .entity(entity)
-                        .insert((SceneRoot(tile_models.fountain_corner.clone()), transform));
+                    .with_rotation(Quat::from_rotation_y(angle));
+                    let mut tile = commands.entity(entity);
+                    tile.insert((
+                        Mesh3d(tile_meshes.water_mesh.clone()),
+                        MeshMaterial3d(tile_meshes.water_material.clone()),
+                        transform,
+                    ));
+
+                    // Planks from bank to bank, across the flow
+                    if matches!(tile_type, TileType::Bridge1 | TileType::Bridge2) {
+                        tile.with_child((
+                            Mesh3d(tile_meshes.block_mesh.clone()),
+                            MeshMaterial3d(tile_meshes.bridge_material.clone()),
+                            Transform::from_xyz(0.0, 0.2, 0.0)
+                                .with_scale(Vec3::new(1.0, 0.4, 0.35)),
+                        ));
+                    }
                 }
 
-                TileType::FountainEdge1 => {
-                    let transform = Transform::from_translation(Vec3::new(
-                        0.0 + transform.translation.x,
-                        0.0,
-                        0.0 + transform.translation.z,
-                    ))
-                    .with_scale(Vec3 {
-                        x: settings.cell_edge_length as f32,
-                        y: settings.cell_edge_length as f32,
-                        z: settings.cell_edge_length as f32,
-                    }); // No rotation (0°)
-                    commands
-                        .entity(entity)
-                        .insert((SceneRoot(tile_models.fountain_edge.clone()), transform));
+                // Step tiles stand on the upper height and reach down to the lower one, whose
+                // top is a full level below the cell's own
+                TileType::Cliff => {
+                    let transform = Transform::from_translation(
+                        transform.translation.with_y(lift - LEVEL_HEIGHT / 2.0),
+                    )
+                    .with_scale(Vec3::new(
+                        settings.cell_edge_length as f32,
+                        LEVEL_HEIGHT,
+                        settings.cell_edge_length as f32,
+                    ));
+                    commands.entity(entity).insert((
+                        Mesh3d(tile_meshes.block_mesh.clone()),
+                        MeshMaterial3d(tile_meshes.cliff_material.clone()),
+                        transform,
+                    ));
+                }
+
+                // A bank halfway up, to climb in one go
+                TileType::Slope => {
+                    let transform = Transform::from_translation(
+                        transform.translation.with_y(lift - LEVEL_HEIGHT * 0.75),
+                    )
+                    .with_scale(Vec3::new(
+                        settings.cell_edge_length as f32,
+                        LEVEL_HEIGHT / 2.0,
+                        settings.cell_edge_length as f32,
+                    ));
+                    commands.entity(entity).insert((
+                        Mesh3d(tile_meshes.block_mesh.clone()),
+                        MeshMaterial3d(tile_meshes.slope_material.clone()),
+                        transform,
+                    ));
+                }
+
+                // Two half-level steps going down towards the lower neighbor; the cell keeps its
+                // own transform, lifted on spawn
+                TileType::Stairs => {
+                    let edge = settings.cell_edge_length as f32;
+                    // Laid out going down towards -Z, then turned
+                    let facing = elevation
+                        .downhill(cell.position)
+                        .map_or(Quat::IDENTITY, |(dx, dz)| {
+                            Quat::from_rotation_y((-dx as f32).atan2(-dz as f32))
+                        });
+                    for (y, z, depth) in [(-0.75, 0.0, 1.0), (-0.25, 0.25, 0.5)] {
+                        commands.entity(entity).with_child((
+                            Mesh3d(tile_meshes.block_mesh.clone()),
+                            MeshMaterial3d(tile_meshes.path_material.clone()),
+                            Transform::from_translation(
+                                facing * Vec3::new(0.0, y * LEVEL_HEIGHT, z * edge),
+                            )
+                            .with_rotation(facing)
+                            .with_scale(Vec3::new(
+                                edge,
+                                LEVEL_HEIGHT / 2.0,
+                                edge * depth,
+                            )),
+                        ));
+                    }
                 }
 
-                TileType::FountainEdge2 => {
+                TileType::Wall => {
+                    let height = settings.cell_edge_length as f32 * 0.6;
                     let transform = Transform::from_translation(Vec3::new(
                         0.0 + transform.translation.x,
-                        0.0,
+                        lift + height / 2.0,
                         0.0 + transform.translation.z,
                     ))
                     .with_scale(Vec3 {
                         x: settings.cell_edge_length as f32,
-                        y: settings.cell_edge_length as f32,
+                        y: height,
                         z: settings.cell_edge_length as f32,
-                    })
-                    .with_rotation(Quat::from_rotation_y(0.5 * std::f32::consts::PI)); // 90° rotation
-                    commands
-                        .entity(entity)
-                        .insert((SceneRoot(tile_models.fountain_edge.clone()), transform));
+                    });
+                    commands.entity(entity).insert((
+                        Mesh3d(tile_meshes.block_mesh.clone()),
+                        MeshMaterial3d(tile_meshes.wall_material.clone()),
+                        transform,
+                    ));
                 }
 
-                TileType::FountainEdge3 => {
+                TileType::Door | TileType::Floor | TileType::Path => {
+                    let material = match tile_type {
+                        TileType::Door => tile_meshes.door_material.clone(),
+                        TileType::Floor => tile_meshes.floor_material.clone(),
+                        _ => tile_meshes.path_material.clone(),
+                    };
                     let transform = Transform::from_translation(Vec3::new(
                         0.0 + transform.translation.x,
-                        0.0,
+                        lift + 0.02,
                         0.0 + transform.translation.z,
                     ))
                     .with_scale(Vec3 {
                         x: settings.cell_edge_length as f32,
-                        y: settings.cell_edge_length as f32,
+                        y: 1.0,
                         z: settings.cell_edge_length as f32,
-                    })
-                    .with_rotation(Quat::from_rotation_y(1.5 * std::f32::consts::PI)); // 270° rotation
-                    commands
-                        .entity(entity)
-                        .insert((SceneRoot(tile_models.fountain_edge.clone()), transform));
+                    });
+                    commands.entity(entity).insert((
+                        Mesh3d(tile_meshes.water_mesh.clone()),
+                        MeshMaterial3d(material),
+                        transform,
+                    ));
                 }
 
-                TileType::FountainEdge4 => {
+                TileType::Well => {
                     let transform = Transform::from_translation(Vec3::new(
                         0.0 + transform.translation.x,
-                        0.0,
+                        lift,
                         0.0 + transform.translation.z,
                     ))
                     .with_scale(Vec3 {
-                        x: settings.cell_edge_length as f32,
-                        y: settings.cell_edge_length as f32,
-                        z: settings.cell_edge_length as f32,
-                    })
-                    .with_rotation(Quat::from_rotation_y(1.0 * std::f32::consts::PI)); // 180° rotation
+                        x: settings.cell_edge_length as f32 * 0.5, // Small fountain basin
+                        y: settings.cell_edge_length as f32 * 0.5,
+                        z: settings.cell_edge_length as f32 * 0.5,
+                    });
                     commands
                         .entity(entity)
-                        .insert((SceneRoot(tile_models.fountain_edge.clone()), transform));
+                        .insert((SceneRoot(tile_models.fountain_center.clone()), transform));
+                }
+
+                // Multi-cell tiles are drawn on a child centered on the footprint, so the
+                // anchor cell keeps its own grid position
+                TileType::LargeTree => {
+                    let (width, depth) = rules.footprint(tile_type);
+                    let edge = settings.cell_edge_length as f32;
+                    commands.entity(entity).with_child((
+                        SceneRoot(tile_models.tree.clone()),
+                        Transform::from_translation(footprint_offset(width, depth, edge))
+                            .with_scale(Vec3::splat(edge * width.min(depth) as f32)),
+                    ));
+                }
+
+                TileType::RuinEntrance => {
+                    let (width, depth) = rules.footprint(tile_type);
+                    let edge = settings.cell_edge_length as f32;
+                    let height = edge * 0.3; // Low, half-sunken stones
+                    commands.entity(entity).with_child((
+                        Mesh3d(tile_meshes.block_mesh.clone()),
+                        MeshMaterial3d(tile_meshes.wall_material.clone()),
+                        Transform::from_translation(
+                            footprint_offset(width, depth, edge).with_y(height / 2.0),
+                        )
+                        .with_scale(Vec3::new(edge * width as f32, height, edge * depth as f32)),
+                    ));
                 }
             };
         }
     }
 }
+
+// From the anchor cell's center to the center of the whole footprint
+fn footprint_offset(width: i32, depth: i32, edge: f32) -> Vec3 {
+    Vec3::new(
+        (width - 1) as f32 * edge / 2.0,
+        0.0,
+        (depth - 1) as f32 * edge / 2.0,
+    )
+}
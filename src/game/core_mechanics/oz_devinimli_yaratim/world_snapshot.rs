@@ -0,0 +1,367 @@
+use std::fs;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::game::{
+    core_mechanics::oz_devinimli_yaratim::{
+        cells::{Cell, CellSpatialIndex, CollapsedTileCache, GenerationSettings},
+        elevation::ElevationMap,
+        grid::world_to_grid,
+        odyrules::{biomes::BiomeMap, commons::TileType},
+    },
+    save::write_atomically,
+    spawn::player::Player,
+    ui::console::{ConsoleCommand, ConsoleState},
+};
+
+const SNAPSHOT_DIRECTORY: &str = "snapshots";
+const DEFAULT_RADIUS: i32 = 16; // In cells
+const UNSOLVED_GLYPH: char = '?'; // Not collapsed yet, or never generated
+const HEADER: &str = "# world snapshot";
+const CHANGES_SHOWN: usize = 5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, snapshot_command);
+}
+
+// The collapsed ground-floor tiles of a square of cells, one glyph each, along with the seeds
+// that shaped them. Saved as text so two snapshots can be read and diffed by hand too.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldSnapshot {
+    pub center: (i32, i32),
+    pub radius: i32,
+    pub biome_seed: u64,
+    pub elevation_seed: u64,
+    rows: Vec<Vec<Option<TileType>>>, // Row n is z = center.1 - radius + n, likewise columns
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TileChange {
+    pub position: (i32, i32),
+    pub before: TileType,
+    pub after: TileType,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotDiff {
+    pub compared: usize, // Cells collapsed in both; the rest can't tell a change apart
+    pub changes: Vec<TileChange>,
+    pub seeds_differ: bool,
+}
+
+impl WorldSnapshot {
+    pub fn capture(
+        center: (i32, i32),
+        radius: i32,
+        (biome_seed, elevation_seed): (u64, u64),
+        tile_at: impl Fn((i32, i32)) -> Option<TileType>,
+    ) -> Self {
+        let rows = (center.1 - radius..=center.1 + radius)
+            .map(|z| {
+                (center.0 - radius..=center.0 + radius)
+                    .map(|x| tile_at((x, z)))
+                    .collect()
+            })
+            .collect();
+        Self {
+            center,
+            radius,
+            biome_seed,
+            elevation_seed,
+            rows,
+        }
+    }
+
+    fn tiles(&self) -> impl Iterator<Item = ((i32, i32), Option<TileType>)> + '_ {
+        let corner = (self.center.0 - self.radius, self.center.1 - self.radius);
+        self.rows.iter().enumerate().flat_map(move |(z, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, tile)| ((corner.0 + x as i32, corner.1 + z as i32), *tile))
+        })
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "{}\n# center {} {} radius {} biome_seed {} elevation_seed {}\n",
+            HEADER, self.center.0, self.center.1, self.radius, self.biome_seed, self.elevation_seed
+        );
+        for row in self.rows.iter() {
+            text.extend(
+                row.iter()
+                    .map(|tile| tile.map_or(UNSOLVED_GLYPH, TileType::glyph)),
+            );
+            text.push('\n');
+        }
+        text
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines();
+        if lines.next() != Some(HEADER) {
+            return Err("not a world snapshot".to_string());
+        }
+        let fields: Vec<&str> = lines
+            .next()
+            .and_then(|line| line.strip_prefix('#'))
+            .ok_or("missing the center and seeds line")?
+            .split_whitespace()
+            .collect();
+        let field = |name: &str, offset: usize| {
+            fields
+                .iter()
+                .position(|field| *field == name)
+                .and_then(|index| fields.get(index + 1 + offset))
+                .ok_or(format!("missing {}", name))
+        };
+        let number = |name: &str, offset: usize| -> Result<i64, String> {
+            field(name, offset)?
+                .parse()
+                .map_err(|error| format!("{}: {}", name, error))
+        };
+
+        let center = (number("center", 0)? as i32, number("center", 1)? as i32);
+        let radius = number("radius", 0)? as i32;
+        let seed = |name: &str| -> Result<u64, String> {
+            field(name, 0)?
+                .parse()
+                .map_err(|error| format!("{}: {}", name, error))
+        };
+        let (biome_seed, elevation_seed) = (seed("biome_seed")?, seed("elevation_seed")?);
+
+        let width = (radius * 2 + 1) as usize;
+        let rows = lines
+            .enumerate()
+            .map(|(z, line)| {
+                let row = line
+                    .chars()
+                    .map(|glyph| match glyph {
+                        UNSOLVED_GLYPH => Ok(None),
+                        glyph => TileType::from_glyph(glyph)
+                            .map(Some)
+                            .ok_or(format!("unknown tile '{}' in row {}", glyph, z)),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                if row.len() != width {
+                    return Err(format!("row {} is {} wide, not {}", z, row.len(), width));
+                }
+                Ok(row)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if rows.len() != width {
+            return Err(format!("{} rows, not {}", rows.len(), width));
+        }
+
+        Ok(Self {
+            center,
+            radius,
+            biome_seed,
+            elevation_seed,
+            rows,
+        })
+    }
+
+    // Only for snapshots of the same square; comparing different seeds is allowed but flagged,
+    // since every cell is then expected to differ
+    pub fn diff(&self, other: &WorldSnapshot) -> Result<SnapshotDiff, String> {
+        if (self.center, self.radius) != (other.center, other.radius) {
+            return Err(format!(
+                "different areas: {:?} radius {} and {:?} radius {}",
+                self.center, self.radius, other.center, other.radius
+            ));
+        }
+
+        let mut diff = SnapshotDiff {
+            seeds_differ: (self.biome_seed, self.elevation_seed)
+                != (other.biome_seed, other.elevation_seed),
+            ..Default::default()
+        };
+        for ((position, before), (_, after)) in self.tiles().zip(other.tiles()) {
+            let (Some(before), Some(after)) = (before, after) else {
+                continue;
+            };
+            diff.compared += 1;
+            if before != after {
+                diff.changes.push(TileChange {
+                    position,
+                    before,
+                    after,
+                });
+            }
+        }
+        Ok(diff)
+    }
+}
+
+// Ground-floor tiles wherever they've been generated. Unloaded cells come back as they were,
+// so the tile cache counts as generated.
+#[derive(SystemParam)]
+pub struct GeneratedTiles<'w, 's> {
+    cells: Query<'w, 's, &'static Cell>,
+    spatial_index: Res<'w, CellSpatialIndex>,
+    cache: Res<'w, CollapsedTileCache>,
+}
+
+impl GeneratedTiles<'_, '_> {
+    pub fn at(&self, position: (i32, i32)) -> Option<TileType> {
+        match self.spatial_index.ground(&position) {
+            Some(entity) => self
+                .cells
+                .get(*entity)
+                .ok()
+                .filter(|cell| cell.is_collapsed)
+                .and_then(|cell| cell.tile_type),
+            None => self.cache.tiles.get(&position).copied(),
+        }
+    }
+}
+
+fn snapshot_path(name: &str) -> String {
+    format!("{}/{}.txt", SNAPSHOT_DIRECTORY, name)
+}
+
+fn load_snapshot(name: &str) -> Result<WorldSnapshot, String> {
+    let path = snapshot_path(name);
+    let text = fs::read_to_string(&path).map_err(|error| format!("{}: {}", path, error))?;
+    WorldSnapshot::parse(&text).map_err(|error| format!("{}: {}", path, error))
+}
+
+fn print_diff(console: &mut ConsoleState, diff: &SnapshotDiff) {
+    if diff.seeds_differ {
+        console.print("seeds differ, so the tiles are expected to");
+    }
+    console.print(format!(
+        "{} of {} cells changed",
+        diff.changes.len(),
+        diff.compared
+    ));
+    for change in diff.changes.iter().take(CHANGES_SHOWN) {
+        console.print(format!(
+            "  {:?}: {:?} -> {:?}",
+            change.position, change.before, change.after
+        ));
+    }
+}
+
+// `snapshot save <name> [radius]` writes the tiles round the player to snapshots/<name>.txt,
+// `snapshot diff <name>` compares the world as it is now against it and
+// `snapshot diff <a> <b>` compares two saved ones
+fn snapshot_command(
+    mut commands_in: EventReader<ConsoleCommand>,
+    mut console: ResMut<ConsoleState>,
+    player: Single<&Transform, With<Player>>,
+    tiles: GeneratedTiles,
+    settings: Res<GenerationSettings>,
+    biomes: Res<BiomeMap>,
+    elevation: Res<ElevationMap>,
+) {
+    let seeds = (biomes.seed, elevation.seed);
+    let tile_at = |position: (i32, i32)| tiles.at(position);
+
+    for command in commands_in.read().filter(|c| c.name == "snapshot") {
+        let args: Vec<&str> = command.args.iter().map(String::as_str).collect();
+        match args.as_slice() {
+            ["save", name, radius @ ..] if radius.len() <= 1 => {
+                let Ok(radius @ 0..) = radius.first().map_or(Ok(DEFAULT_RADIUS), |r| r.parse())
+                else {
+                    console.print("the radius is a whole number of cells, 0 or more");
+                    continue;
+                };
+                let center = world_to_grid(player.translation, settings.cell_edge_length as f32);
+                let snapshot = WorldSnapshot::capture(center, radius, seeds, tile_at);
+                let path = snapshot_path(name);
+                let result = fs::create_dir_all(SNAPSHOT_DIRECTORY)
+                    .map_err(|error| error.to_string())
+                    .and_then(|()| write_atomically(&path, &snapshot.to_text()));
+                match result {
+                    Ok(()) => console.print(format!("wrote {}", path)),
+                    Err(error) => console.print(format!("couldn't write {}: {}", path, error)),
+                }
+            }
+            ["diff", name] => {
+                let diff = load_snapshot(name).and_then(|saved| {
+                    let now = WorldSnapshot::capture(saved.center, saved.radius, seeds, tile_at);
+                    saved.diff(&now)
+                });
+                match diff {
+                    Ok(diff) => print_diff(&mut console, &diff),
+                    Err(error) => console.print(format!("couldn't compare: {}", error)),
+                }
+            }
+            ["diff", before, after] => {
+                let diff =
+                    load_snapshot(before).and_then(|before| before.diff(&load_snapshot(after)?));
+                match diff {
+                    Ok(diff) => print_diff(&mut console, &diff),
+                    Err(error) => console.print(format!("couldn't compare: {}", error)),
+                }
+            }
+            _ => {
+                console.print("usage: snapshot save <name> [radius] | snapshot diff <name> [other]")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use super::*;
+
+    const SEEDS: (u64, u64) = (1, 2);
+
+    fn meadow(position: (i32, i32)) -> Option<TileType> {
+        match position {
+            (0, 0) => None,
+            (1, _) => Some(TileType::Tree),
+            _ => Some(TileType::Ground),
+        }
+    }
+
+    #[test]
+    fn every_tile_has_its_own_glyph() {
+        for tile in TileType::iter() {
+            assert_ne!(tile.glyph(), UNSOLVED_GLYPH);
+            assert_eq!(TileType::from_glyph(tile.glyph()), Some(tile));
+        }
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let snapshot = WorldSnapshot::capture((0, 0), 1, SEEDS, meadow);
+        let text = snapshot.to_text();
+
+        assert!(text.ends_with(".?t\n..t\n"));
+        assert_eq!(WorldSnapshot::parse(&text), Ok(snapshot));
+    }
+
+    #[test]
+    fn diff_skips_unsolved_cells() {
+        let before = WorldSnapshot::capture((0, 0), 1, SEEDS, meadow);
+        let after = WorldSnapshot::capture((0, 0), 1, SEEDS, |position| match position {
+            (-1, 1) => Some(TileType::Chest),
+            _ => meadow(position).or(Some(TileType::Wall)),
+        });
+        let diff = before.diff(&after).expect("same area");
+
+        assert_eq!(diff.compared, 8);
+        assert!(!diff.seeds_differ);
+        assert_eq!(
+            diff.changes,
+            vec![TileChange {
+                position: (-1, 1),
+                before: TileType::Ground,
+                after: TileType::Chest,
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_refuses_other_areas() {
+        let here = WorldSnapshot::capture((0, 0), 1, SEEDS, meadow);
+        let there = WorldSnapshot::capture((5, 0), 1, SEEDS, meadow);
+
+        assert!(here.diff(&there).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::game::{
+    core_mechanics::{
+        enemy_ai::o_insan::components::Path,
+        oz_devinimli_yaratim::{
+            cells::{Cell, CellSpatialIndex, GenerationSettings},
+            elevation::cell_lift,
+            grid::{cells_in_radius, grid_to_world},
+        },
+        walkability::{PathSearchTraces, WalkabilityGrid},
+    },
+    spawn::player::Player,
+    ui::palette::Palette,
+};
+
+const OVERLAY_RADIUS: f32 = 80.0; // Around the player, for the walkability grid
+const LIFT: f32 = 0.1; // Above the tile tops, so the quads don't flicker into them
+// Nested, so a searched cell still shows its walkability round the edge
+const GRID_QUAD: f32 = 0.9;
+const CLOSED_QUAD: f32 = 0.65;
+const OPEN_QUAD: f32 = 0.4;
+
+// Flips the overlay; the traces only exist while it's on, so pathing records nothing otherwise
+pub fn toggle_path_overlay(mut commands: Commands, traces: Option<Res<PathSearchTraces>>) {
+    if traces.is_some() {
+        commands.remove_resource::<PathSearchTraces>();
+        println!("🧭 Path overlay: OFF");
+    } else {
+        commands.init_resource::<PathSearchTraces>();
+        println!("🧭 Path overlay: ON");
+    }
+}
+
+// Where a cell's quad goes: just above the top of its ground-floor tile
+#[derive(SystemParam)]
+pub struct OverlayGround<'w, 's> {
+    cells: Query<'w, 's, &'static Cell>,
+    spatial_index: Res<'w, CellSpatialIndex>,
+    settings: Res<'w, GenerationSettings>,
+}
+
+impl OverlayGround<'_, '_> {
+    fn edge(&self) -> f32 {
+        self.settings.cell_edge_length as f32
+    }
+
+    // Unsolved cells have no height yet; they're drawn at the base level
+    fn at(&self, position: (i32, i32)) -> Vec3 {
+        let lift = self
+            .spatial_index
+            .ground(&position)
+            .and_then(|entity| self.cells.get(*entity).ok())
+            .map_or(0.0, cell_lift);
+        grid_to_world(position, self.edge()).with_y(lift + LIFT)
+    }
+}
+
+// Walkability round the player tinted by how slow each tile is to cross, then every live search:
+// closed cells along the cost ramp by their cost from the start, open cells highlighted and the
+// path itself as a line. Reads the same grid the searches did, so a quad that disagrees with the
+// tile under it is stale data rather than a search bug.
+pub fn draw_path_overlay(
+    mut gizmos: Gizmos,
+    mut traces: ResMut<PathSearchTraces>,
+    pathing: Query<&Path>,
+    player: Single<&Transform, With<Player>>,
+    grid: Res<WalkabilityGrid>,
+    ground: OverlayGround,
+    palette: Res<Palette>,
+) {
+    let edge = ground.edge();
+    let mut quad = |position: (i32, i32), scale: f32, color: Color| {
+        gizmos.rect(
+            Isometry3d::new(ground.at(position), Quat::from_rotation_x(FRAC_PI_2)),
+            Vec2::splat(edge * scale),
+            color,
+        );
+    };
+
+    for position in cells_in_radius(player.translation, OVERLAY_RADIUS, edge) {
+        let Some(walkable) = grid.get(position) else {
+            continue;
+        };
+        let color = if walkable {
+            // Cool on plain ground, hotter the slower the tile
+            palette.heat(1.0 - grid.speed(position))
+        } else {
+            palette.blocked
+        };
+        quad(position, GRID_QUAD, color);
+    }
+
+    // Dying AIs are left out of pathing, so their last search is dropped here instead
+    traces
+        .searches
+        .retain(|entity, _| pathing.get(*entity).is_ok_and(|path| path.goal.is_some()));
+
+    for search in traces.searches.values() {
+        let highest = search
+            .closed
+            .iter()
+            .filter_map(|position| search.costs.get(position))
+            .fold(0.0_f32, |highest, cost| highest.max(*cost))
+            .max(f32::EPSILON);
+        for position in search.closed.iter() {
+            let cost = search.costs.get(position).copied().unwrap_or_default();
+            quad(*position, CLOSED_QUAD, palette.cost(cost / highest));
+        }
+        for position in search.open() {
+            quad(position, OPEN_QUAD, palette.highlight);
+        }
+    }
+
+    for search in traces.searches.values() {
+        gizmos.linestrip(
+            search.path.iter().map(|position| ground.at(*position)),
+            palette.path,
+        );
+    }
+}
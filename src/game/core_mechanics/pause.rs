@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+use crate::game::{
+    core_mechanics::{fast_travel::GateMenu, permadeath::RunEnded},
+    ui::{console::ConsoleState, difficulty_panel::DifficultyPanel, world_map::WorldMapState},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<SimulationPaused>()
+        .add_systems(PreUpdate, update_simulation_paused);
+}
+
+// True while a menu or the hardcore end screen covers the game; world generation and AI stop until it closes
+#[derive(Resource, Debug, Default)]
+pub struct SimulationPaused(pub bool);
+
+pub fn simulation_running(paused: Res<SimulationPaused>) -> bool {
+    !paused.0
+}
+
+fn update_simulation_paused(
+    mut paused: ResMut<SimulationPaused>,
+    world_map: Res<WorldMapState>,
+    console: Res<ConsoleState>,
+    gate_menu: Res<GateMenu>,
+    difficulty_panel: Res<DifficultyPanel>,
+    run_ended: Option<Res<RunEnded>>,
+) {
+    let menu_open = world_map.open
+        || console.open
+        || gate_menu.open
+        || difficulty_panel.open
+        || run_ended.is_some();
+    if paused.0 != menu_open {
+        paused.0 = menu_open;
+    }
+}
@@ -0,0 +1,171 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::common_conditions::on_timer};
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    core_mechanics::{
+        enemy_ai::o_insan::components::{AIBehavior, Dying, OInsanAI},
+        health::Health,
+        movement::PlayerTeleported,
+        oz_devinimli_yaratim::{cells::GenerationSettings, grid::world_to_grid},
+        pause::simulation_running,
+        play_stats::PlayStats,
+        walkability::WalkabilityGrid,
+    },
+    save::{delete_save, save_exists},
+    spawn::player::{PLAYER_SPAWN, Player},
+    ui::{
+        console::{ConsoleCommand, ConsoleState},
+        toasts::ShowToast,
+    },
+};
+
+const SAFE_GROUND_CHECK_MS: u64 = 1000;
+const CHASER_CLEARANCE: f32 = 40.0; // Nowhere this close to a chasing AI counts as safe
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<GameMode>()
+        .init_resource::<SafeGround>()
+        .add_systems(
+            Update,
+            (
+                hardcore_command,
+                follow_teleports,
+                record_safe_ground.run_if(
+                    simulation_running.and(on_timer(Duration::from_millis(SAFE_GROUND_CHECK_MS))),
+                ),
+                handle_player_death
+                    .run_if(simulation_running.and(not(resource_exists::<RunEnded>))),
+            )
+                .chain(),
+        );
+}
+
+// Picked when the world is created and saved with it
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GameMode {
+    pub hardcore: bool, // Death deletes the save
+}
+
+// A hardcore character has died; nothing gets saved from here on
+#[derive(Resource, Debug)]
+pub struct RunEnded;
+
+// Where the player gets back up after dying: the last spot they stood on solid, hazard-free
+// ground with nothing chasing them, or wherever they last arrived by teleport
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct SafeGround(pub Vec3);
+
+impl Default for SafeGround {
+    fn default() -> Self {
+        Self(PLAYER_SPAWN)
+    }
+}
+
+// `hardcore` shows the mode, `hardcore on|off` changes it while the world has never been saved
+fn hardcore_command(
+    mut commands_in: EventReader<ConsoleCommand>,
+    mut console: ResMut<ConsoleState>,
+    mut mode: ResMut<GameMode>,
+) {
+    for command in commands_in.read().filter(|c| c.name == "hardcore") {
+        let args: Vec<&str> = command.args.iter().map(String::as_str).collect();
+
+        let hardcore = match args.as_slice() {
+            [] => None,
+            ["on"] => Some(true),
+            ["off"] => Some(false),
+            _ => {
+                console.print("usage: hardcore [on|off]");
+                continue;
+            }
+        };
+        if let Some(hardcore) = hardcore {
+            if save_exists() {
+                console.print("hardcore can only be chosen for a world that hasn't been saved yet");
+                continue;
+            }
+            mode.hardcore = hardcore;
+        }
+
+        console.print(if mode.hardcore {
+            "hardcore: on, death deletes the save"
+        } else {
+            "hardcore: off"
+        });
+    }
+}
+
+// Fast travel, loading and respawning all land somewhere meant to be stood on
+fn follow_teleports(
+    mut teleports: EventReader<PlayerTeleported>,
+    mut safe_ground: ResMut<SafeGround>,
+) {
+    if let Some(teleport) = teleports.read().last() {
+        safe_ground.0 = teleport.to;
+    }
+}
+
+type LivingAiQuery<'w, 's> =
+    Query<'w, 's, (&'static Transform, &'static OInsanAI), (Without<Dying>, Without<Player>)>;
+
+fn record_safe_ground(
+    mut safe_ground: ResMut<SafeGround>,
+    player: Single<(&Transform, &Health), With<Player>>,
+    ais: LivingAiQuery,
+    grid: Res<WalkabilityGrid>,
+    settings: Res<GenerationSettings>,
+) {
+    let (transform, health) = *player;
+    let position = transform.translation;
+    if health.current <= 0.0
+        || !grid.is_safe(world_to_grid(position, settings.cell_edge_length as f32))
+    {
+        return;
+    }
+
+    let chased = ais.iter().any(|(ai_transform, ai)| {
+        ai.current_behavior == AIBehavior::Chasing
+            && ai_transform.translation.distance(position) <= CHASER_CLEARANCE
+    });
+    if !chased {
+        safe_ground.0 = position;
+    }
+}
+
+// Normally the player gets back up on the last safe ground; in hardcore the save goes with them
+fn handle_player_death(
+    mut commands: Commands,
+    mut stats: ResMut<PlayStats>,
+    mut toasts: EventWriter<ShowToast>,
+    mut teleports: EventWriter<PlayerTeleported>,
+    mut player: Single<(&mut Health, &mut Transform), With<Player>>,
+    mode: Res<GameMode>,
+    safe_ground: Res<SafeGround>,
+) {
+    let (health, transform) = &mut *player;
+    if health.current > 0.0 {
+        return;
+    }
+
+    stats.deaths += 1;
+    if mode.hardcore {
+        println!("💀 The player died for good");
+        delete_save();
+        commands.insert_resource(RunEnded);
+        return;
+    }
+
+    println!("💀 The player died ({} so far)", stats.deaths);
+    health.current = health.max;
+    let from = transform.translation;
+    transform.translation = safe_ground.0;
+    teleports.write(PlayerTeleported {
+        from,
+        to: safe_ground.0,
+    });
+    toasts.write(ShowToast {
+        message: "You died".to_string(),
+    });
+}
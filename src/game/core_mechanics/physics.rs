@@ -0,0 +1,229 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::game::{
+    core_mechanics::{
+        combat::PLAYER_RADIUS,
+        movement::player_movement,
+        oz_devinimli_yaratim::{
+            cells::{Cell, GenerationSettings},
+            elevation::{LEVEL_HEIGHT, cell_lift},
+            grid::grid_to_world,
+            odyrules::{commons::TileType, open_space_rules::OpenSpaceRules},
+            tiles_meshes_models::update_tile_visuals,
+        },
+        pause::simulation_running,
+    },
+    input::{Action, Actions},
+    spawn::player::{PLAYER_SPAWN, Player},
+    ui::console::console_closed,
+};
+
+const SLAB_THICKNESS: f32 = 0.2;
+const EYE_HEIGHT: f32 = PLAYER_SPAWN.y; // From the player's feet to their transform
+const GRAVITY: f32 = 30.0;
+const JUMP_SPEED: f32 = 9.5; // About a meter and a half up
+const CLIMB_RATE: f32 = 8.0; // How quickly the player settles onto a higher step, per second
+const GROUND_PROBE: f32 = 100.0; // How far below the eye ground is looked for
+
+pub const GROUND_GROUP: Group = Group::GROUP_1; // Slabs under every tile, to stand on
+pub const SOLID_GROUP: Group = Group::GROUP_2; // Trees, fountains, walls and the like
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins(RapierPhysicsPlugin::<NoUserData>::default())
+        .add_systems(
+            Update,
+            (
+                attach_tile_colliders.after(update_tile_visuals),
+                (jump.run_if(console_closed), apply_gravity)
+                    .chain()
+                    .after(player_movement)
+                    .run_if(simulation_running),
+            ),
+        );
+}
+
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct VerticalMotion {
+    pub speed: f32, // Upwards, in world units per second
+    pub grounded: bool,
+}
+
+// The controller only blocks the player against solid tiles; the ground is found by
+// apply_gravity, since a shape reaching down to the feet would tilt as the view pitches
+pub fn player_controller() -> impl Bundle {
+    (
+        KinematicCharacterController {
+            // A ball round the eye, since the player's transform pitches with the view
+            custom_shape: Some((Collider::ball(PLAYER_RADIUS), Vec3::ZERO, Quat::IDENTITY)),
+            offset: CharacterLength::Absolute(0.05),
+            up: Vec3::Y,
+            slide: true,
+            autostep: None,
+            snap_to_ground: None,
+            filter_groups: Some(CollisionGroups::new(Group::ALL, SOLID_GROUP)),
+            ..Default::default()
+        },
+        VerticalMotion::default(),
+    )
+}
+
+fn jump(actions: Actions, mut motion: Single<&mut VerticalMotion, With<Player>>) {
+    if motion.grounded && actions.just_pressed(Action::Jump) {
+        motion.speed = JUMP_SPEED;
+        motion.grounded = false;
+    }
+}
+
+// Adds the fall to whatever walking step player_movement handed the controller
+fn apply_gravity(
+    player: Single<
+        (
+            &Transform,
+            &mut KinematicCharacterController,
+            &mut VerticalMotion,
+        ),
+        With<Player>,
+    >,
+    rapier: ReadRapierContext,
+    time: Res<Time>,
+) {
+    let (transform, mut controller, mut motion) = player.into_inner();
+    let Ok(context) = rapier.single() else {
+        return;
+    };
+    let dt = time.delta_secs();
+    let eye = transform.translation;
+
+    let filter =
+        QueryFilter::default().groups(CollisionGroups::new(Group::ALL, GROUND_GROUP | SOLID_GROUP));
+    // Nothing generated below yet: hold still rather than fall out of the world
+    let Some((_, distance)) = context.cast_ray(eye, Vec3::NEG_Y, GROUND_PROBE, true, filter) else {
+        *motion = VerticalMotion {
+            speed: 0.0,
+            grounded: true,
+        };
+        return;
+    };
+    let ground = eye.y - distance;
+    let feet = eye.y - EYE_HEIGHT;
+
+    motion.speed -= GRAVITY * dt;
+    let mut rise = motion.speed * dt;
+    motion.grounded = feet + rise <= ground;
+    if motion.grounded {
+        motion.speed = 0.0;
+        rise = if ground > feet {
+            (ground - feet) * (CLIMB_RATE * dt).min(1.0)
+        } else {
+            ground - feet
+        };
+    }
+
+    let step = controller.translation.unwrap_or_default();
+    controller.translation = Some(step.with_y(rise));
+}
+
+// Shapes relative to the center of the cell's top, axis-aligned
+fn tile_shapes(tile: TileType, edge: f32, footprint: (i32, i32)) -> (Vec<(Vec3, Collider)>, Group) {
+    let half = edge / 2.0;
+    let slab = |top: f32| {
+        (
+            Vec3::Y * (top - SLAB_THICKNESS / 2.0),
+            Collider::cuboid(half, SLAB_THICKNESS / 2.0, half),
+        )
+    };
+    // Multi-cell tiles sit on their anchor cell, at the lowest x and z of the footprint
+    let footprint_center = Vec3::new(
+        (footprint.0 - 1) as f32 * half,
+        0.0,
+        (footprint.1 - 1) as f32 * half,
+    );
+
+    let solid = match tile {
+        TileType::Tree => (Vec3::Y * half, Collider::cylinder(half, edge * 0.12)),
+        TileType::LargeTree => (
+            footprint_center + Vec3::Y * edge,
+            Collider::cylinder(edge, edge * 0.25),
+        ),
+        TileType::Chest => (
+            Vec3::Y * edge * 0.15,
+            Collider::cuboid(edge * 0.25, edge * 0.15, edge * 0.2),
+        ),
+        TileType::FountainCenter
+        | TileType::FountainCorner1
+        | TileType::FountainCorner2
+        | TileType::FountainCorner3
+        | TileType::FountainCorner4
+        | TileType::FountainEdge1
+        | TileType::FountainEdge2
+        | TileType::FountainEdge3
+        | TileType::FountainEdge4 => (
+            Vec3::Y * edge * 0.25,
+            Collider::cuboid(half, edge * 0.25, half),
+        ),
+        TileType::Well => (
+            Vec3::Y * edge * 0.25,
+            Collider::cylinder(edge * 0.25, edge * 0.25),
+        ),
+        TileType::Wall => (
+            Vec3::Y * edge * 0.3,
+            Collider::cuboid(half, edge * 0.3, half),
+        ),
+        TileType::RuinEntrance => (
+            footprint_center + Vec3::Y * edge * 0.15,
+            Collider::cuboid(
+                half * footprint.0 as f32,
+                edge * 0.15,
+                half * footprint.1 as f32,
+            ),
+        ),
+        // The face down to the lower height
+        TileType::Cliff => (
+            Vec3::NEG_Y * LEVEL_HEIGHT / 2.0,
+            Collider::cuboid(half, LEVEL_HEIGHT / 2.0, half),
+        ),
+        // Both are drawn reaching halfway down to the lower height
+        TileType::Slope | TileType::Stairs => {
+            return (vec![slab(-LEVEL_HEIGHT / 2.0)], GROUND_GROUP);
+        }
+        _ => return (vec![slab(0.0)], GROUND_GROUP),
+    };
+    (vec![slab(0.0), solid], SOLID_GROUP)
+}
+
+// Tile entities are placed and scaled differently per tile, so the shapes are laid out from the
+// cell itself and the entity's scale is ignored
+fn attach_tile_colliders(
+    mut commands: Commands,
+    changed_cells: Query<(Entity, &Cell, &Transform), Changed<Cell>>,
+    settings: Res<GenerationSettings>,
+    rules: Res<OpenSpaceRules>,
+) {
+    let edge = settings.cell_edge_length as f32;
+    for (entity, cell, transform) in changed_cells.iter() {
+        let Some(tile) = cell.tile_type.filter(|_| cell.is_collapsed) else {
+            continue;
+        };
+
+        let top = grid_to_world(cell.position, edge).with_y(cell_lift(cell));
+        let to_local = transform.rotation.inverse();
+        let (shapes, group) = tile_shapes(tile, edge, rules.footprint(tile));
+        let shapes = shapes
+            .into_iter()
+            .map(|(offset, shape)| {
+                (
+                    to_local * (top + offset - transform.translation),
+                    to_local,
+                    shape,
+                )
+            })
+            .collect();
+
+        commands.entity(entity).insert((
+            Collider::compound(shapes),
+            ColliderScale::Absolute(Vec3::ONE),
+            CollisionGroups::new(group, Group::ALL),
+        ));
+    }
+}
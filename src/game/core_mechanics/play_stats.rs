@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    core_mechanics::{
+        chests::ChestOpened, enemy_ai::o_insan::components::Dying, pause::simulation_running,
+    },
+    spawn::player::Player,
+};
+
+const MAX_STEP: f32 = 50.0; // Anything further in one frame was a teleport, not a walk
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PlayStats>().add_systems(
+        Update,
+        (record_kills, record_chests, record_distance).run_if(simulation_running),
+    );
+}
+
+// Running totals for the world, saved with it and shown when a hardcore run ends
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct PlayStats {
+    pub kills: u32,
+    pub chests_looted: u32,
+    pub distance_walked: f32,
+    pub deaths: u32,
+}
+
+fn record_kills(mut stats: ResMut<PlayStats>, killed: Query<(), Added<Dying>>) {
+    stats.kills += killed.iter().count() as u32;
+}
+
+fn record_chests(mut stats: ResMut<PlayStats>, mut opened: EventReader<ChestOpened>) {
+    stats.chests_looted += opened.read().count() as u32;
+}
+
+fn record_distance(
+    mut stats: ResMut<PlayStats>,
+    mut last_position: Local<Option<Vec3>>,
+    player: Single<&Transform, With<Player>>,
+) {
+    let position = player.translation.with_y(0.0);
+    if let Some(last) = last_position.replace(position) {
+        let step = last.distance(position);
+        if step < MAX_STEP {
+            stats.distance_walked += step;
+        }
+    }
+}
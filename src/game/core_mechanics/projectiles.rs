@@ -0,0 +1,217 @@
+use bevy::{ecs::system::SystemParam, prelude::*, window::PrimaryWindow};
+use bevy_rapier3d::prelude::*;
+
+use crate::game::{
+    core_mechanics::{
+        combat::{AI_RADIUS, AttackHitEvent, HitProbe, PLAYER_RADIUS},
+        enemy_ai::o_insan::components::{Dying, OInsanAI},
+        health::{DamageEvent, DamageSource},
+        inventory::{Inventory, Item},
+        pause::simulation_running,
+        physics::{GROUND_GROUP, SOLID_GROUP},
+        status_effects::{ApplyStatus, StatusKind, player_can_act},
+    },
+    input::{Action, action_just_pressed},
+    spawn::player::Player,
+    ui::{console::console_closed, toasts::ShowToast, world_map::world_map_closed},
+};
+
+const ARROW_SPEED: f32 = 70.0;
+const ARROW_GRAVITY: f32 = 12.0; // Gentler than the player's, so a level shot carries
+const ARROW_LIFETIME_SECS: f32 = 3.0;
+const ARROW_DAMAGE: f32 = 20.0;
+const BOW_COOLDOWN_SECS: f32 = 0.7; // Between shots, to nock the next arrow
+const AI_HEIGHT: f32 = 3.0; // Above the AI's feet, where its transform is
+const ARROW_SLOW_SECS: f32 = 2.0; // An arrow in the leg
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Startup, setup_projectile_assets)
+        .add_systems(
+            Update,
+            (
+                fire_bow.run_if(
+                    action_just_pressed(Action::Attack)
+                        .and(console_closed)
+                        .and(world_map_closed)
+                        .and(player_can_act)
+                        .and(simulation_running),
+                ),
+                move_projectiles.run_if(simulation_running),
+            )
+                .chain(),
+        );
+}
+
+// In flight until it hits an AI or anything solid, or runs out of time
+#[derive(Component, Debug)]
+pub struct Projectile {
+    pub shooter: Entity,
+    pub velocity: Vec3,
+    pub damage: f32,
+    pub lifetime: Timer,
+}
+
+#[derive(Resource)]
+struct ProjectileAssets {
+    arrow_mesh: Handle<Mesh>,
+    arrow_material: Handle<StandardMaterial>,
+}
+
+fn setup_projectile_assets(
+    mut commands: Commands,
+    mut mesh_assets: ResMut<Assets<Mesh>>,
+    mut material_assets: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(ProjectileAssets {
+        arrow_mesh: mesh_assets.add(Cuboid::new(0.06, 0.06, 1.0)),
+        arrow_material: material_assets.add(StandardMaterial {
+            base_color: Color::srgb(0.55, 0.4, 0.25),
+            ..Default::default()
+        }),
+    });
+}
+
+// With a bow in hand, Attack looses one arrow from wherever it is carried along the view
+fn fire_bow(
+    mut commands: Commands,
+    player: Single<(Entity, &Transform, &mut Inventory), With<Player>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    assets: Res<ProjectileAssets>,
+    mut toasts: EventWriter<ShowToast>,
+    mut last_shot: Local<Option<f32>>,
+    time: Res<Time>,
+) {
+    let (player_entity, transform, mut inventory) = player.into_inner();
+    if !inventory.held().is_some_and(|stack| stack.item.is_ranged()) || !window.focused {
+        return;
+    }
+    let now = time.elapsed_secs();
+    if last_shot.is_some_and(|shot| now - shot < BOW_COOLDOWN_SECS) {
+        return;
+    }
+    if !inventory.consume(Item::Arrow) {
+        toasts.write(ShowToast {
+            message: "Out of arrows".to_string(),
+        });
+        return;
+    }
+    *last_shot = Some(now);
+
+    let forward = transform.forward().as_vec3();
+    // Starts clear of the player's own controller ball
+    let position = transform.translation + forward * (PLAYER_RADIUS + 0.5);
+    commands.spawn((
+        Name::new("Arrow"),
+        Projectile {
+            shooter: player_entity,
+            velocity: forward * ARROW_SPEED,
+            damage: ARROW_DAMAGE,
+            lifetime: Timer::from_seconds(ARROW_LIFETIME_SECS, TimerMode::Once),
+        },
+        Mesh3d(assets.arrow_mesh.clone()),
+        MeshMaterial3d(assets.arrow_material.clone()),
+        Transform::from_translation(position).looking_to(forward, Vec3::Y),
+    ));
+}
+
+// Everything an arrow does to the AI it hits
+#[derive(SystemParam)]
+struct ArrowHitEvents<'w> {
+    damage: EventWriter<'w, DamageEvent>,
+    hits: EventWriter<'w, AttackHitEvent>,
+    statuses: EventWriter<'w, ApplyStatus>,
+}
+
+type ArrowTargetQuery<'w, 's> =
+    Query<'w, 's, (Entity, &'static Transform), (With<OInsanAI>, Without<Dying>)>;
+
+// Each frame's flight is a segment: the nearest of an AI or a solid tile or the ground along
+// it takes the arrow, and an AI behind a wall is left alone
+fn move_projectiles(
+    mut commands: Commands,
+    mut projectiles: Query<(Entity, &mut Projectile, &mut Transform), Without<OInsanAI>>,
+    targets: ArrowTargetQuery,
+    mut hit_events: ArrowHitEvents,
+    rapier: ReadRapierContext,
+    time: Res<Time>,
+) {
+    let Ok(context) = rapier.single() else {
+        return;
+    };
+    let solid =
+        QueryFilter::default().groups(CollisionGroups::new(Group::ALL, SOLID_GROUP | GROUND_GROUP));
+
+    for (entity, mut projectile, mut transform) in projectiles.iter_mut() {
+        projectile.lifetime.tick(time.delta());
+        if projectile.lifetime.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        projectile.velocity.y -= ARROW_GRAVITY * time.delta_secs();
+        let step = projectile.velocity * time.delta_secs();
+        let length = step.length();
+        let Some(direction) = step.try_normalize() else {
+            continue;
+        };
+        let from = transform.translation;
+
+        let wall = context
+            .cast_ray(from, direction, length, true, solid)
+            .map(|(_, distance)| distance);
+        let hit = targets
+            .iter()
+            .filter_map(|(target, target_transform)| {
+                body_hit(from, step, target_transform.translation).map(|along| (target, along))
+            })
+            .filter(|(_, along)| wall.is_none_or(|wall| *along < wall))
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+
+        if let Some((target, _)) = hit {
+            hit_events.hits.write(AttackHitEvent {
+                attacker: projectile.shooter,
+                target,
+            });
+            hit_events.damage.write(DamageEvent {
+                target,
+                amount: projectile.damage,
+                source: DamageSource::Attack {
+                    attacker: projectile.shooter,
+                },
+            });
+            hit_events.statuses.write(ApplyStatus {
+                target,
+                kind: StatusKind::Slow,
+                duration_secs: ARROW_SLOW_SECS,
+            });
+            commands.entity(entity).despawn();
+        } else if wall.is_some() {
+            commands.entity(entity).despawn();
+        } else {
+            transform.translation += step;
+            transform.look_to(direction, Vec3::Y);
+        }
+    }
+}
+
+// Distance along the step at which it passes through an AI's body, taken as an upright
+// cylinder standing on the AI's feet
+fn body_hit(from: Vec3, step: Vec3, feet: Vec3) -> Option<f32> {
+    let flat_length = step.with_y(0.0).length();
+    let probe = HitProbe {
+        origin: from,
+        direction: step,
+        reach: flat_length,
+    };
+    let along_flat = probe.test(feet, AI_RADIUS)?;
+
+    let fraction = if flat_length > 0.0 {
+        along_flat / flat_length
+    } else {
+        0.0
+    };
+    let height = from.y + step.y * fraction - feet.y;
+    (0.0..=AI_HEIGHT)
+        .contains(&height)
+        .then_some(step.length() * fraction)
+}
@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+use crate::game::{
+    core_mechanics::{
+        chests::ChestOpened,
+        enemy_ai::o_insan::{
+            components::{AIBehavior, Disposition, Dying, OInsanAI},
+            events::BehaviorChangedEvent,
+        },
+        pause::simulation_running,
+    },
+    ui::toasts::ShowToast,
+};
+
+pub const MAX_STANDING: f32 = 100.0;
+const KILL_STANDING: f32 = -20.0;
+const SPARE_STANDING: f32 = 10.0;
+const THEFT_STANDING: f32 = -15.0;
+const THEFT_RADIUS: f32 = 20.0; // A chest this close to a guard's post belongs to its camp
+const BASELINE_FRIENDLINESS: f32 = 0.8; // At full standing, either way
+const PRICE_SWING: f32 = 0.4; // Prices run from 1 - swing when trusted to 1 + swing when hated
+const REFUSE_TRADE_BELOW: f32 = -60.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Reputation>().add_systems(
+        Update,
+        (
+            record_kills,
+            record_sparings,
+            record_thefts,
+            apply_reputation_to_dispositions,
+        )
+            .chain()
+            .run_if(simulation_running),
+    );
+}
+
+#[derive(
+    Component, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default, EnumIter,
+)]
+pub enum Faction {
+    #[default]
+    Drifters, // Lone wanderers and roaming packs
+    Wardens, // Posted to watch over caches and chests
+    Caravan, // Traders and their escorts
+}
+
+impl Faction {
+    pub fn name(self) -> &'static str {
+        match self {
+            Faction::Drifters => "Drifters",
+            Faction::Wardens => "Wardens",
+            Faction::Caravan => "Caravan",
+        }
+    }
+}
+
+// How each faction regards the player, from -MAX_STANDING to MAX_STANDING. Saved with the
+// world; a faction with no entry is neutral.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Reputation {
+    pub standing: HashMap<Faction, f32>,
+}
+
+impl Reputation {
+    pub fn standing(&self, faction: Faction) -> f32 {
+        self.standing.get(&faction).copied().unwrap_or(0.0)
+    }
+
+    // What members of the faction feel towards the player before any emotes
+    pub fn baseline_friendliness(&self, faction: Faction) -> f32 {
+        self.standing(faction) / MAX_STANDING * BASELINE_FRIENDLINESS
+    }
+
+    // None when the faction won't trade with the player at all
+    pub fn price(&self, faction: Faction, base_price: f32) -> Option<f32> {
+        let standing = self.standing(faction);
+        (standing >= REFUSE_TRADE_BELOW)
+            .then(|| base_price * (1.0 - standing / MAX_STANDING * PRICE_SWING))
+    }
+}
+
+pub fn standing_label(standing: f32) -> &'static str {
+    match standing {
+        s if s <= -60.0 => "Hated",
+        s if s <= -20.0 => "Distrusted",
+        s if s < 20.0 => "Neutral",
+        s if s < 60.0 => "Liked",
+        _ => "Trusted",
+    }
+}
+
+// Marks an AI that has already been let go after begging, so it only counts once
+#[derive(Component)]
+struct Spared;
+
+fn change_standing(
+    reputation: &mut Reputation,
+    toasts: &mut EventWriter<ShowToast>,
+    faction: Faction,
+    amount: f32,
+    reason: &str,
+) {
+    let before = standing_label(reputation.standing(faction));
+    let standing = reputation.standing.entry(faction).or_default();
+    *standing = (*standing + amount).clamp(-MAX_STANDING, MAX_STANDING);
+    println!(
+        "⚖️ {} the {}: standing {:+.0} -> {:.0}",
+        reason,
+        faction.name(),
+        amount,
+        standing
+    );
+
+    let after = standing_label(*standing);
+    if after != before {
+        toasts.write(ShowToast {
+            message: format!("{}: {}", faction.name(), after),
+        });
+    }
+}
+
+fn record_kills(
+    mut reputation: ResMut<Reputation>,
+    mut toasts: EventWriter<ShowToast>,
+    killed: Query<&Faction, Added<Dying>>,
+) {
+    for faction in killed.iter() {
+        change_standing(
+            &mut reputation,
+            &mut toasts,
+            *faction,
+            KILL_STANDING,
+            "Killed one of",
+        );
+    }
+}
+
+// An AI that stops begging without being killed was spared
+fn record_sparings(
+    mut commands: Commands,
+    mut reputation: ResMut<Reputation>,
+    mut toasts: EventWriter<ShowToast>,
+    mut behavior_events: EventReader<BehaviorChangedEvent>,
+    ais: Query<&Faction, (Without<Dying>, Without<Spared>)>,
+) {
+    for event in behavior_events.read() {
+        if event.old != AIBehavior::Begging || event.new == AIBehavior::Begging {
+            continue;
+        }
+        let Ok(faction) = ais.get(event.entity) else {
+            continue;
+        };
+
+        commands.entity(event.entity).insert(Spared);
+        change_standing(
+            &mut reputation,
+            &mut toasts,
+            *faction,
+            SPARE_STANDING,
+            "Spared one of",
+        );
+    }
+}
+
+// Opening a chest counts as theft when someone is posted to guard it
+fn record_thefts(
+    mut reputation: ResMut<Reputation>,
+    mut toasts: EventWriter<ShowToast>,
+    mut chest_events: EventReader<ChestOpened>,
+    guards: Query<(&OInsanAI, &Faction), Without<Dying>>,
+) {
+    for event in chest_events.read() {
+        let Some((_, faction)) = guards.iter().find(|(ai, _)| {
+            ai.guard_post.is_some_and(|post| {
+                post.with_y(0.0).distance(event.position.with_y(0.0)) <= THEFT_RADIUS
+            })
+        }) else {
+            continue;
+        };
+
+        change_standing(
+            &mut reputation,
+            &mut toasts,
+            *faction,
+            THEFT_STANDING,
+            "Robbed a camp of",
+        );
+    }
+}
+
+// Newcomers start out feeling the way their faction does, and emotes wear off back towards it
+fn apply_reputation_to_dispositions(
+    reputation: Res<Reputation>,
+    mut ais: Query<(Ref<Faction>, &mut Disposition)>,
+) {
+    for (faction, mut disposition) in ais.iter_mut() {
+        if faction.is_changed() {
+            disposition.baseline = reputation.baseline_friendliness(*faction);
+            disposition.friendliness = disposition.baseline;
+        } else if reputation.is_changed() {
+            disposition.baseline = reputation.baseline_friendliness(*faction);
+        }
+    }
+}
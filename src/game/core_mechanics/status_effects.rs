@@ -0,0 +1,178 @@
+use bevy::{platform::collections::HashMap, prelude::*};
+use strum_macros::EnumIter;
+
+use crate::game::{
+    core_mechanics::{
+        health::{DamageEvent, DamageSource},
+        pause::simulation_running,
+    },
+    spawn::player::Player,
+};
+
+const SLOW_FACTOR: f32 = 0.5;
+const BURN_DAMAGE_PER_SECOND: f32 = 4.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_event::<ApplyStatus>().add_systems(
+        Update,
+        (apply_status_events, tick_status_effects)
+            .chain()
+            .run_if(simulation_running),
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+pub enum StatusKind {
+    Stun, // Can't move or act
+    Slow,
+    Burn, // Damage over time
+}
+
+impl StatusKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            StatusKind::Stun => "stun",
+            StatusKind::Slow => "slow",
+            StatusKind::Burn => "burn",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    pub remaining: Timer,
+}
+
+// Timed modifiers on the player or an AI; each kind is held at most once
+#[derive(Component, Debug, Clone, Default)]
+pub struct StatusEffects {
+    pub effects: Vec<StatusEffect>,
+}
+
+impl StatusEffects {
+    // A kind that's already running keeps going for whichever lasts longer
+    pub fn add(&mut self, kind: StatusKind, duration_secs: f32) {
+        match self.effects.iter_mut().find(|effect| effect.kind == kind) {
+            Some(effect) => {
+                if effect.remaining.remaining_secs() < duration_secs {
+                    effect.remaining = Timer::from_seconds(duration_secs, TimerMode::Once);
+                }
+            }
+            None => self.effects.push(StatusEffect {
+                kind,
+                remaining: Timer::from_seconds(duration_secs, TimerMode::Once),
+            }),
+        }
+    }
+
+    pub fn has(&self, kind: StatusKind) -> bool {
+        self.effects.iter().any(|effect| effect.kind == kind)
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        self.has(StatusKind::Stun)
+    }
+
+    pub fn is_burning(&self) -> bool {
+        self.has(StatusKind::Burn)
+    }
+
+    // Multiplies whatever speed the terrain and movement state give
+    pub fn speed_factor(&self) -> f32 {
+        if self.is_stunned() {
+            0.0
+        } else if self.has(StatusKind::Slow) {
+            SLOW_FACTOR
+        } else {
+            1.0
+        }
+    }
+}
+
+// Run condition for whatever the player does with their hands
+pub fn player_can_act(player: Query<&StatusEffects, With<Player>>) -> bool {
+    !player.iter().any(StatusEffects::is_stunned)
+}
+
+// Puts `kind` on `target` for `duration_secs`, starting its StatusEffects if it has none
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ApplyStatus {
+    pub target: Entity,
+    pub kind: StatusKind,
+    pub duration_secs: f32,
+}
+
+fn apply_status_events(
+    mut commands: Commands,
+    mut events: EventReader<ApplyStatus>,
+    mut targets: Query<&mut StatusEffects>,
+) {
+    // Several events for a target without the component yet all land in the one inserted
+    let mut started: HashMap<Entity, StatusEffects> = HashMap::new();
+    for event in events.read() {
+        debug!(
+            "{:?} got {} for {:.1}s",
+            event.target,
+            event.kind.name(),
+            event.duration_secs
+        );
+        match targets.get_mut(event.target) {
+            Ok(mut effects) => effects.add(event.kind, event.duration_secs),
+            Err(_) => started
+                .entry(event.target)
+                .or_default()
+                .add(event.kind, event.duration_secs),
+        }
+    }
+
+    for (target, effects) in started {
+        commands.entity(target).try_insert(effects);
+    }
+}
+
+// Burning hurts a little every frame; effects that run out are dropped
+fn tick_status_effects(
+    mut targets: Query<(Entity, &mut StatusEffects)>,
+    mut damage_events: EventWriter<DamageEvent>,
+    time: Res<Time>,
+) {
+    for (entity, mut effects) in targets.iter_mut() {
+        if effects.effects.is_empty() {
+            continue;
+        }
+        if effects.is_burning() {
+            damage_events.write(DamageEvent {
+                target: entity,
+                amount: BURN_DAMAGE_PER_SECOND * time.delta_secs(),
+                source: DamageSource::Burning,
+            });
+        }
+
+        for effect in effects.effects.iter_mut() {
+            effect.remaining.tick(time.delta());
+        }
+        effects
+            .effects
+            .retain(|effect| !effect.remaining.finished());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reapplying_keeps_the_longer_duration() {
+        let mut effects = StatusEffects::default();
+        effects.add(StatusKind::Slow, 4.0);
+        effects.add(StatusKind::Slow, 1.0);
+
+        assert_eq!(effects.effects.len(), 1);
+        assert_eq!(effects.effects[0].remaining.remaining_secs(), 4.0);
+        assert_eq!(effects.speed_factor(), SLOW_FACTOR);
+
+        effects.add(StatusKind::Stun, 1.0);
+        assert_eq!(effects.speed_factor(), 0.0);
+    }
+}
@@ -0,0 +1,381 @@
+use std::{collections::VecDeque, f32::consts::TAU, fs};
+
+use bevy::{
+    ecs::system::SystemParam,
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+use rand::prelude::*;
+use serde::Deserialize;
+
+use crate::game::{
+    core_mechanics::{
+        enemy_ai::{director::AiDirector, o_insan::spawn::spawn_o_insan},
+        oz_devinimli_yaratim::{
+            cells::{Cell, GenerationSettings},
+            grid::grid_to_world,
+            odyrules::commons::TileType,
+        },
+        pause::simulation_running,
+    },
+    spawn::player::Player,
+    ui::{
+        console::{ConsoleCommand, ConsoleState},
+        toasts::ShowToast,
+    },
+};
+
+pub const SEQUENCE_DIRECTORY: &str = "assets/sequences";
+pub const RUIN_SEQUENCE: &str = "ruin_ambush"; // Played the first time the player nears a ruin
+
+const RUIN_TRIGGER_RADIUS: f32 = 15.0;
+const DEFAULT_TRIGGER_RADIUS: f32 = 10.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(ScriptedSequences::load_or_default())
+        .init_resource::<SequencePlayer>()
+        .add_systems(
+            Update,
+            (
+                (place_ruin_triggers, enter_trigger_volumes, run_sequence)
+                    .chain()
+                    .run_if(simulation_running),
+                trigger_command,
+            ),
+        );
+}
+
+// Positions are relative to the volume that started the sequence
+#[derive(Deserialize, Debug, Clone)]
+pub enum ScriptStep {
+    // Flies the camera along the points over `secs`, then hands it back to the player
+    CameraPath {
+        points: Vec<(f32, f32, f32)>,
+        look_at: (f32, f32, f32),
+        secs: f32,
+    },
+    // Shown as a toast; the next step waits `secs`
+    Dialogue {
+        line: String,
+        secs: f32,
+    },
+    // Evenly round the volume, `radius` away
+    SpawnWave {
+        count: u32,
+        radius: f32,
+    },
+    Wait {
+        secs: f32,
+    },
+}
+
+impl ScriptStep {
+    pub fn secs(&self) -> f32 {
+        match self {
+            ScriptStep::CameraPath { secs, .. }
+            | ScriptStep::Dialogue { secs, .. }
+            | ScriptStep::Wait { secs } => *secs,
+            ScriptStep::SpawnWave { .. } => 0.0,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ScriptedSequence {
+    pub steps: Vec<ScriptStep>,
+}
+
+// One file per sequence in SEQUENCE_DIRECTORY, named by its file stem
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ScriptedSequences {
+    pub sequences: HashMap<String, ScriptedSequence>,
+}
+
+impl ScriptedSequences {
+    pub fn load() -> Result<Self, String> {
+        let entries = fs::read_dir(SEQUENCE_DIRECTORY)
+            .map_err(|error| format!("{}: {}", SEQUENCE_DIRECTORY, error))?;
+
+        let mut sequences = HashMap::new();
+        for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+            if path.extension().is_none_or(|extension| extension != "ron") {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let text = fs::read_to_string(&path)
+                .map_err(|error| format!("{}: {}", path.display(), error))?;
+            let sequence: ScriptedSequence =
+                ron::from_str(&text).map_err(|error| format!("{}: {}", path.display(), error))?;
+            sequences.insert(name.to_string(), sequence);
+        }
+        Ok(Self { sequences })
+    }
+
+    fn load_or_default() -> Self {
+        Self::load().unwrap_or_else(|error| {
+            warn!(
+                "Failed to load scripted sequences, triggers will do nothing: {}",
+                error
+            );
+            Self::default()
+        })
+    }
+}
+
+// Fires `sequence` when the player walks within `radius` of it on the ground plane. Volumes that
+// repeat fire again once the player has left and come back.
+#[derive(Component, Debug, Clone)]
+pub struct TriggerVolume {
+    pub sequence: String,
+    pub radius: f32,
+    pub repeat: bool,
+    inside: bool,
+}
+
+impl TriggerVolume {
+    pub fn new(sequence: impl Into<String>, radius: f32) -> Self {
+        Self {
+            sequence: sequence.into(),
+            radius,
+            repeat: false,
+            inside: false,
+        }
+    }
+}
+
+// Sequences play one at a time; any fired meanwhile wait their turn
+#[derive(Resource, Debug, Default)]
+pub struct SequencePlayer {
+    queue: VecDeque<(String, Vec3)>,
+    current: Option<RunningSequence>,
+}
+
+#[derive(Debug)]
+struct RunningSequence {
+    origin: Vec3,
+    steps: VecDeque<ScriptStep>,
+    step_secs: f32, // Into the front step; negative until it has started
+}
+
+impl SequencePlayer {
+    pub fn play(&mut self, sequence: impl Into<String>, origin: Vec3) {
+        self.queue.push_back((sequence.into(), origin));
+    }
+}
+
+// Camera systems stand down while a path has the camera
+pub fn camera_free(player: Res<SequencePlayer>) -> bool {
+    player
+        .current
+        .as_ref()
+        .is_none_or(|running| !matches!(running.steps.front(), Some(ScriptStep::CameraPath { .. })))
+}
+
+fn place_ruin_triggers(
+    mut commands: Commands,
+    mut placed: Local<HashSet<(i32, i32)>>,
+    changed_cells: Query<(&Cell, &Transform), Changed<Cell>>,
+    sequences: Res<ScriptedSequences>,
+) {
+    if !sequences.sequences.contains_key(RUIN_SEQUENCE) {
+        return;
+    }
+
+    // Not under the cell, so the volume outlives the cell unloading and never fires twice
+    for (cell, transform) in changed_cells.iter() {
+        if cell.tile_type == Some(TileType::RuinEntrance)
+            && cell.is_collapsed
+            && placed.insert(cell.position)
+        {
+            commands.spawn((
+                Name::new("Ruin trigger"),
+                TriggerVolume::new(RUIN_SEQUENCE, RUIN_TRIGGER_RADIUS),
+                Transform::from_translation(transform.translation),
+            ));
+        }
+    }
+}
+
+fn enter_trigger_volumes(
+    mut commands: Commands,
+    mut volumes: Query<(Entity, &mut TriggerVolume, &Transform)>,
+    mut sequence_player: ResMut<SequencePlayer>,
+    player: Single<&Transform, With<Player>>,
+) {
+    let player_position = player.translation.xz();
+    for (entity, mut volume, transform) in volumes.iter_mut() {
+        let inside = transform.translation.xz().distance(player_position) <= volume.radius;
+        if inside && !volume.inside {
+            println!("🎬 Triggered {}", volume.sequence);
+            sequence_player.play(volume.sequence.clone(), transform.translation);
+            if !volume.repeat {
+                commands.entity(entity).despawn();
+            }
+        }
+        volume.inside = inside;
+    }
+}
+
+#[derive(SystemParam)]
+struct WaveSpawner<'w, 's> {
+    commands: Commands<'w, 's>,
+    director: Res<'w, AiDirector>,
+    asset_server: Res<'w, AssetServer>,
+}
+
+impl WaveSpawner<'_, '_> {
+    fn spawn(&mut self, origin: Vec3, count: u32, radius: f32) {
+        let start = rand::rng().random_range(0.0..TAU);
+        for index in 0..count {
+            let angle = start + index as f32 / count as f32 * TAU;
+            let position = origin + Vec3::new(angle.cos(), 0.0, angle.sin()) * radius;
+            spawn_o_insan(
+                &mut self.commands,
+                position,
+                self.director.enemy_health,
+                &self.asset_server,
+            );
+        }
+    }
+}
+
+fn run_sequence(
+    mut sequence_player: ResMut<SequencePlayer>,
+    mut waves: WaveSpawner,
+    mut toasts: EventWriter<ShowToast>,
+    mut camera: Single<&mut Transform, (With<Camera3d>, Without<Player>)>,
+    player: Single<&Transform, (With<Player>, Without<Camera3d>)>,
+    sequences: Res<ScriptedSequences>,
+    time: Res<Time>,
+) {
+    if sequence_player.current.is_none() {
+        let Some((name, origin)) = sequence_player.queue.pop_front() else {
+            return;
+        };
+        let Some(sequence) = sequences.sequences.get(&name) else {
+            warn!("No scripted sequence named {}", name);
+            return;
+        };
+        sequence_player.current = Some(RunningSequence {
+            origin,
+            steps: sequence.steps.iter().cloned().collect(),
+            step_secs: -1.0,
+        });
+    }
+    let Some(running) = sequence_player.current.as_mut() else {
+        return;
+    };
+    let Some(step) = running.steps.front() else {
+        sequence_player.current = None;
+        return;
+    };
+
+    if running.step_secs < 0.0 {
+        running.step_secs = 0.0;
+        match step {
+            ScriptStep::CameraPath { .. } | ScriptStep::Wait { .. } => {}
+            ScriptStep::Dialogue { line, .. } => {
+                toasts.write(ShowToast {
+                    message: line.clone(),
+                });
+            }
+            ScriptStep::SpawnWave { count, radius } => {
+                waves.spawn(running.origin, *count, *radius);
+            }
+        }
+    } else {
+        running.step_secs += time.delta_secs();
+    }
+
+    let progress = (running.step_secs / step.secs()).min(1.0);
+    if let ScriptStep::CameraPath {
+        points, look_at, ..
+    } = step
+    {
+        let points: Vec<Vec3> = points
+            .iter()
+            .map(|&point| running.origin + Vec3::from(point))
+            .collect();
+        if let Some(position) = point_along(&points, progress) {
+            camera.translation = position;
+            camera.look_at(running.origin + Vec3::from(*look_at), Vec3::Y);
+        }
+    }
+
+    // Steps without a duration finish the frame they start; NaN from 0 / 0 compares false
+    if progress >= 1.0 || progress.is_nan() {
+        // The camera turns with the mouse just like the player, so this puts the view back where
+        // the player has been looking meanwhile
+        if matches!(step, ScriptStep::CameraPath { .. }) {
+            camera.rotation = player.rotation;
+        }
+        running.steps.pop_front();
+        running.step_secs = -1.0;
+    }
+}
+
+// `t` of the way along the polyline, by length
+fn point_along(points: &[Vec3], t: f32) -> Option<Vec3> {
+    let total: f32 = points
+        .windows(2)
+        .map(|pair| pair[0].distance(pair[1]))
+        .sum();
+    let mut remaining = total * t;
+    for pair in points.windows(2) {
+        let length = pair[0].distance(pair[1]);
+        if remaining <= length && length > 0.0 {
+            return Some(pair[0].lerp(pair[1], remaining / length));
+        }
+        remaining -= length;
+    }
+    points.last().copied()
+}
+
+// `trigger <sequence> <x> <z>` places a volume on the given grid cell; `trigger <sequence>`
+// plays the sequence where the player stands
+fn trigger_command(
+    mut commands: Commands,
+    mut commands_in: EventReader<ConsoleCommand>,
+    mut console: ResMut<ConsoleState>,
+    mut sequence_player: ResMut<SequencePlayer>,
+    sequences: Res<ScriptedSequences>,
+    player: Single<&Transform, With<Player>>,
+    settings: Res<GenerationSettings>,
+) {
+    for command in commands_in.read().filter(|c| c.name == "trigger") {
+        let Some(name) = command.args.first() else {
+            console.print("usage: trigger <sequence> [<x> <z>]");
+            continue;
+        };
+        if !sequences.sequences.contains_key(name) {
+            let mut names: Vec<&str> = sequences.sequences.keys().map(String::as_str).collect();
+            names.sort();
+            console.print(format!("no sequence {}; have {}", name, names.join(", ")));
+            continue;
+        }
+
+        match command.args[1..]
+            .iter()
+            .map(|arg| arg.parse::<i32>())
+            .collect::<Result<Vec<_>, _>>()
+            .as_deref()
+        {
+            Ok([]) => {
+                sequence_player.play(name.clone(), player.translation);
+                console.print(format!("playing {}", name));
+            }
+            Ok([x, z]) => {
+                let position = grid_to_world((*x, *z), settings.cell_edge_length as f32);
+                commands.spawn((
+                    Name::new("Placed trigger"),
+                    TriggerVolume::new(name.clone(), DEFAULT_TRIGGER_RADIUS),
+                    Transform::from_translation(position),
+                ));
+                console.print(format!("{} will play near ({}, {})", name, x, z));
+            }
+            _ => console.print("usage: trigger <sequence> [<x> <z>]"),
+        }
+    }
+}
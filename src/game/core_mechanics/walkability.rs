@@ -0,0 +1,242 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+    f32::consts::SQRT_2,
+};
+
+use bevy::{
+    ecs::system::SystemParam,
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+use strum::IntoEnumIterator;
+
+use crate::game::core_mechanics::oz_devinimli_yaratim::{
+    cells::{Cell, GenerationSettings},
+    grid::{GROUND_FLOOR, world_to_grid},
+    odyrules::commons::{TileMetadata, TileType},
+};
+
+const MAX_SEARCH_NODES: usize = 2048; // Cells a path search expands before giving up on the goal
+const NEIGHBOR_OFFSETS: [(i32, i32); 8] = [
+    (1, 0),
+    (-1, 0),
+    (0, 1),
+    (0, -1),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<WalkabilityGrid>()
+        .add_systems(Update, (forget_removed_cells, record_walkability).chain());
+}
+
+// Walkability of every loaded, collapsed ground-floor cell. Unsolved or unloaded cells are
+// absent; floors of structures stack on the same positions and aren't walked on yet.
+#[derive(Resource, Default)]
+pub struct WalkabilityGrid {
+    cells: HashMap<(i32, i32), (Entity, TileMetadata)>,
+}
+
+impl WalkabilityGrid {
+    pub fn get(&self, position: (i32, i32)) -> Option<bool> {
+        self.cells
+            .get(&position)
+            .map(|(_, metadata)| metadata.walkable)
+    }
+
+    // Solved, walkable and free of hazards; nowhere is safe before it has been generated
+    pub fn is_safe(&self, position: (i32, i32)) -> bool {
+        self.cells
+            .get(&position)
+            .is_some_and(|(_, metadata)| metadata.walkable && metadata.hazard.is_none())
+    }
+
+    // Plain ground speed where nothing has been solved yet
+    pub fn speed(&self, position: (i32, i32)) -> f32 {
+        self.cells
+            .get(&position)
+            .map_or(1.0, |(_, metadata)| metadata.speed)
+    }
+
+    // Unsolved cells count as open, so paths can lead out of the generated area
+    fn passable(&self, position: (i32, i32)) -> bool {
+        self.get(position).unwrap_or(true)
+    }
+
+    // A* over the eight neighbors of each cell, weighted by how slow the tiles are to cross.
+    // Diagonals can't cut the corner of a blocked cell. If the goal can't be reached within the
+    // search budget, the path leads to the closest cell found instead. The path excludes `start`.
+    pub fn search(&self, start: (i32, i32), goal: (i32, i32)) -> PathSearch {
+        // Octile distance at the speed of the fastest tile (roads beat plain ground), so it
+        // never overestimates
+        let fastest = TileType::iter()
+            .map(|tile| tile.metadata().speed)
+            .fold(1.0, f32::max);
+        let heuristic = |position: (i32, i32)| {
+            let dx = (position.0 - goal.0).abs() as f32;
+            let dz = (position.1 - goal.1).abs() as f32;
+            (dx.max(dz) + (SQRT_2 - 1.0) * dx.min(dz)) / fastest
+        };
+
+        let mut open = BinaryHeap::from([Reverse(PathNode {
+            estimate: heuristic(start),
+            position: start,
+        })]);
+        let mut costs = HashMap::from([(start, 0.0)]);
+        let mut came_from = HashMap::new();
+        let mut closed = HashSet::new();
+        let mut closest = (heuristic(start), start);
+
+        while let Some(Reverse(PathNode { position, .. })) = open.pop() {
+            if !closed.insert(position) {
+                continue; // A stale entry; the cell was reached more cheaply since
+            }
+            let remaining = heuristic(position);
+            if remaining < closest.0 {
+                closest = (remaining, position);
+            }
+            if position == goal || closed.len() > MAX_SEARCH_NODES {
+                break;
+            }
+
+            let cost = costs[&position];
+            for (dx, dz) in NEIGHBOR_OFFSETS {
+                let next = (position.0 + dx, position.1 + dz);
+                let diagonal = dx != 0 && dz != 0;
+                if !self.passable(next)
+                    || diagonal
+                        && !(self.passable((position.0 + dx, position.1))
+                            && self.passable((position.0, position.1 + dz)))
+                {
+                    continue;
+                }
+
+                let step = if diagonal { SQRT_2 } else { 1.0 };
+                let next_cost = cost + step / self.speed(next).max(0.1);
+                if costs.get(&next).is_some_and(|known| *known <= next_cost) {
+                    continue;
+                }
+                costs.insert(next, next_cost);
+                came_from.insert(next, position);
+                open.push(Reverse(PathNode {
+                    estimate: next_cost + heuristic(next),
+                    position: next,
+                }));
+            }
+        }
+
+        let mut path = Vec::new();
+        let mut position = closest.1;
+        while position != start {
+            path.push(position);
+            position = came_from[&position];
+        }
+        path.reverse();
+        PathSearch {
+            path,
+            #[cfg(feature = "debug-tools")]
+            costs,
+            #[cfg(feature = "debug-tools")]
+            closed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PathSearch {
+    pub path: Vec<(i32, i32)>,
+    // Only the path overlay looks at how the search spread
+    #[cfg(feature = "debug-tools")]
+    pub costs: HashMap<(i32, i32), f32>, // Cheapest cost from the start of every cell reached
+    #[cfg(feature = "debug-tools")]
+    pub closed: HashSet<(i32, i32)>,
+}
+
+#[cfg(feature = "debug-tools")]
+impl PathSearch {
+    // Reached but never expanded, as the search left them
+    pub fn open(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.costs
+            .keys()
+            .copied()
+            .filter(|position| !self.closed.contains(position))
+    }
+}
+
+// The last search of every pathing AI. Only present while something wants to look at them,
+// so pathing doesn't keep the sets around otherwise.
+#[derive(Resource, Debug, Default)]
+pub struct PathSearchTraces {
+    pub searches: HashMap<Entity, PathSearch>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PathNode {
+    estimate: f32, // Cost so far plus the heuristic
+    position: (i32, i32),
+}
+
+impl PartialEq for PathNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for PathNode {}
+
+impl PartialOrd for PathNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PathNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.estimate
+            .total_cmp(&other.estimate)
+            .then(self.position.cmp(&other.position))
+    }
+}
+
+// Speed multiplier of whatever tile lies under a world position, for the player and AI alike
+#[derive(SystemParam)]
+pub struct TerrainSpeed<'w> {
+    grid: Res<'w, WalkabilityGrid>,
+    settings: Res<'w, GenerationSettings>,
+}
+
+impl TerrainSpeed<'_> {
+    pub fn at(&self, point: Vec3) -> f32 {
+        self.grid
+            .speed(world_to_grid(point, self.settings.cell_edge_length as f32))
+    }
+}
+
+fn record_walkability(
+    mut grid: ResMut<WalkabilityGrid>,
+    changed_cells: Query<(Entity, &Cell), Changed<Cell>>,
+) {
+    for (entity, cell) in changed_cells.iter() {
+        if cell.floor != GROUND_FLOOR {
+            continue;
+        }
+        if let (true, Some(tile)) = (cell.is_collapsed, cell.tile_type) {
+            grid.cells
+                .insert(cell.position, (entity, tile.metadata()));
+        }
+    }
+}
+
+fn forget_removed_cells(mut grid: ResMut<WalkabilityGrid>, mut removed: RemovedComponents<Cell>) {
+    let removed: HashSet<Entity> = removed.read().collect();
+    if removed.is_empty() {
+        return;
+    }
+
+    grid.cells
+        .retain(|_, (entity, _)| !removed.contains(entity));
+}
@@ -0,0 +1,168 @@
+use bevy::{diagnostic::FrameCount, prelude::*};
+use rand::prelude::*;
+
+use crate::game::{
+    core_mechanics::{
+        ai_lod::{AiLod, AiLodSettings},
+        oz_devinimli_yaratim::{
+            cells::{Cell, GenerationSettings},
+            odyrules::commons::TileType,
+        },
+        pause::simulation_running,
+    },
+    spawn::player::Player,
+};
+
+const MAX_WILDLIFE: usize = 40;
+const FLEE_DISTANCE: f32 = 18.0;
+const BIRD_SPAWN_CHANCE: f64 = 0.08; // Per collapsed Tree cell
+const RABBIT_SPAWN_CHANCE: f64 = 0.03; // Per collapsed Ground cell
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Startup, setup_wildlife_assets).add_systems(
+        Update,
+        (spawn_wildlife, wildlife_flee, despawn_distant_wildlife).run_if(simulation_running),
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WildlifeKind {
+    Bird,
+    Rabbit,
+}
+
+#[derive(Component, Debug)]
+pub struct Wildlife {
+    pub kind: WildlifeKind,
+    pub flee_speed: f32,
+    pub fleeing: bool,
+}
+
+#[derive(Resource)]
+struct WildlifeAssets {
+    bird_mesh: Handle<Mesh>,
+    bird_material: Handle<StandardMaterial>,
+    rabbit_mesh: Handle<Mesh>,
+    rabbit_material: Handle<StandardMaterial>,
+}
+
+fn setup_wildlife_assets(
+    mut commands: Commands,
+    mut mesh_assets: ResMut<Assets<Mesh>>,
+    mut material_assets: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(WildlifeAssets {
+        bird_mesh: mesh_assets.add(Sphere::new(0.3)),
+        bird_material: material_assets.add(StandardMaterial {
+            base_color: Color::srgb(0.2, 0.2, 0.25),
+            ..Default::default()
+        }),
+        rabbit_mesh: mesh_assets.add(Capsule3d::new(0.3, 0.4)),
+        rabbit_material: material_assets.add(StandardMaterial {
+            base_color: Color::srgb(0.6, 0.5, 0.4),
+            ..Default::default()
+        }),
+    });
+}
+
+fn spawn_wildlife(
+    mut commands: Commands,
+    changed_cells: Query<(&Cell, &Transform), Changed<Cell>>,
+    existing: Query<(), With<Wildlife>>,
+    assets: Res<WildlifeAssets>,
+) {
+    let mut count = existing.iter().count();
+    let mut rng = rand::rng();
+
+    for (cell, transform) in changed_cells.iter() {
+        if count >= MAX_WILDLIFE {
+            return;
+        }
+
+        let (kind, height) = match cell.tile_type {
+            Some(TileType::Tree) if rng.random_bool(BIRD_SPAWN_CHANCE) => (WildlifeKind::Bird, 6.0),
+            Some(TileType::Ground) if rng.random_bool(RABBIT_SPAWN_CHANCE) => {
+                (WildlifeKind::Rabbit, 0.3)
+            }
+            _ => continue,
+        };
+
+        let (mesh, material, flee_speed) = match kind {
+            WildlifeKind::Bird => (assets.bird_mesh.clone(), assets.bird_material.clone(), 14.0),
+            WildlifeKind::Rabbit => (
+                assets.rabbit_mesh.clone(),
+                assets.rabbit_material.clone(),
+                9.0,
+            ),
+        };
+
+        commands.spawn((
+            Name::new(format!("{:?}", kind)),
+            Wildlife {
+                kind,
+                flee_speed,
+                fleeing: false,
+            },
+            AiLod::new(rng.random_range(0..8)),
+            Mesh3d(mesh),
+            MeshMaterial3d(material),
+            Transform::from_translation(transform.translation + Vec3::Y * height),
+        ));
+        count += 1;
+    }
+}
+
+fn wildlife_flee(
+    mut wildlife: Query<(&mut Transform, &mut Wildlife, &mut AiLod), Without<Player>>,
+    player: Single<&Transform, With<Player>>,
+    lod_settings: Res<AiLodSettings>,
+    frame: Res<FrameCount>,
+    time: Res<Time>,
+) {
+    for (mut transform, mut animal, mut lod) in wildlife.iter_mut() {
+        let Some(delta) = lod.tick(&frame, time.delta_secs(), &lod_settings) else {
+            continue;
+        };
+
+        let away = (transform.translation - player.translation).with_y(0.0);
+        if away.length() < FLEE_DISTANCE {
+            animal.fleeing = true;
+        }
+        if !animal.fleeing {
+            continue;
+        }
+
+        // Once startled they keep running until they are despawned
+        let mut direction = away.normalize_or_zero();
+        if animal.kind == WildlifeKind::Bird {
+            direction.y = 0.5;
+        }
+        transform.translation += direction * animal.flee_speed * delta;
+
+        let look_target = transform.translation + direction.with_y(0.0);
+        if look_target != transform.translation {
+            transform.look_at(look_target, Vec3::Y);
+        }
+    }
+}
+
+fn despawn_distant_wildlife(
+    mut commands: Commands,
+    wildlife: Query<(Entity, &Transform), With<Wildlife>>,
+    player: Single<&Transform, With<Player>>,
+    settings: Res<GenerationSettings>,
+) {
+    let despawn_distance = (settings.total_cells_on_edge as f32 * settings.cell_edge_length as f32)
+        * settings.spawn_distance;
+
+    for (entity, transform) in wildlife.iter() {
+        if transform
+            .translation
+            .with_y(0.0)
+            .distance(player.translation.with_y(0.0))
+            > despawn_distance
+        {
+            commands.entity(entity).despawn();
+        }
+    }
+}
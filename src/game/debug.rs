@@ -0,0 +1,249 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::common_conditions::on_timer};
+use strum::IntoEnumIterator;
+
+use crate::game::{
+    core_mechanics::{
+        enemy_ai::o_insan::{
+            components::{Dying, OInsanAI},
+            debug::ai_debug_system,
+            possession::{drive_possessed, release_dying_possessed, toggle_possession},
+        },
+        fast_travel::gate_menu_closed,
+        movement::PlayerTeleported,
+        oz_devinimli_yaratim::odycore::{
+            invariants::{AdjacencyAudit, check_adjacency_invariants},
+            resolve::resolve_command,
+        },
+        path_overlay::{draw_path_overlay, toggle_path_overlay},
+        pause::simulation_running,
+        status_effects::{ApplyStatus, StatusKind},
+        walkability::PathSearchTraces,
+    },
+    input::{Action, InputMap, action_just_pressed},
+    spawn::player::Player,
+    ui::{
+        console::{ConsoleCommand, ConsoleState, console_closed},
+        layers::UiLayer,
+    },
+};
+
+const DEBUG_FLAG: &str = "--debug";
+const INVARIANT_CHECK_SECS: u64 = 3;
+const STUN_SECS: f32 = 2.0; // For `status` without a duration
+const LINGERING_STATUS_SECS: f32 = 5.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(DebugMode::from_args())
+        .init_resource::<AdjacencyAudit>()
+        .add_systems(
+            Startup,
+            (print_debug_controls, spawn_debug_overlay).run_if(debug_mode_enabled),
+        )
+        .add_systems(
+            Update,
+            (
+                ai_debug_system.run_if(console_closed.and(gate_menu_closed)),
+                (
+                    toggle_possession.run_if(
+                        action_just_pressed(Action::PossessAI)
+                            .and(console_closed)
+                            .and(gate_menu_closed),
+                    ),
+                    release_dying_possessed,
+                    drive_possessed.run_if(console_closed.and(simulation_running)),
+                )
+                    .chain(),
+                teleport_command,
+                resolve_command,
+                status_command,
+                (
+                    toggle_path_overlay.run_if(
+                        action_just_pressed(Action::TogglePathOverlay)
+                            .and(console_closed)
+                            .and(gate_menu_closed),
+                    ),
+                    draw_path_overlay.run_if(resource_exists::<PathSearchTraces>),
+                )
+                    .chain(),
+                (
+                    check_adjacency_invariants
+                        .run_if(on_timer(Duration::from_secs(INVARIANT_CHECK_SECS))),
+                    update_debug_overlay.run_if(resource_changed::<AdjacencyAudit>),
+                )
+                    .chain(),
+            )
+                .run_if(debug_mode_enabled),
+        );
+}
+
+#[derive(Component)]
+struct DebugOverlay;
+
+// Cheats need both a build with the `debug-tools` feature and `--debug` on the command line
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct DebugMode {
+    pub enabled: bool,
+}
+
+impl DebugMode {
+    fn from_args() -> Self {
+        Self {
+            enabled: std::env::args().any(|arg| arg == DEBUG_FLAG),
+        }
+    }
+}
+
+pub fn debug_mode_enabled(mode: Res<DebugMode>) -> bool {
+    mode.enabled
+}
+
+fn print_debug_controls(input_map: Res<InputMap>) {
+    println!("🎮 DEBUG CONTROLS:");
+    for (action, description) in [
+        (Action::DamageAI, "damage AI (-20 health)"),
+        (Action::HealAI, "heal AI (+20 health)"),
+        (Action::ToggleWeapon, "toggle player weapon"),
+        (Action::InspectAI, "print AI state"),
+        (Action::TogglePathOverlay, "toggle the path overlay"),
+        (Action::PossessAI, "possess the nearest AI, or release it"),
+    ] {
+        println!("   Press '{}' to {}", input_map.label(action), description);
+    }
+    println!(
+        "   Console: tp <x> <z>, resolve [<chunk_x> <chunk_z>], status <effect> [secs] [player]"
+    );
+    println!();
+}
+
+fn spawn_debug_overlay(mut commands: Commands) {
+    commands.spawn((
+        Name::new("DebugOverlay"),
+        DebugOverlay,
+        Text::new("adjacency: not checked yet"),
+        TextFont::from_font_size(14.0),
+        TextColor(Color::srgb(0.6, 1.0, 0.6)),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(8.0),
+            left: Val::Px(8.0),
+            ..Default::default()
+        },
+        UiLayer::Hud.z_index(),
+    ));
+}
+
+fn update_debug_overlay(
+    audit: Res<AdjacencyAudit>,
+    mut overlay: Single<&mut Text, With<DebugOverlay>>,
+) {
+    overlay.0 = format!(
+        "adjacency: {} bad of {} sides, {} logged ({} scans)",
+        audit.violations, audit.checked_sides, audit.reported, audit.scans
+    );
+}
+
+// `tp <x> <z>` in world units
+fn teleport_command(
+    mut commands_in: EventReader<ConsoleCommand>,
+    mut teleports: EventWriter<PlayerTeleported>,
+    mut console: ResMut<ConsoleState>,
+    mut player: Single<&mut Transform, With<Player>>,
+) {
+    for command in commands_in.read().filter(|c| c.name == "tp") {
+        let coordinates: Vec<f32> = command
+            .args
+            .iter()
+            .filter_map(|arg| arg.parse().ok())
+            .collect();
+        let [x, z] = coordinates[..] else {
+            console.print("usage: tp <x> <z>");
+            continue;
+        };
+
+        let from = player.translation;
+        player.translation = Vec3::new(x, from.y, z);
+        teleports.write(PlayerTeleported {
+            from,
+            to: player.translation,
+        });
+        console.print(format!("teleported to ({}, {})", x, z));
+    }
+}
+
+type LivingAiQuery<'w, 's> =
+    Query<'w, 's, (Entity, &'static Transform), (With<OInsanAI>, Without<Dying>)>;
+
+// `status <stun|slow|burn> [secs] [player]` puts an effect on the AI nearest the player, or on
+// the player themselves
+fn status_command(
+    mut commands_in: EventReader<ConsoleCommand>,
+    mut console: ResMut<ConsoleState>,
+    mut statuses: EventWriter<ApplyStatus>,
+    player: Single<(Entity, &Transform), With<Player>>,
+    ais: LivingAiQuery,
+) {
+    let (player_entity, player_transform) = *player;
+    for command in commands_in.read().filter(|c| c.name == "status") {
+        let names: Vec<_> = StatusKind::iter().map(StatusKind::name).collect();
+        let usage = format!("usage: status <{}> [secs] [player]", names.join("|"));
+        let Some((name, rest)) = command.args.split_first() else {
+            console.print(usage);
+            continue;
+        };
+        let Some(kind) = StatusKind::iter().find(|kind| kind.name() == name) else {
+            console.print(usage);
+            continue;
+        };
+
+        let mut duration_secs = match kind {
+            StatusKind::Stun => STUN_SECS,
+            StatusKind::Slow | StatusKind::Burn => LINGERING_STATUS_SECS,
+        };
+        let mut on_player = false;
+        let mut understood = true;
+        for arg in rest {
+            match arg.parse::<f32>() {
+                Ok(secs) if secs > 0.0 => duration_secs = secs,
+                _ if arg == "player" => on_player = true,
+                _ => understood = false,
+            }
+        }
+        if !understood {
+            console.print(usage);
+            continue;
+        }
+
+        let distance_to_player =
+            |transform: &Transform| transform.translation.distance(player_transform.translation);
+        let target = if on_player {
+            Some(player_entity)
+        } else {
+            ais.iter()
+                .min_by(|(_, a), (_, b)| distance_to_player(a).total_cmp(&distance_to_player(b)))
+                .map(|(entity, _)| entity)
+        };
+        let Some(target) = target else {
+            console.print("no AI to put it on");
+            continue;
+        };
+
+        statuses.write(ApplyStatus {
+            target,
+            kind,
+            duration_secs,
+        });
+        let on = if on_player {
+            "the player"
+        } else {
+            "the nearest AI"
+        };
+        console.print(format!(
+            "{} on {} for {:.1}s",
+            kind.name(),
+            on,
+            duration_secs
+        ));
+    }
+}
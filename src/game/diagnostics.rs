@@ -0,0 +1,42 @@
+use bevy::{
+    diagnostic::{
+        Diagnostic, DiagnosticPath, Diagnostics, EntityCountDiagnosticsPlugin,
+        FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin, RegisterDiagnostic,
+    },
+    prelude::*,
+};
+
+use crate::game::core_mechanics::{
+    enemy_ai::o_insan::components::OInsanAI,
+    oz_devinimli_yaratim::{cells::Cell, odycore::open_space::OpenSpacePropagationQueue},
+};
+
+const WFC_QUEUE: DiagnosticPath = DiagnosticPath::const_new("wfc/propagation_queue");
+const WFC_UNCOLLAPSED: DiagnosticPath = DiagnosticPath::const_new("wfc/uncollapsed_cells");
+const AI_COUNT: DiagnosticPath = DiagnosticPath::const_new("ai/count");
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins((
+        FrameTimeDiagnosticsPlugin::default(),
+        EntityCountDiagnosticsPlugin,
+        LogDiagnosticsPlugin::default(),
+    ))
+    .register_diagnostic(Diagnostic::new(WFC_QUEUE))
+    .register_diagnostic(Diagnostic::new(WFC_UNCOLLAPSED))
+    .register_diagnostic(Diagnostic::new(AI_COUNT))
+    .add_systems(Last, measure_workload);
+}
+
+// Workload sizes next to frame time, to tell a slow system apart from a big view distance
+fn measure_workload(
+    mut diagnostics: Diagnostics,
+    queue: Res<OpenSpacePropagationQueue>,
+    cells: Query<&Cell>,
+    ais: Query<(), With<OInsanAI>>,
+) {
+    diagnostics.add_measurement(&WFC_QUEUE, || queue.queue.len() as f64);
+    diagnostics.add_measurement(&WFC_UNCOLLAPSED, || {
+        cells.iter().filter(|cell| !cell.is_collapsed).count() as f64
+    });
+    diagnostics.add_measurement(&AI_COUNT, || ais.iter().len() as f64);
+}
@@ -0,0 +1,234 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::game::save::save_ron;
+
+const INPUT_PATH: &str = "saves/input.ron";
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(InputMap::load_or_default());
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Action {
+    MoveForward,
+    MoveBack,
+    MoveLeft,
+    MoveRight,
+    Jump,
+    Sprint,
+    Crouch,
+    Attack,
+    Focus,
+    Interact,
+    NextSlot,
+    DropItem,
+    EmoteWheel,
+    ReleaseCursor,
+    ToggleConsole,
+    WorldMap,
+    Reputation,
+    DifficultyPanel,
+    Confirm, // Starts a new run from the end screen
+    QuickSave,
+    QuickLoad,
+    // Destinations in the fast-travel gate menu, nearest first
+    TravelTo1,
+    TravelTo2,
+    TravelTo3,
+    TravelTo4,
+    TravelTo5,
+    TravelTo6,
+    TravelTo7,
+    TravelTo8,
+    TravelTo9,
+    // Cheats, with the debug-tools feature and --debug
+    DamageAI,
+    HealAI,
+    ToggleWeapon,
+    InspectAI,
+    TogglePathOverlay,
+    PossessAI,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+}
+
+// Written out with the defaults on first run; edit the file to rebind. Actions missing from the
+// file keep their default bindings, so files from older versions pick up new actions.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct InputMap {
+    pub bindings: BTreeMap<Action, Vec<Binding>>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        use Binding::{Key, Mouse};
+
+        let bindings = [
+            (
+                Action::MoveForward,
+                vec![Key(KeyCode::KeyW), Key(KeyCode::ArrowUp)],
+            ),
+            (
+                Action::MoveBack,
+                vec![Key(KeyCode::KeyS), Key(KeyCode::ArrowDown)],
+            ),
+            (
+                Action::MoveLeft,
+                vec![Key(KeyCode::KeyA), Key(KeyCode::ArrowLeft)],
+            ),
+            (
+                Action::MoveRight,
+                vec![Key(KeyCode::KeyD), Key(KeyCode::ArrowRight)],
+            ),
+            (Action::Jump, vec![Key(KeyCode::Space)]),
+            (Action::Sprint, vec![Key(KeyCode::ShiftLeft)]),
+            (Action::Crouch, vec![Key(KeyCode::ControlLeft)]),
+            (Action::Attack, vec![Mouse(MouseButton::Left)]),
+            (Action::Focus, vec![Mouse(MouseButton::Right)]),
+            (Action::Interact, vec![Key(KeyCode::KeyE)]),
+            (Action::NextSlot, vec![Key(KeyCode::Tab)]),
+            (Action::DropItem, vec![Key(KeyCode::KeyQ)]),
+            (Action::EmoteWheel, vec![Key(KeyCode::KeyG)]),
+            (Action::ReleaseCursor, vec![Key(KeyCode::Escape)]),
+            (Action::ToggleConsole, vec![Key(KeyCode::Backquote)]),
+            (Action::WorldMap, vec![Key(KeyCode::KeyM)]),
+            (Action::Reputation, vec![Key(KeyCode::KeyR)]),
+            (Action::DifficultyPanel, vec![Key(KeyCode::KeyP)]),
+            (Action::Confirm, vec![Key(KeyCode::Enter)]),
+            (Action::QuickSave, vec![Key(KeyCode::F5)]),
+            (Action::QuickLoad, vec![Key(KeyCode::F9)]),
+            (Action::TravelTo1, vec![Key(KeyCode::Digit1)]),
+            (Action::TravelTo2, vec![Key(KeyCode::Digit2)]),
+            (Action::TravelTo3, vec![Key(KeyCode::Digit3)]),
+            (Action::TravelTo4, vec![Key(KeyCode::Digit4)]),
+            (Action::TravelTo5, vec![Key(KeyCode::Digit5)]),
+            (Action::TravelTo6, vec![Key(KeyCode::Digit6)]),
+            (Action::TravelTo7, vec![Key(KeyCode::Digit7)]),
+            (Action::TravelTo8, vec![Key(KeyCode::Digit8)]),
+            (Action::TravelTo9, vec![Key(KeyCode::Digit9)]),
+            (Action::DamageAI, vec![Key(KeyCode::Digit1)]),
+            (Action::HealAI, vec![Key(KeyCode::Digit2)]),
+            (Action::ToggleWeapon, vec![Key(KeyCode::Digit3)]),
+            (Action::InspectAI, vec![Key(KeyCode::Digit4)]),
+            (Action::TogglePathOverlay, vec![Key(KeyCode::Digit5)]),
+            (Action::PossessAI, vec![Key(KeyCode::Digit6)]),
+        ];
+        Self {
+            bindings: bindings.into_iter().collect(),
+        }
+    }
+}
+
+impl InputMap {
+    fn load() -> Result<Self, String> {
+        let text = fs::read_to_string(INPUT_PATH).map_err(|error| error.to_string())?;
+        let mut map: InputMap = ron::from_str(&text).map_err(|error| error.to_string())?;
+        for (action, bindings) in InputMap::default().bindings {
+            map.bindings.entry(action).or_insert(bindings);
+        }
+        Ok(map)
+    }
+
+    fn load_or_default() -> Self {
+        // A file that doesn't parse is left alone, so a typo doesn't cost the player a rebinding
+        if Path::new(INPUT_PATH).exists() {
+            return Self::load().unwrap_or_else(|error| {
+                warn!(
+                    "Failed to read {}, using the default bindings: {}",
+                    INPUT_PATH, error
+                );
+                Self::default()
+            });
+        }
+
+        let map = Self::default();
+        if let Err(error) = save_ron(INPUT_PATH, &map) {
+            warn!("Failed to write {}: {}", INPUT_PATH, error);
+        }
+        map
+    }
+
+    pub fn bindings(&self, action: Action) -> &[Binding] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    // The first binding, as hints on screen should name it
+    pub fn label(&self, action: Action) -> String {
+        match self.bindings(action).first() {
+            Some(Binding::Key(code)) => {
+                let name = format!("{:?}", code);
+                name.strip_prefix("Key")
+                    .or_else(|| name.strip_prefix("Digit"))
+                    .unwrap_or(&name)
+                    .to_string()
+            }
+            Some(Binding::Mouse(button)) => format!("{:?} Mouse", button),
+            None => "unbound".to_string(),
+        }
+    }
+}
+
+// Button state by action rather than by key
+#[derive(SystemParam)]
+pub struct Actions<'w> {
+    map: Res<'w, InputMap>,
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    mouse: Res<'w, ButtonInput<MouseButton>>,
+}
+
+impl Actions<'_> {
+    fn any(
+        &self,
+        action: Action,
+        key: impl Fn(KeyCode) -> bool,
+        button: impl Fn(MouseButton) -> bool,
+    ) -> bool {
+        self.map
+            .bindings(action)
+            .iter()
+            .any(|binding| match *binding {
+                Binding::Key(code) => key(code),
+                Binding::Mouse(mouse_button) => button(mouse_button),
+            })
+    }
+
+    pub fn pressed(&self, action: Action) -> bool {
+        self.any(
+            action,
+            |code| self.keys.pressed(code),
+            |button| self.mouse.pressed(button),
+        )
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.any(
+            action,
+            |code| self.keys.just_pressed(code),
+            |button| self.mouse.just_pressed(button),
+        )
+    }
+
+    pub fn just_released(&self, action: Action) -> bool {
+        self.any(
+            action,
+            |code| self.keys.just_released(code),
+            |button| self.mouse.just_released(button),
+        )
+    }
+}
+
+// Run conditions, like bevy's input_just_pressed but by action
+pub fn action_just_pressed(action: Action) -> impl FnMut(Actions) -> bool + Clone {
+    move |actions: Actions| actions.just_pressed(action)
+}
+
+pub fn action_just_released(action: Action) -> impl FnMut(Actions) -> bool + Clone {
+    move |actions: Actions| actions.just_released(action)
+}
@@ -1,11 +1,36 @@
+pub mod audio;
 pub mod core_mechanics; 
+pub mod music;
+#[cfg(feature = "debug-tools")]
+pub mod debug;
+#[cfg(feature = "trace")]
+pub mod diagnostics;
+pub mod input;
+pub mod save;
+pub mod settings;
 pub mod spawn; 
+pub mod telemetry;
+pub mod ui;
+pub mod validate;
 
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
         spawn::plugin,        
+        audio::plugin,
+        music::plugin,
+        ui::plugin,
         core_mechanics::plugin, 
+        save::plugin,
+        settings::plugin,
+        input::plugin,
+        telemetry::plugin,
     ));
+
+    #[cfg(feature = "debug-tools")]
+    app.add_plugins(debug::plugin);
+
+    #[cfg(feature = "trace")]
+    app.add_plugins(diagnostics::plugin);
 }
@@ -0,0 +1,88 @@
+use bevy::{
+    audio::Volume,
+    prelude::*,
+    render::primitives::{Frustum, Sphere as BoundingSphere},
+};
+
+use crate::game::core_mechanics::oz_devinimli_yaratim::{
+    cells::GenerationSettings,
+    grid::grid_to_world,
+    structures::{StructureCompleted, StructureKind},
+};
+
+const STINGER_RANGE: f32 = 150.0; // Structures finishing further away pass without music
+const STINGER_COOLDOWN_SECS: f32 = 45.0;
+const STINGER_VOLUME: f32 = 0.35;
+const FOUNTAIN_STINGER: &str = "music/stinger_fountain.ogg";
+const VILLAGE_STINGER: &str = "music/stinger_village.ogg";
+pub const STINGER_PATHS: [&str; 2] = [FOUNTAIN_STINGER, VILLAGE_STINGER];
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Startup, setup_stinger_handles)
+        .add_systems(Update, play_structure_stingers);
+}
+
+#[derive(Resource)]
+struct StingerHandles {
+    fountain: Handle<AudioSource>,
+    village: Handle<AudioSource>,
+}
+
+fn setup_stinger_handles(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(StingerHandles {
+        fountain: asset_server.load(FOUNTAIN_STINGER),
+        village: asset_server.load(VILLAGE_STINGER),
+    });
+}
+
+// A short phrase when the player watches a structure finish, at most one per cooldown
+fn play_structure_stingers(
+    mut commands: Commands,
+    mut completed: EventReader<StructureCompleted>,
+    handles: Res<StingerHandles>,
+    camera: Single<(&Frustum, &GlobalTransform), With<Camera3d>>,
+    settings: Res<GenerationSettings>,
+    time: Res<Time>,
+    mut last_played: Local<Option<f32>>,
+) {
+    let (frustum, camera_transform) = *camera;
+    let cell_edge_length = settings.cell_edge_length as f32;
+    let now = time.elapsed_secs();
+
+    for structure in completed.read() {
+        // Houses finish inside a village, which gets the stinger
+        let handle = match structure.kind {
+            StructureKind::FountainPlaza => handles.fountain.clone(),
+            StructureKind::Village => handles.village.clone(),
+            StructureKind::Building => continue,
+        };
+        if last_played.is_some_and(|played_at| now - played_at < STINGER_COOLDOWN_SECS) {
+            continue;
+        }
+
+        let (min, max) = structure.bounds;
+        let (min, max) = (
+            grid_to_world(min, cell_edge_length),
+            grid_to_world(max, cell_edge_length),
+        );
+        let center = (min + max) / 2.0;
+        if center.distance(camera_transform.translation()) > STINGER_RANGE {
+            continue;
+        }
+        let sphere = BoundingSphere {
+            center: center.into(),
+            radius: min.distance(max) / 2.0 + cell_edge_length,
+        };
+        if !frustum.intersects_sphere(&sphere, true) {
+            continue;
+        }
+
+        commands.spawn((
+            Name::new(format!("Stinger_{:?}", structure.kind)),
+            AudioPlayer(handle),
+            PlaybackSettings::DESPAWN.with_volume(Volume::Linear(STINGER_VOLUME)),
+        ));
+        println!("🎵 A {:?} takes shape before your eyes", structure.kind);
+        *last_played = Some(now);
+    }
+}
@@ -0,0 +1,887 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::game::{
+    core_mechanics::{
+        difficulty::{Difficulty, DifficultyPreset},
+        enemy_ai::o_insan::components::{AIBehavior, EmotionalState},
+        exploration::ExploredCells,
+        interactions::{InteractionState, InteractionStore},
+        inventory::{HOTBAR_SLOTS, Inventory, Item, ItemStack},
+        oz_devinimli_yaratim::{
+            cells::CollapsedTileCache,
+            odycore::snapshot::{CellSnapshot, PropagationSnapshot},
+            odyrules::commons::TileType,
+        },
+        permadeath::GameMode,
+        play_stats::PlayStats,
+        reputation::{Faction, Reputation},
+    },
+    save::{SAVE_VERSION, SaveFile, SavedEnemy, SavedMind},
+    ui::world_map::MapWaypoints,
+};
+
+// Saves written before versioning have no `version` field
+const LEGACY_VERSION: u32 = 1;
+
+// Each older format is frozen here together with a step to the next version.
+// When SaveFile changes: bump SAVE_VERSION, copy the old layout in as `SaveFileV<n>`
+// and add an `upgrade_v<n>` step, so every old save walks the chain to the current one.
+// Old layouts never embed the live types: each struct they hold is frozen alongside them,
+// named after the version it first appeared in, and turned into the live type on the way
+// to SaveFile. Plain enums like TileType only ever gain variants, so they're used as is.
+
+#[derive(Deserialize)]
+struct VersionHeader {
+    #[serde(default = "legacy_version")]
+    version: u32,
+}
+
+fn legacy_version() -> u32 {
+    LEGACY_VERSION
+}
+
+pub fn parse_save(text: &str) -> Result<SaveFile, String> {
+    let header: VersionHeader = ron::from_str(text).map_err(|error| error.to_string())?;
+
+    match header.version {
+        1 => ron::from_str::<SaveFileV1>(text)
+            .map(upgrade_v1)
+            .map(upgrade_v2)
+            .map(upgrade_v3)
+            .map(upgrade_v4)
+            .map(upgrade_v5)
+            .map(upgrade_v6)
+            .map(upgrade_v7)
+            .map(upgrade_v8)
+            .map(upgrade_v9)
+            .map_err(|error| error.to_string()),
+        2 => ron::from_str::<SaveFileV2>(text)
+            .map(upgrade_v2)
+            .map(upgrade_v3)
+            .map(upgrade_v4)
+            .map(upgrade_v5)
+            .map(upgrade_v6)
+            .map(upgrade_v7)
+            .map(upgrade_v8)
+            .map(upgrade_v9)
+            .map_err(|error| error.to_string()),
+        3 => ron::from_str::<SaveFileV3>(text)
+            .map(upgrade_v3)
+            .map(upgrade_v4)
+            .map(upgrade_v5)
+            .map(upgrade_v6)
+            .map(upgrade_v7)
+            .map(upgrade_v8)
+            .map(upgrade_v9)
+            .map_err(|error| error.to_string()),
+        4 => ron::from_str::<SaveFileV4>(text)
+            .map(upgrade_v4)
+            .map(upgrade_v5)
+            .map(upgrade_v6)
+            .map(upgrade_v7)
+            .map(upgrade_v8)
+            .map(upgrade_v9)
+            .map_err(|error| error.to_string()),
+        5 => ron::from_str::<SaveFileV5>(text)
+            .map(upgrade_v5)
+            .map(upgrade_v6)
+            .map(upgrade_v7)
+            .map(upgrade_v8)
+            .map(upgrade_v9)
+            .map_err(|error| error.to_string()),
+        6 => ron::from_str::<SaveFileV6>(text)
+            .map(upgrade_v6)
+            .map(upgrade_v7)
+            .map(upgrade_v8)
+            .map(upgrade_v9)
+            .map_err(|error| error.to_string()),
+        7 => ron::from_str::<SaveFileV7>(text)
+            .map(upgrade_v7)
+            .map(upgrade_v8)
+            .map(upgrade_v9)
+            .map_err(|error| error.to_string()),
+        8 => ron::from_str::<SaveFileV8>(text)
+            .map(upgrade_v8)
+            .map(upgrade_v9)
+            .map_err(|error| error.to_string()),
+        9 => ron::from_str::<SaveFileV9>(text)
+            .map(upgrade_v9)
+            .map_err(|error| error.to_string()),
+        SAVE_VERSION => ron::from_str::<SaveFile>(text).map_err(|error| error.to_string()),
+        version => Err(format!(
+            "save version {} is newer than supported version {}",
+            version, SAVE_VERSION
+        )),
+    }
+}
+
+// v1: domains stored as a bitmask over TileType declaration order
+#[derive(Deserialize)]
+struct SaveFileV1 {
+    player_position: (f32, f32, f32),
+    wfc: SnapshotV1,
+}
+
+#[derive(Deserialize)]
+struct SnapshotV1 {
+    cells: Vec<CellSnapshotV1>,
+    queue: Vec<(i32, i32)>,
+}
+
+#[derive(Deserialize)]
+struct CellSnapshotV1 {
+    position: (i32, i32),
+    tile_type: Option<TileType>,
+    valid_tiles: u32,
+}
+
+// TileType order at the time v1 saves were written; bit n is V1_TILE_ORDER[n]
+const V1_TILE_ORDER: [TileType; 19] = [
+    TileType::Ground,
+    TileType::Tree,
+    TileType::Chest,
+    TileType::FountainCenter,
+    TileType::FountainCorner1,
+    TileType::FountainCorner2,
+    TileType::FountainCorner3,
+    TileType::FountainCorner4,
+    TileType::FountainEdge1,
+    TileType::FountainEdge2,
+    TileType::FountainEdge3,
+    TileType::FountainEdge4,
+    TileType::ThornBush,
+    TileType::DeepWater,
+    TileType::Wall,
+    TileType::Door,
+    TileType::Floor,
+    TileType::Path,
+    TileType::Well,
+];
+
+fn upgrade_v1(save: SaveFileV1) -> SaveFileV2 {
+    let cells = save
+        .wfc
+        .cells
+        .into_iter()
+        .map(|cell| CellSnapshotV2 {
+            position: cell.position,
+            tile_type: cell.tile_type,
+            valid_tiles: V1_TILE_ORDER
+                .iter()
+                .enumerate()
+                .filter(|(bit, _)| cell.valid_tiles & (1 << bit) != 0)
+                .map(|(_, tile)| *tile)
+                .collect(),
+        })
+        .collect();
+
+    SaveFileV2 {
+        player_position: save.player_position,
+        wfc: SnapshotV2 {
+            cells,
+            queue: save.wfc.queue,
+        },
+    }
+}
+
+// v2: no interaction state; chests, fountains and doors start fresh
+#[derive(Deserialize)]
+struct SaveFileV2 {
+    player_position: (f32, f32, f32),
+    wfc: SnapshotV2,
+}
+
+#[derive(Deserialize)]
+struct SnapshotV2 {
+    cells: Vec<CellSnapshotV2>,
+    queue: Vec<(i32, i32)>,
+}
+
+#[derive(Deserialize)]
+struct CellSnapshotV2 {
+    position: (i32, i32),
+    tile_type: Option<TileType>,
+    valid_tiles: Vec<TileType>,
+}
+
+impl From<SnapshotV2> for PropagationSnapshot {
+    fn from(snapshot: SnapshotV2) -> Self {
+        Self {
+            cells: snapshot
+                .cells
+                .into_iter()
+                .map(|cell| CellSnapshot {
+                    position: cell.position,
+                    tile_type: cell.tile_type,
+                    valid_tiles: cell.valid_tiles,
+                })
+                .collect(),
+            queue: snapshot.queue,
+        }
+    }
+}
+
+fn upgrade_v2(save: SaveFileV2) -> SaveFileV3 {
+    SaveFileV3 {
+        player_position: save.player_position,
+        wfc: save.wfc,
+        interactions: InteractionStoreV3::default(),
+    }
+}
+
+// v3: no difficulty; older worlds continue on Normal
+#[derive(Deserialize)]
+struct SaveFileV3 {
+    player_position: (f32, f32, f32),
+    wfc: SnapshotV2,
+    interactions: InteractionStoreV3,
+}
+
+#[derive(Deserialize, Default)]
+struct InteractionStoreV3 {
+    play_time: f64,
+    tiles: HashMap<(i32, i32), InteractionStateV3>,
+}
+
+#[derive(Deserialize)]
+struct InteractionStateV3 {
+    tile: TileType,
+    opened: bool,
+    looted: bool,
+    last_used: Option<f64>,
+}
+
+impl From<InteractionStoreV3> for InteractionStore {
+    fn from(store: InteractionStoreV3) -> Self {
+        Self {
+            play_time: store.play_time,
+            tiles: store
+                .tiles
+                .into_iter()
+                .map(|(position, state)| {
+                    let state = InteractionState {
+                        tile: state.tile,
+                        opened: state.opened,
+                        looted: state.looted,
+                        last_used: state.last_used,
+                    };
+                    (position, state)
+                })
+                .collect(),
+        }
+    }
+}
+
+fn upgrade_v3(save: SaveFileV3) -> SaveFileV4 {
+    let normal = Difficulty::default();
+    SaveFileV4 {
+        player_position: save.player_position,
+        wfc: save.wfc,
+        interactions: save.interactions,
+        difficulty: DifficultyV4 {
+            preset: normal.preset,
+            enemy_damage: normal.enemy_damage,
+            spawn_density: normal.spawn_density,
+            stamina_drain: normal.stamina_drain,
+        },
+    }
+}
+
+// v4: no player rotation, tile cache or enemies; unloaded areas regenerate and the
+// director repopulates
+#[derive(Deserialize)]
+struct SaveFileV4 {
+    player_position: (f32, f32, f32),
+    wfc: SnapshotV2,
+    interactions: InteractionStoreV3,
+    difficulty: DifficultyV4,
+}
+
+#[derive(Deserialize)]
+struct DifficultyV4 {
+    preset: DifficultyPreset,
+    enemy_damage: f32,
+    spawn_density: f32,
+    stamina_drain: f32,
+}
+
+impl From<DifficultyV4> for Difficulty {
+    fn from(difficulty: DifficultyV4) -> Self {
+        Self {
+            preset: difficulty.preset,
+            enemy_damage: difficulty.enemy_damage,
+            spawn_density: difficulty.spawn_density,
+            stamina_drain: difficulty.stamina_drain,
+        }
+    }
+}
+
+fn upgrade_v4(save: SaveFileV4) -> SaveFileV5 {
+    SaveFileV5 {
+        player_position: save.player_position,
+        player_rotation: (0.0, 0.0, 0.0, 1.0),
+        wfc: save.wfc,
+        tile_cache: TileCacheV5::default(),
+        interactions: save.interactions,
+        difficulty: save.difficulty,
+        enemies: Vec::new(),
+    }
+}
+
+// v5: no reputation or factions; every faction starts neutral and saved enemies were Drifters
+#[derive(Deserialize)]
+struct SaveFileV5 {
+    player_position: (f32, f32, f32),
+    player_rotation: (f32, f32, f32, f32),
+    wfc: SnapshotV2,
+    tile_cache: TileCacheV5,
+    interactions: InteractionStoreV3,
+    difficulty: DifficultyV4,
+    enemies: Vec<SavedEnemyV5>,
+}
+
+#[derive(Deserialize, Default)]
+struct TileCacheV5 {
+    tiles: HashMap<(i32, i32), TileType>,
+}
+
+impl From<TileCacheV5> for CollapsedTileCache {
+    fn from(cache: TileCacheV5) -> Self {
+        Self { tiles: cache.tiles }
+    }
+}
+
+#[derive(Deserialize)]
+struct SavedEnemyV5 {
+    position: (f32, f32, f32),
+    health: f32,
+    max_health: f32,
+    home: (f32, f32, f32),
+    aggression: f32,
+}
+
+fn upgrade_v5(save: SaveFileV5) -> SaveFileV6 {
+    SaveFileV6 {
+        player_position: save.player_position,
+        player_rotation: save.player_rotation,
+        wfc: save.wfc,
+        tile_cache: save.tile_cache,
+        interactions: save.interactions,
+        difficulty: save.difficulty,
+        enemies: save
+            .enemies
+            .into_iter()
+            .map(|enemy| SavedEnemyV7 {
+                position: enemy.position,
+                health: enemy.health,
+                max_health: enemy.max_health,
+                home: enemy.home,
+                aggression: enemy.aggression,
+                faction: Faction::Drifters,
+            })
+            .collect(),
+        reputation: ReputationV6::default(),
+    }
+}
+
+// v6: no game mode or play stats; older worlds were never hardcore and start counting now
+#[derive(Deserialize)]
+struct SaveFileV6 {
+    player_position: (f32, f32, f32),
+    player_rotation: (f32, f32, f32, f32),
+    wfc: SnapshotV2,
+    tile_cache: TileCacheV5,
+    interactions: InteractionStoreV3,
+    difficulty: DifficultyV4,
+    enemies: Vec<SavedEnemyV7>,
+    reputation: ReputationV6,
+}
+
+#[derive(Deserialize, Default)]
+struct ReputationV6 {
+    standing: HashMap<Faction, f32>,
+}
+
+impl From<ReputationV6> for Reputation {
+    fn from(reputation: ReputationV6) -> Self {
+        Self {
+            standing: reputation.standing,
+        }
+    }
+}
+
+fn upgrade_v6(save: SaveFileV6) -> SaveFileV7 {
+    SaveFileV7 {
+        player_position: save.player_position,
+        player_rotation: save.player_rotation,
+        wfc: save.wfc,
+        tile_cache: save.tile_cache,
+        interactions: save.interactions,
+        difficulty: save.difficulty,
+        enemies: save.enemies,
+        reputation: save.reputation,
+        mode: GameModeV7::default(),
+        stats: PlayStatsV7::default(),
+    }
+}
+
+// v7: enemies saved without what they were doing; they pick up as if freshly spawned
+#[derive(Deserialize)]
+struct SaveFileV7 {
+    player_position: (f32, f32, f32),
+    player_rotation: (f32, f32, f32, f32),
+    wfc: SnapshotV2,
+    tile_cache: TileCacheV5,
+    interactions: InteractionStoreV3,
+    difficulty: DifficultyV4,
+    enemies: Vec<SavedEnemyV7>,
+    reputation: ReputationV6,
+    mode: GameModeV7,
+    stats: PlayStatsV7,
+}
+
+#[derive(Deserialize)]
+struct SavedEnemyV7 {
+    position: (f32, f32, f32),
+    health: f32,
+    max_health: f32,
+    home: (f32, f32, f32),
+    aggression: f32,
+    faction: Faction,
+}
+
+#[derive(Deserialize, Default)]
+struct GameModeV7 {
+    hardcore: bool,
+}
+
+impl From<GameModeV7> for GameMode {
+    fn from(mode: GameModeV7) -> Self {
+        Self {
+            hardcore: mode.hardcore,
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+struct PlayStatsV7 {
+    kills: u32,
+    chests_looted: u32,
+    distance_walked: f32,
+    deaths: u32,
+}
+
+impl From<PlayStatsV7> for PlayStats {
+    fn from(stats: PlayStatsV7) -> Self {
+        Self {
+            kills: stats.kills,
+            chests_looted: stats.chests_looted,
+            distance_walked: stats.distance_walked,
+            deaths: stats.deaths,
+        }
+    }
+}
+
+fn upgrade_v7(save: SaveFileV7) -> SaveFileV8 {
+    SaveFileV8 {
+        player_position: save.player_position,
+        player_rotation: save.player_rotation,
+        wfc: save.wfc,
+        tile_cache: save.tile_cache,
+        interactions: save.interactions,
+        difficulty: save.difficulty,
+        enemies: save
+            .enemies
+            .into_iter()
+            .map(|enemy| SavedEnemyV8 {
+                position: enemy.position,
+                health: enemy.health,
+                max_health: enemy.max_health,
+                home: enemy.home,
+                aggression: enemy.aggression,
+                faction: enemy.faction,
+                mind: fresh_mind_v8(),
+            })
+            .collect(),
+        reputation: save.reputation,
+        mode: save.mode,
+        stats: save.stats,
+    }
+}
+
+// v8: no inventory; the player carries on empty-handed
+#[derive(Deserialize)]
+struct SaveFileV8 {
+    player_position: (f32, f32, f32),
+    player_rotation: (f32, f32, f32, f32),
+    wfc: SnapshotV2,
+    tile_cache: TileCacheV5,
+    interactions: InteractionStoreV3,
+    difficulty: DifficultyV4,
+    enemies: Vec<SavedEnemyV8>,
+    reputation: ReputationV6,
+    mode: GameModeV7,
+    stats: PlayStatsV7,
+}
+
+#[derive(Deserialize)]
+struct SavedEnemyV8 {
+    position: (f32, f32, f32),
+    health: f32,
+    max_health: f32,
+    home: (f32, f32, f32),
+    aggression: f32,
+    faction: Faction,
+    mind: SavedMindV8,
+}
+
+#[derive(Deserialize)]
+struct SavedMindV8 {
+    emotion: EmotionalState,
+    behavior: AIBehavior,
+    last_player_position: Option<(f32, f32, f32)>,
+    time_since_seen_player: f32,
+    guard_post: Option<(f32, f32, f32)>,
+    pursuit_secs: f32,
+    behavior_timer_secs: f32,
+    speech_timer_secs: f32,
+    morale_broken_secs: f32,
+    path_goal: Option<(i32, i32)>,
+    waypoints: Vec<(f32, f32, f32)>,
+    repath_timer_secs: f32,
+}
+
+// A freshly spawned AI, for enemies brought up from v7
+fn fresh_mind_v8() -> SavedMindV8 {
+    let mind = SavedMind::default();
+    SavedMindV8 {
+        emotion: mind.emotion,
+        behavior: mind.behavior,
+        last_player_position: mind.last_player_position,
+        time_since_seen_player: mind.time_since_seen_player,
+        guard_post: mind.guard_post,
+        pursuit_secs: mind.pursuit_secs,
+        behavior_timer_secs: mind.behavior_timer_secs,
+        speech_timer_secs: mind.speech_timer_secs,
+        morale_broken_secs: mind.morale_broken_secs,
+        path_goal: mind.path_goal,
+        waypoints: mind.waypoints,
+        repath_timer_secs: mind.repath_timer_secs,
+    }
+}
+
+impl From<SavedEnemyV8> for SavedEnemy {
+    fn from(enemy: SavedEnemyV8) -> Self {
+        Self {
+            position: enemy.position,
+            health: enemy.health,
+            max_health: enemy.max_health,
+            home: enemy.home,
+            aggression: enemy.aggression,
+            faction: enemy.faction,
+            mind: SavedMind {
+                emotion: enemy.mind.emotion,
+                behavior: enemy.mind.behavior,
+                last_player_position: enemy.mind.last_player_position,
+                time_since_seen_player: enemy.mind.time_since_seen_player,
+                guard_post: enemy.mind.guard_post,
+                pursuit_secs: enemy.mind.pursuit_secs,
+                behavior_timer_secs: enemy.mind.behavior_timer_secs,
+                speech_timer_secs: enemy.mind.speech_timer_secs,
+                morale_broken_secs: enemy.mind.morale_broken_secs,
+                path_goal: enemy.mind.path_goal,
+                waypoints: enemy.mind.waypoints,
+                repath_timer_secs: enemy.mind.repath_timer_secs,
+            },
+        }
+    }
+}
+
+fn upgrade_v8(save: SaveFileV8) -> SaveFileV9 {
+    SaveFileV9 {
+        player_position: save.player_position,
+        player_rotation: save.player_rotation,
+        inventory: InventoryV9::default(),
+        wfc: save.wfc,
+        tile_cache: save.tile_cache,
+        interactions: save.interactions,
+        difficulty: save.difficulty,
+        enemies: save.enemies,
+        reputation: save.reputation,
+        mode: save.mode,
+        stats: save.stats,
+    }
+}
+
+// v9: no explored cells or map waypoints; the map starts blank again
+#[derive(Deserialize)]
+struct SaveFileV9 {
+    player_position: (f32, f32, f32),
+    player_rotation: (f32, f32, f32, f32),
+    inventory: InventoryV9,
+    wfc: SnapshotV2,
+    tile_cache: TileCacheV5,
+    interactions: InteractionStoreV3,
+    difficulty: DifficultyV4,
+    enemies: Vec<SavedEnemyV8>,
+    reputation: ReputationV6,
+    mode: GameModeV7,
+    stats: PlayStatsV7,
+}
+
+#[derive(Deserialize, Default)]
+struct InventoryV9 {
+    slots: [Option<ItemStackV9>; 5],
+    selected: usize,
+}
+
+#[derive(Deserialize)]
+struct ItemStackV9 {
+    item: Item,
+    #[serde(default = "one")]
+    count: u32,
+    #[serde(default)]
+    wear: u32,
+}
+
+fn one() -> u32 {
+    1
+}
+
+// Slots past the end of the hotbar, were it ever made shorter, are dropped
+impl From<InventoryV9> for Inventory {
+    fn from(old: InventoryV9) -> Self {
+        let mut inventory = Inventory::default();
+        for (slot, stack) in inventory.slots.iter_mut().zip(old.slots) {
+            *slot = stack.map(|stack| ItemStack {
+                item: stack.item,
+                count: stack.count,
+                wear: stack.wear,
+            });
+        }
+        inventory.selected = old.selected.min(HOTBAR_SLOTS - 1);
+        inventory
+    }
+}
+
+fn upgrade_v9(save: SaveFileV9) -> SaveFile {
+    SaveFile {
+        version: 10,
+        player_position: save.player_position,
+        player_rotation: save.player_rotation,
+        inventory: save.inventory.into(),
+        wfc: save.wfc.into(),
+        tile_cache: save.tile_cache.into(),
+        interactions: save.interactions.into(),
+        difficulty: save.difficulty.into(),
+        enemies: save.enemies.into_iter().map(SavedEnemy::from).collect(),
+        reputation: save.reputation.into(),
+        mode: save.mode.into(),
+        stats: save.stats.into(),
+        explored: ExploredCells::default(),
+        waypoints: MapWaypoints::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE_V1: &str = include_str!("fixtures/world_v1.ron");
+    const FIXTURE_V2: &str = include_str!("fixtures/world_v2.ron");
+    const FIXTURE_V3: &str = include_str!("fixtures/world_v3.ron");
+    const FIXTURE_V4: &str = include_str!("fixtures/world_v4.ron");
+    const FIXTURE_V5: &str = include_str!("fixtures/world_v5.ron");
+    const FIXTURE_V6: &str = include_str!("fixtures/world_v6.ron");
+    const FIXTURE_V7: &str = include_str!("fixtures/world_v7.ron");
+    const FIXTURE_V8: &str = include_str!("fixtures/world_v8.ron");
+    const FIXTURE_V9: &str = include_str!("fixtures/world_v9.ron");
+    const FIXTURE_V10: &str = include_str!("fixtures/world_v10.ron");
+
+    #[test]
+    fn loads_v1_fixture() {
+        let save = parse_save(FIXTURE_V1).expect("v1 fixture should migrate");
+
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(save.player_position, (10.0, 2.0, -4.0));
+        assert_eq!(save.wfc.queue, vec![(1, 0), (0, 1)]);
+
+        let cells = &save.wfc.cells;
+        assert_eq!(cells[0].tile_type, Some(TileType::Ground));
+        assert_eq!(cells[0].valid_tiles, vec![TileType::Ground]);
+        assert_eq!(
+            cells[1].valid_tiles,
+            vec![TileType::Ground, TileType::Tree, TileType::Chest]
+        );
+        assert_eq!(
+            cells[2].valid_tiles,
+            vec![TileType::Ground, TileType::ThornBush, TileType::Well]
+        );
+    }
+
+    #[test]
+    fn loads_v2_fixture() {
+        let save = parse_save(FIXTURE_V2).expect("v2 fixture should migrate");
+
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(save.wfc.cells.len(), 2);
+        assert_eq!(
+            save.wfc.cells[1].valid_tiles,
+            vec![TileType::Tree, TileType::DeepWater]
+        );
+        assert_eq!(save.wfc.queue, vec![(0, 1)]);
+        assert!(save.interactions.tiles.is_empty());
+    }
+
+    #[test]
+    fn loads_v3_fixture() {
+        let save = parse_save(FIXTURE_V3).expect("v3 fixture should migrate");
+
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(save.difficulty, Difficulty::default());
+        assert_eq!(save.interactions.play_time, 120.0);
+
+        let chest = save.interactions.state((0, 0), TileType::Chest);
+        assert!(chest.opened && chest.looted);
+        assert_eq!(chest.last_used, Some(95.5));
+
+        // State recorded for another tile doesn't leak onto whatever regenerated there
+        assert_eq!(
+            save.interactions.state((0, 0), TileType::Door).last_used,
+            None
+        );
+        assert_eq!(
+            save.interactions
+                .cooldown_remaining((3, -2), TileType::FountainCenter, 60.0),
+            Some(20.0)
+        );
+    }
+
+    #[test]
+    fn loads_v4_fixture() {
+        let save = parse_save(FIXTURE_V4).expect("v4 fixture should migrate");
+
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(save.difficulty.preset, DifficultyPreset::Custom);
+        assert_eq!(save.difficulty.enemy_damage, 2.0);
+        assert_eq!(save.difficulty.spawn_density, 0.5);
+        assert_eq!(save.player_rotation, (0.0, 0.0, 0.0, 1.0));
+        assert!(save.tile_cache.tiles.is_empty());
+        assert!(save.enemies.is_empty());
+    }
+
+    #[test]
+    fn loads_v5_fixture() {
+        let save = parse_save(FIXTURE_V5).expect("v5 fixture should migrate");
+
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(save.tile_cache.tiles.get(&(40, -3)), Some(&TileType::Tree));
+        assert_eq!(save.tile_cache.tiles.len(), 2);
+
+        let enemy = &save.enemies[0];
+        assert_eq!(enemy.health, 55.0);
+        assert_eq!(enemy.home, (30.0, 0.0, 12.0));
+        assert_eq!(enemy.aggression, 0.5);
+        assert_eq!(enemy.faction, Faction::Drifters);
+        assert_eq!(save.reputation, Reputation::default());
+    }
+
+    #[test]
+    fn loads_v6_fixture() {
+        let save = parse_save(FIXTURE_V6).expect("v6 fixture should migrate");
+
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(save.enemies[0].faction, Faction::Wardens);
+        assert_eq!(save.reputation.standing(Faction::Wardens), -35.0);
+        assert_eq!(save.reputation.standing(Faction::Caravan), 10.0);
+        // Factions the player never dealt with are neutral
+        assert_eq!(save.reputation.standing(Faction::Drifters), 0.0);
+        assert!(!save.mode.hardcore);
+        assert_eq!(save.stats, PlayStats::default());
+    }
+
+    #[test]
+    fn loads_v7_fixture() {
+        let save = parse_save(FIXTURE_V7).expect("v7 fixture should migrate");
+
+        assert_eq!(save.version, SAVE_VERSION);
+        assert!(save.mode.hardcore);
+        assert_eq!(save.stats.kills, 4);
+        assert_eq!(save.stats.chests_looted, 2);
+        assert_eq!(save.stats.distance_walked, 812.5);
+        assert_eq!(save.enemies[0].faction, Faction::Wardens);
+        assert_eq!(save.enemies[0].mind, SavedMind::default());
+    }
+
+    #[test]
+    fn loads_v8_fixture() {
+        let save = parse_save(FIXTURE_V8).expect("v8 fixture should migrate");
+
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(save.inventory, Inventory::default());
+        let chaser = &save.enemies[0].mind;
+        assert_eq!(chaser.behavior, AIBehavior::Chasing);
+        assert_eq!(chaser.emotion, EmotionalState::Angry);
+        assert_eq!(chaser.last_player_position, Some((5.0, 2.0, 5.0)));
+        assert_eq!(chaser.path_goal, Some((1, 1)));
+        assert_eq!(chaser.waypoints, vec![(27.0, 0.0, 18.0), (18.0, 0.0, 9.0)]);
+        assert_eq!(chaser.repath_timer_secs, 0.25);
+
+        // A coward's morale never recovers, so it has to survive the trip through RON
+        let coward = &save.enemies[1].mind;
+        assert_eq!(coward.behavior, AIBehavior::Escaping);
+        assert_eq!(coward.morale_broken_secs, f32::INFINITY);
+    }
+
+    #[test]
+    fn loads_v9_fixture() {
+        let save = parse_save(FIXTURE_V9).expect("v9 fixture should migrate");
+
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(save.inventory.selected, 1);
+        let blade = save.inventory.slots[0].expect("the blade should be kept");
+        assert_eq!(blade.uses_left(), Some(3));
+        assert_eq!(save.inventory.slots[2].map(|arrows| arrows.count), Some(7));
+        assert!(save.inventory.contains(Item::Bow));
+        assert!(save.explored.tiles.is_empty());
+        assert_eq!(save.waypoints, MapWaypoints::default());
+    }
+
+    #[test]
+    fn loads_v10_fixture() {
+        let save = parse_save(FIXTURE_V10).expect("v10 fixture should load");
+
+        assert_eq!(save.version, 10);
+        assert_eq!(save.explored.tiles.len(), 3);
+        assert_eq!(
+            save.explored.tiles.get(&(-4, 7)),
+            Some(&TileType::FountainCenter)
+        );
+        assert_eq!(save.waypoints.waypoints, vec![(12, -30), (-4, 7)]);
+    }
+
+    #[test]
+    fn rejects_newer_versions() {
+        let text = format!(
+            "(version: {}, player_position: (0.0, 0.0, 0.0))",
+            SAVE_VERSION + 1
+        );
+        assert!(parse_save(&text).is_err());
+    }
+
+    #[test]
+    fn current_version_round_trips() {
+        let save = parse_save(FIXTURE_V1).unwrap();
+        let text = ron::ser::to_string_pretty(&save, ron::ser::PrettyConfig::default()).unwrap();
+        let reloaded = parse_save(&text).unwrap();
+
+        assert_eq!(reloaded.version, SAVE_VERSION);
+        assert_eq!(reloaded.wfc.cells.len(), save.wfc.cells.len());
+        assert_eq!(
+            reloaded.wfc.cells[2].valid_tiles,
+            save.wfc.cells[2].valid_tiles
+        );
+    }
+}
@@ -0,0 +1,413 @@
+pub mod migration;
+
+use std::{fs, path::Path, time::Duration};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    core_mechanics::{
+        difficulty::Difficulty,
+        enemy_ai::o_insan::{
+            components::{AIBehavior, EmotionalState, Leash, Morale, OInsanAI, Path as AiPath},
+            spawn::spawn_o_insan,
+        },
+        exploration::ExploredCells,
+        interactions::InteractionStore,
+        inventory::Inventory,
+        movement::PlayerTeleported,
+        oz_devinimli_yaratim::{
+            cells::{
+                Cell, CellSpatialIndex, Chunk, CollapsedTileCache, GenerationSettings, InChunk,
+            },
+            odycore::{
+                open_space::OpenSpacePropagationQueue, propagation_tasks::stop_propagation_tasks,
+                snapshot::PropagationSnapshot,
+            },
+        },
+        permadeath::{GameMode, RunEnded},
+        play_stats::PlayStats,
+        reputation::{Faction, Reputation},
+    },
+    input::{Action, action_just_pressed},
+    settings::save_settings,
+    spawn::player::Player,
+    ui::world_map::MapWaypoints,
+};
+
+const SAVE_DIRECTORY: &str = "saves";
+const SAVE_PATH: &str = "saves/world.ron";
+
+// Bump together with a migration step in migration.rs whenever SaveFile changes shape
+pub const SAVE_VERSION: u32 = 10;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            save_game.run_if(action_just_pressed(Action::QuickSave).and(run_ongoing)),
+            load_game.run_if(action_just_pressed(Action::QuickLoad)),
+            restore_enemies.run_if(resource_exists::<RestoredEnemies>),
+        ),
+    )
+    // Pick up where the last session left off, and save again when the window closes
+    .add_systems(Startup, remove_temp_files)
+    .add_systems(PostStartup, load_game.run_if(save_exists))
+    .add_systems(
+        Last,
+        (
+            stop_propagation_tasks,
+            save_game.run_if(run_ongoing),
+            save_settings,
+        )
+            .chain()
+            .run_if(on_event::<AppExit>),
+    );
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SaveFile {
+    pub version: u32,
+    pub player_position: (f32, f32, f32),
+    pub player_rotation: (f32, f32, f32, f32),
+    pub inventory: Inventory, // Blade wear and arrows included
+    pub wfc: PropagationSnapshot,
+    pub tile_cache: CollapsedTileCache,
+    pub interactions: InteractionStore,
+    pub difficulty: Difficulty,
+    pub enemies: Vec<SavedEnemy>,
+    pub reputation: Reputation,
+    pub mode: GameMode,
+    pub stats: PlayStats,
+    pub explored: ExploredCells,
+    pub waypoints: MapWaypoints,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SavedEnemy {
+    pub position: (f32, f32, f32),
+    pub health: f32,
+    pub max_health: f32,
+    pub home: (f32, f32, f32),
+    pub aggression: f32,
+    pub faction: Faction,
+    pub mind: SavedMind,
+}
+
+// What the AI was in the middle of, so a save made mid-chase carries on with the chase.
+// Timers keep how far along they were.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SavedMind {
+    pub emotion: EmotionalState,
+    pub behavior: AIBehavior,
+    pub last_player_position: Option<(f32, f32, f32)>,
+    pub time_since_seen_player: f32,
+    pub guard_post: Option<(f32, f32, f32)>,
+    pub pursuit_secs: f32,
+    pub behavior_timer_secs: f32,
+    pub speech_timer_secs: f32,
+    pub morale_broken_secs: f32,
+    pub path_goal: Option<(i32, i32)>,
+    pub waypoints: Vec<(f32, f32, f32)>,
+    pub repath_timer_secs: f32,
+}
+
+impl Default for SavedMind {
+    // A freshly spawned AI
+    fn default() -> Self {
+        Self::capture(&OInsanAI::default(), &AiPath::default(), &Morale::default())
+    }
+}
+
+impl SavedMind {
+    fn capture(ai: &OInsanAI, path: &AiPath, morale: &Morale) -> Self {
+        Self {
+            emotion: ai.emotional_state,
+            behavior: ai.current_behavior,
+            last_player_position: ai.last_player_position.map(Into::into),
+            time_since_seen_player: ai.time_since_seen_player,
+            guard_post: ai.guard_post.map(Into::into),
+            pursuit_secs: ai.pursuit_secs,
+            behavior_timer_secs: ai.behavior_update_timer.elapsed_secs(),
+            speech_timer_secs: ai.speech_timer.elapsed_secs(),
+            morale_broken_secs: morale.broken_secs,
+            path_goal: path.goal,
+            waypoints: path
+                .waypoints
+                .iter()
+                .map(|&waypoint| waypoint.into())
+                .collect(),
+            repath_timer_secs: path.repath_timer.elapsed_secs(),
+        }
+    }
+
+    fn restore(&self, ai: &mut OInsanAI, path: &mut AiPath, morale: &mut Morale) {
+        ai.emotional_state = self.emotion;
+        ai.current_behavior = self.behavior;
+        ai.last_player_position = self.last_player_position.map(Into::into);
+        ai.time_since_seen_player = self.time_since_seen_player;
+        ai.guard_post = self.guard_post.map(Into::into);
+        ai.pursuit_secs = self.pursuit_secs;
+        ai.behavior_update_timer
+            .set_elapsed(Duration::from_secs_f32(self.behavior_timer_secs));
+        ai.speech_timer
+            .set_elapsed(Duration::from_secs_f32(self.speech_timer_secs));
+        morale.broken_secs = self.morale_broken_secs;
+        path.goal = self.path_goal;
+        path.waypoints = self
+            .waypoints
+            .iter()
+            .map(|&waypoint| waypoint.into())
+            .collect();
+        path.repath_timer
+            .set_elapsed(Duration::from_secs_f32(self.repath_timer_secs));
+    }
+}
+
+// World-wide state that is saved as-is
+#[derive(SystemParam)]
+struct SavedResources<'w> {
+    tile_cache: Res<'w, CollapsedTileCache>,
+    interactions: Res<'w, InteractionStore>,
+    difficulty: Res<'w, Difficulty>,
+    reputation: Res<'w, Reputation>,
+    mode: Res<'w, GameMode>,
+    stats: Res<'w, PlayStats>,
+    explored: Res<'w, ExploredCells>,
+    waypoints: Res<'w, MapWaypoints>,
+}
+
+// Enemies from a loaded save, spawned once the current ones are cleared away
+#[derive(Resource)]
+struct RestoredEnemies(Vec<SavedEnemy>);
+
+pub fn save_exists() -> bool {
+    Path::new(SAVE_PATH).exists()
+}
+
+// A hardcore world that ended stays gone, so neither saving nor the exit autosave brings it back
+fn run_ongoing(ended: Option<Res<RunEnded>>) -> bool {
+    ended.is_none()
+}
+
+// Writes next to the file and renames over it, so a write cut short by a crash or a killed
+// process leaves the old file whole rather than half a new one
+pub fn write_atomically(path: &str, text: &str) -> Result<(), String> {
+    let temp_path = format!("{}.tmp", path);
+    fs::write(&temp_path, text)
+        .and_then(|()| fs::rename(&temp_path, path))
+        .map_err(|error| {
+            let _ = fs::remove_file(&temp_path);
+            error.to_string()
+        })
+}
+
+// Pretty-printed and written atomically, making the directory it goes in if need be
+pub fn save_ron<T: Serialize>(path: &str, value: &T) -> Result<(), String> {
+    let text = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+        .map_err(|error| error.to_string())?;
+    if let Some(directory) = Path::new(path).parent() {
+        fs::create_dir_all(directory).map_err(|error| error.to_string())?;
+    }
+    write_atomically(path, &text)
+}
+
+// Left behind when the process died between writing and renaming
+fn remove_temp_files() {
+    let Ok(entries) = fs::read_dir(SAVE_DIRECTORY) else {
+        return;
+    };
+    for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+        if path.extension().is_some_and(|extension| extension == "tmp") {
+            match fs::remove_file(&path) {
+                Ok(()) => println!("🧹 Removed unfinished {}", path.display()),
+                Err(error) => warn!("Failed to remove {}: {}", path.display(), error),
+            }
+        }
+    }
+}
+
+pub fn delete_save() {
+    match fs::remove_file(SAVE_PATH) {
+        Ok(()) => println!("🗑️ Deleted {}", SAVE_PATH),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+        Err(error) => warn!("Failed to delete {}: {}", SAVE_PATH, error),
+    }
+}
+
+type SavedEnemiesQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static OInsanAI,
+        &'static Transform,
+        &'static Leash,
+        &'static Faction,
+        &'static AiPath,
+        &'static Morale,
+    ),
+    Without<Player>,
+>;
+
+fn save_game(
+    cells: Query<(Entity, &Cell)>,
+    queue: Res<OpenSpacePropagationQueue>,
+    resources: SavedResources,
+    player: Single<(&Transform, &Inventory), With<Player>>,
+    enemies: SavedEnemiesQuery,
+) {
+    let (transform, inventory) = *player;
+    let position = transform.translation;
+    let rotation = transform.rotation;
+    let save = SaveFile {
+        version: SAVE_VERSION,
+        player_position: (position.x, position.y, position.z),
+        player_rotation: (rotation.x, rotation.y, rotation.z, rotation.w),
+        inventory: inventory.clone(),
+        wfc: PropagationSnapshot::capture(cells.iter(), &queue),
+        tile_cache: resources.tile_cache.clone(),
+        interactions: resources.interactions.clone(),
+        difficulty: *resources.difficulty,
+        enemies: enemies
+            .iter()
+            .filter(|(ai, ..)| ai.health > 0.0)
+            .map(|(ai, transform, leash, faction, path, morale)| SavedEnemy {
+                position: transform.translation.into(),
+                health: ai.health,
+                max_health: ai.max_health,
+                home: leash.home.into(),
+                aggression: leash.aggression,
+                faction: *faction,
+                mind: SavedMind::capture(ai, path, morale),
+            })
+            .collect(),
+        reputation: resources.reputation.clone(),
+        mode: *resources.mode,
+        stats: resources.stats.clone(),
+        explored: resources.explored.clone(),
+        waypoints: resources.waypoints.clone(),
+    };
+
+    match save_ron(SAVE_PATH, &save) {
+        Ok(()) => println!(
+            "💾 Saved {} cells ({} queued, {} cached) and {} enemies to {}",
+            save.wfc.cells.len(),
+            save.wfc.queue.len(),
+            save.tile_cache.tiles.len(),
+            save.enemies.len(),
+            SAVE_PATH
+        ),
+        Err(error) => warn!("Failed to save game: {}", error),
+    }
+}
+
+// Chunks take their cells with them; cells not yet in a chunk go on their own
+type LoadedWorldQuery<'w, 's> =
+    Query<'w, 's, Entity, Or<(With<Chunk>, (With<Cell>, Without<InChunk>))>>;
+
+fn load_game(
+    mut commands: Commands,
+    loaded: LoadedWorldQuery,
+    mut spatial_index: ResMut<CellSpatialIndex>,
+    mut queue: ResMut<OpenSpacePropagationQueue>,
+    player: Single<(&mut Transform, &mut Inventory), With<Player>>,
+    mut teleports: EventWriter<PlayerTeleported>,
+    settings: Res<GenerationSettings>,
+) {
+    if !Path::new(SAVE_PATH).exists() {
+        warn!("No save file at {}", SAVE_PATH);
+        return;
+    }
+
+    let save = match fs::read_to_string(SAVE_PATH)
+        .map_err(|error| error.to_string())
+        .and_then(|text| migration::parse_save(&text))
+    {
+        Ok(save) => save,
+        Err(error) => {
+            warn!("Failed to load {}: {}", SAVE_PATH, error);
+            return;
+        }
+    };
+
+    for entity in loaded.iter() {
+        commands.entity(entity).despawn();
+    }
+    spatial_index.grid.clear();
+    spatial_index.chunks.clear();
+    spatial_index.footprints.clear();
+
+    let (mut transform, mut inventory) = player.into_inner();
+    let (x, y, z) = save.player_position;
+    let (rx, ry, rz, rw) = save.player_rotation;
+    let from = transform.translation;
+    transform.translation = Vec3::new(x, y, z);
+    transform.rotation = Quat::from_xyzw(rx, ry, rz, rw).normalize();
+    teleports.write(PlayerTeleported {
+        from,
+        to: transform.translation,
+    });
+    *inventory = save.inventory.clone();
+
+    save.wfc.restore(&mut commands, &mut queue, &settings);
+    // Replaces what despawning the old cells above just cached
+    commands.insert_resource(save.tile_cache.clone());
+    commands.insert_resource(save.interactions.clone());
+    commands.insert_resource(save.difficulty);
+    commands.insert_resource(save.reputation.clone());
+    commands.insert_resource(save.mode);
+    commands.insert_resource(save.stats.clone());
+    commands.insert_resource(save.explored.clone());
+    commands.insert_resource(save.waypoints.clone());
+    commands.insert_resource(RestoredEnemies(save.enemies.clone()));
+
+    println!(
+        "📂 Loaded {} cells ({} queued, {} cached) and {} enemies from {}",
+        save.wfc.cells.len(),
+        save.wfc.queue.len(),
+        save.tile_cache.tiles.len(),
+        save.enemies.len(),
+        SAVE_PATH
+    );
+}
+
+fn restore_enemies(
+    mut commands: Commands,
+    restored: Res<RestoredEnemies>,
+    current: Query<Entity, With<OInsanAI>>,
+    asset_server: Res<AssetServer>,
+) {
+    for entity in current.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    for saved in restored.0.iter() {
+        let entity = spawn_o_insan(
+            &mut commands,
+            saved.position.into(),
+            saved.max_health,
+            &asset_server,
+        );
+        let mut ai = OInsanAI {
+            health: saved.health,
+            max_health: saved.max_health,
+            ..Default::default()
+        };
+        let mut path = AiPath::default();
+        let mut morale = Morale::default();
+        saved.mind.restore(&mut ai, &mut path, &mut morale);
+
+        commands.entity(entity).insert((
+            ai,
+            path,
+            morale,
+            Leash {
+                aggression: saved.aggression,
+                ..Leash::new(saved.home.into())
+            },
+            saved.faction,
+        ));
+    }
+
+    commands.remove_resource::<RestoredEnemies>();
+}
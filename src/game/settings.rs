@@ -0,0 +1,209 @@
+use std::{fs, path::Path};
+
+use bevy::{
+    prelude::*,
+    window::{PresentMode, PrimaryWindow},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    core_mechanics::oz_devinimli_yaratim::cells::GenerationSettings, save::save_ron,
+    ui::palette::PalettePreset,
+};
+
+const SETTINGS_PATH: &str = "saves/settings.ron";
+const ACCESSIBILITY_PATH: &str = "saves/accessibility.ron";
+
+const PROBE_WARMUP_FRAMES: u32 = 10; // Shader compilation and asset loading skew the first frames
+const PROBE_SECS: f32 = 1.5;
+const PROBE_GRID: i32 = 20; // Blocks per side of the synthetic tile burst
+
+// Average probe frame time in milliseconds up to which a tier is picked: (ms, view cells, shadows).
+// Measured with vsync off, as vsync would hold every frame to the refresh interval.
+const TIERS: [(f32, i32, bool); 3] = [(12.0, 17, true), (22.0, 13, true), (f32::MAX, 9, false)];
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(AccessibilitySettings::load_or_default())
+        .add_systems(Startup, load_or_probe_settings)
+        .add_systems(
+            Update,
+            (
+                run_hardware_probe.run_if(resource_exists::<HardwareProbe>),
+                apply_graphics_settings.run_if(resource_exists_and_changed::<GraphicsSettings>),
+            )
+                .chain(),
+        );
+}
+
+// Written once after the first-run probe; edit the file to override what was detected
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct GraphicsSettings {
+    pub view_cells: i32, // Cells per edge of the loaded square
+    pub shadows: bool,
+}
+
+// Kept apart from the graphics settings so a new probe never resets them
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(default)]
+pub struct AccessibilitySettings {
+    pub palette: PalettePreset,
+}
+
+impl AccessibilitySettings {
+    fn load_or_default() -> Self {
+        if !Path::new(ACCESSIBILITY_PATH).exists() {
+            return Self::default();
+        }
+        fs::read_to_string(ACCESSIBILITY_PATH)
+            .map_err(|error| error.to_string())
+            .and_then(|text| ron::from_str(&text).map_err(|error| error.to_string()))
+            .unwrap_or_else(|error| {
+                warn!(
+                    "Failed to read {}, using the defaults: {}",
+                    ACCESSIBILITY_PATH, error
+                );
+                Self::default()
+            })
+    }
+
+    pub fn save(&self) {
+        if let Err(error) = save_ron(ACCESSIBILITY_PATH, self) {
+            warn!("Failed to write {}: {}", ACCESSIBILITY_PATH, error);
+        }
+    }
+}
+
+#[derive(Resource)]
+struct HardwareProbe {
+    timer: Timer,
+    warmup_frames: u32,
+    frame_secs: Vec<f32>,
+    blocks: Vec<Entity>,
+    present_mode: PresentMode, // The window's own, put back once the probe is done
+}
+
+fn load_or_probe_settings(
+    mut commands: Commands,
+    mut mesh_assets: ResMut<Assets<Mesh>>,
+    mut material_assets: ResMut<Assets<StandardMaterial>>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+) {
+    if Path::new(SETTINGS_PATH).exists() {
+        let loaded = fs::read_to_string(SETTINGS_PATH)
+            .map_err(|error| error.to_string())
+            .and_then(|text| {
+                ron::from_str::<GraphicsSettings>(&text).map_err(|error| error.to_string())
+            });
+
+        match loaded {
+            Ok(settings) => {
+                commands.insert_resource(settings);
+                return;
+            }
+            Err(error) => warn!(
+                "Failed to read {}, detecting again: {}",
+                SETTINGS_PATH, error
+            ),
+        }
+    }
+
+    // A burst of tile-sized blocks in front of the camera stands in for a loaded world
+    let mesh = mesh_assets.add(Cuboid::new(1.0, 1.0, 1.0));
+    let material = material_assets.add(StandardMaterial {
+        base_color: Color::srgb(0.4, 0.4, 0.45),
+        ..Default::default()
+    });
+    let blocks = (0..PROBE_GRID * PROBE_GRID)
+        .map(|index| {
+            let (x, z) = (index % PROBE_GRID, index / PROBE_GRID);
+            commands
+                .spawn((
+                    Mesh3d(mesh.clone()),
+                    MeshMaterial3d(material.clone()),
+                    Transform::from_xyz(
+                        (x - PROBE_GRID / 2) as f32 * 3.0,
+                        0.5,
+                        -20.0 - z as f32 * 3.0,
+                    )
+                    .with_scale(Vec3::splat(2.0)),
+                ))
+                .id()
+        })
+        .collect();
+
+    println!("⚙️ First run: measuring performance to pick graphics settings");
+    commands.insert_resource(HardwareProbe {
+        timer: Timer::from_seconds(PROBE_SECS, TimerMode::Once),
+        warmup_frames: PROBE_WARMUP_FRAMES,
+        frame_secs: Vec::new(),
+        blocks,
+        present_mode: std::mem::replace(&mut window.present_mode, PresentMode::AutoNoVsync),
+    });
+}
+
+fn run_hardware_probe(
+    mut commands: Commands,
+    mut probe: ResMut<HardwareProbe>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+    time: Res<Time>,
+) {
+    if probe.warmup_frames > 0 {
+        probe.warmup_frames -= 1;
+        return;
+    }
+
+    probe.frame_secs.push(time.delta_secs());
+    probe.timer.tick(time.delta());
+    if !probe.timer.finished() {
+        return;
+    }
+
+    for block in probe.blocks.drain(..) {
+        commands.entity(block).despawn();
+    }
+    commands.remove_resource::<HardwareProbe>();
+    window.present_mode = probe.present_mode;
+
+    let average_ms = probe.frame_secs.iter().sum::<f32>() / probe.frame_secs.len() as f32 * 1000.0;
+    let (_, view_cells, shadows) = TIERS
+        .into_iter()
+        .find(|(max_ms, _, _)| average_ms <= *max_ms)
+        .unwrap_or(TIERS[TIERS.len() - 1]);
+    let settings = GraphicsSettings {
+        view_cells,
+        shadows,
+    };
+    println!(
+        "⚙️ Averaged {:.1} ms per frame: view distance {} cells, shadows {}",
+        average_ms,
+        view_cells,
+        if shadows { "on" } else { "off" }
+    );
+
+    write_settings(&settings);
+    commands.insert_resource(settings);
+}
+
+fn write_settings(settings: &GraphicsSettings) {
+    if let Err(error) = save_ron(SETTINGS_PATH, settings) {
+        warn!("Failed to write {}: {}", SETTINGS_PATH, error);
+    }
+}
+
+// Nothing is written while the probe is still running, so it runs again next time
+pub fn save_settings(settings: Option<Res<GraphicsSettings>>) {
+    if let Some(settings) = settings {
+        write_settings(&settings);
+    }
+}
+
+fn apply_graphics_settings(
+    settings: Res<GraphicsSettings>,
+    mut generation: ResMut<GenerationSettings>,
+    mut lights: Query<&mut DirectionalLight>,
+) {
+    generation.total_cells_on_edge = settings.view_cells;
+    for mut light in lights.iter_mut() {
+        light.shadows_enabled = settings.shadows;
+    }
+}
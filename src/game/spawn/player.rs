@@ -1,20 +1,28 @@
-use bevy::prelude::*;
+use bevy::{pbr::NotShadowReceiver, prelude::*, render::view::RenderLayers};
+
+use crate::game::core_mechanics::{
+    combat::TransformHistory,
+    health::Health,
+    inventory::Inventory,
+    movement::{MovementState, Stamina},
+    physics::player_controller,
+};
+
+// Seen by the sun but not by the camera, so the body only shows up as a shadow
+pub const SHADOW_ONLY_LAYER: usize = 1;
+
+pub const PLAYER_SPAWN: Vec3 = Vec3::new(0.0, 2.0, 0.0);
+
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(Startup, spawn_player);
+    app.add_systems(Startup, spawn_player)
+        .add_systems(Update, keep_body_upright);
 }
 
-#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
 #[reflect(Component)]
 pub struct Player {
-    pub has_weapon: bool,
-}
-
-impl Default for Player {
-    fn default() -> Self {
-        Self {
-            has_weapon: false,
-        }
-    }
+    pub focused: bool, // Holding the focus button, see core_mechanics::focus
+    pub coins: u32,
 }
 
 fn spawn_player(
@@ -30,18 +38,28 @@ fn spawn_player(
     });
 
     commands.spawn((
-        Transform::from_translation(Vec3 {
-            x: 0.0, 
-            y: 2.0, 
-            z: 0.0, 
-        })
-        .with_scale(Vec3 {
+        Transform::from_translation(PLAYER_SPAWN).with_scale(Vec3 {
             x: 5.0, 
             y: 5.0,
             z: 5.0,
         }),
         Visibility::default(),
         Player::default(),
+        Health::new(100.0),
+        Stamina::new(100.0),
+        MovementState::default(),
+        TransformHistory::default(),
+        Inventory::default(),
+        player_controller(),
+    ))
+    .with_child((
+        PlayerBody,
+        // Local units: the player is scaled by 5, so this reaches from the ground to eye level
+        Mesh3d(mesh_assets.add(Capsule3d::new(0.16, 0.58))),
+        MeshMaterial3d(material_assets.add(StandardMaterial::default())),
+        Transform::from_xyz(0.0, 0.05, 0.0),
+        RenderLayers::layer(SHADOW_ONLY_LAYER),
+        NotShadowReceiver,
     ));
 
     /*
@@ -56,3 +74,15 @@ fn spawn_player(
     })
     */
 }
+
+#[derive(Component)]
+struct PlayerBody;
+
+// The player pitches with the mouse; the body should only follow the yaw
+fn keep_body_upright(
+    player: Single<&Transform, (With<Player>, Without<PlayerBody>)>,
+    mut body: Single<&mut Transform, With<PlayerBody>>,
+) {
+    let (yaw, _, _) = player.rotation.to_euler(EulerRot::YXZ);
+    body.rotation = player.rotation.inverse() * Quat::from_rotation_y(yaw);
+}
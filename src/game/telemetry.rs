@@ -0,0 +1,144 @@
+use std::{
+    fs,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    core_mechanics::{
+        difficulty::{Difficulty, DifficultyPreset},
+        oz_devinimli_yaratim::{
+            elevation::ElevationMap, odycore::history::GenerationHistory,
+            odyrules::biomes::BiomeMap,
+        },
+    },
+    save::{save_ron, write_atomically},
+    settings::GraphicsSettings,
+};
+
+const TELEMETRY_PATH: &str = "saves/telemetry.ron";
+const REPORT_DIRECTORY: &str = "saves/reports";
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(TelemetrySettings::load_or_default())
+        .init_resource::<FrameTimes>()
+        .add_systems(Last, record_frame_time.run_if(telemetry_enabled))
+        .add_systems(
+            Last,
+            export_session_report
+                .after(record_frame_time)
+                .run_if(telemetry_enabled.and(on_event::<AppExit>)),
+        );
+}
+
+// Off until the player sets `enabled: true` in the file. Reports stay on disk in
+// REPORT_DIRECTORY and are never sent anywhere; players attach them to bug reports by hand.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct TelemetrySettings {
+    pub enabled: bool,
+}
+
+impl TelemetrySettings {
+    fn load() -> Result<Self, String> {
+        let text = fs::read_to_string(TELEMETRY_PATH).map_err(|error| error.to_string())?;
+        ron::from_str(&text).map_err(|error| error.to_string())
+    }
+
+    fn load_or_default() -> Self {
+        if Path::new(TELEMETRY_PATH).exists() {
+            return Self::load().unwrap_or_else(|error| {
+                warn!(
+                    "Failed to read {}, telemetry stays off: {}",
+                    TELEMETRY_PATH, error
+                );
+                Self::default()
+            });
+        }
+
+        // Written out switched off, so the option is there to find
+        let settings = Self::default();
+        if let Err(error) = save_ron(TELEMETRY_PATH, &settings) {
+            warn!("Failed to write {}: {}", TELEMETRY_PATH, error);
+        }
+        settings
+    }
+}
+
+fn telemetry_enabled(settings: Res<TelemetrySettings>) -> bool {
+    settings.enabled
+}
+
+#[derive(Resource, Debug, Default)]
+struct FrameTimes {
+    frames: u64,
+    total_secs: f64,
+}
+
+// Nothing that identifies the player or their machine
+#[derive(Serialize, Debug)]
+struct SessionReport {
+    version: &'static str,
+    biome_seed: u64,
+    elevation_seed: u64,
+    view_cells: Option<i32>,
+    shadows: Option<bool>,
+    difficulty: DifficultyPreset,
+    session_secs: f64,
+    average_frame_ms: f64,
+    cells_collapsed: u64,
+    contradictions: u64,
+    fallbacks: u64,        // Contradictions backtracking couldn't save, left as rubble
+    fallback_cells: usize, // Distinct cells those landed on
+}
+
+// Wall-clock time, so pauses and slow motion don't skew it
+fn record_frame_time(mut frame_times: ResMut<FrameTimes>, time: Res<Time<Real>>) {
+    frame_times.frames += 1;
+    frame_times.total_secs += time.delta_secs_f64();
+}
+
+fn export_session_report(
+    frame_times: Res<FrameTimes>,
+    graphics: Option<Res<GraphicsSettings>>,
+    difficulty: Res<Difficulty>,
+    history: Res<GenerationHistory>,
+    biomes: Res<BiomeMap>,
+    elevation: Res<ElevationMap>,
+) {
+    let report = SessionReport {
+        version: env!("CARGO_PKG_VERSION"),
+        biome_seed: biomes.seed,
+        elevation_seed: elevation.seed,
+        view_cells: graphics.as_ref().map(|graphics| graphics.view_cells),
+        shadows: graphics.as_ref().map(|graphics| graphics.shadows),
+        difficulty: difficulty.preset,
+        session_secs: frame_times.total_secs,
+        average_frame_ms: frame_times.total_secs * 1000.0 / frame_times.frames.max(1) as f64,
+        cells_collapsed: history.total_collapsed,
+        contradictions: history.contradictions,
+        fallbacks: history.total_fallbacks(),
+        fallback_cells: history.fallbacks.len(),
+    };
+    let text = match serde_json::to_string_pretty(&report) {
+        Ok(text) => text,
+        Err(error) => {
+            warn!("Failed to encode the session report: {}", error);
+            return;
+        }
+    };
+
+    let finished = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let path = format!("{}/session-{}.json", REPORT_DIRECTORY, finished);
+    let written = fs::create_dir_all(REPORT_DIRECTORY)
+        .map_err(|error| error.to_string())
+        .and_then(|_| write_atomically(&path, &text));
+    match written {
+        Ok(()) => println!("📊 Session report written to {}", path),
+        Err(error) => warn!("Failed to write {}: {}", path, error),
+    }
+}
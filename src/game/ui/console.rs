@@ -0,0 +1,144 @@
+use bevy::{
+    input::{
+        ButtonState,
+        keyboard::{Key, KeyboardInput},
+    },
+    prelude::*,
+};
+
+use crate::game::{
+    input::{Action, action_just_pressed},
+    ui::layers::UiLayer,
+};
+
+const MAX_LOG_LINES: usize = 12;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ConsoleState>()
+        .add_event::<ConsoleCommand>()
+        .add_systems(Startup, setup_console)
+        .add_systems(
+            Update,
+            (
+                toggle_console.run_if(action_just_pressed(Action::ToggleConsole)),
+                console_input.run_if(console_open),
+                redraw_console.run_if(resource_changed::<ConsoleState>),
+            )
+                .chain(),
+        );
+}
+
+#[derive(Resource, Default)]
+pub struct ConsoleState {
+    pub open: bool,
+    pub input: String,
+    pub log: Vec<String>,
+}
+
+impl ConsoleState {
+    pub fn print(&mut self, line: impl Into<String>) {
+        self.log.push(line.into());
+        if self.log.len() > MAX_LOG_LINES {
+            self.log.remove(0);
+        }
+    }
+}
+
+// A submitted line split on whitespace, e.g. `tp 120 -40` -> name "tp", args ["120", "-40"]
+#[derive(Event, Debug, Clone)]
+pub struct ConsoleCommand {
+    pub name: String,
+    pub args: Vec<String>,
+}
+
+#[derive(Component)]
+struct ConsoleRoot;
+
+#[derive(Component)]
+struct ConsoleText;
+
+pub fn console_open(state: Res<ConsoleState>) -> bool {
+    state.open
+}
+
+pub fn console_closed(state: Res<ConsoleState>) -> bool {
+    !state.open
+}
+
+fn setup_console(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("Console"),
+            ConsoleRoot,
+            Node {
+                width: Val::Percent(100.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                display: Display::None,
+                ..Default::default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+            UiLayer::Console.z_index(),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                ConsoleText,
+                Text::new(""),
+                TextFont {
+                    font_size: 16.0,
+                    ..Default::default()
+                },
+            ));
+        });
+}
+
+fn toggle_console(mut state: ResMut<ConsoleState>, mut root: Single<&mut Node, With<ConsoleRoot>>) {
+    state.open = !state.open;
+    state.input.clear();
+    root.display = if state.open {
+        Display::Flex
+    } else {
+        Display::None
+    };
+}
+
+fn console_input(
+    mut state: ResMut<ConsoleState>,
+    mut keyboard: EventReader<KeyboardInput>,
+    mut commands_out: EventWriter<ConsoleCommand>,
+) {
+    for event in keyboard.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Enter => {
+                let line = std::mem::take(&mut state.input);
+                let mut words = line.split_whitespace().map(str::to_string);
+                if let Some(name) = words.next() {
+                    state.print(format!("> {}", line));
+                    commands_out.write(ConsoleCommand {
+                        name,
+                        args: words.collect(),
+                    });
+                }
+            }
+            Key::Backspace => {
+                state.input.pop();
+            }
+            Key::Space => state.input.push(' '),
+            Key::Character(text) => {
+                // The toggle key itself arrives as a character too
+                let text = text.replace('`', "");
+                state.input.push_str(&text);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn redraw_console(state: Res<ConsoleState>, mut text: Single<&mut Text, With<ConsoleText>>) {
+    let mut lines = state.log.clone();
+    lines.push(format!("> {}_", state.input));
+    text.0 = lines.join("\n");
+}
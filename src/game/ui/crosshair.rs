@@ -0,0 +1,204 @@
+use bevy::{audio::Volume, prelude::*};
+use std::f32::consts::FRAC_PI_4;
+
+use crate::game::{
+    audio::{SfxHandles, SfxKind},
+    core_mechanics::{combat::AttackHitEvent, interactions::LookTarget},
+    input::{Action, InputMap},
+    spawn::player::Player,
+    ui::layers::UiLayer,
+};
+
+const CROSSHAIR_SIZE_PX: f32 = 22.0;
+const CROSSHAIR_GAP_PX: f32 = 8.0;
+const HIT_MARKER_SIZE_PX: f32 = 30.0;
+const HIT_MARKER_GAP_PX: f32 = 14.0;
+const LINE_THICKNESS_PX: f32 = 2.0;
+const HIT_MARKER_SECS: f32 = 0.25;
+const HIT_SOUND_VOLUME: f32 = 0.35;
+const HINT_OFFSET_PX: f32 = 56.0; // Below the crosshair
+const IDLE_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.8);
+const TARGET_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Startup, setup_crosshair)
+        .add_systems(Update, (show_hit_marker, fade_hit_marker).chain())
+        .add_systems(
+            Update,
+            show_interaction_hint.run_if(resource_changed::<LookTarget>),
+        );
+}
+
+#[derive(Component)]
+struct Crosshair;
+
+#[derive(Component)]
+struct HitMarker {
+    timer: Timer,
+}
+
+#[derive(Component)]
+struct HitMarkerLine;
+
+#[derive(Component)]
+struct CrosshairLine;
+
+#[derive(Component)]
+struct InteractionHint;
+
+fn setup_crosshair(mut commands: Commands) {
+    // Starts expired so the marker stays hidden until the first hit
+    let mut finished = Timer::from_seconds(HIT_MARKER_SECS, TimerMode::Once);
+    finished.tick(finished.duration());
+
+    commands
+        .spawn((
+            Name::new("Crosshair"),
+            Crosshair,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            UiLayer::Hud.z_index(),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(Node {
+                    width: Val::Px(CROSSHAIR_SIZE_PX),
+                    height: Val::Px(CROSSHAIR_SIZE_PX),
+                    position_type: PositionType::Absolute,
+                    ..Default::default()
+                })
+                .with_children(|cross| {
+                    for arm in cross_arms(CROSSHAIR_SIZE_PX, CROSSHAIR_GAP_PX) {
+                        cross.spawn((CrosshairLine, arm, BackgroundColor(IDLE_COLOR)));
+                    }
+                });
+
+            // Same shape turned 45° into an X
+            parent
+                .spawn((
+                    HitMarker { timer: finished },
+                    Node {
+                        width: Val::Px(HIT_MARKER_SIZE_PX),
+                        height: Val::Px(HIT_MARKER_SIZE_PX),
+                        position_type: PositionType::Absolute,
+                        ..Default::default()
+                    },
+                    Transform::from_rotation(Quat::from_rotation_z(FRAC_PI_4)),
+                    Visibility::Hidden,
+                ))
+                .with_children(|marker| {
+                    for arm in cross_arms(HIT_MARKER_SIZE_PX, HIT_MARKER_GAP_PX) {
+                        marker.spawn((HitMarkerLine, arm, BackgroundColor(Color::WHITE)));
+                    }
+                });
+
+            parent.spawn((
+                InteractionHint,
+                Text::default(),
+                TextFont::from_font_size(18.0),
+                TextColor(TARGET_COLOR),
+                Node {
+                    position_type: PositionType::Absolute,
+                    margin: UiRect::top(Val::Px(HINT_OFFSET_PX)),
+                    ..Default::default()
+                },
+            ));
+        });
+}
+
+// Four lines around an empty center square of `gap` pixels
+fn cross_arms(size: f32, gap: f32) -> [Node; 4] {
+    let length = (size - gap) / 2.0;
+    let middle = (size - LINE_THICKNESS_PX) / 2.0;
+    let arm = |left: f32, top: f32, width: f32, height: f32| Node {
+        position_type: PositionType::Absolute,
+        left: Val::Px(left),
+        top: Val::Px(top),
+        width: Val::Px(width),
+        height: Val::Px(height),
+        ..Default::default()
+    };
+
+    [
+        arm(0.0, middle, length, LINE_THICKNESS_PX),
+        arm(size - length, middle, length, LINE_THICKNESS_PX),
+        arm(middle, 0.0, LINE_THICKNESS_PX, length),
+        arm(middle, size - length, LINE_THICKNESS_PX, length),
+    ]
+}
+
+fn show_hit_marker(
+    mut commands: Commands,
+    mut hits: EventReader<AttackHitEvent>,
+    mut marker: Single<(&mut HitMarker, &mut Visibility)>,
+    player: Single<Entity, With<Player>>,
+    handles: Res<SfxHandles>,
+) {
+    let Some(hit) = hits.read().filter(|hit| hit.attacker == *player).last() else {
+        return;
+    };
+    debug!("Player hit {:?}", hit.target);
+
+    let (hit_marker, visibility) = &mut *marker;
+    hit_marker.timer.reset();
+    **visibility = Visibility::Inherited;
+
+    // Plain UI sound: not spatial and not occluded
+    commands.spawn((
+        AudioPlayer(handles.get(SfxKind::Hit)),
+        PlaybackSettings::DESPAWN.with_volume(Volume::Linear(HIT_SOUND_VOLUME)),
+    ));
+}
+
+fn fade_hit_marker(
+    mut marker: Single<(&mut HitMarker, &mut Visibility)>,
+    mut lines: Query<&mut BackgroundColor, With<HitMarkerLine>>,
+    time: Res<Time>,
+) {
+    let (hit_marker, visibility) = &mut *marker;
+    if hit_marker.timer.finished() {
+        return;
+    }
+
+    hit_marker.timer.tick(time.delta());
+    if hit_marker.timer.finished() {
+        **visibility = Visibility::Hidden;
+        return;
+    }
+
+    let alpha = hit_marker.timer.fraction_remaining();
+    for mut color in lines.iter_mut() {
+        color.0 = Color::WHITE.with_alpha(alpha);
+    }
+}
+
+// Tints the crosshair while the player looks at something usable, and names what it is
+fn show_interaction_hint(
+    target: Res<LookTarget>,
+    mut lines: Query<&mut BackgroundColor, With<CrosshairLine>>,
+    mut hint: Single<&mut Text, With<InteractionHint>>,
+    input_map: Res<InputMap>,
+) {
+    let color = if target.verb.is_some() {
+        TARGET_COLOR
+    } else {
+        IDLE_COLOR
+    };
+    for mut line in lines.iter_mut() {
+        line.0 = color;
+    }
+
+    let label = target.label.as_deref().unwrap_or_default();
+    hint.0 = match target.verb {
+        Some(verb) => format!("{}  [{}] {}", label, input_map.label(Action::Interact), verb),
+        None => label.to_string(),
+    }
+    .trim_start()
+    .to_string();
+}
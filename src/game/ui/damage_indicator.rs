@@ -0,0 +1,183 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+use crate::game::{
+    core_mechanics::health::{DamageEvent, DamageSource},
+    spawn::player::Player,
+    ui::layers::UiLayer,
+};
+
+const ARC_WIDTH_PX: u32 = 160;
+const ARC_HEIGHT_PX: u32 = 40;
+const ARC_RADIUS_PX: f32 = 200.0; // From the screen center to the arc's circle center
+const ARC_THICKNESS_PX: f32 = 12.0;
+const INDICATOR_SECS: f32 = 1.5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Startup, setup_damage_indicators)
+        .add_systems(
+            Update,
+            (spawn_damage_indicators, update_damage_indicators).chain(),
+        );
+}
+
+// One per attacker; points from the screen center toward where the hit came from
+#[derive(Component, Debug)]
+pub struct DamageIndicator {
+    pub attacker: Entity,
+    pub last_known_position: Vec3,
+    pub lifetime: Timer,
+    pub arc: Entity,
+}
+
+#[derive(Component)]
+struct DamageIndicatorRoot;
+
+#[derive(Resource)]
+struct DamageArcImage(Handle<Image>);
+
+fn setup_damage_indicators(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    commands.insert_resource(DamageArcImage(images.add(draw_arc())));
+
+    commands.spawn((
+        Name::new("DamageIndicators"),
+        DamageIndicatorRoot,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..Default::default()
+        },
+        UiLayer::Hud.z_index(),
+    ));
+}
+
+// A slice of a ring whose circle center sits ARC_RADIUS_PX below the image, fading toward the ends
+fn draw_arc() -> Image {
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: ARC_WIDTH_PX,
+            height: ARC_HEIGHT_PX,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::all(),
+    );
+
+    let center = Vec2::new(ARC_WIDTH_PX as f32 / 2.0, ARC_RADIUS_PX);
+    let outer = ARC_RADIUS_PX - 2.0;
+    let inner = outer - ARC_THICKNESS_PX;
+
+    for px in 0..ARC_WIDTH_PX {
+        for py in 0..ARC_HEIGHT_PX {
+            let pixel = Vec2::new(px as f32 + 0.5, py as f32 + 0.5);
+            let distance = pixel.distance(center);
+            if distance < inner || distance > outer {
+                continue;
+            }
+
+            let edge = (pixel.x - center.x).abs() / center.x;
+            let alpha = (1.0 - edge * edge).max(0.0) * 0.85;
+            let _ = image.set_color_at(px, py, Color::srgba(0.9, 0.05, 0.05, alpha));
+        }
+    }
+
+    image
+}
+
+fn spawn_damage_indicators(
+    mut commands: Commands,
+    mut events: EventReader<DamageEvent>,
+    mut indicators: Query<&mut DamageIndicator>,
+    player: Single<Entity, With<Player>>,
+    positions: Query<&GlobalTransform>,
+    root: Single<Entity, With<DamageIndicatorRoot>>,
+    arc_image: Res<DamageArcImage>,
+) {
+    for event in events.read().filter(|event| event.target == *player) {
+        let DamageSource::Attack { attacker } = event.source else {
+            continue; // Hazards have no direction
+        };
+        let Ok(attacker_transform) = positions.get(attacker) else {
+            continue;
+        };
+
+        // Repeated hits from the same attacker refresh its arc instead of stacking
+        if let Some(mut existing) = indicators.iter_mut().find(|i| i.attacker == attacker) {
+            existing.last_known_position = attacker_transform.translation();
+            existing.lifetime.reset();
+            continue;
+        }
+
+        let arc = commands
+            .spawn((
+                ImageNode::new(arc_image.0.clone()),
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(-(ARC_WIDTH_PX as f32) / 2.0),
+                    top: Val::Px(-ARC_RADIUS_PX),
+                    width: Val::Px(ARC_WIDTH_PX as f32),
+                    height: Val::Px(ARC_HEIGHT_PX as f32),
+                    ..Default::default()
+                },
+            ))
+            .id();
+
+        // Zero-sized pivot at the screen center; rotating it swings the arc around the crosshair
+        let pivot = commands
+            .spawn((
+                DamageIndicator {
+                    attacker,
+                    last_known_position: attacker_transform.translation(),
+                    lifetime: Timer::from_seconds(INDICATOR_SECS, TimerMode::Once),
+                    arc,
+                },
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(50.0),
+                    top: Val::Percent(50.0),
+                    ..Default::default()
+                },
+            ))
+            .add_child(arc)
+            .id();
+
+        commands.entity(*root).add_child(pivot);
+    }
+}
+
+fn update_damage_indicators(
+    mut commands: Commands,
+    mut indicators: Query<(Entity, &mut DamageIndicator, &mut Transform)>,
+    mut arcs: Query<&mut ImageNode>,
+    player: Single<&GlobalTransform, With<Player>>,
+    positions: Query<&GlobalTransform, Without<DamageIndicator>>,
+    time: Res<Time>,
+) {
+    for (entity, mut indicator, mut transform) in indicators.iter_mut() {
+        indicator.lifetime.tick(time.delta());
+        if indicator.lifetime.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        // Keep tracking the attacker while it lives, so the arc follows it as the player turns
+        if let Ok(attacker_transform) = positions.get(indicator.attacker) {
+            indicator.last_known_position = attacker_transform.translation();
+        }
+
+        let local =
+            player.rotation().inverse() * (indicator.last_known_position - player.translation());
+        let angle = local.x.atan2(-local.z); // 0 = straight ahead, positive = to the right
+        transform.rotation = Quat::from_rotation_z(angle);
+
+        if let Ok(mut image) = arcs.get_mut(indicator.arc) {
+            image.color = Color::WHITE.with_alpha(indicator.lifetime.fraction_remaining());
+        }
+    }
+}
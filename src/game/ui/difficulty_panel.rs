@@ -0,0 +1,183 @@
+use bevy::prelude::*;
+
+use crate::game::{
+    core_mechanics::difficulty::{Difficulty, DifficultyPreset, MAX_MULTIPLIER, MIN_MULTIPLIER},
+    input::{Action, Actions, InputMap, action_just_pressed},
+    ui::{console::console_closed, layers::UiLayer},
+};
+
+const MULTIPLIER_STEP: f32 = 0.1;
+const ROWS: [PanelRow; 4] = [
+    PanelRow::Preset,
+    PanelRow::EnemyDamage,
+    PanelRow::SpawnDensity,
+    PanelRow::StaminaDrain,
+];
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<DifficultyPanel>()
+        .add_systems(Startup, setup_difficulty_panel)
+        .add_systems(
+            Update,
+            (
+                toggle_difficulty_panel
+                    .run_if(action_just_pressed(Action::DifficultyPanel).and(console_closed)),
+                navigate_difficulty_panel.run_if(difficulty_panel_open.and(console_closed)),
+                redraw_difficulty_panel
+                    .run_if(resource_changed::<DifficultyPanel>.or(resource_changed::<Difficulty>)),
+            )
+                .chain(),
+        );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PanelRow {
+    Preset,
+    EnemyDamage,
+    SpawnDensity,
+    StaminaDrain,
+}
+
+// Walking keys pick a row and change it while the panel is open, so the player stands still
+#[derive(Resource, Debug, Default)]
+pub struct DifficultyPanel {
+    pub open: bool,
+    selected: usize, // Index into ROWS
+}
+
+#[derive(Component)]
+struct DifficultyPanelRoot;
+
+#[derive(Component)]
+struct DifficultyPanelText;
+
+fn difficulty_panel_open(panel: Res<DifficultyPanel>) -> bool {
+    panel.open
+}
+
+pub fn difficulty_panel_closed(panel: Res<DifficultyPanel>) -> bool {
+    !panel.open
+}
+
+fn setup_difficulty_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("DifficultyPanel"),
+            DifficultyPanelRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                display: Display::None,
+                ..Default::default()
+            },
+            UiLayer::Menu.z_index(),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                DifficultyPanelText,
+                Text::new(""),
+                TextFont::from_font_size(20.0),
+                Node {
+                    padding: UiRect::all(Val::Px(24.0)),
+                    ..Default::default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            ));
+        });
+}
+
+fn toggle_difficulty_panel(mut panel: ResMut<DifficultyPanel>) {
+    panel.open = !panel.open;
+}
+
+// The preset row steps through Story, Normal and Hard; the others nudge one multiplier and
+// turn the preset into Custom, as the `difficulty` console command does
+fn navigate_difficulty_panel(
+    actions: Actions,
+    mut panel: ResMut<DifficultyPanel>,
+    mut difficulty: ResMut<Difficulty>,
+) {
+    if actions.just_pressed(Action::MoveForward) {
+        panel.selected = (panel.selected + ROWS.len() - 1) % ROWS.len();
+    }
+    if actions.just_pressed(Action::MoveBack) {
+        panel.selected = (panel.selected + 1) % ROWS.len();
+    }
+
+    let step = match (
+        actions.just_pressed(Action::MoveLeft),
+        actions.just_pressed(Action::MoveRight),
+    ) {
+        (true, false) => -1.0,
+        (false, true) => 1.0,
+        _ => return,
+    };
+
+    let multiplier = match ROWS[panel.selected] {
+        PanelRow::Preset => {
+            let presets = [
+                DifficultyPreset::Story,
+                DifficultyPreset::Normal,
+                DifficultyPreset::Hard,
+            ];
+            // Custom steps as if it were Normal
+            let current = presets
+                .iter()
+                .position(|preset| *preset == difficulty.preset)
+                .unwrap_or(1);
+            let next = (current as i32 + step as i32).clamp(0, presets.len() as i32 - 1);
+            *difficulty = Difficulty::preset(presets[next as usize]);
+            return;
+        }
+        PanelRow::EnemyDamage => &mut difficulty.enemy_damage,
+        PanelRow::SpawnDensity => &mut difficulty.spawn_density,
+        PanelRow::StaminaDrain => &mut difficulty.stamina_drain,
+    };
+    // Rounded so repeated steps don't drift off the tenths
+    *multiplier = ((*multiplier + step * MULTIPLIER_STEP) * 10.0).round() / 10.0;
+    *multiplier = multiplier.clamp(MIN_MULTIPLIER, MAX_MULTIPLIER);
+    difficulty.preset = DifficultyPreset::Custom;
+}
+
+fn redraw_difficulty_panel(
+    panel: Res<DifficultyPanel>,
+    difficulty: Res<Difficulty>,
+    mut root: Single<&mut Node, With<DifficultyPanelRoot>>,
+    mut text: Single<&mut Text, With<DifficultyPanelText>>,
+    input_map: Res<InputMap>,
+) {
+    root.display = if panel.open {
+        Display::Flex
+    } else {
+        Display::None
+    };
+
+    let mut lines = vec!["⚔️ Difficulty".to_string(), String::new()];
+    for (index, row) in ROWS.iter().enumerate() {
+        let (name, value) = match row {
+            PanelRow::Preset => ("Preset", format!("{:?}", difficulty.preset)),
+            PanelRow::EnemyDamage => ("Enemy damage", format!("x{:.1}", difficulty.enemy_damage)),
+            PanelRow::SpawnDensity => {
+                ("Spawn density", format!("x{:.1}", difficulty.spawn_density))
+            }
+            PanelRow::StaminaDrain => {
+                ("Stamina drain", format!("x{:.1}", difficulty.stamina_drain))
+            }
+        };
+        let marker = if index == panel.selected { ">" } else { " " };
+        lines.push(format!("{} {:<14} < {} >", marker, name, value));
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "[{}/{}] Choose  [{}/{}] Change  [{}] Close",
+        input_map.label(Action::MoveForward),
+        input_map.label(Action::MoveBack),
+        input_map.label(Action::MoveLeft),
+        input_map.label(Action::MoveRight),
+        input_map.label(Action::DifficultyPanel),
+    ));
+    text.0 = lines.join("\n");
+}
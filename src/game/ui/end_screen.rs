@@ -0,0 +1,133 @@
+use bevy::prelude::*;
+
+use crate::game::{
+    core_mechanics::{
+        exploration::ExploredCells, interactions::InteractionStore, permadeath::RunEnded,
+        play_stats::PlayStats,
+    },
+    input::{Action, InputMap, action_just_pressed},
+    ui::layers::UiLayer,
+};
+
+const FADE_SECS: f32 = 3.0;
+const BACKDROP_ALPHA: f32 = 0.92;
+const TEXT_DELAY: f32 = 0.3; // Share of the fade before anything but the title shows
+const TITLE_COLOR: Color = Color::srgb(0.75, 0.05, 0.05);
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            show_end_screen.run_if(resource_added::<RunEnded>),
+            fade_in_end_screen,
+            quit_from_end_screen
+                .run_if(resource_exists::<RunEnded>.and(action_just_pressed(Action::Confirm))),
+        )
+            .chain(),
+    );
+}
+
+#[derive(Component)]
+struct EndScreen {
+    fade: Timer,
+}
+
+fn show_end_screen(
+    mut commands: Commands,
+    stats: Res<PlayStats>,
+    store: Res<InteractionStore>,
+    explored: Res<ExploredCells>,
+    input_map: Res<InputMap>,
+) {
+    let survived = store.play_time as u64;
+    let lines = [
+        format!(
+            "Survived {}:{:02}:{:02}",
+            survived / 3600,
+            survived / 60 % 60,
+            survived % 60
+        ),
+        format!("Enemies killed: {}", stats.kills),
+        format!("Chests looted: {}", stats.chests_looted),
+        format!("Distance walked: {:.0} m", stats.distance_walked),
+        format!("Cells explored: {}", explored.tiles.len()),
+    ];
+
+    commands
+        .spawn((
+            Name::new("EndScreen"),
+            EndScreen {
+                fade: Timer::from_seconds(FADE_SECS, TimerMode::Once),
+            },
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(16.0),
+                ..Default::default()
+            },
+            BackgroundColor(Color::BLACK.with_alpha(0.0)),
+            UiLayer::Menu.z_index(),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("YOU DIED"),
+                TextFont::from_font_size(72.0),
+                TextColor(TITLE_COLOR.with_alpha(0.0)),
+            ));
+            parent.spawn((
+                Text::new("This world is gone for good"),
+                TextFont::from_font_size(22.0),
+                TextColor(Color::WHITE.with_alpha(0.0)),
+            ));
+            parent.spawn((
+                Text::new(lines.join("\n")),
+                TextFont::from_font_size(20.0),
+                TextColor(Color::WHITE.with_alpha(0.0)),
+                TextLayout::new_with_justify(JustifyText::Center),
+                Node {
+                    margin: UiRect::top(Val::Px(24.0)),
+                    ..Default::default()
+                },
+            ));
+            parent.spawn((
+                Text::new(format!(
+                    "Press {} to quit",
+                    input_map.label(Action::Confirm)
+                )),
+                TextFont::from_font_size(16.0),
+                TextColor(Color::WHITE.with_alpha(0.0)),
+            ));
+        });
+}
+
+fn fade_in_end_screen(
+    mut screen: Query<(&mut EndScreen, &mut BackgroundColor, &Children)>,
+    mut texts: Query<&mut TextColor>,
+    time: Res<Time>,
+) {
+    for (mut end_screen, mut backdrop, children) in screen.iter_mut() {
+        if end_screen.fade.finished() {
+            continue;
+        }
+        end_screen.fade.tick(time.delta());
+
+        let progress = end_screen.fade.fraction();
+        backdrop.0.set_alpha(progress * BACKDROP_ALPHA);
+        for (index, child) in children.iter().enumerate() {
+            if let Ok(mut color) = texts.get_mut(child) {
+                let delay = if index == 0 { 0.0 } else { TEXT_DELAY };
+                color
+                    .0
+                    .set_alpha(((progress - delay) / (1.0 - delay)).clamp(0.0, 1.0));
+            }
+        }
+    }
+}
+
+fn quit_from_end_screen(mut exit: EventWriter<AppExit>) {
+    exit.write(AppExit::Success);
+}
@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+
+use crate::game::{
+    core_mechanics::fast_travel::{DESTINATION_ACTIONS, GateMenu},
+    input::{Action, InputMap},
+    ui::layers::UiLayer,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Startup, setup_gate_menu).add_systems(
+        Update,
+        redraw_gate_menu.run_if(resource_changed::<GateMenu>),
+    );
+}
+
+#[derive(Component)]
+struct GateMenuRoot;
+
+#[derive(Component)]
+struct GateMenuText;
+
+fn setup_gate_menu(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("GateMenu"),
+            GateMenuRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                display: Display::None,
+                ..Default::default()
+            },
+            UiLayer::Menu.z_index(),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                GateMenuText,
+                Text::new(""),
+                TextFont::from_font_size(20.0),
+                Node {
+                    padding: UiRect::all(Val::Px(24.0)),
+                    ..Default::default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            ));
+        });
+}
+
+fn redraw_gate_menu(
+    menu: Res<GateMenu>,
+    mut root: Single<&mut Node, With<GateMenuRoot>>,
+    mut text: Single<&mut Text, With<GateMenuText>>,
+    input_map: Res<InputMap>,
+) {
+    root.display = if menu.open {
+        Display::Flex
+    } else {
+        Display::None
+    };
+
+    let mut lines = vec!["⛲ Travel to".to_string(), String::new()];
+    for (action, destination) in DESTINATION_ACTIONS.iter().zip(menu.destinations.iter()) {
+        lines.push(format!(
+            "{}  {:<28} {:>6.0} m",
+            input_map.label(*action),
+            destination.name,
+            destination.distance
+        ));
+    }
+    lines.push(String::new());
+    lines.push(format!("[{}] Stay here", input_map.label(Action::Interact)));
+    text.0 = lines.join("\n");
+}
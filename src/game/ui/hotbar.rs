@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+
+use crate::game::{
+    core_mechanics::inventory::{HOTBAR_SLOTS, Inventory},
+    spawn::player::Player,
+    ui::layers::UiLayer,
+};
+
+const SLOT_SIZE_PX: f32 = 64.0;
+const SLOT_GAP_PX: f32 = 6.0;
+const BOTTOM_MARGIN_PX: f32 = 24.0;
+const SLOT_BACKGROUND: Color = Color::srgba(0.0, 0.0, 0.0, 0.45);
+const IDLE_BORDER: Color = Color::srgba(1.0, 1.0, 1.0, 0.25);
+const SELECTED_BORDER: Color = Color::srgb(1.0, 0.85, 0.3);
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Startup, setup_hotbar)
+        .add_systems(Update, update_hotbar);
+}
+
+#[derive(Component)]
+struct HotbarSlot(usize);
+
+#[derive(Component)]
+struct HotbarLabel(usize);
+
+fn setup_hotbar(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("Hotbar"),
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                bottom: Val::Px(BOTTOM_MARGIN_PX),
+                justify_content: JustifyContent::Center,
+                column_gap: Val::Px(SLOT_GAP_PX),
+                ..Default::default()
+            },
+            UiLayer::Hud.z_index(),
+        ))
+        .with_children(|parent| {
+            for index in 0..HOTBAR_SLOTS {
+                parent
+                    .spawn((
+                        HotbarSlot(index),
+                        Node {
+                            width: Val::Px(SLOT_SIZE_PX),
+                            height: Val::Px(SLOT_SIZE_PX),
+                            border: UiRect::all(Val::Px(2.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        BackgroundColor(SLOT_BACKGROUND),
+                        BorderColor(IDLE_BORDER),
+                    ))
+                    .with_child((
+                        HotbarLabel(index),
+                        Text::default(),
+                        TextFont::from_font_size(13.0),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                    ));
+            }
+        });
+}
+
+// Each slot names its item, with the stack size or the swings a blade has left
+fn update_hotbar(
+    inventory: Single<&Inventory, (With<Player>, Changed<Inventory>)>,
+    mut slots: Query<(&HotbarSlot, &mut BorderColor)>,
+    mut labels: Query<(&HotbarLabel, &mut Text)>,
+) {
+    for (slot, mut border) in slots.iter_mut() {
+        border.0 = if slot.0 == inventory.selected {
+            SELECTED_BORDER
+        } else {
+            IDLE_BORDER
+        };
+    }
+
+    for (label, mut text) in labels.iter_mut() {
+        text.0 = match inventory.slots[label.0] {
+            Some(stack) => match stack.uses_left() {
+                Some(uses) => format!("{}\n{}", stack.item.display_name(), uses),
+                None if stack.count > 1 => {
+                    format!("{}\nx{}", stack.item.display_name(), stack.count)
+                }
+                None => stack.item.display_name().to_string(),
+            },
+            None => String::new(),
+        };
+    }
+}
@@ -0,0 +1,18 @@
+use bevy::prelude::*;
+
+// Every root UI node picks one of these so stacking stays predictable as more UI is added.
+// Intended order, bottom to top: world-space labels, HUD, map, menus, console.
+// Gaps of 100 leave room for new layers without renumbering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum UiLayer {
+    Hud = 100,
+    Map = 200,
+    Menu = 300,
+    Console = 400,
+}
+
+impl UiLayer {
+    pub fn z_index(self) -> GlobalZIndex {
+        GlobalZIndex(self as i32)
+    }
+}
@@ -0,0 +1,32 @@
+pub mod console;
+pub mod crosshair;
+pub mod damage_indicator;
+pub mod difficulty_panel;
+pub mod end_screen;
+pub mod gate_menu;
+pub mod hotbar;
+pub mod layers;
+pub mod palette;
+pub mod reputation_screen;
+pub mod scale;
+pub mod toasts;
+pub mod world_map;
+
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins((
+        console::plugin,
+        crosshair::plugin,
+        damage_indicator::plugin,
+        difficulty_panel::plugin,
+        end_screen::plugin,
+        gate_menu::plugin,
+        hotbar::plugin,
+        palette::plugin,
+        reputation_screen::plugin,
+        scale::plugin,
+        toasts::plugin,
+        world_map::plugin,
+    ));
+}
@@ -0,0 +1,205 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::game::{
+    settings::AccessibilitySettings,
+    ui::console::{ConsoleCommand, ConsoleState},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Palette>().add_systems(
+        Update,
+        (
+            palette_command,
+            apply_palette.run_if(resource_changed::<AccessibilitySettings>),
+        )
+            .chain(),
+    );
+}
+
+// The standard colors, or ones kept apart for a kind of color blindness
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, EnumIter)]
+pub enum PalettePreset {
+    #[default]
+    Standard,
+    Deuteranopia, // Red and green run together, the most common kind
+    Protanopia,   // Red and green run together and reds look dark
+    Tritanopia,   // Blue and yellow run together
+}
+
+impl PalettePreset {
+    fn name(self) -> &'static str {
+        match self {
+            PalettePreset::Standard => "standard",
+            PalettePreset::Deuteranopia => "deuteranopia",
+            PalettePreset::Protanopia => "protanopia",
+            PalettePreset::Tritanopia => "tritanopia",
+        }
+    }
+}
+
+// Every color debug overlays and the UI use to tell things apart, so the accessibility setting
+// reaches all of them. Ramps also climb in lightness, so they still read without any hue.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct Palette {
+    pub preset: PalettePreset,
+    pub heat: [Color; 3], // Low to high, e.g. how slow a tile is to cross
+    pub cost: [Color; 2], // Low to high, for path search costs drawn over the heat
+    pub blocked: Color,
+    pub highlight: Color, // Open search cells, chests on the map
+    pub path: Color,
+    pub waypoint: Color,
+    pub fountain: Color,
+    pub vegetation: Color,
+    pub thorns: Color,
+    pub water: Color,
+    pub deep_water: Color,
+    pub alert: Color,
+    pub affection: Color,
+    pub sorrow: Color,
+    pub anger: [Color; 2], // Inner flame, outer flame
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::new(PalettePreset::Standard)
+    }
+}
+
+impl Palette {
+    pub fn new(preset: PalettePreset) -> Self {
+        let standard = Self {
+            preset,
+            heat: [
+                Color::srgb(0.2, 0.8, 0.2),
+                Color::srgb(0.9, 0.85, 0.2),
+                Color::srgb(0.9, 0.2, 0.2),
+            ],
+            cost: [Color::srgb(0.2, 0.4, 0.95), Color::srgb(0.7, 0.2, 0.9)],
+            blocked: Color::srgb(0.25, 0.25, 0.25),
+            highlight: Color::srgb(1.0, 0.85, 0.15),
+            path: Color::WHITE,
+            waypoint: Color::srgb(1.0, 0.2, 0.2),
+            fountain: Color::srgb(0.4, 0.8, 1.0),
+            vegetation: Color::srgb(0.1, 0.45, 0.15),
+            thorns: Color::srgb(0.3, 0.3, 0.1),
+            water: Color::srgb(0.15, 0.35, 0.6),
+            deep_water: Color::srgb(0.05, 0.15, 0.4),
+            alert: Color::srgb(1.0, 0.85, 0.1),
+            affection: Color::srgb(0.95, 0.2, 0.35),
+            sorrow: Color::srgb(0.45, 0.45, 0.65),
+            anger: [Color::srgb(1.0, 0.85, 0.2), Color::srgb(1.0, 0.35, 0.05)],
+        };
+
+        // Blues against oranges and yellows, after Okabe and Ito
+        let red_green_safe = Self {
+            heat: [
+                Color::srgb(0.25, 0.05, 0.35),
+                Color::srgb(0.1, 0.55, 0.55),
+                Color::srgb(0.98, 0.9, 0.15),
+            ],
+            cost: [Color::srgb(0.0, 0.2, 0.5), Color::srgb(0.6, 0.85, 1.0)],
+            highlight: Color::srgb(0.9, 0.6, 0.0),
+            waypoint: Color::srgb(0.95, 0.35, 0.85),
+            fountain: Color::srgb(0.35, 0.7, 0.9),
+            vegetation: Color::srgb(0.0, 0.45, 0.38),
+            thorns: Color::srgb(0.5, 0.35, 0.45),
+            water: Color::srgb(0.0, 0.45, 0.7),
+            deep_water: Color::srgb(0.0, 0.2, 0.45),
+            alert: Color::srgb(0.95, 0.9, 0.25),
+            affection: Color::srgb(0.85, 0.45, 0.7),
+            sorrow: Color::srgb(0.0, 0.45, 0.7),
+            anger: [Color::srgb(0.95, 0.9, 0.25), Color::srgb(0.8, 0.4, 0.0)],
+            ..standard.clone()
+        };
+
+        match preset {
+            PalettePreset::Standard => standard,
+            PalettePreset::Deuteranopia => red_green_safe,
+            // Reds lose their brightness, so whatever relied on one is lifted
+            PalettePreset::Protanopia => Self {
+                affection: Color::srgb(0.9, 0.6, 0.8),
+                anger: [Color::srgb(1.0, 0.95, 0.6), Color::srgb(0.9, 0.55, 0.0)],
+                ..red_green_safe
+            },
+            // Reds and pinks against teals, with yellow kept off anything that matters
+            PalettePreset::Tritanopia => Self {
+                heat: [
+                    Color::srgb(0.1, 0.3, 0.35),
+                    Color::srgb(0.85, 0.4, 0.4),
+                    Color::srgb(1.0, 0.85, 0.85),
+                ],
+                cost: [Color::srgb(0.0, 0.3, 0.3), Color::srgb(0.55, 0.9, 0.85)],
+                highlight: Color::srgb(1.0, 0.5, 0.6),
+                fountain: Color::srgb(0.5, 0.9, 0.9),
+                vegetation: Color::srgb(0.2, 0.5, 0.2),
+                thorns: Color::srgb(0.55, 0.2, 0.25),
+                water: Color::srgb(0.1, 0.45, 0.5),
+                deep_water: Color::srgb(0.05, 0.25, 0.3),
+                alert: Color::srgb(0.95, 0.95, 0.95),
+                affection: Color::srgb(0.95, 0.5, 0.7),
+                sorrow: Color::srgb(0.35, 0.55, 0.55),
+                anger: [Color::srgb(1.0, 0.75, 0.75), Color::srgb(0.85, 0.1, 0.1)],
+                ..standard
+            },
+        }
+    }
+
+    // `t` from 0 to 1 along the heat ramp
+    #[cfg(feature = "debug-tools")]
+    pub fn heat(&self, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0) * 2.0;
+        if t <= 1.0 {
+            mix(self.heat[0], self.heat[1], t)
+        } else {
+            mix(self.heat[1], self.heat[2], t - 1.0)
+        }
+    }
+
+    #[cfg(feature = "debug-tools")]
+    pub fn cost(&self, t: f32) -> Color {
+        mix(self.cost[0], self.cost[1], t.clamp(0.0, 1.0))
+    }
+}
+
+// In Oklab, so the steps look even
+#[cfg(feature = "debug-tools")]
+fn mix(from: Color, to: Color, t: f32) -> Color {
+    Oklaba::from(from).mix(&Oklaba::from(to), t).into()
+}
+
+fn apply_palette(settings: Res<AccessibilitySettings>, mut palette: ResMut<Palette>) {
+    if palette.preset != settings.palette {
+        *palette = Palette::new(settings.palette);
+    }
+}
+
+// `palette` shows the preset, `palette <name>` switches to another and keeps it
+fn palette_command(
+    mut commands_in: EventReader<ConsoleCommand>,
+    mut console: ResMut<ConsoleState>,
+    mut settings: ResMut<AccessibilitySettings>,
+) {
+    for command in commands_in.read().filter(|c| c.name == "palette") {
+        let names: Vec<_> = PalettePreset::iter().map(PalettePreset::name).collect();
+        match command.args.as_slice() {
+            [] => {}
+            [name] => {
+                let Some(preset) = PalettePreset::iter().find(|preset| preset.name() == name)
+                else {
+                    console.print(format!("usage: palette [{}]", names.join("|")));
+                    continue;
+                };
+                settings.palette = preset;
+                settings.save();
+            }
+            _ => {
+                console.print(format!("usage: palette [{}]", names.join("|")));
+                continue;
+            }
+        }
+        console.print(format!("palette: {}", settings.palette.name()));
+    }
+}
@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+use strum::IntoEnumIterator;
+
+use crate::game::{
+    core_mechanics::reputation::{Faction, Reputation, standing_label},
+    input::{Action, action_just_pressed},
+    ui::{console::console_closed, layers::UiLayer},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ReputationScreen>()
+        .add_systems(Startup, setup_reputation_screen)
+        .add_systems(
+            Update,
+            (
+                toggle_reputation_screen
+                    .run_if(action_just_pressed(Action::Reputation).and(console_closed)),
+                redraw_reputation_screen.run_if(
+                    resource_changed::<ReputationScreen>.or(resource_changed::<Reputation>),
+                ),
+            )
+                .chain(),
+        );
+}
+
+#[derive(Resource, Default)]
+struct ReputationScreen {
+    open: bool,
+}
+
+#[derive(Component)]
+struct ReputationRoot;
+
+#[derive(Component)]
+struct ReputationText;
+
+fn setup_reputation_screen(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("ReputationScreen"),
+            ReputationRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                display: Display::None,
+                ..Default::default()
+            },
+            UiLayer::Menu.z_index(),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                ReputationText,
+                Text::new(""),
+                TextFont::from_font_size(20.0),
+                Node {
+                    padding: UiRect::all(Val::Px(24.0)),
+                    ..Default::default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            ));
+        });
+}
+
+fn toggle_reputation_screen(mut screen: ResMut<ReputationScreen>) {
+    screen.open = !screen.open;
+}
+
+fn redraw_reputation_screen(
+    screen: Res<ReputationScreen>,
+    reputation: Res<Reputation>,
+    mut root: Single<&mut Node, With<ReputationRoot>>,
+    mut text: Single<&mut Text, With<ReputationText>>,
+) {
+    root.display = if screen.open {
+        Display::Flex
+    } else {
+        Display::None
+    };
+
+    let mut lines = vec!["⚖️ Reputation".to_string(), String::new()];
+    for faction in Faction::iter() {
+        let standing = reputation.standing(faction);
+        lines.push(format!(
+            "{:<10} {:>+4.0}  {}",
+            faction.name(),
+            standing,
+            standing_label(standing)
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(match reputation.price(Faction::Caravan, 1.0) {
+        Some(multiplier) => format!("Trader prices: x{:.2}", multiplier),
+        None => "Traders refuse to deal with you".to_string(),
+    });
+    text.0 = lines.join("\n");
+}
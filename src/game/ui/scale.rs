@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+
+use crate::game::ui::console::{ConsoleCommand, ConsoleState};
+
+const MIN_SCALE: f32 = 0.5;
+const MAX_SCALE: f32 = 3.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<UiSettings>().add_systems(
+        Update,
+        (
+            ui_scale_command,
+            apply_ui_scale.run_if(resource_changed::<UiSettings>),
+        )
+            .chain(),
+    );
+}
+
+// On top of the window's own scale factor, so 1.0 already looks right on HiDPI displays
+#[derive(Resource, Debug)]
+pub struct UiSettings {
+    pub scale: f32,
+}
+
+impl Default for UiSettings {
+    fn default() -> Self {
+        Self { scale: 1.0 }
+    }
+}
+
+fn apply_ui_scale(settings: Res<UiSettings>, mut ui_scale: ResMut<UiScale>) {
+    ui_scale.0 = settings.scale.clamp(MIN_SCALE, MAX_SCALE);
+}
+
+// `ui_scale <factor>`, e.g. `ui_scale 1.5`
+fn ui_scale_command(
+    mut commands_in: EventReader<ConsoleCommand>,
+    mut console: ResMut<ConsoleState>,
+    mut settings: ResMut<UiSettings>,
+) {
+    for command in commands_in.read().filter(|c| c.name == "ui_scale") {
+        let Some(scale) = command.args.first().and_then(|arg| arg.parse::<f32>().ok()) else {
+            console.print(format!("usage: ui_scale <{}..{}>", MIN_SCALE, MAX_SCALE));
+            continue;
+        };
+
+        settings.scale = scale.clamp(MIN_SCALE, MAX_SCALE);
+        console.print(format!("ui scale set to {}", settings.scale));
+    }
+}
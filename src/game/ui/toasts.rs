@@ -0,0 +1,142 @@
+use std::{cmp::Reverse, time::Duration};
+
+use bevy::prelude::*;
+
+use crate::game::{
+    core_mechanics::{
+        inventory::{ItemDropped, ItemPickedUp},
+        naming::{PlaceDiscovered, PlaceKind},
+    },
+    ui::layers::UiLayer,
+};
+
+const TOAST_SECS: f32 = 4.0;
+const FADE_SECS: f32 = 1.0; // At the end of TOAST_SECS
+const MAX_TOASTS: usize = 3; // The oldest goes away early when more arrive
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_event::<ShowToast>()
+        .add_systems(Startup, setup_toasts)
+        .add_systems(
+            Update,
+            (toast_discoveries, toast_items, spawn_toasts, update_toasts).chain(),
+        );
+}
+
+// Anything can write one of these to put a short message on screen
+#[derive(Event, Debug, Clone)]
+pub struct ShowToast {
+    pub message: String,
+}
+
+#[derive(Component)]
+struct ToastStack;
+
+#[derive(Component)]
+struct Toast {
+    lifetime: Timer,
+}
+
+fn setup_toasts(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Toasts"),
+        ToastStack,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            top: Val::Percent(15.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(8.0),
+            ..Default::default()
+        },
+        UiLayer::Hud.z_index(),
+    ));
+}
+
+fn toast_discoveries(
+    mut discoveries: EventReader<PlaceDiscovered>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    for discovery in discoveries.read() {
+        let message = match discovery.kind {
+            PlaceKind::Region => format!("You have entered {}", discovery.name),
+            PlaceKind::Fountain | PlaceKind::Ruins => format!("Discovered {}", discovery.name),
+            PlaceKind::Settlement => format!("A settlement rises nearby: {}", discovery.name),
+        };
+        toasts.write(ShowToast { message });
+    }
+}
+
+fn toast_items(
+    mut picked_up: EventReader<ItemPickedUp>,
+    mut dropped: EventReader<ItemDropped>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    for pickup in picked_up.read() {
+        toasts.write(ShowToast {
+            message: format!("Picked up {} x{}", pickup.item.display_name(), pickup.count),
+        });
+    }
+    for drop in dropped.read() {
+        toasts.write(ShowToast {
+            message: format!(
+                "Dropped {} x{}",
+                drop.stack.item.display_name(),
+                drop.stack.count
+            ),
+        });
+    }
+}
+
+fn spawn_toasts(
+    mut commands: Commands,
+    mut requests: EventReader<ShowToast>,
+    stack: Single<Entity, With<ToastStack>>,
+    mut existing: Query<&mut Toast>,
+) {
+    for request in requests.read() {
+        // Push the oldest toasts into their fade when the stack is full
+        let mut toasts: Vec<_> = existing.iter_mut().collect();
+        toasts.sort_by_key(|toast| Reverse(toast.lifetime.elapsed()));
+        let overflow = (toasts.len() + 1).saturating_sub(MAX_TOASTS);
+        let fade_start = Duration::from_secs_f32(TOAST_SECS - FADE_SECS);
+        for toast in toasts.iter_mut().take(overflow) {
+            if toast.lifetime.elapsed() < fade_start {
+                toast.lifetime.set_elapsed(fade_start);
+            }
+        }
+
+        let toast = commands
+            .spawn((
+                Toast {
+                    lifetime: Timer::from_seconds(TOAST_SECS, TimerMode::Once),
+                },
+                Text::new(request.message.clone()),
+                TextFont {
+                    font_size: 28.0,
+                    ..Default::default()
+                },
+                TextColor(Color::srgb(0.95, 0.9, 0.75)),
+            ))
+            .id();
+        commands.entity(*stack).add_child(toast);
+    }
+}
+
+fn update_toasts(
+    mut commands: Commands,
+    mut toasts: Query<(Entity, &mut Toast, &mut TextColor)>,
+    time: Res<Time>,
+) {
+    for (entity, mut toast, mut color) in toasts.iter_mut() {
+        toast.lifetime.tick(time.delta());
+        if toast.lifetime.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let alpha = (toast.lifetime.remaining_secs() / FADE_SECS).clamp(0.0, 1.0);
+        color.0 = color.0.with_alpha(alpha);
+    }
+}
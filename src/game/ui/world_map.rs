@@ -0,0 +1,347 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    ecs::system::SystemParam,
+    input::mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll},
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+    time::common_conditions::on_timer,
+    ui::RelativeCursorPosition,
+    window::{CursorGrabMode, PrimaryWindow},
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::game::{
+    core_mechanics::{
+        exploration::ExploredCells,
+        naming::{DiscoveredPlaces, PlaceKind},
+        oz_devinimli_yaratim::{cells::GenerationSettings, odyrules::commons::TileType},
+    },
+    input::{Action, action_just_pressed},
+    spawn::player::Player,
+    ui::{console::console_closed, layers::UiLayer, palette::Palette},
+};
+
+const MAP_SIZE_PX: u32 = 256;
+const MIN_ZOOM: f32 = 1.0; // Pixels per cell
+const MAX_ZOOM: f32 = 12.0;
+const REDRAW_INTERVAL_MS: u64 = 100;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<WorldMapState>()
+        .init_resource::<MapWaypoints>()
+        .add_systems(Startup, setup_world_map)
+        .add_systems(
+            Update,
+            (
+                toggle_world_map.run_if(action_just_pressed(Action::WorldMap).and(console_closed)),
+                (
+                    world_map_controls,
+                    (redraw_world_map, update_map_labels).run_if(
+                        on_timer(Duration::from_millis(REDRAW_INTERVAL_MS))
+                            .or(resource_changed::<WorldMapState>),
+                    ),
+                )
+                    .chain()
+                    .run_if(world_map_open),
+            )
+                .chain(),
+        );
+}
+
+#[derive(Resource)]
+pub struct WorldMapState {
+    pub open: bool,
+    pub center: Vec2, // In grid coordinates
+    pub zoom: f32,
+}
+
+impl Default for WorldMapState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            center: Vec2::ZERO,
+            zoom: 4.0,
+        }
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct MapWaypoints {
+    pub waypoints: Vec<(i32, i32)>,
+}
+
+#[derive(Resource)]
+struct WorldMapImage(Handle<Image>);
+
+// The image the map is drawn into
+#[derive(SystemParam)]
+struct MapCanvas<'w> {
+    handle: Res<'w, WorldMapImage>,
+    images: ResMut<'w, Assets<Image>>,
+}
+
+#[derive(Component)]
+struct WorldMapRoot;
+
+#[derive(Component)]
+struct WorldMapCanvas;
+
+#[derive(Component)]
+struct WorldMapLabel;
+
+pub fn world_map_open(state: Res<WorldMapState>) -> bool {
+    state.open
+}
+
+pub fn world_map_closed(state: Res<WorldMapState>) -> bool {
+    !state.open
+}
+
+fn setup_world_map(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let image = Image::new_fill(
+        Extent3d {
+            width: MAP_SIZE_PX,
+            height: MAP_SIZE_PX,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::all(),
+    );
+    let handle = images.add(image);
+
+    commands
+        .spawn((
+            Name::new("WorldMap"),
+            WorldMapRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                display: Display::None,
+                ..Default::default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            UiLayer::Map.z_index(),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                WorldMapCanvas,
+                ImageNode::new(handle.clone()),
+                Node {
+                    width: Val::VMin(90.0),
+                    height: Val::VMin(90.0),
+                    ..Default::default()
+                },
+                RelativeCursorPosition::default(),
+            ));
+        });
+
+    commands.insert_resource(WorldMapImage(handle));
+}
+
+fn toggle_world_map(
+    mut state: ResMut<WorldMapState>,
+    mut root: Single<&mut Node, With<WorldMapRoot>>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+    player: Single<&Transform, With<Player>>,
+    settings: Res<GenerationSettings>,
+) {
+    state.open = !state.open;
+
+    if state.open {
+        state.center = player.translation.xz() / settings.cell_edge_length as f32;
+        root.display = Display::Flex;
+        window.cursor_options.visible = true;
+        window.cursor_options.grab_mode = CursorGrabMode::None;
+    } else {
+        root.display = Display::None;
+        window.cursor_options.visible = false;
+        window.cursor_options.grab_mode = CursorGrabMode::Locked;
+    }
+}
+
+fn world_map_controls(
+    mut state: ResMut<WorldMapState>,
+    mut waypoints: ResMut<MapWaypoints>,
+    canvas: Single<(&RelativeCursorPosition, &ComputedNode), With<WorldMapCanvas>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+) {
+    let (cursor, computed_node) = *canvas;
+
+    if mouse_scroll.delta.y != 0.0 {
+        state.zoom = (state.zoom * 1.2_f32.powf(mouse_scroll.delta.y)).clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+
+    // Drag to pan, converting screen pixels to map pixels to cells
+    if mouse_buttons.pressed(MouseButton::Left) && cursor.mouse_over() {
+        let screen_to_map = MAP_SIZE_PX as f32 / computed_node.size().x.max(1.0);
+        let delta_cells = mouse_motion.delta * screen_to_map / state.zoom;
+        state.center -= delta_cells;
+    }
+
+    if mouse_buttons.just_pressed(MouseButton::Right)
+        && cursor.mouse_over()
+        && let Some(normalized) = cursor.normalized
+    {
+        let waypoint = map_pixel_to_grid(&state, normalized * MAP_SIZE_PX as f32);
+        // Clicking an existing waypoint removes it
+        if let Some(index) = waypoints.waypoints.iter().position(|w| *w == waypoint) {
+            waypoints.waypoints.remove(index);
+        } else {
+            waypoints.waypoints.push(waypoint);
+        }
+    }
+}
+
+fn map_pixel_to_grid(state: &WorldMapState, pixel: Vec2) -> (i32, i32) {
+    let offset = (pixel - Vec2::splat(MAP_SIZE_PX as f32 / 2.0)) / state.zoom;
+    let grid = state.center + offset;
+    (grid.x.round() as i32, grid.y.round() as i32)
+}
+
+fn grid_to_map_pixel(state: &WorldMapState, grid: Vec2) -> Vec2 {
+    (grid - state.center) * state.zoom + Vec2::splat(MAP_SIZE_PX as f32 / 2.0)
+}
+
+// Earth and stone stay as they are; whatever has to stand out from them comes from the palette
+fn tile_map_color(tile: TileType, palette: &Palette) -> Color {
+    match tile {
+        TileType::Ground => Color::srgb(0.45, 0.4, 0.3),
+        TileType::Tree => palette.vegetation,
+        TileType::Chest => Color::srgb(0.5, 0.5, 0.5),
+        TileType::ThornBush => palette.thorns,
+        TileType::DeepWater => palette.deep_water,
+        TileType::Water
+        | TileType::RiverStraight1
+        | TileType::RiverStraight2
+        | TileType::RiverBend1
+        | TileType::RiverBend2
+        | TileType::RiverBend3
+        | TileType::RiverBend4
+        | TileType::Well => palette.water,
+        TileType::Bridge1 | TileType::Bridge2 => Color::srgb(0.45, 0.3, 0.15),
+        TileType::Cliff => Color::srgb(0.3, 0.28, 0.25),
+        TileType::Slope | TileType::Stairs => Color::srgb(0.5, 0.45, 0.35),
+        TileType::Wall => Color::srgb(0.25, 0.22, 0.2),
+        TileType::Door | TileType::Floor => Color::srgb(0.5, 0.35, 0.2),
+        TileType::Path => Color::srgb(0.65, 0.55, 0.4),
+        TileType::LargeTree => palette.vegetation.darker(0.08),
+        TileType::RuinEntrance | TileType::Covered => Color::srgb(0.35, 0.32, 0.3),
+        TileType::Rubble => Color::srgb(0.42, 0.4, 0.38),
+        _ => palette.fountain.darker(0.2), // Fountain pieces
+    }
+}
+
+fn redraw_world_map(
+    state: Res<WorldMapState>,
+    waypoints: Res<MapWaypoints>,
+    explored: Res<ExploredCells>,
+    mut canvas: MapCanvas,
+    player: Single<&Transform, With<Player>>,
+    settings: Res<GenerationSettings>,
+    palette: Res<Palette>,
+) {
+    let Some(image) = canvas.images.get_mut(&canvas.handle.0) else {
+        return;
+    };
+
+    for y in 0..MAP_SIZE_PX {
+        for x in 0..MAP_SIZE_PX {
+            let grid = map_pixel_to_grid(&state, Vec2::new(x as f32, y as f32));
+            let color = explored
+                .tiles
+                .get(&grid)
+                .map(|tile| tile_map_color(*tile, &palette))
+                .unwrap_or(Color::BLACK);
+            let _ = image.set_color_at(x, y, color);
+        }
+    }
+
+    // Points of interest get a bigger icon so they stay visible when zoomed out
+    for (position, tile) in explored.tiles.iter() {
+        let icon_color = match tile {
+            TileType::FountainCenter => palette.fountain,
+            TileType::Chest => palette.highlight,
+            _ => continue,
+        };
+        let pixel = grid_to_map_pixel(&state, Vec2::new(position.0 as f32, position.1 as f32));
+        draw_marker(image, pixel, 1, icon_color);
+    }
+
+    for waypoint in waypoints.waypoints.iter() {
+        let pixel = grid_to_map_pixel(&state, Vec2::new(waypoint.0 as f32, waypoint.1 as f32));
+        draw_marker(image, pixel, 2, palette.waypoint);
+    }
+
+    let player_grid = player.translation.xz() / settings.cell_edge_length as f32;
+    draw_marker(
+        image,
+        grid_to_map_pixel(&state, player_grid),
+        2,
+        Color::WHITE,
+    );
+}
+
+// Names of discovered places, laid over the canvas at their map position
+fn update_map_labels(
+    mut commands: Commands,
+    state: Res<WorldMapState>,
+    places: Res<DiscoveredPlaces>,
+    canvas: Single<Entity, With<WorldMapCanvas>>,
+    labels: Query<Entity, With<WorldMapLabel>>,
+) {
+    for label in labels.iter() {
+        commands.entity(label).despawn();
+    }
+
+    for place in places.places.iter() {
+        let grid = Vec2::new(place.position.0 as f32, place.position.1 as f32);
+        let pixel = grid_to_map_pixel(&state, grid);
+        if pixel.min_element() < 0.0 || pixel.max_element() >= MAP_SIZE_PX as f32 {
+            continue;
+        }
+
+        let (font_size, color) = match place.kind {
+            PlaceKind::Region => (16.0, Color::srgba(1.0, 0.95, 0.8, 0.8)),
+            PlaceKind::Settlement => (14.0, Color::srgb(1.0, 0.85, 0.5)),
+            PlaceKind::Fountain | PlaceKind::Ruins => (12.0, Color::WHITE),
+        };
+        let label = commands
+            .spawn((
+                WorldMapLabel,
+                Text::new(place.name.clone()),
+                TextFont {
+                    font_size,
+                    ..Default::default()
+                },
+                TextColor(color),
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(pixel.x / MAP_SIZE_PX as f32 * 100.0),
+                    top: Val::Percent(pixel.y / MAP_SIZE_PX as f32 * 100.0),
+                    ..Default::default()
+                },
+            ))
+            .id();
+        commands.entity(*canvas).add_child(label);
+    }
+}
+
+fn draw_marker(image: &mut Image, center: Vec2, radius: i32, color: Color) {
+    for dy in -radius..=radius {
+        for dx in -radius..=radius {
+            let x = center.x.round() as i32 + dx;
+            let y = center.y.round() as i32 + dy;
+            if x < 0 || y < 0 || x >= MAP_SIZE_PX as i32 || y >= MAP_SIZE_PX as i32 {
+                continue;
+            }
+            let _ = image.set_color_at(x as u32, y as u32, color);
+        }
+    }
+}
@@ -0,0 +1,399 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::game::{
+    audio::SfxKind,
+    core_mechanics::{
+        chests::ChestLootTable,
+        enemy_ai::{o_insan::spawn::MONSTER_MODEL, spawn_tables::SpawnTables},
+        loot::LootKind,
+        oz_devinimli_yaratim::{
+            odycore::constraints::{GlobalConstraint, GlobalConstraints},
+            odyrules::{
+                biomes::BIOMES,
+                commons::{DIRECTIONS, Rules, TileType},
+                example_rules::{EXAMPLE_DIRECTORY, load_example},
+                open_space_rules::OpenSpaceRules,
+            },
+            prefabs::{StructureGrammar, StructurePart, StructureSymbol},
+            templates::StructureTemplates,
+            tiles_meshes_models::TILE_MODEL_PATHS,
+        },
+        triggers::{RUIN_SEQUENCE, ScriptStep, ScriptedSequences},
+    },
+    music::STINGER_PATHS,
+};
+
+pub const FLAG: &str = "--validate-assets";
+const ASSET_DIRECTORY: &str = "assets";
+
+// What one check found; errors fail the run, warnings are only reported
+#[derive(Default)]
+struct Report {
+    errors: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl Report {
+    fn section(&mut self, name: &str, check: impl FnOnce(&mut Report)) {
+        let mut found = Report::default();
+        check(&mut found);
+        let mark = if !found.errors.is_empty() {
+            "❌"
+        } else if !found.warnings.is_empty() {
+            "⚠️"
+        } else {
+            "✅"
+        };
+        println!("{} {}", mark, name);
+        for error in found.errors.iter() {
+            println!("     error: {}", error);
+        }
+        for warning in found.warnings.iter() {
+            println!("     warning: {}", warning);
+        }
+        self.errors.extend(found.errors);
+        self.warnings.extend(found.warnings);
+    }
+}
+
+// Loads everything the game reads as data, checks it against itself and the asset folder, and
+// prints a report. True when nothing is broken.
+pub fn run() -> bool {
+    println!("🔎 Validating assets");
+    let mut report = Report::default();
+    let rules = OpenSpaceRules::default();
+
+    report.section("Asset files", check_files);
+    report.section("Open-space rules", |found| check_rules(&rules, found));
+    report.section("Example maps", check_example_maps);
+    report.section("Structure grammar", check_grammar);
+    report.section("Structure templates", |found| {
+        check_templates(&rules, found)
+    });
+    report.section("Global constraints", |found| {
+        check_constraints(&rules, found)
+    });
+    report.section("Spawn tables", check_spawn_tables);
+    report.section("Scripted sequences", check_sequences);
+    report.section("Chest loot", check_chest_loot);
+
+    println!(
+        "{} error(s), {} warning(s)",
+        report.errors.len(),
+        report.warnings.len()
+    );
+    report.errors.is_empty()
+}
+
+fn check_files(found: &mut Report) {
+    let paths = TILE_MODEL_PATHS
+        .iter()
+        .copied()
+        .chain([MONSTER_MODEL])
+        .chain(SfxKind::ALL.iter().map(|kind| kind.path()))
+        .chain(STINGER_PATHS.iter().copied());
+
+    for path in paths {
+        if !Path::new(ASSET_DIRECTORY).join(path).is_file() {
+            found
+                .errors
+                .push(format!("missing {}/{}", ASSET_DIRECTORY, path));
+        }
+    }
+}
+
+fn check_rules(rules: &OpenSpaceRules, found: &mut Report) {
+    for tile in rules.all_tiles.iter() {
+        if !rules.weights().contains_key(tile) {
+            found.errors.push(format!("{:?} has no weight", tile));
+        }
+        let Some(sides) = rules.allowed_neighbors().get(tile) else {
+            found.errors.push(format!("{:?} has no adjacency", tile));
+            continue;
+        };
+
+        for direction in DIRECTIONS {
+            let neighbors = sides.get(&direction).map_or(&[][..], Vec::as_slice);
+            if !neighbors
+                .iter()
+                .any(|neighbor| rules.all_tiles.contains(neighbor))
+            {
+                found.warnings.push(format!(
+                    "{:?} can't have any generated tile on its {:?} side",
+                    tile, direction
+                ));
+            }
+            // A pairing allowed from one side only makes propagation depend on which cell
+            // settles first
+            for neighbor in neighbors {
+                let mirrored = rules
+                    .allowed_neighbors()
+                    .get(neighbor)
+                    .and_then(|sides| sides.get(&direction.opposite()))
+                    .is_some_and(|back| back.contains(tile));
+                if !mirrored {
+                    found.errors.push(format!(
+                        "{:?} allows {:?} on its {:?} side, but not the other way round",
+                        tile, neighbor, direction
+                    ));
+                }
+            }
+        }
+    }
+
+    for tile in rules.footprints.keys() {
+        if !rules.all_tiles.contains(tile) {
+            found
+                .warnings
+                .push(format!("{:?} has a footprint but is never generated", tile));
+        }
+    }
+}
+
+fn check_example_maps(found: &mut Report) {
+    let Ok(entries) = fs::read_dir(EXAMPLE_DIRECTORY) else {
+        return;
+    };
+
+    for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+        if path.extension().is_none_or(|extension| extension != "ron") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+
+        match load_example(name) {
+            Ok(rules) => {
+                for tile in rules.all_tiles.iter() {
+                    let sides = rules.allowed_neighbors().get(tile);
+                    for direction in DIRECTIONS {
+                        if sides.and_then(|sides| sides.get(&direction)).is_none() {
+                            found.warnings.push(format!(
+                                "{}: {:?} is never seen with anything on its {:?} side",
+                                name, tile, direction
+                            ));
+                        }
+                    }
+                }
+            }
+            Err(error) => found.errors.push(error),
+        }
+    }
+}
+
+fn check_grammar(found: &mut Report) {
+    let grammar = StructureGrammar::default();
+    if !grammar.rules.contains_key(&StructureSymbol::Village) {
+        found.errors.push("no expansion for Village".to_string());
+    }
+
+    for (symbol, expansions) in grammar.rules.iter() {
+        if !expansions.iter().any(|expansion| expansion.weight > 0.0) {
+            found.errors.push(format!(
+                "{:?} has no expansion with a positive weight",
+                symbol
+            ));
+        }
+        for placement in expansions.iter().flat_map(|e| e.placements.iter()) {
+            match placement.part {
+                StructurePart::Symbol(child) if !grammar.rules.contains_key(&child) => {
+                    found.errors.push(format!(
+                        "{:?} places {:?}, which has no expansion",
+                        symbol, child
+                    ));
+                }
+                StructurePart::Tile(TileType::Covered) => found.errors.push(format!(
+                    "{:?} places Covered, which only footprints may",
+                    symbol
+                )),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn check_templates(rules: &OpenSpaceRules, found: &mut Report) {
+    let templates = match StructureTemplates::load() {
+        Ok(templates) => templates,
+        Err(error) => {
+            found.errors.push(error);
+            return;
+        }
+    };
+
+    for template in templates.templates.iter() {
+        let name = &template.name;
+        if template.weight <= 0.0 {
+            found
+                .warnings
+                .push(format!("{} has a weight of {}", name, template.weight));
+        }
+        let tiles: HashMap<_, _> = template.tiles((0, 0)).collect();
+        if tiles.is_empty() {
+            found.errors.push(format!("{} has no tiles", name));
+        }
+        // Stamped cells skip the solver, so multi-cell tiles need their Covered cells drawn in
+        for (&(x, z), &tile) in tiles.iter() {
+            let (width, depth) = rules.footprint(tile);
+            let missing = (0..width)
+                .flat_map(|dx| (0..depth).map(move |dz| (x + dx, z + dz)))
+                .filter(|&position| position != (x, z))
+                .any(|position| tiles.get(&position) != Some(&TileType::Covered));
+            if missing {
+                found.errors.push(format!(
+                    "{}: {:?} at ({}, {}) doesn't have its footprint drawn as Covered",
+                    name, tile, x, z
+                ));
+            }
+        }
+    }
+}
+
+fn check_constraints(rules: &OpenSpaceRules, found: &mut Report) {
+    for constraint in GlobalConstraints::default().constraints {
+        let (tile, area) = match constraint {
+            GlobalConstraint::AtMost { tile, area, .. } => (tile, area),
+            GlobalConstraint::AtLeast { tile, area, .. } => (tile, area),
+        };
+        if area <= 0 {
+            found
+                .errors
+                .push(format!("{:?} constraint has an empty area", tile));
+        }
+        if !rules.all_tiles.contains(&tile) {
+            found.warnings.push(format!(
+                "{:?} constraint has no effect, the tile is never generated",
+                tile
+            ));
+        }
+    }
+}
+
+fn check_spawn_tables(found: &mut Report) {
+    let tables = match SpawnTables::load() {
+        Ok(tables) => tables,
+        Err(error) => {
+            found.errors.push(error);
+            return;
+        }
+    };
+
+    for biome in BIOMES {
+        let Some(spawns) = tables.biomes.get(&biome) else {
+            found.warnings.push(format!(
+                "{:?} has no spawn table, so nothing spawns there",
+                biome
+            ));
+            continue;
+        };
+        if spawns.density <= 0.0 {
+            found
+                .warnings
+                .push(format!("{:?} has a density of {}", biome, spawns.density));
+        }
+        for entry in spawns.entries.iter() {
+            if entry.weight <= 0.0 {
+                found.errors.push(format!(
+                    "{:?}: {:?} has a weight of {}",
+                    biome, entry.encounter, entry.weight
+                ));
+            }
+            if entry.group.0 == 0 || entry.group.0 > entry.group.1 {
+                found.errors.push(format!(
+                    "{:?}: {:?} has a group size of {:?}",
+                    biome, entry.encounter, entry.group
+                ));
+            }
+        }
+    }
+}
+
+fn check_chest_loot(found: &mut Report) {
+    let table = match ChestLootTable::load() {
+        Ok(table) => table,
+        Err(error) => {
+            found.errors.push(error);
+            return;
+        }
+    };
+
+    for entry in table.entries.iter() {
+        if entry.weight <= 0.0 {
+            found
+                .errors
+                .push(format!("{:?} has a weight of {}", entry.loot, entry.weight));
+        }
+        if let LootKind::Item(stack) = entry.loot
+            && !(1..=stack.item.max_stack()).contains(&stack.count)
+        {
+            found.errors.push(format!(
+                "{:?} comes {} at a time, but stacks of 1 to {} fit in a slot",
+                stack.item,
+                stack.count,
+                stack.item.max_stack()
+            ));
+        }
+    }
+    if table.entries.is_empty() {
+        found
+            .warnings
+            .push("no entries, so every chest is empty".to_string());
+    } else if table.entries.iter().all(|entry| entry.loot.is_weapon()) {
+        found.warnings.push(
+            "only weapons, so chests are empty once the player carries each of them".to_string(),
+        );
+    }
+}
+
+fn check_sequences(found: &mut Report) {
+    let sequences = match ScriptedSequences::load() {
+        Ok(sequences) => sequences,
+        Err(error) => {
+            found.errors.push(error);
+            return;
+        }
+    };
+
+    if !sequences.sequences.contains_key(RUIN_SEQUENCE) {
+        found.warnings.push(format!(
+            "no {} sequence, so ruins place no triggers",
+            RUIN_SEQUENCE
+        ));
+    }
+    for (name, sequence) in sequences.sequences.iter() {
+        if sequence.steps.is_empty() {
+            found.warnings.push(format!("{} has no steps", name));
+        }
+        for (index, step) in sequence.steps.iter().enumerate() {
+            if step.secs() < 0.0 {
+                found.errors.push(format!(
+                    "{}: step {} lasts {} seconds",
+                    name,
+                    index,
+                    step.secs()
+                ));
+            }
+            match step {
+                ScriptStep::CameraPath { points, .. } if points.is_empty() => {
+                    found.errors.push(format!(
+                        "{}: step {} is a camera path with no points",
+                        name, index
+                    ));
+                }
+                ScriptStep::Dialogue { line, .. } if line.trim().is_empty() => {
+                    found.warnings.push(format!(
+                        "{}: step {} has an empty dialogue line",
+                        name, index
+                    ));
+                }
+                ScriptStep::SpawnWave { count: 0, .. } => {
+                    found
+                        .warnings
+                        .push(format!("{}: step {} spawns an empty wave", name, index));
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -4,6 +4,11 @@ mod camera;
 mod game;
 
 fn main() {
+    if std::env::args().any(|arg| arg == game::validate::FLAG) {
+        let valid = game::validate::run();
+        std::process::exit(if valid { 0 } else { 1 });
+    }
+
     App::new()
         .add_plugins(DefaultPlugins)
         .add_plugins(camera::plugin)